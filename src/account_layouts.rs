@@ -0,0 +1,63 @@
+//! Offline account data decoding
+//!
+//! Decodes raw account bytes a client already holds (e.g. from a cached
+//! getAccountInfo) against the layouts solana-account-decoder knows: SPL Token
+//! and Token-2022 accounts and mints, stake, nonce, vote, config, address
+//! lookup table, upgradeable loader and sysvar accounts.
+
+use crate::error::{McpError, McpResult};
+use base64::Engine;
+use serde_json::Value;
+use solana_account_decoder::parse_account_data::{
+    parse_account_data_v3, AccountAdditionalDataV3, ParseAccountError, SplTokenAdditionalDataV2,
+    PARSABLE_PROGRAM_IDS,
+};
+use solana_sdk::pubkey::Pubkey;
+
+/// Decodes `data` according to the layout of its owner program
+///
+/// # Arguments
+/// * `owner` - Program that owns the account
+/// * `data` - Raw account data
+/// * `pubkey` - Account address; only sysvar and config accounts need it
+/// * `decimals` - Mint decimals, required for SPL token accounts
+///
+/// # Returns
+/// * `McpResult<Value>` - `{owner, program, parsed, space}`, or the raw data
+///   as base64 with a `note` when no decoder exists for `owner`
+pub fn decode_account_data(
+    owner: &Pubkey,
+    data: &[u8],
+    pubkey: Option<&Pubkey>,
+    decimals: Option<u8>,
+) -> McpResult<Value> {
+    let Some(program) = PARSABLE_PROGRAM_IDS.get(owner) else {
+        return Ok(serde_json::json!({
+            "owner": owner.to_string(),
+            "program": null,
+            "space": data.len(),
+            "data": base64::engine::general_purpose::STANDARD.encode(data),
+            "note": format!("No decoder for owner program {owner}; returned raw")
+        }));
+    };
+
+    let additional_data = AccountAdditionalDataV3 {
+        spl_token_additional_data: decimals.map(SplTokenAdditionalDataV2::with_decimals),
+    };
+    match parse_account_data_v3(&pubkey.copied().unwrap_or_default(), owner, data, Some(additional_data)) {
+        Ok(parsed) => Ok(serde_json::json!({
+            "owner": owner.to_string(),
+            "program": parsed.program,
+            "parsed": parsed.parsed,
+            "space": parsed.space
+        })),
+        Err(ParseAccountError::AdditionalDataMissing(_)) => Err(McpError::validation(
+            "decimals (the mint's decimals) is required to decode an SPL token account",
+        )
+        .with_parameter("decimals")),
+        Err(e) => Err(McpError::validation(format!(
+            "Account data does not match the {program:?} layout: {e}"
+        ))
+        .with_parameter("data")),
+    }
+}
@@ -0,0 +1,116 @@
+use crate::config::AuthConfig;
+use crate::server::ServerState;
+use axum::{
+    extract::{Request, State},
+    http::{header::{AUTHORIZATION, WWW_AUTHENTICATE}, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+/// JSON-RPC error code returned alongside HTTP 401
+pub const UNAUTHORIZED_ERROR_CODE: i32 = -32001;
+
+/// Axum middleware enforcing `Authorization: Bearer <token>` on protected routes
+///
+/// When no tokens are configured every request is let through, preserving the
+/// open behavior of deployments that predate the `auth` section.
+pub async fn require_bearer_token(
+    State(auth): State<Arc<AuthConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !auth.is_enabled() {
+        return next.run(request).await;
+    }
+
+    let presented = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_bearer_token);
+
+    match presented {
+        Some(token) if auth.accepts(token) => {
+            // Attribute any configuration changes made by this request to its token
+            let actor = crate::logging::token_actor(token);
+            crate::logging::with_audit_actor(actor, next.run(request)).await
+        }
+        _ => {
+            warn!("Rejected request to {} without a valid bearer token", request.uri().path());
+            unauthorized_response()
+        }
+    }
+}
+
+/// Like `require_bearer_token`, but reads the tokens from the running server config
+///
+/// The tokens are looked up on every request, so a config reload that adds or
+/// removes a token takes effect without a restart.
+pub async fn require_configured_bearer_token(
+    State(state): State<Arc<RwLock<ServerState>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let auth = Arc::new(state.read().await.config.auth.clone());
+    require_bearer_token(State(auth), request, next).await
+}
+
+/// Extract the token from an `Authorization` header value using the Bearer scheme
+pub fn parse_bearer_token(header: &str) -> Option<&str> {
+    let (scheme, token) = header.trim().split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return None;
+    }
+    let token = token.trim();
+    (!token.is_empty()).then_some(token)
+}
+
+/// Compare two tokens without short-circuiting on the first differing byte
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn unauthorized_response() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(WWW_AUTHENTICATE, "Bearer")],
+        Json(json!({
+            "jsonrpc": "2.0",
+            "error": {
+                "code": UNAUTHORIZED_ERROR_CODE,
+                "message": "Unauthorized: missing or invalid bearer token"
+            },
+            "id": null
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bearer_token() {
+        assert_eq!(parse_bearer_token("Bearer abc123"), Some("abc123"));
+        assert_eq!(parse_bearer_token("bearer  abc123 "), Some("abc123"));
+        assert_eq!(parse_bearer_token("Basic abc123"), None);
+        assert_eq!(parse_bearer_token("Bearer "), None);
+        assert_eq!(parse_bearer_token("abc123"), None);
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secrets"));
+    }
+}
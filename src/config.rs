@@ -1,15 +1,17 @@
 use crate::cache::CacheConfig;
 use crate::protocol::LATEST_PROTOCOL_VERSION;
-use crate::validation::{validate_commitment, validate_rpc_url};
+use crate::transport::StdioFraming;
+use crate::validation::{account_encoding_from_name, sanitize_for_logging, validate_commitment, validate_rpc_url};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, env, fs};
+use serde_json::Value;
+use std::{collections::HashMap, env, fs, path::Path};
 
 #[cfg(feature = "x402")]
 use crate::x402::X402Config;
 
 /// Represents a Solana Virtual Machine (SVM) network configuration
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct SvmNetwork {
     /// Human-readable name of the network
     pub name: String,
@@ -17,6 +19,9 @@ pub struct SvmNetwork {
     pub rpc_url: String,
     /// Whether this network is currently enabled for use
     pub enabled: bool,
+    /// Default commitment for this network's client, overriding the global `commitment`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<String>,
 }
 
 /// Main configuration structure for the Solana MCP server
@@ -31,6 +36,9 @@ pub struct Config {
     pub commitment: String,
     /// Protocol version for MCP communication
     pub protocol_version: String,
+    /// Message framing on the stdio transport (auto, line or content-length)
+    #[serde(default)]
+    pub stdio_framing: StdioFraming,
     /// Additional SVM networks configuration
     #[serde(default)]
     pub svm_networks: HashMap<String, SvmNetwork>,
@@ -40,12 +48,184 @@ pub struct Config {
     /// Cache configuration
     #[serde(default)]
     pub cache: CacheConfig,
+    /// Tools listed and callable by clients (empty enables all)
+    #[serde(default)]
+    pub enabled_tools: Vec<String>,
+    /// Tools hidden and rejected, even if present in `enabled_tools`
+    #[serde(default)]
+    pub disabled_tools: Vec<String>,
+    /// Maximum number of RPC calls allowed in flight at once
+    #[serde(default = "default_max_concurrent_rpc")]
+    pub max_concurrent_rpc: usize,
+    /// Per-request HTTP timeout for upstream Solana RPC calls, in seconds
+    #[serde(default = "default_rpc_timeout")]
+    pub rpc_timeout_seconds: u64,
+    /// Maximum number of tools/call requests in flight before new ones are rejected as busy
+    #[serde(default = "default_max_in_flight_requests")]
+    pub max_in_flight_requests: usize,
+    /// Maximum accepted request body size for the HTTP MCP endpoint, in bytes
+    ///
+    /// Read once when the HTTP server starts; a config reload does not change
+    /// it, so a new value takes effect only after a restart.
+    #[serde(default = "default_max_request_bytes")]
+    pub max_request_bytes: usize,
+    /// Largest serialized tools/call result sent to a client, in bytes (0 disables)
+    #[serde(default = "default_max_response_bytes")]
+    pub max_response_bytes: usize,
+    /// Serialized result size above which `compress: true` tools/call results are compressed, in bytes
+    #[serde(default = "default_compression_threshold_bytes")]
+    pub compression_threshold_bytes: usize,
+    /// Widest slot range getBlocks accepts in one call; wider ranges need `scan: true`
+    #[serde(default = "default_max_block_range_slots")]
+    pub max_block_range_slots: u64,
+    /// Whether MCP clients may list, enable, disable or repoint SVM networks
+    #[serde(default = "default_allow_network_mutation")]
+    pub allow_network_mutation: bool,
+    /// Hosts that network management tools may point RPC URLs at (empty permits any)
+    #[serde(default)]
+    pub allowed_rpc_hosts: Vec<String>,
+    /// How long a fetched awesome-svm network list is reused, in seconds (0 always refetches)
+    #[serde(default = "default_svm_networks_cache_ttl")]
+    pub svm_networks_cache_ttl_seconds: u64,
+    /// How long a complete getClusterStatus snapshot is reused, in milliseconds (0 disables)
+    #[serde(default = "default_cluster_status_cache_ttl")]
+    pub cluster_status_cache_ttl_ms: u64,
+    /// How long a resolved .sol domain is reused, in seconds (0 disables)
+    #[serde(default = "default_domain_cache_ttl")]
+    pub domain_cache_ttl_seconds: u64,
+    /// How long a probeNetworkMethods report is reused, in seconds (0 disables)
+    #[serde(default = "default_network_methods_cache_ttl")]
+    pub network_methods_cache_ttl_seconds: u64,
+    /// How often the primary and enabled SVM networks are probed for the health metrics, in seconds (0 disables)
+    #[serde(default = "default_network_probe_interval")]
+    pub network_probe_interval_seconds: u64,
+    /// Socket address for a dedicated metrics listener, e.g. "127.0.0.1:9090"
+    /// (unset serves metrics on 0.0.0.0:8080 in stdio mode and on the API port in web mode).
+    /// Read at startup; a change needs a restart.
+    #[serde(default)]
+    pub metrics_bind_addr: Option<String>,
+    /// HTTP path the Prometheus metrics are served at (read at startup)
+    #[serde(default = "default_metrics_path")]
+    pub metrics_path: String,
+    /// Browser origins allowed to call the HTTP endpoints cross-origin, as a list
+    /// or "*" for any (empty sends no CORS headers). Read at startup.
+    #[serde(default, deserialize_with = "deserialize_origins")]
+    pub cors_allowed_origins: Vec<String>,
+    /// Encoding used by getAccountInfo and getMultipleAccounts when a request omits one
+    /// (base58, base64, base64+zstd or jsonParsed; unset keeps the raw account shape)
+    #[serde(default)]
+    pub default_account_encoding: Option<String>,
+    /// Maximum number of messages queued for a WebSocket client before the overflow policy applies
+    #[serde(default = "default_websocket_send_queue_capacity")]
+    pub websocket_send_queue_capacity: usize,
+    /// What to do when a WebSocket client's send queue is full
+    #[serde(default)]
+    pub websocket_overflow_policy: WebSocketOverflowPolicy,
+    /// Browser origins allowed to open a WebSocket (empty accepts any origin)
+    #[serde(default)]
+    pub allowed_ws_origins: Vec<String>,
+    /// Log full MCP request and response bodies at info level (off by default)
+    #[serde(default)]
+    pub log_bodies: bool,
+    /// Field names whose values are redacted from logged bodies, matched case-insensitively at any depth
+    #[serde(default = "default_log_redact_fields")]
+    pub log_redact_fields: Vec<String>,
+    /// Attach `_meta.durationMs` and `_meta.rpcUrl` to successful tools/call results
+    #[serde(default)]
+    pub include_timing: bool,
+    /// Reload config.json when it changes on disk (stdio and web modes)
+    #[serde(default)]
+    pub watch_config: bool,
+    /// Bearer-token authentication for the web and WebSocket servers
+    #[serde(default)]
+    pub auth: AuthConfig,
+    /// Devnet deployment settings for the sBPF tools
+    #[serde(default)]
+    pub sbpf_deploy: SbpfDeployConfig,
     /// x402 payment protocol configuration (feature-gated)
     #[cfg(feature = "x402")]
     #[serde(default)]
     pub x402: X402Config,
 }
 
+/// What the WebSocket server does when a client's send queue is full
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebSocketOverflowPolicy {
+    /// Drop the oldest queued message and count it in the dropped-messages metric
+    #[default]
+    DropOldest,
+    /// Close the connection with a "client too slow" close frame
+    Disconnect,
+}
+
+/// Bearer-token authentication settings for network-facing transports
+///
+/// Authentication is disabled when neither `bearer_token` nor `bearer_tokens`
+/// is set, which keeps existing deployments open by default.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct AuthConfig {
+    /// Single static bearer token accepted by the server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bearer_token: Option<String>,
+    /// Additional bearer tokens accepted by the server, e.g. during rotation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bearer_tokens: Vec<String>,
+}
+
+impl AuthConfig {
+    /// Returns true when at least one bearer token is configured
+    pub fn is_enabled(&self) -> bool {
+        self.tokens().next().is_some()
+    }
+
+    /// Checks a presented token against every configured token
+    pub fn accepts(&self, presented: &str) -> bool {
+        self.tokens()
+            .fold(false, |matched, token| {
+                crate::auth::constant_time_eq(token.as_bytes(), presented.as_bytes()) | matched
+            })
+    }
+
+    fn tokens(&self) -> impl Iterator<Item = &str> {
+        self.bearer_token
+            .iter()
+            .chain(self.bearer_tokens.iter())
+            .map(String::as_str)
+            .filter(|token| !token.is_empty())
+    }
+}
+
+/// Settings for deploying sBPF programs to devnet from MCP tools
+///
+/// Deployment spends SOL, so it is disabled unless explicitly enabled.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SbpfDeployConfig {
+    /// Whether `deploySbpfToDevnet` is available
+    #[serde(default)]
+    pub enabled: bool,
+    /// RPC endpoint deployments are sent to; must serve Solana devnet
+    #[serde(default = "default_sbpf_deploy_rpc_url")]
+    pub rpc_url: String,
+    /// Solana CLI keypair file used as payer when the caller supplies none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payer_keypair_path: Option<String>,
+}
+
+impl Default for SbpfDeployConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rpc_url: default_sbpf_deploy_rpc_url(),
+            payer_keypair_path: None,
+        }
+    }
+}
+
+fn default_sbpf_deploy_rpc_url() -> String {
+    crate::sbpf::devnet_deployer::DEFAULT_DEVNET_URL.to_string()
+}
+
 /// Timeout configuration for various operations
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct TimeoutConfig {
@@ -64,6 +244,21 @@ pub struct TimeoutConfig {
     /// Maximum idle time for WebSocket connections in seconds
     #[serde(default = "default_max_idle_timeout")]
     pub max_idle_seconds: u64,
+    /// Maximum time to wait for an RPC concurrency permit in milliseconds
+    #[serde(default = "default_rpc_permit_wait")]
+    pub rpc_permit_wait_ms: u64,
+    /// Interval between server-initiated WebSocket pings in seconds
+    #[serde(default = "default_ws_ping_interval")]
+    pub websocket_ping_interval_seconds: u64,
+    /// Time to wait for a pong before treating a WebSocket as half-open in seconds
+    #[serde(default = "default_ws_pong_timeout")]
+    pub websocket_pong_timeout_seconds: u64,
+    /// Attempts to reconnect a dropped upstream subscription before giving up
+    #[serde(default = "default_ws_reconnect_attempts")]
+    pub websocket_reconnect_attempts: u32,
+    /// Upper bound on the exponential backoff between reconnect attempts in seconds
+    #[serde(default = "default_ws_reconnect_max_backoff")]
+    pub websocket_reconnect_max_backoff_seconds: u64,
 }
 
 impl Default for TimeoutConfig {
@@ -74,6 +269,11 @@ impl Default for TimeoutConfig {
             websocket_message_seconds: default_ws_message_timeout(),
             subscription_seconds: default_subscription_timeout(),
             max_idle_seconds: default_max_idle_timeout(),
+            rpc_permit_wait_ms: default_rpc_permit_wait(),
+            websocket_ping_interval_seconds: default_ws_ping_interval(),
+            websocket_pong_timeout_seconds: default_ws_pong_timeout(),
+            websocket_reconnect_attempts: default_ws_reconnect_attempts(),
+            websocket_reconnect_max_backoff_seconds: default_ws_reconnect_max_backoff(),
         }
     }
 }
@@ -84,6 +284,101 @@ fn default_ws_connection_timeout() -> u64 { 30 }
 fn default_ws_message_timeout() -> u64 { 10 }
 fn default_subscription_timeout() -> u64 { 15 }
 fn default_max_idle_timeout() -> u64 { 300 }
+fn default_rpc_permit_wait() -> u64 { 5000 }
+fn default_ws_ping_interval() -> u64 { 30 }
+fn default_ws_pong_timeout() -> u64 { 10 }
+fn default_ws_reconnect_attempts() -> u32 { 5 }
+fn default_ws_reconnect_max_backoff() -> u64 { 30 }
+
+// Default concurrency limit for upstream RPC calls
+fn default_max_concurrent_rpc() -> usize { 64 }
+
+// Default upstream RPC timeout, matching the Solana client's own default
+fn default_rpc_timeout() -> u64 { 30 }
+
+// Default global ceiling on in-flight tool calls
+fn default_max_in_flight_requests() -> usize { 256 }
+
+// Default HTTP request body limit (1 MiB)
+fn default_max_request_bytes() -> usize { 1024 * 1024 }
+
+// Large enough for paged account lists, small enough not to stall a client (10 MiB)
+fn default_max_response_bytes() -> usize { 10 * 1024 * 1024 }
+
+// Below this the base64 overhead outweighs what zstd saves on typical JSON (16 KiB)
+fn default_compression_threshold_bytes() -> usize { 16 * 1024 }
+
+// Nodes reject getBlocks ranges wider than 500,000 slots
+fn default_max_block_range_slots() -> u64 { 500_000 }
+
+// Default reuse window for the awesome-svm network list (5 minutes)
+fn default_svm_networks_cache_ttl() -> u64 { 300 }
+
+// Short enough that slot numbers stay current for monitoring dashboards
+fn default_cluster_status_cache_ttl() -> u64 { 1000 }
+
+// Names rarely change hands, but a transfer should be visible within a minute
+fn default_domain_cache_ttl() -> u64 { 60 }
+
+// Method support only changes when a node is upgraded, so an hour is plenty
+fn default_network_methods_cache_ttl() -> u64 { 3600 }
+
+// Frequent enough for alerting without adding noticeable load upstream
+fn default_network_probe_interval() -> u64 { 30 }
+
+// The path Prometheus scrapes by default
+fn default_metrics_path() -> String { "/metrics".to_string() }
+
+/// Accepts `cors_allowed_origins` either as a single string such as "*" or as a list
+fn deserialize_origins<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Origins {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Origins::deserialize(deserializer)? {
+        Origins::One(origin) => vec![origin],
+        Origins::Many(origins) => origins,
+    })
+}
+
+/// Reads a comma-separated list of origins from `var`
+fn origins_from_env(var: &str) -> Vec<String> {
+    env::var(var)
+        .map(|origins| origins.split(',').map(|origin| origin.trim().to_string()).filter(|origin| !origin.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// Checks that every entry of `field` is a bare origin such as https://app.example.com
+fn validate_origins(field: &str, origins: &[String]) -> Result<()> {
+    for origin in origins {
+        let is_origin = url::Url::parse(origin)
+            .is_ok_and(|url| url.origin().is_tuple() && url.origin().ascii_serialization() == *origin);
+        if !is_origin {
+            return Err(anyhow::anyhow!(
+                "{} entry '{}' must be a scheme, host and optional port such as https://app.example.com",
+                field,
+                origin
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Default per-client WebSocket send queue, enough to absorb short bursts
+fn default_websocket_send_queue_capacity() -> usize { 1024 }
+
+// Signed transactions and credentials stay out of logged bodies unless an operator opts in
+fn default_log_redact_fields() -> Vec<String> {
+    ["transaction", "authorization", "token", "secretKey", "privateKey", "keypair", "payerKeypair"]
+        .map(String::from)
+        .to_vec()
+}
+
+// Network management stays enabled unless an operator opts out
+fn default_allow_network_mutation() -> bool { true }
 
 impl Config {
     /// Loads configuration from file or environment variables
@@ -116,14 +411,64 @@ impl Config {
             let protocol_version = env::var("SOLANA_PROTOCOL_VERSION")
                 .unwrap_or_else(|_| LATEST_PROTOCOL_VERSION.to_string());
 
+            let auth = AuthConfig {
+                bearer_token: env::var("SOLANA_MCP_AUTH_TOKEN").ok(),
+                bearer_tokens: Vec::new(),
+            };
+
             Config {
                 rpc_url,
                 rpc_urls: Vec::new(),
                 commitment,
                 protocol_version,
+                stdio_framing: env::var("SOLANA_STDIO_FRAMING")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or_default(),
                 svm_networks: HashMap::new(),
                 timeouts: TimeoutConfig::default(),
                 cache: CacheConfig::default(),
+                enabled_tools: Vec::new(),
+                disabled_tools: Vec::new(),
+                max_concurrent_rpc: default_max_concurrent_rpc(),
+                rpc_timeout_seconds: env::var("SOLANA_RPC_TIMEOUT_SECONDS")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or_else(default_rpc_timeout),
+                max_in_flight_requests: default_max_in_flight_requests(),
+                max_request_bytes: default_max_request_bytes(),
+                max_response_bytes: default_max_response_bytes(),
+                compression_threshold_bytes: default_compression_threshold_bytes(),
+                max_block_range_slots: default_max_block_range_slots(),
+                allow_network_mutation: default_allow_network_mutation(),
+                allowed_rpc_hosts: Vec::new(),
+                svm_networks_cache_ttl_seconds: default_svm_networks_cache_ttl(),
+                cluster_status_cache_ttl_ms: default_cluster_status_cache_ttl(),
+                domain_cache_ttl_seconds: default_domain_cache_ttl(),
+                network_methods_cache_ttl_seconds: default_network_methods_cache_ttl(),
+                network_probe_interval_seconds: default_network_probe_interval(),
+                metrics_bind_addr: env::var("SOLANA_METRICS_BIND_ADDR").ok(),
+                metrics_path: env::var("SOLANA_METRICS_PATH").unwrap_or_else(|_| default_metrics_path()),
+                cors_allowed_origins: origins_from_env("SOLANA_CORS_ALLOWED_ORIGINS"),
+                default_account_encoding: env::var("SOLANA_DEFAULT_ACCOUNT_ENCODING").ok(),
+                log_bodies: env::var("SOLANA_LOG_BODIES")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(false),
+                log_redact_fields: default_log_redact_fields(),
+                include_timing: env::var("SOLANA_INCLUDE_TIMING")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(false),
+                watch_config: env::var("SOLANA_WATCH_CONFIG")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(false),
+                websocket_send_queue_capacity: default_websocket_send_queue_capacity(),
+                websocket_overflow_policy: WebSocketOverflowPolicy::default(),
+                allowed_ws_origins: origins_from_env("SOLANA_WS_ALLOWED_ORIGINS"),
+                auth,
+                sbpf_deploy: SbpfDeployConfig::default(),
                 #[cfg(feature = "x402")]
                 x402: X402Config::default(),
             }
@@ -134,6 +479,101 @@ impl Config {
         Ok(config)
     }
 
+    /// Loads and validates configuration from a JSON file
+    ///
+    /// Unlike [`Config::load`], a missing file is an error rather than a
+    /// fallback to environment variables.
+    ///
+    /// # Errors
+    /// * The file cannot be read or parsed
+    /// * The parsed configuration fails validation
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let config: Config = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Lists the settings that differ from `other` as dotted paths
+    ///
+    /// Only names are returned, never values, so the result is safe to log
+    /// even when tokens or RPC credentials changed.
+    pub fn changed_fields(&self, other: &Config) -> Vec<String> {
+        fn collect(prefix: &str, old: &Value, new: &Value, changed: &mut Vec<String>) {
+            match (old, new) {
+                (Value::Object(old), Value::Object(new)) => {
+                    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+                    keys.sort();
+                    keys.dedup();
+                    for key in keys {
+                        let path = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+                        collect(
+                            &path,
+                            old.get(key).unwrap_or(&Value::Null),
+                            new.get(key).unwrap_or(&Value::Null),
+                            changed,
+                        );
+                    }
+                }
+                (old, new) if old != new => changed.push(prefix.to_string()),
+                _ => {}
+            }
+        }
+
+        let mut changed = Vec::new();
+        collect(
+            "",
+            &serde_json::to_value(self).unwrap_or(Value::Null),
+            &serde_json::to_value(other).unwrap_or(Value::Null),
+            &mut changed,
+        );
+        changed
+    }
+
+    /// Whether `tool` is exposed under the tool allow and deny lists
+    ///
+    /// `disabled_tools` wins over `enabled_tools`, so a tool in both stays
+    /// disabled; an empty `enabled_tools` enables every tool.
+    pub fn is_tool_enabled(&self, tool: &str) -> bool {
+        if self.disabled_tools.iter().any(|t| t == tool) {
+            return false;
+        }
+        self.enabled_tools.is_empty() || self.enabled_tools.iter().any(|t| t == tool)
+    }
+
+    /// Serializes the configuration with secrets removed, for display to clients
+    ///
+    /// RPC and facilitator URLs are reduced to scheme and host so embedded API
+    /// keys are not exposed; bearer tokens and the payer keypair path are
+    /// replaced with `[REDACTED]`.
+    pub fn sanitized(&self) -> Value {
+        fn sanitize_url(url: Option<&mut Value>) {
+            if let Some(url) = url {
+                if let Some(sanitized) = url.as_str().map(sanitize_for_logging) {
+                    *url = Value::String(sanitized);
+                }
+            }
+        }
+
+        let mut value = serde_json::to_value(self).unwrap_or(Value::Null);
+        sanitize_url(value.get_mut("rpc_url"));
+        if let Some(urls) = value.get_mut("rpc_urls").and_then(Value::as_array_mut) {
+            urls.iter_mut().for_each(|url| sanitize_url(Some(url)));
+        }
+        if let Some(networks) = value.get_mut("svm_networks").and_then(Value::as_object_mut) {
+            for network in networks.values_mut() {
+                sanitize_url(network.get_mut("rpc_url"));
+            }
+        }
+        sanitize_url(value.get_mut("sbpf_deploy").and_then(|deploy| deploy.get_mut("rpc_url")));
+        sanitize_url(value.get_mut("x402").and_then(|x402| x402.get_mut("facilitator_base_url")));
+
+        let secrets = ["bearer_token", "bearer_tokens", "payer_keypair_path"].map(String::from);
+        crate::logging::redact_body_fields(&value, &secrets)
+    }
+
     /// Validates the configuration for security and correctness
     ///
     /// # Returns
@@ -156,6 +596,76 @@ impl Config {
         // Validate commitment level
         validate_commitment(&self.commitment).context("Invalid commitment level")?;
 
+        if self.max_concurrent_rpc == 0 {
+            return Err(anyhow::anyhow!("max_concurrent_rpc must be greater than zero"));
+        }
+
+        if self.rpc_timeout_seconds == 0 {
+            return Err(anyhow::anyhow!("rpc_timeout_seconds must be greater than zero"));
+        }
+
+        if self.max_in_flight_requests == 0 {
+            return Err(anyhow::anyhow!("max_in_flight_requests must be greater than zero"));
+        }
+
+        if self.max_request_bytes == 0 {
+            return Err(anyhow::anyhow!("max_request_bytes must be greater than zero"));
+        }
+
+        // A misspelled name would silently hide a tool or leave one exposed
+        for (field, tools) in [
+            ("enabled_tools", &self.enabled_tools),
+            ("disabled_tools", &self.disabled_tools),
+        ] {
+            if let Some(unknown) = tools.iter().find(|tool| !crate::tools::is_known_tool(tool)) {
+                return Err(anyhow::anyhow!("{} entry '{}' is not a known tool", field, unknown));
+            }
+        }
+
+        if self.max_block_range_slots == 0 {
+            return Err(anyhow::anyhow!("max_block_range_slots must be greater than zero"));
+        }
+
+        if self.websocket_send_queue_capacity == 0 {
+            return Err(anyhow::anyhow!("websocket_send_queue_capacity must be greater than zero"));
+        }
+
+        if self.timeouts.websocket_ping_interval_seconds == 0 {
+            return Err(anyhow::anyhow!("websocket_ping_interval_seconds must be greater than zero"));
+        }
+
+        if let Some(encoding) = &self.default_account_encoding {
+            if account_encoding_from_name(encoding).is_none() {
+                return Err(anyhow::anyhow!(
+                    "default_account_encoding '{}' must be base58, base64, base64+zstd or jsonParsed",
+                    encoding
+                ));
+            }
+        }
+
+        if let Some(addr) = &self.metrics_bind_addr {
+            addr.parse::<std::net::SocketAddr>()
+                .with_context(|| format!("metrics_bind_addr '{addr}' must be an IP address and port"))?;
+        }
+
+        if !self.metrics_path.starts_with('/') || ["/", "/health", "/api/mcp", "/llms.txt"].contains(&self.metrics_path.as_str()) {
+            return Err(anyhow::anyhow!(
+                "metrics_path '{}' must start with '/' and not collide with another route",
+                self.metrics_path
+            ));
+        }
+
+        if self.cors_allowed_origins.iter().any(|origin| origin == "*") {
+            if self.cors_allowed_origins.len() > 1 {
+                return Err(anyhow::anyhow!("cors_allowed_origins must be \"*\" alone or a list of origins"));
+            }
+        } else {
+            validate_origins("cors_allowed_origins", &self.cors_allowed_origins)?;
+        }
+        validate_origins("allowed_ws_origins", &self.allowed_ws_origins)?;
+
+        validate_rpc_url(&self.sbpf_deploy.rpc_url).context("Invalid sbpf_deploy RPC URL")?;
+
         // Validate all SVM network configurations
         for (network_id, network) in &self.svm_networks {
             validate_rpc_url(&network.rpc_url)
@@ -167,6 +677,11 @@ impl Config {
                     network_id
                 ));
             }
+
+            if let Some(commitment) = &network.commitment {
+                validate_commitment(commitment)
+                    .with_context(|| format!("Invalid commitment for network '{network_id}'"))?;
+            }
         }
 
         // Validate x402 configuration if enabled
@@ -192,8 +707,11 @@ impl Config {
         self.validate()
             .context("Cannot save invalid configuration")?;
 
+        // Write a sibling file and rename it over config.json so a crash or
+        // full disk never leaves a truncated config behind
         let content = serde_json::to_string_pretty(self).context("Failed to serialize config")?;
-        fs::write("config.json", content).context("Failed to write config.json")?;
+        fs::write("config.json.tmp", content).context("Failed to write config.json.tmp")?;
+        fs::rename("config.json.tmp", "config.json").context("Failed to replace config.json")?;
 
         log::info!("Configuration saved to config.json");
         Ok(())
@@ -0,0 +1,129 @@
+//! Reloads config.json when it is edited on disk
+//!
+//! Opt-in through `watch_config`. Each change is parsed and validated before
+//! it replaces the running configuration; an invalid edit is logged and the
+//! previous configuration stays in effect.
+
+use crate::config::Config;
+use crate::server::ServerState;
+use anyhow::{Context, Result};
+use notify::{Event, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
+
+/// Editors often save in several writes; wait this long for them to settle
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Settings read once when the HTTP server starts, which a reload cannot change
+const RESTART_ONLY_FIELDS: &[&str] = &["max_request_bytes", "cors_allowed_origins", "metrics_path", "metrics_bind_addr"];
+
+/// Starts watching `path` and reloading it into `state` on every change
+///
+/// The parent directory is watched rather than the file itself, so editors
+/// that save by replacing the file are still picked up.
+///
+/// # Errors
+/// * The platform file watcher cannot be created or attached to the directory
+pub fn start_config_watcher_task(path: PathBuf, state: Arc<RwLock<ServerState>>) -> Result<JoinHandle<()>> {
+    let (changed_tx, mut changed_rx) = mpsc::unbounded_channel();
+    let file_name = path.file_name().map(|name| name.to_os_string());
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| match event {
+        Ok(event) if !event.kind.is_access() => {
+            if event.paths.iter().any(|changed| changed.file_name() == file_name.as_deref()) {
+                let _ = changed_tx.send(());
+            }
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Config watcher error: {e}"),
+    })
+    .context("Failed to create config file watcher")?;
+
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    watcher
+        .watch(&directory, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", directory.display()))?;
+    log::info!("Watching {} for configuration changes", path.display());
+
+    Ok(tokio::spawn(async move {
+        // Dropping the watcher stops event delivery, so the task owns it
+        let _watcher = watcher;
+        while changed_rx.recv().await.is_some() {
+            tokio::time::sleep(RELOAD_DEBOUNCE).await;
+            while changed_rx.try_recv().is_ok() {}
+            let _ = reload_config(&path, &state).await;
+        }
+    }))
+}
+
+/// Reloads the configuration file into the running server
+///
+/// # Returns
+/// * `Result<Vec<String>>` - The settings that changed, empty when the file
+///   matches the running configuration
+///
+/// # Errors
+/// * The file is missing, unparsable or fails validation; the running
+///   configuration is left untouched
+pub async fn reload_config(path: &Path, state: &Arc<RwLock<ServerState>>) -> Result<Vec<String>> {
+    let new_config = match Config::from_file(path) {
+        Ok(config) => config,
+        Err(e) => {
+            log::error!("Rejected change to {}, keeping previous config: {e:#}", path.display());
+            return Err(e);
+        }
+    };
+
+    let mut state_guard = state.write().await;
+    let changed = state_guard.config.changed_fields(&new_config);
+    if changed.is_empty() {
+        log::debug!("{} changed on disk but matches the running config", path.display());
+        return Ok(changed);
+    }
+
+    log::info!("Reloading {}: changed {}", path.display(), changed.join(", "));
+    // These are built into the HTTP routers when the server starts
+    for field in changed.iter().filter(|field| RESTART_ONLY_FIELDS.contains(&field.as_str())) {
+        log::warn!("{field} changed; restart the server for the new value to apply");
+    }
+    state_guard.update_config(new_config);
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reload_config_applies_valid_and_rejects_invalid_edits() {
+        let path = std::env::temp_dir().join(format!("solana-mcp-config-{}.json", uuid::Uuid::new_v4()));
+        let config = Config::load().expect("Failed to load config");
+        let state = Arc::new(RwLock::new(ServerState::new(config.clone())));
+
+        let mut edited = serde_json::to_value(&config).unwrap();
+        edited["commitment"] = "finalized".into();
+        edited["timeouts"]["rpc_permit_wait_ms"] = 1234.into();
+        std::fs::write(&path, edited.to_string()).unwrap();
+
+        let mut changed = reload_config(&path, &state).await.unwrap();
+        changed.sort();
+        assert_eq!(changed, vec!["commitment", "timeouts.rpc_permit_wait_ms"]);
+        assert_eq!(state.read().await.config.commitment, "finalized");
+        assert!(reload_config(&path, &state).await.unwrap().is_empty());
+
+        edited["commitment"] = "latest".into();
+        std::fs::write(&path, edited.to_string()).unwrap();
+        assert!(reload_config(&path, &state).await.is_err());
+        std::fs::write(&path, "{ not json").unwrap();
+        assert!(reload_config(&path, &state).await.is_err());
+        assert_eq!(state.read().await.config.commitment, "finalized");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(reload_config(&path, &state).await.is_err());
+    }
+}
@@ -3,6 +3,7 @@ pub mod core;
 pub mod guides;
 
 use std::collections::HashMap;
+use std::fmt::Write;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -13,6 +14,33 @@ pub struct RpcMethodDoc {
     pub examples: Vec<Example>,
 }
 
+impl RpcMethodDoc {
+    /// Renders the method's parameters, response fields and examples as markdown
+    pub fn to_markdown(&self, method: &str) -> String {
+        let mut markdown = format!("# {method}\n\n{}\n", self.description);
+        for (heading, fields) in [("Parameters", &self.request_params), ("Response", &self.response_fields)] {
+            if fields.is_empty() {
+                continue;
+            }
+            let _ = write!(markdown, "\n## {heading}\n\n");
+            for field in fields {
+                let _ = writeln!(markdown, "{}", field.to_markdown());
+            }
+        }
+        if !self.examples.is_empty() {
+            markdown.push_str("\n## Examples\n");
+        }
+        for example in &self.examples {
+            let _ = write!(
+                markdown,
+                "\n### {}\n\nRequest:\n\n```json\n{}\n```\n\nResponse:\n\n```json\n{}\n```\n",
+                example.description, example.request, example.response
+            );
+        }
+        markdown
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ParamDoc {
     pub name: String,
@@ -21,6 +49,14 @@ pub struct ParamDoc {
     pub required: bool,
 }
 
+impl ParamDoc {
+    /// Renders the field as a markdown list item, e.g. "- `pubkey` (string): ..."
+    pub fn to_markdown(&self) -> String {
+        let optional = if self.required { "" } else { ", optional" };
+        format!("- `{}` ({}{optional}): {}", self.name, self.type_info, self.description)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Example {
     pub description: String,
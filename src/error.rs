@@ -318,6 +318,40 @@ impl McpError {
         Value::Object(log_data)
     }
 
+    /// Builds the `data` object attached to JSON-RPC error responses
+    ///
+    /// Carries `errorType` plus whichever of `requestId`, `method` and
+    /// `parameter` are known. Validation errors always include `parameter`,
+    /// null when unknown. RPC URLs and endpoints are reduced to scheme and host;
+    /// source error text is left out because it may echo full URLs.
+    pub fn to_error_data(&self) -> Value {
+        let mut data = serde_json::Map::new();
+        data.insert("errorType".to_string(), Value::String(self.error_type().to_string()));
+        if let Some(request_id) = self.request_id() {
+            data.insert("requestId".to_string(), Value::String(request_id.to_string()));
+        }
+        if let Some(method) = self.method() {
+            data.insert("method".to_string(), Value::String(method.to_string()));
+        }
+
+        match self {
+            McpError::Validation { parameter, .. } => {
+                data.insert("parameter".to_string(), serde_json::json!(parameter));
+            }
+            McpError::Rpc { rpc_url: Some(url), .. } => {
+                let sanitized = crate::validation::sanitize_for_logging(url);
+                data.insert("rpcUrl".to_string(), Value::String(sanitized));
+            }
+            McpError::Network { endpoint: Some(endpoint), .. } => {
+                let sanitized = crate::validation::sanitize_for_logging(endpoint);
+                data.insert("endpoint".to_string(), Value::String(sanitized));
+            }
+            _ => {}
+        }
+
+        Value::Object(data)
+    }
+
     /// Returns the error type as a string for categorization
     pub fn error_type(&self) -> &'static str {
         match self {
@@ -400,6 +434,26 @@ mod tests {
         assert!(log_value.get("rpc_url").is_some());
     }
 
+    #[test]
+    fn test_error_data_redacts_url() {
+        let request_id = Uuid::new_v4();
+        let error = McpError::rpc("Connection timeout")
+            .with_request_id(request_id)
+            .with_method("getBalance")
+            .with_rpc_url("https://rpc.example.com/v2/secret-key");
+
+        let data = error.to_error_data();
+        assert_eq!(data["errorType"], "rpc");
+        assert_eq!(data["requestId"], request_id.to_string());
+        assert_eq!(data["method"], "getBalance");
+        assert_eq!(data["rpcUrl"], "https://rpc.example.com/[PATH_REDACTED]");
+
+        let data = McpError::validation("Missing pubkey").to_error_data();
+        assert_eq!(data["errorType"], "validation");
+        assert_eq!(data["parameter"], Value::Null);
+        assert!(data.get("requestId").is_none());
+    }
+
     #[test]
     fn test_derived_traits() {
         let request_id = Uuid::new_v4();
@@ -1,6 +1,7 @@
 use axum::{
-    extract::State,
-    http::{StatusCode, HeaderMap, header::CONTENT_TYPE},
+    extract::{rejection::JsonRejection, DefaultBodyLimit, State},
+    http::{StatusCode, HeaderMap, HeaderValue, Method, header::{AUTHORIZATION, CONTENT_TYPE}},
+    middleware,
     response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
@@ -9,17 +10,25 @@ use serde_json::Value;
 use tokio::net::TcpListener;
 use tokio::time::Duration;
 use tower::ServiceBuilder;
+use tower_http::cors::{AllowOrigin, CorsLayer};
 use tower_http::timeout::TimeoutLayer;
 use tracing::{info, error, debug};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use crate::server::ServerState;
 use crate::transport::{JsonRpcRequest, JsonRpcVersion};
+use crate::auth::require_configured_bearer_token;
 use crate::config::Config;
 
 /// HTTP request timeout (can be overridden by config)
 const DEFAULT_HTTP_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Metrics route used when no configuration is supplied
+const DEFAULT_METRICS_PATH: &str = "/metrics";
+
+/// How long browsers may cache a CORS preflight result
+const CORS_PREFLIGHT_MAX_AGE: Duration = Duration::from_secs(3600);
+
 /// HTTP server for metrics, health, and MCP API endpoints
 pub struct McpHttpServer {
     port: u16,
@@ -57,37 +66,138 @@ impl McpHttpServer {
             .unwrap_or(DEFAULT_HTTP_REQUEST_TIMEOUT);
 
         let app = if let Some(state) = &self.server_state {
-            // Create router with MCP API endpoints and state
-            Router::new()
-                .route("/metrics", get(metrics_handler))
-                .route("/health", get(health_handler))
-                .route("/api/mcp", post(mcp_api_handler))
-                .route("/llms.txt", get(llms_txt_handler))
-                .with_state(state.clone())
+            // Bearer tokens are checked against the live config on each request;
+            // these settings are built into the routers and need a restart
+            let (max_request_bytes, auth_enabled, metrics_path, metrics_bind_addr, cors_allowed_origins) = {
+                let state = state.read().await;
+                (
+                    state.config.max_request_bytes,
+                    state.config.auth.is_enabled(),
+                    state.config.metrics_path.clone(),
+                    state.config.metrics_bind_addr.clone(),
+                    state.config.cors_allowed_origins.clone(),
+                )
+            };
+            if auth_enabled {
+                info!("Bearer-token authentication enabled for /api/mcp");
+            }
+            // A dedicated metrics listener keeps metrics off the API interface
+            let api_metrics_path = match metrics_bind_addr {
+                Some(addr) => {
+                    let router = with_cors(metrics_router(&metrics_path), &cors_allowed_origins)
+                        .layer(ServiceBuilder::new().layer(TimeoutLayer::new(http_timeout)));
+                    tokio::spawn(async move {
+                        if let Err(e) = serve(&addr, router).await {
+                            error!("Metrics server failed: {}", e);
+                        }
+                    });
+                    None
+                }
+                None => Some(metrics_path),
+            };
+            let router = mcp_router(state.clone(), max_request_bytes, api_metrics_path.as_deref());
+            with_cors(router, &cors_allowed_origins)
                 .layer(ServiceBuilder::new()
                     .layer(TimeoutLayer::new(http_timeout))
                 )
         } else {
             // Create router with only metrics and health endpoints
-            Router::new()
-                .route("/metrics", get(metrics_handler))
-                .route("/health", get(health_handler))
-                .route("/llms.txt", get(llms_txt_handler))
+            let metrics_path = self.config.as_ref().map_or(DEFAULT_METRICS_PATH, |c| c.metrics_path.as_str());
+            let cors_allowed_origins = self.config.as_ref().map_or(&[][..], |c| c.cors_allowed_origins.as_slice());
+            with_cors(metrics_router(metrics_path), cors_allowed_origins)
                 .layer(ServiceBuilder::new()
                     .layer(TimeoutLayer::new(http_timeout))
                 )
         };
 
-        let addr = format!("0.0.0.0:{}", self.port);
+        // The API always listens publicly; a standalone metrics server may be bound elsewhere
+        let addr = match (&self.server_state, &self.config) {
+            (None, Some(config)) => config.metrics_bind_addr.clone(),
+            _ => None,
+        }
+        .unwrap_or_else(|| format!("0.0.0.0:{}", self.port));
         info!("Started HTTP server on {} with timeout {}s", 
               addr, 
               http_timeout.as_secs());
 
-        let listener = TcpListener::bind(&addr).await?;
-        
-        // Start server and run indefinitely
-        axum::serve(listener, app).await.map_err(|e| e.into())
+        serve(&addr, app).await
+    }
+}
+
+/// Binds `addr` and serves `app` until the listener fails
+async fn serve(addr: &str, app: Router) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let listener = TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await.map_err(|e| e.into())
+}
+
+/// Serves `app` on an ephemeral local port in the background, returning its address
+#[cfg(test)]
+pub(crate) async fn spawn_test_server(app: Router) -> std::net::SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    addr
+}
+
+/// Applies the CORS policy for `allowed_origins` to `router`
+///
+/// With no origins configured the router is returned unchanged, so browsers
+/// keep blocking cross-origin calls. `"*"` allows any origin. Preflight
+/// OPTIONS requests are answered by the layer before routing and auth.
+fn with_cors(router: Router, allowed_origins: &[String]) -> Router {
+    if allowed_origins.is_empty() {
+        return router;
     }
+    let allow_origin = if allowed_origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(allowed_origins.iter().filter_map(|origin| HeaderValue::from_str(origin).ok()))
+    };
+    router.layer(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods([Method::GET, Method::POST])
+            .allow_headers([AUTHORIZATION, CONTENT_TYPE])
+            .max_age(CORS_PREFLIGHT_MAX_AGE),
+    )
+}
+
+/// Build the router serving metrics at `metrics_path` alongside health and llms.txt
+fn metrics_router(metrics_path: &str) -> Router {
+    Router::new()
+        .route(metrics_path, get(metrics_handler))
+        .route("/health", get(health_handler))
+        .route("/llms.txt", get(llms_txt_handler))
+}
+
+/// Build the router serving MCP API endpoints alongside metrics and health
+///
+/// Request bodies larger than `max_request_bytes` are rejected with HTTP 413
+/// before they are buffered in full. The limit is fixed when the router is
+/// built, so reloading the config does not change it. When the config has
+/// auth tokens configured, `/api/mcp` additionally requires a matching bearer
+/// token, checked against the running config on every request. Metrics are
+/// served at `metrics_path` unless it is `None` (they are on a separate
+/// listener).
+fn mcp_router(
+    state: Arc<RwLock<ServerState>>,
+    max_request_bytes: usize,
+    metrics_path: Option<&str>,
+) -> Router {
+    let router = match metrics_path {
+        Some(path) => Router::new().route(path, get(metrics_handler)),
+        None => Router::new(),
+    };
+    router
+        .route("/health", get(health_handler))
+        .route(
+            "/api/mcp",
+            post(mcp_api_handler)
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_configured_bearer_token)),
+        )
+        .route("/llms.txt", get(llms_txt_handler))
+        .with_state(state)
+        .layer(DefaultBodyLimit::max(max_request_bytes))
 }
 
 /// Handler for /metrics endpoint
@@ -114,9 +224,31 @@ async fn metrics_handler() -> Response {
 async fn mcp_api_handler(
     State(server_state): State<Arc<RwLock<ServerState>>>,
     headers: HeaderMap,
-    Json(request): Json<serde_json::Value>,
+    payload: Result<Json<serde_json::Value>, JsonRejection>,
 ) -> Response {
-    debug!("Received MCP API request: {}", serde_json::to_string(&request).unwrap_or_default());
+    let request = match payload {
+        Ok(Json(request)) => request,
+        Err(rejection) if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+            error!("Rejected oversized MCP API request");
+            let mut response = create_json_rpc_error_response(
+                -32600,
+                "Invalid Request: request body exceeds the configured size limit",
+                None,
+            );
+            *response.status_mut() = StatusCode::PAYLOAD_TOO_LARGE;
+            return response;
+        }
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    if tracing::enabled!(tracing::Level::DEBUG) {
+        // Tool arguments may carry secrets such as payerKeypair
+        let redact_fields = server_state.read().await.config.log_redact_fields.clone();
+        debug!(
+            "Received MCP API request: {}",
+            crate::logging::redact_body_fields(&request, &redact_fields)
+        );
+    }
     
     // Validate Content-Type header (should be application/json for MCP)
     if let Some(content_type) = headers.get(CONTENT_TYPE) {
@@ -140,8 +272,16 @@ async fn mcp_api_handler(
         Err(error_response) => return *error_response,
     };
 
+    // Cancellations only reach calls made by the same client session; without
+    // a session header a request cannot be cancelled by anyone else
+    let session = match headers.get("mcp-session-id").and_then(|value| value.to_str().ok()) {
+        Some(session_id) => format!("{}:{session_id}", crate::logging::current_audit_actor()),
+        None => uuid::Uuid::new_v4().to_string(),
+    };
+
     // Process the MCP request through the existing handler
-    match crate::tools::handle_request(&serde_json::to_string(&request).unwrap_or_default(), server_state).await {
+    let message = serde_json::to_string(&request).unwrap_or_default();
+    match crate::server::with_call_session(session, crate::tools::handle_request(&message, server_state)).await {
         Ok(response_message) => {
             // Convert JsonRpcMessage back to proper JSON-RPC 2.0 format
             match serde_json::to_value(&response_message) {
@@ -290,6 +430,19 @@ pub fn start_metrics_server_task(port: u16) -> tokio::task::JoinHandle<()> {
     })
 }
 
+/// Start the metrics server in a background task using the given config
+///
+/// Binds `metrics_bind_addr` when configured, otherwise `0.0.0.0:<port>`, and
+/// serves metrics at `metrics_path`.
+pub fn start_metrics_server_task_with_config(port: u16, config: Arc<Config>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let server = McpHttpServer::new(port).with_config(config);
+        if let Err(e) = server.start().await {
+            error!("HTTP server failed: {}", e);
+        }
+    })
+}
+
 /// Start the HTTP server with MCP API support in a background task
 pub fn start_mcp_server_task(port: u16, server_state: Arc<RwLock<ServerState>>) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
@@ -303,6 +456,7 @@ pub fn start_mcp_server_task(port: u16, server_state: Arc<RwLock<ServerState>>)
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::AuthConfig;
 
     #[tokio::test]
     async fn test_metrics_handler() {
@@ -330,6 +484,143 @@ mod tests {
         // Real integration tests would be in a separate test file with proper setup
     }
 
+    #[tokio::test]
+    async fn test_mcp_api_rejects_oversized_body() {
+        use tower::ServiceExt;
+
+        let config = Config::load().expect("Failed to load config");
+        let state = Arc::new(RwLock::new(ServerState::new(config)));
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "getMultipleAccounts", "arguments": { "pubkeys": vec!["x"; 512] } }
+        })
+        .to_string();
+
+        let response = mcp_router(state, 256, Some(DEFAULT_METRICS_PATH))
+            .oneshot(
+                axum::http::Request::post("/api/mcp")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(axum::body::Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["error"]["code"], -32600);
+    }
+
+    #[tokio::test]
+    async fn test_mcp_api_requires_bearer_token_when_configured() {
+        use tower::ServiceExt;
+
+        let mut config = Config::load().expect("Failed to load config");
+        config.auth = AuthConfig {
+            bearer_token: Some("s3cret".to_string()),
+            bearer_tokens: vec!["rotated".to_string()],
+        };
+        let state = Arc::new(RwLock::new(ServerState::new(config)));
+        let router = mcp_router(state.clone(), 1024 * 1024, Some(DEFAULT_METRICS_PATH));
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#;
+        let request = |authorization: Option<&str>| {
+            let mut builder = axum::http::Request::post("/api/mcp").header(CONTENT_TYPE, "application/json");
+            if let Some(value) = authorization {
+                builder = builder.header(axum::http::header::AUTHORIZATION, value);
+            }
+            builder.body(axum::body::Body::from(body)).unwrap()
+        };
+
+        let missing = router.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+
+        let wrong = router.clone().oneshot(request(Some("Bearer nope"))).await.unwrap();
+        assert_eq!(wrong.status(), StatusCode::UNAUTHORIZED);
+
+        let rotated = router.clone().oneshot(request(Some("Bearer rotated"))).await.unwrap();
+        assert_ne!(rotated.status(), StatusCode::UNAUTHORIZED);
+
+        // Removing a token from the running config revokes it without a restart
+        state.write().await.config.auth.bearer_tokens.clear();
+        let revoked = router.clone().oneshot(request(Some("Bearer rotated"))).await.unwrap();
+        assert_eq!(revoked.status(), StatusCode::UNAUTHORIZED);
+        let kept = router.clone().oneshot(request(Some("Bearer s3cret"))).await.unwrap();
+        assert_ne!(kept.status(), StatusCode::UNAUTHORIZED);
+
+        // Metrics and health stay reachable for probes
+        let health = router
+            .oneshot(axum::http::Request::get("/health").body(axum::body::Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(health.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_path_and_separate_listener() {
+        use tower::ServiceExt;
+
+        crate::metrics::init_prometheus_metrics().expect("Failed to init metrics");
+        let get = |path: &str| axum::http::Request::get(path).body(axum::body::Body::empty()).unwrap();
+
+        let metrics = metrics_router("/internal/metrics");
+        let response = metrics.clone().oneshot(get("/internal/metrics")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = metrics.oneshot(get("/metrics")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // With a dedicated metrics listener the API router no longer exposes them
+        let config = Config::load().expect("Failed to load config");
+        let state = Arc::new(RwLock::new(ServerState::new(config)));
+        let api = mcp_router(state, 1024, None);
+        let response = api.clone().oneshot(get("/metrics")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = api.oneshot(get("/health")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight_for_allowed_origins_only() {
+        use axum::http::header::{ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN};
+        use tower::ServiceExt;
+
+        let mut config = Config::load().expect("Failed to load config");
+        config.auth = AuthConfig { bearer_token: Some("s3cret".to_string()), bearer_tokens: Vec::new() };
+        let state = Arc::new(RwLock::new(ServerState::new(config)));
+        let api = mcp_router(state, 1024, Some(DEFAULT_METRICS_PATH));
+        let preflight = |origin: &str| {
+            axum::http::Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/api/mcp")
+                .header(ORIGIN, origin)
+                .header(ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                .body(axum::body::Body::empty())
+                .unwrap()
+        };
+
+        // Default: no CORS headers, so browsers keep blocking cross-origin calls
+        let response = api.clone().oneshot(preflight("https://app.example.com")).await.unwrap();
+        assert!(response.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+
+        // Preflight is answered before the bearer-token check
+        let cors_api = with_cors(api.clone(), &["https://app.example.com".to_string()]);
+        let response = cors_api.clone().oneshot(preflight("https://app.example.com")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], "https://app.example.com");
+        let response = cors_api.oneshot(preflight("https://evil.example.com")).await.unwrap();
+        assert!(response.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+
+        let any_api = with_cors(api, &["*".to_string()]);
+        let request = axum::http::Request::get(DEFAULT_METRICS_PATH)
+            .header(ORIGIN, "https://dashboard.example.com")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = any_api.oneshot(request).await.unwrap();
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+    }
+
     #[tokio::test]
     async fn test_llms_txt_handler() {
         let _response = llms_txt_handler().await;
@@ -1,9 +1,16 @@
+pub mod account_layouts;
+pub mod auth;
 pub mod cache;
 pub mod config;
+pub mod config_watcher;
+pub mod docs;
 pub mod error;
 pub mod http_server;
 pub mod logging;
 pub mod metrics;
+pub mod network_health;
+pub mod program_errors;
+pub mod progress;
 pub mod protocol;
 pub mod rpc;
 pub mod sbpf;
@@ -17,11 +24,13 @@ pub mod websocket_server;
 pub mod x402;
 
 pub use cache::{with_cache, CacheConfig, RpcCache, GLOBAL_RPC_CACHE};
-pub use config::{Config, SvmNetwork};
+pub use config::{AuthConfig, Config, SvmNetwork};
+pub use config_watcher::start_config_watcher_task;
 pub use error::{McpError, McpResult};
-pub use http_server::{start_metrics_server_task, start_mcp_server_task};
+pub use http_server::{start_metrics_server_task, start_metrics_server_task_with_config, start_mcp_server_task};
 pub use logging::{init_logging, get_metrics};
 pub use metrics::{init_prometheus_metrics, get_metrics_text, PROMETHEUS_METRICS};
-pub use server::{start_server, ServerState};
-pub use transport::CustomStdioTransport;
+pub use network_health::start_network_health_task;
+pub use server::{run_self_test, start_server, ServerState};
+pub use transport::{CustomStdioTransport, StdioFraming};
 pub use websocket_server::start_websocket_server_task;
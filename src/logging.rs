@@ -425,6 +425,127 @@ pub fn log_server_error(
     error!("Server error: {}", error_message);
 }
 
+/// Placeholder logged in place of redacted body fields
+const REDACTED: &str = "[REDACTED]";
+
+/// Copies a JSON body with the values of the named fields redacted
+///
+/// Field names match case-insensitively at any depth, so `authorization`
+/// also covers an `Authorization` key nested inside tool arguments.
+pub fn redact_body_fields(body: &Value, fields: &[String]) -> Value {
+    match body {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let value = if fields.iter().any(|field| field.eq_ignore_ascii_case(key)) {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact_body_fields(value, fields)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(|item| redact_body_fields(item, fields)).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Log a full MCP request or response body with configured fields redacted
+///
+/// Only used when `log_bodies` is enabled. Bodies that are not valid JSON
+/// cannot be redacted, so only their size is logged.
+pub fn log_body(direction: &str, body: &str, redact_fields: &[String]) {
+    match serde_json::from_str::<Value>(body) {
+        Ok(json) => info!(direction, "MCP {} body: {}", direction, redact_body_fields(&json, redact_fields)),
+        Err(_) => info!(direction, "MCP {} body: [{} bytes, not JSON]", direction, body.len()),
+    }
+}
+
+/// Tracing target of the configuration audit trail, filterable on its own
+/// (e.g. `RUST_LOG=info,solana_mcp_server::audit=info`)
+pub const AUDIT_TARGET: &str = "solana_mcp_server::audit";
+
+/// Actor recorded when the request carried no authenticated identity
+pub const ANONYMOUS_ACTOR: &str = "anonymous";
+
+tokio::task_local! {
+    /// Who issued the request currently being handled
+    static AUDIT_ACTOR: String;
+}
+
+/// Runs `future` with `actor` recorded as the originator of its audit events
+pub async fn with_audit_actor<F: std::future::Future>(actor: String, future: F) -> F::Output {
+    AUDIT_ACTOR.scope(actor, future).await
+}
+
+/// The actor of the request being handled, or `ANONYMOUS_ACTOR` outside one
+pub fn current_audit_actor() -> String {
+    AUDIT_ACTOR
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| ANONYMOUS_ACTOR.to_string())
+}
+
+/// Identifies a bearer token in audit events without revealing it
+///
+/// Uses the first 8 characters of the token's SHA-256 hash, which is stable
+/// across restarts so operators can tell configured tokens apart.
+pub fn token_actor(token: &str) -> String {
+    let digest = solana_sdk::hash::hash(token.as_bytes()).to_string();
+    format!("token:{}", &digest[..8])
+}
+
+/// Builds the audit event for a change to an SVM network
+///
+/// `before` is `None` for a newly added network. RPC URLs are sanitized so
+/// API keys embedded in them stay out of the trail.
+pub fn audit_event(
+    tool: &str,
+    network_id: &str,
+    actor: &str,
+    before: Option<&crate::config::SvmNetwork>,
+    after: Option<&crate::config::SvmNetwork>,
+) -> Value {
+    let snapshot = |network: Option<&crate::config::SvmNetwork>| {
+        network.map_or(Value::Null, |network| {
+            serde_json::json!({
+                "name": network.name,
+                "rpcUrl": crate::validation::sanitize_for_logging(&network.rpc_url),
+                "enabled": network.enabled,
+                "commitment": network.commitment,
+            })
+        })
+    };
+    serde_json::json!({
+        "tool": tool,
+        "networkId": network_id,
+        "actor": actor,
+        "before": snapshot(before),
+        "after": snapshot(after),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Records a network mutation on the audit target, attributed to the current actor
+pub fn log_audit_event(
+    tool: &str,
+    network_id: &str,
+    before: Option<&crate::config::SvmNetwork>,
+    after: Option<&crate::config::SvmNetwork>,
+) {
+    let event = audit_event(tool, network_id, &current_audit_actor(), before, after);
+    info!(
+        target: AUDIT_TARGET,
+        tool = %event["tool"],
+        network_id = %event["networkId"],
+        actor = %event["actor"],
+        before = %event["before"],
+        after = %event["after"],
+        timestamp = %event["timestamp"],
+        "Configuration mutation"
+    );
+}
+
 /// Create a new request ID for tracing
 pub fn new_request_id() -> Uuid {
     Uuid::new_v4()
@@ -673,6 +794,26 @@ mod tests {
         assert_eq!(metrics.failed_calls_by_method.get("getBalance").map(|v| v.load(Ordering::Relaxed)), Some(1));
     }
 
+    #[test]
+    fn test_redact_body_fields() {
+        let body = serde_json::json!({
+            "method": "tools/call",
+            "params": {
+                "name": "sendTransaction",
+                "arguments": { "transaction": "AQID", "skipPreflight": true },
+                "headers": [{ "Authorization": "Bearer secret" }]
+            }
+        });
+
+        let redacted = redact_body_fields(&body, &["transaction".to_string(), "authorization".to_string()]);
+        assert_eq!(redacted["params"]["arguments"]["transaction"], REDACTED);
+        assert_eq!(redacted["params"]["arguments"]["skipPreflight"], true);
+        assert_eq!(redacted["params"]["headers"][0]["Authorization"], REDACTED);
+        assert_eq!(redacted["method"], "tools/call");
+
+        assert_eq!(redact_body_fields(&body, &[]), body);
+    }
+
     #[test]
     fn test_params_summary_creation() {
         let params = serde_json::json!({
@@ -842,4 +983,35 @@ mod tests {
         assert!(transaction.signatures.is_empty());
         assert!(transaction.message.instructions.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_audit_event_attributes_actor_and_sanitizes_urls() {
+        use crate::config::SvmNetwork;
+
+        let before = SvmNetwork {
+            name: "Devnet".to_string(),
+            rpc_url: "https://rpc.example.com/secret-api-key".to_string(),
+            enabled: true,
+            commitment: None,
+        };
+        let after = SvmNetwork { enabled: false, ..before.clone() };
+
+        let actor = token_actor("s3cret");
+        assert!(actor.starts_with("token:") && !actor.contains("s3cret"));
+        assert_eq!(actor, token_actor("s3cret"));
+
+        let event = with_audit_actor(actor.clone(), async {
+            audit_event("disableSvmNetwork", "devnet", &current_audit_actor(), Some(&before), Some(&after))
+        })
+        .await;
+        assert_eq!(event["tool"], "disableSvmNetwork");
+        assert_eq!(event["networkId"], "devnet");
+        assert_eq!(event["actor"], actor);
+        assert_eq!(event["before"]["enabled"], true);
+        assert_eq!(event["after"]["enabled"], false);
+        assert!(!event["before"]["rpcUrl"].as_str().unwrap().contains("secret-api-key"));
+        assert!(event["timestamp"].is_string());
+
+        assert_eq!(current_audit_actor(), ANONYMOUS_ACTOR);
+    }
 }
\ No newline at end of file
@@ -1,7 +1,8 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use solana_mcp_server::{init_logging, start_server, start_mcp_server_task, start_websocket_server_task, Config, ServerState};
+use solana_mcp_server::{init_logging, start_config_watcher_task, start_network_health_task, run_self_test, start_server, start_mcp_server_task, start_websocket_server_task, Config, ServerState, StdioFraming};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
 #[derive(Parser)]
@@ -15,7 +16,11 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Run as stdio transport (default mode)
-    Stdio,
+    Stdio {
+        /// Message framing: auto, line or content-length (default: stdio_framing from config)
+        #[arg(long)]
+        framing: Option<StdioFraming>,
+    },
     /// Run as web service on HTTP
     Web {
         /// Port to run the web service on
@@ -28,21 +33,27 @@ enum Commands {
         #[arg(short, long, default_value = "8900")]
         port: u16,
     },
+    /// Check that the server answers initialize and tools/list, then exit 0 or 1
+    SelfTest {
+        /// Seconds to wait for both responses
+        #[arg(long, default_value_t = solana_mcp_server::server::SELF_TEST_TIMEOUT.as_secs())]
+        timeout: u64,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
     
-    match cli.command.unwrap_or(Commands::Stdio) {
-        Commands::Stdio => {
+    match cli.command.unwrap_or(Commands::Stdio { framing: None }) {
+        Commands::Stdio { framing } => {
             // For stdio mode, logging MUST go to stderr to avoid corrupting the JSON-RPC protocol on stdout
             if let Err(e) = init_logging(Some("info"), true) {
                 eprintln!("Failed to initialize logging: {e}");
                 std::process::exit(1);
             }
             tracing::info!("Starting Solana MCP server in stdio mode...");
-            start_server().await
+            start_server(framing).await
         }
         Commands::Web { port } => {
             // For web mode, logging can go to stdout since it doesn't interfere with HTTP protocol
@@ -62,6 +73,19 @@ async fn main() -> Result<()> {
             tracing::info!("Starting Solana MCP server in WebSocket mode on port {}...", port);
             start_websocket_service(port).await
         }
+        Commands::SelfTest { timeout } => {
+            // Logs go to stderr, as in stdio mode
+            if let Err(e) = init_logging(Some("info"), true) {
+                eprintln!("Failed to initialize logging: {e}");
+                std::process::exit(1);
+            }
+            if let Err(e) = run_self_test(Duration::from_secs(timeout)).await {
+                eprintln!("Self-test failed: {e:#}");
+                std::process::exit(1);
+            }
+            eprintln!("Self-test passed");
+            Ok(())
+        }
     }
 }
 
@@ -83,17 +107,31 @@ async fn start_web_service(port: u16) -> Result<()> {
     );
 
     // Create server state
+    let watch_config = config.watch_config;
+    let metrics_endpoint = match &config.metrics_bind_addr {
+        Some(addr) => format!("{} on {}", config.metrics_path, addr),
+        None => config.metrics_path.clone(),
+    };
     let mut server_state = ServerState::new(config);
     server_state.initialized = true; // Auto-initialize for web service mode
     let state = Arc::new(RwLock::new(server_state));
 
+    if watch_config {
+        if let Err(e) = start_config_watcher_task("config.json".into(), state.clone()) {
+            tracing::error!("Config hot reload disabled: {:#}", e);
+        }
+    }
+
+    let _network_health_handle = start_network_health_task(state.clone()).await;
+    tokio::spawn(solana_mcp_server::rpc::capabilities::probe_node_capabilities(state.clone()));
+
     // Start the MCP HTTP server
     let server_handle = start_mcp_server_task(port, state);
     
     tracing::info!("MCP web service started on port {}", port);
     tracing::info!("Available endpoints:");
     tracing::info!("  POST /api/mcp - MCP JSON-RPC API");
-    tracing::info!("  GET  /metrics - Prometheus metrics");
+    tracing::info!("  GET  {} - Prometheus metrics", metrics_endpoint);
     tracing::info!("  GET  /health  - Health check");
     
     // Wait for the server to complete
@@ -1,5 +1,5 @@
 use prometheus::{
-    CounterVec, GaugeVec, HistogramOpts, HistogramVec, Opts, Registry, Encoder, TextEncoder
+    Counter, CounterVec, Gauge, GaugeVec, HistogramOpts, HistogramVec, Opts, Registry, Encoder, TextEncoder
 };
 use std::sync::Arc;
 use once_cell::sync::Lazy;
@@ -28,6 +28,18 @@ pub struct PrometheusMetrics {
     pub cache_misses_total: CounterVec,
     /// Current cache size
     pub cache_size: GaugeVec,
+    /// Number of RPC calls currently in flight
+    pub rpc_in_flight: Gauge,
+    /// Messages dropped because a WebSocket client's send queue was full
+    pub websocket_messages_dropped: Counter,
+    /// Whether each upstream network answered its last health probe (1) or not (0)
+    pub network_up: GaugeVec,
+    /// Latency of each upstream network's last health probe
+    pub network_latency: GaugeVec,
+    /// Messages sent or received on the stdio transport
+    pub transport_messages_total: CounterVec,
+    /// Bytes of message payload sent or received on the stdio transport
+    pub transport_bytes_total: CounterVec,
 }
 
 impl PrometheusMetrics {
@@ -76,6 +88,35 @@ impl PrometheusMetrics {
             &["cache_type"]
         )?;
 
+        let rpc_in_flight = Gauge::new(
+            "solana_mcp_rpc_in_flight", "RPC calls currently in flight"
+        )?;
+
+        let websocket_messages_dropped = Counter::new(
+            "solana_mcp_websocket_messages_dropped_total",
+            "WebSocket messages dropped because a client's send queue was full"
+        )?;
+
+        let network_up = GaugeVec::new(
+            Opts::new("solana_mcp_network_up", "Whether the upstream network passed its last health probe"),
+            &["network"]
+        )?;
+
+        let network_latency = GaugeVec::new(
+            Opts::new("solana_mcp_network_latency_seconds", "Latency of the last upstream network health probe"),
+            &["network"]
+        )?;
+
+        let transport_messages_total = CounterVec::new(
+            Opts::new("solana_mcp_transport_messages_total", "Messages sent or received on the stdio transport"),
+            &["direction"]
+        )?;
+
+        let transport_bytes_total = CounterVec::new(
+            Opts::new("solana_mcp_transport_bytes_total", "Message payload bytes sent or received on the stdio transport"),
+            &["direction"]
+        )?;
+
         // Try to register metrics, but ignore "AlreadyReg" errors for tests
         let _ = METRICS_REGISTRY.register(Box::new(rpc_requests_total.clone()));
         let _ = METRICS_REGISTRY.register(Box::new(rpc_requests_successful.clone()));
@@ -85,6 +126,12 @@ impl PrometheusMetrics {
         let _ = METRICS_REGISTRY.register(Box::new(cache_hits_total.clone()));
         let _ = METRICS_REGISTRY.register(Box::new(cache_misses_total.clone()));
         let _ = METRICS_REGISTRY.register(Box::new(cache_size.clone()));
+        let _ = METRICS_REGISTRY.register(Box::new(rpc_in_flight.clone()));
+        let _ = METRICS_REGISTRY.register(Box::new(websocket_messages_dropped.clone()));
+        let _ = METRICS_REGISTRY.register(Box::new(network_up.clone()));
+        let _ = METRICS_REGISTRY.register(Box::new(network_latency.clone()));
+        let _ = METRICS_REGISTRY.register(Box::new(transport_messages_total.clone()));
+        let _ = METRICS_REGISTRY.register(Box::new(transport_bytes_total.clone()));
 
         Ok(Self {
             rpc_requests_total,
@@ -95,6 +142,12 @@ impl PrometheusMetrics {
             cache_hits_total,
             cache_misses_total,
             cache_size,
+            rpc_in_flight,
+            websocket_messages_dropped,
+            network_up,
+            network_latency,
+            transport_messages_total,
+            transport_bytes_total,
         })
     }
 
@@ -152,6 +205,45 @@ impl PrometheusMetrics {
             .with_label_values(&[cache_type])
             .set(size as f64);
     }
+
+    /// Increment the in-flight RPC call gauge
+    pub fn inc_in_flight(&self) {
+        self.rpc_in_flight.inc();
+    }
+
+    /// Decrement the in-flight RPC call gauge
+    pub fn dec_in_flight(&self) {
+        self.rpc_in_flight.dec();
+    }
+
+    /// Record a WebSocket message dropped by the drop-oldest overflow policy
+    pub fn record_websocket_message_dropped(&self) {
+        self.websocket_messages_dropped.inc();
+    }
+
+    /// Record the outcome of an upstream network health probe
+    pub fn record_network_probe(&self, network: &str, up: bool, latency_seconds: f64) {
+        self.network_up
+            .with_label_values(&[network])
+            .set(if up { 1.0 } else { 0.0 });
+        self.network_latency
+            .with_label_values(&[network])
+            .set(latency_seconds);
+    }
+
+    /// Record one stdio message; `direction` is "sent" or "received"
+    pub fn record_transport_message(&self, direction: &str, bytes: usize) {
+        self.transport_messages_total.with_label_values(&[direction]).inc();
+        self.transport_bytes_total
+            .with_label_values(&[direction])
+            .inc_by(bytes as f64);
+    }
+
+    /// Drop the health series of a network that is no longer probed
+    pub fn remove_network(&self, network: &str) {
+        let _ = self.network_up.remove_label_values(&[network]);
+        let _ = self.network_latency.remove_label_values(&[network]);
+    }
 }
 
 /// Global metrics instance
@@ -209,6 +301,35 @@ impl PrometheusMetrics {
             &["cache_type"]
         ).unwrap();
 
+        let rpc_in_flight = Gauge::new(
+            "solana_mcp_rpc_in_flight_test", "RPC calls currently in flight (test)"
+        ).unwrap();
+
+        let websocket_messages_dropped = Counter::new(
+            "solana_mcp_websocket_messages_dropped_total_test",
+            "WebSocket messages dropped because a client's send queue was full (test)"
+        ).unwrap();
+
+        let network_up = GaugeVec::new(
+            Opts::new("solana_mcp_network_up_test", "Whether the upstream network passed its last health probe (test)"),
+            &["network"]
+        ).unwrap();
+
+        let network_latency = GaugeVec::new(
+            Opts::new("solana_mcp_network_latency_seconds_test", "Latency of the last upstream network health probe (test)"),
+            &["network"]
+        ).unwrap();
+
+        let transport_messages_total = CounterVec::new(
+            Opts::new("solana_mcp_transport_messages_total_test", "Messages sent or received on the stdio transport (test)"),
+            &["direction"]
+        ).unwrap();
+
+        let transport_bytes_total = CounterVec::new(
+            Opts::new("solana_mcp_transport_bytes_total_test", "Message payload bytes sent or received on the stdio transport (test)"),
+            &["direction"]
+        ).unwrap();
+
         Self {
             rpc_requests_total,
             rpc_requests_successful,
@@ -218,6 +339,12 @@ impl PrometheusMetrics {
             cache_hits_total,
             cache_misses_total,
             cache_size,
+            rpc_in_flight,
+            websocket_messages_dropped,
+            network_up,
+            network_latency,
+            transport_messages_total,
+            transport_bytes_total,
         }
     }
 }
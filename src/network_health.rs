@@ -0,0 +1,114 @@
+//! Periodically probes upstream networks for the health metrics
+//!
+//! Every `network_probe_interval_seconds` the primary RPC client and each
+//! enabled SVM network client are sent `getHealth`. The outcome is recorded as
+//! `solana_mcp_network_up{network="..."}` (1 or 0) and
+//! `solana_mcp_network_latency_seconds{network="..."}`.
+
+use crate::metrics::PROMETHEUS_METRICS;
+use crate::server::ServerState;
+use crate::tools::NETWORK_PROBE_TIMEOUT;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// Label under which the primary RPC client is reported
+pub const PRIMARY_NETWORK_LABEL: &str = "primary";
+
+/// Starts probing on the configured interval
+///
+/// # Returns
+/// * `Option<JoinHandle<()>>` - The probe task, or `None` when the interval is 0
+pub async fn start_network_health_task(state: Arc<RwLock<ServerState>>) -> Option<JoinHandle<()>> {
+    let interval_seconds = state.read().await.config.network_probe_interval_seconds;
+    if interval_seconds == 0 {
+        log::info!("Network health probes disabled");
+        return None;
+    }
+    log::info!("Probing network health every {interval_seconds}s");
+
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut reported = HashSet::new();
+        loop {
+            interval.tick().await;
+            let probed = probe_networks(&state).await;
+            // Networks disabled since the last round would otherwise keep their last value
+            for network in reported.difference(&probed) {
+                PROMETHEUS_METRICS.remove_network(network);
+            }
+            reported = probed;
+        }
+    }))
+}
+
+/// Probes the primary client and every enabled SVM network once
+///
+/// # Returns
+/// * `HashSet<String>` - The network labels that were probed
+pub async fn probe_networks(state: &Arc<RwLock<ServerState>>) -> HashSet<String> {
+    let state_guard = state.read().await;
+    let clients = std::iter::once((PRIMARY_NETWORK_LABEL, &*state_guard.rpc_client))
+        .chain(state_guard.svm_clients.iter().map(|(id, client)| (id.as_str(), client)));
+
+    let probes = clients.map(|(network, client)| async move {
+        let (up, latency) = probe(client).await;
+        if !up {
+            log::warn!("Network '{network}' failed its health probe");
+        }
+        PROMETHEUS_METRICS.record_network_probe(network, up, latency.as_secs_f64());
+        network.to_string()
+    });
+    futures_util::future::join_all(probes).await.into_iter().collect()
+}
+
+/// Sends one bounded `getHealth`, returning whether it succeeded and how long it took
+async fn probe(client: &RpcClient) -> (bool, Duration) {
+    let start = Instant::now();
+    let outcome = tokio::time::timeout(NETWORK_PROBE_TIMEOUT, client.get_health()).await;
+    (matches!(outcome, Ok(Ok(()))), start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, SvmNetwork};
+    use axum::{routing::post, Json, Router};
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn test_probe_networks_records_up_and_down() {
+        let app = Router::new().route(
+            "/",
+            post(|Json(request): Json<Value>| async move {
+                Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": "ok" }))
+            }),
+        );
+        let addr = crate::http_server::spawn_test_server(app).await;
+
+        let mut config = Config::load().expect("Failed to load config");
+        config.rpc_url = format!("http://{addr}");
+        config.rpc_urls.clear();
+        config.svm_networks.insert(
+            "health-test-down".to_string(),
+            SvmNetwork {
+                name: "Down".to_string(),
+                rpc_url: "http://127.0.0.1:1".to_string(),
+                enabled: true,
+                commitment: None,
+            },
+        );
+        let state = Arc::new(RwLock::new(ServerState::new(config)));
+
+        let probed = probe_networks(&state).await;
+        assert_eq!(probed, HashSet::from([PRIMARY_NETWORK_LABEL.to_string(), "health-test-down".to_string()]));
+        let up = |network: &str| PROMETHEUS_METRICS.network_up.with_label_values(&[network]).get();
+        assert_eq!(up(PRIMARY_NETWORK_LABEL), 1.0);
+        assert_eq!(up("health-test-down"), 0.0);
+        assert!(PROMETHEUS_METRICS.network_latency.with_label_values(&[PRIMARY_NETWORK_LABEL]).get() > 0.0);
+    }
+}
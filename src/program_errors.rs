@@ -0,0 +1,161 @@
+//! Program error code decoding
+//!
+//! Maps the numeric `custom program error` codes reported by failed
+//! transactions to the error enums of well-known programs, and classifies
+//! codes in Anchor's reserved framework ranges.
+
+use num_traits::FromPrimitive;
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+/// First error code available to an Anchor program's own `#[error_code]` enum
+pub const ANCHOR_CUSTOM_ERROR_OFFSET: u32 = 6000;
+
+/// System program errors, indexed by code
+const SYSTEM_ERRORS: &[(&str, &str)] = &[
+    ("AccountAlreadyInUse", "An account with the same address already exists"),
+    ("ResultWithNegativeLamports", "Account does not have enough SOL to perform the operation"),
+    ("InvalidProgramId", "Cannot assign account to this program id"),
+    ("InvalidAccountDataLength", "Cannot allocate account data of this length"),
+    ("MaxSeedLengthExceeded", "Length of requested seed is too long"),
+    ("AddressWithSeedMismatch", "Provided address does not match addressed derived from seed"),
+    ("NonceNoRecentBlockhashes", "Advancing stored nonce requires a populated RecentBlockhashes sysvar"),
+    ("NonceBlockhashNotExpired", "Stored nonce is still in recent_blockhashes"),
+    ("NonceUnexpectedBlockhashValue", "Specified nonce does not match stored nonce"),
+];
+
+/// Anchor framework errors from `anchor_lang::error::ErrorCode`
+const ANCHOR_ERRORS: &[(u32, &str, &str)] = &[
+    (100, "InstructionMissing", "8 byte instruction identifier not provided"),
+    (101, "InstructionFallbackNotFound", "Fallback functions are not supported"),
+    (102, "InstructionDidNotDeserialize", "The program could not deserialize the given instruction"),
+    (103, "InstructionDidNotSerialize", "The program could not serialize the given instruction"),
+    (1000, "IdlInstructionStub", "The program was compiled without idl instructions"),
+    (1001, "IdlInstructionInvalidProgram", "Invalid program given to the IDL instruction"),
+    (2000, "ConstraintMut", "A mut constraint was violated"),
+    (2001, "ConstraintHasOne", "A has one constraint was violated"),
+    (2002, "ConstraintSigner", "A signer constraint was violated"),
+    (2003, "ConstraintRaw", "A raw constraint was violated"),
+    (2004, "ConstraintOwner", "An owner constraint was violated"),
+    (2005, "ConstraintRentExempt", "A rent exemption constraint was violated"),
+    (2006, "ConstraintSeeds", "A seeds constraint was violated"),
+    (2007, "ConstraintExecutable", "An executable constraint was violated"),
+    (2011, "ConstraintClose", "A close constraint was violated"),
+    (2012, "ConstraintAddress", "An address constraint was violated"),
+    (2013, "ConstraintZero", "Expected zero account discriminant"),
+    (2014, "ConstraintTokenMint", "A token mint constraint was violated"),
+    (2015, "ConstraintTokenOwner", "A token owner constraint was violated"),
+    (2019, "ConstraintSpace", "A space constraint was violated"),
+    (2500, "RequireViolated", "A require expression was violated"),
+    (2501, "RequireEqViolated", "A require_eq expression was violated"),
+    (2502, "RequireKeysEqViolated", "A require_keys_eq expression was violated"),
+    (2503, "RequireNeqViolated", "A require_neq expression was violated"),
+    (2504, "RequireKeysNeqViolated", "A require_keys_neq expression was violated"),
+    (2505, "RequireGtViolated", "A require_gt expression was violated"),
+    (2506, "RequireGteViolated", "A require_gte expression was violated"),
+    (3000, "AccountDiscriminatorAlreadySet", "The account discriminator was already set on this account"),
+    (3001, "AccountDiscriminatorNotFound", "No 8 byte discriminator was found on the account"),
+    (3002, "AccountDiscriminatorMismatch", "8 byte discriminator did not match what was expected"),
+    (3003, "AccountDidNotDeserialize", "Failed to deserialize the account"),
+    (3004, "AccountDidNotSerialize", "Failed to serialize the account"),
+    (3005, "AccountNotEnoughKeys", "Not enough account keys given to the instruction"),
+    (3006, "AccountNotMutable", "The given account is not mutable"),
+    (3007, "AccountOwnedByWrongProgram", "The given account is owned by a different program than expected"),
+    (3008, "InvalidProgramId", "Program ID was not as expected"),
+    (3009, "InvalidProgramExecutable", "Program account is not executable"),
+    (3010, "AccountNotSigner", "The given account did not sign"),
+    (3011, "AccountNotSystemOwned", "The given account is not owned by the system program"),
+    (3012, "AccountNotInitialized", "The program expected this account to be already initialized"),
+    (4100, "DeclaredProgramIdMismatch", "The declared program id does not match the actual program id"),
+];
+
+/// Parse an error code given as a decimal number or `0x`-prefixed hex string
+pub fn parse_error_code(value: &Value) -> Option<u32> {
+    if let Some(code) = value.as_u64() {
+        return u32::try_from(code).ok();
+    }
+    let text = value.as_str()?.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// Decode a custom program error code into a human-readable description
+///
+/// # Arguments
+/// * `program_id` - Program that returned the error
+/// * `code` - Value of `InstructionError::Custom`
+///
+/// # Returns
+/// * `Value` - `{programId, code, hex, program, name, message}`, or the raw
+///   code with a `note` when the program or code is not recognized
+pub fn decode_program_error(program_id: &Pubkey, code: u32) -> Value {
+    // `None` for programs without a table, `Some(None)` for a code missing from one
+    let known = if *program_id == solana_sdk::system_program::id() {
+        Some((
+            "system",
+            SYSTEM_ERRORS
+                .get(code as usize)
+                .map(|(name, message)| (name.to_string(), message.to_string())),
+        ))
+    } else if *program_id == spl_token::id() {
+        Some((
+            "spl-token",
+            spl_token::error::TokenError::from_u32(code).map(|e| (format!("{e:?}"), e.to_string())),
+        ))
+    } else if *program_id == spl_token_2022::id() {
+        Some((
+            "spl-token-2022",
+            spl_token_2022::error::TokenError::from_u32(code).map(|e| (format!("{e:?}"), e.to_string())),
+        ))
+    } else if *program_id == spl_associated_token_account::id() {
+        Some((
+            "spl-associated-token-account",
+            spl_associated_token_account::error::AssociatedTokenAccountError::from_u32(code)
+                .map(|e| (format!("{e:?}"), e.to_string())),
+        ))
+    } else {
+        None
+    };
+
+    let mut result = serde_json::json!({
+        "programId": program_id.to_string(),
+        "code": code,
+        "hex": format!("0x{code:x}")
+    });
+
+    if let Some((program, decoded)) = known {
+        result["program"] = Value::String(program.to_string());
+        match decoded {
+            Some((name, message)) => {
+                result["name"] = Value::String(name);
+                result["message"] = Value::String(message);
+            }
+            // These programs are not built with Anchor, so its ranges do not apply
+            None => {
+                result["note"] = Value::String(format!("Unknown error code for {program}; returned raw"));
+            }
+        }
+        return result;
+    }
+
+    // Anchor reserves codes below 6000 for framework errors; a program's own
+    // error enum starts at 6000
+    if let Some((_, name, message)) = ANCHOR_ERRORS.iter().find(|(c, _, _)| *c == code) {
+        result["program"] = Value::String("anchor".to_string());
+        result["name"] = Value::String(name.to_string());
+        result["message"] = Value::String(message.to_string());
+        result["note"] = Value::String("Anchor framework error; assumes the program is built with Anchor".to_string());
+    } else if code >= ANCHOR_CUSTOM_ERROR_OFFSET {
+        let index = code - ANCHOR_CUSTOM_ERROR_OFFSET;
+        result["program"] = Value::String("anchor".to_string());
+        result["customErrorIndex"] = Value::from(index);
+        result["note"] = Value::String(format!(
+            "Anchor custom error: variant #{index} of the program's #[error_code] enum; consult the program IDL for its message"
+        ));
+    } else {
+        result["note"] = Value::String("Unknown program or error code; returned raw".to_string());
+    }
+    result
+}
@@ -0,0 +1,106 @@
+//! MCP progress notifications for long-running tools
+//!
+//! A client that wants progress attaches `_meta.progressToken` to its
+//! tools/call. While the call runs, tools report their steps with
+//! [`report_progress`], which sends a `notifications/progress` message to the
+//! transport's sink. Outside such a call, or when the transport cannot deliver
+//! notifications mid-request (HTTP), reporting is a no-op.
+
+use crate::transport::{JsonRpcMessage, JsonRpcNotification, JsonRpcVersion};
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Channel to the transport that delivers notifications to the client
+pub type ProgressSink = UnboundedSender<JsonRpcMessage>;
+
+tokio::task_local! {
+    static PROGRESS: ProgressReporter;
+}
+
+/// Sends progress for one tools/call to the client that requested it
+#[derive(Debug, Clone)]
+pub struct ProgressReporter {
+    token: Value,
+    sink: ProgressSink,
+}
+
+impl ProgressReporter {
+    pub fn new(token: Value, sink: ProgressSink) -> Self {
+        Self { token, sink }
+    }
+
+    /// Builds a reporter when the request carries a progress token
+    ///
+    /// The token is read from `params._meta.progressToken` and must be a
+    /// string or number, as the MCP spec requires.
+    pub fn from_request(params: Option<&Value>, sink: Option<&ProgressSink>) -> Option<Self> {
+        let token = params?.get("_meta")?.get("progressToken")?;
+        if !(token.is_string() || token.is_number()) {
+            return None;
+        }
+        Some(Self::new(token.clone(), sink?.clone()))
+    }
+
+    /// Sends one `notifications/progress`; `progress` must increase per call
+    pub fn report(&self, progress: u64, total: Option<u64>, message: &str) {
+        let mut params = serde_json::json!({
+            "progressToken": self.token,
+            "progress": progress,
+            "message": message,
+        });
+        if let Some(total) = total {
+            params["total"] = Value::from(total);
+        }
+        // The transport is gone once the receiver is dropped; the call still completes
+        let _ = self.sink.send(JsonRpcMessage::Notification(JsonRpcNotification {
+            jsonrpc: JsonRpcVersion::V2,
+            method: "notifications/progress".to_string(),
+            params: Some(params),
+        }));
+    }
+}
+
+/// Runs `future` with `reporter` receiving its progress, if any
+pub async fn with_progress<F: std::future::Future>(reporter: Option<ProgressReporter>, future: F) -> F::Output {
+    match reporter {
+        Some(reporter) => PROGRESS.scope(reporter, future).await,
+        None => future.await,
+    }
+}
+
+/// Reports progress of the tools/call being handled, if its client asked for it
+pub fn report_progress(progress: u64, total: Option<u64>, message: &str) {
+    let _ = PROGRESS.try_with(|reporter| reporter.report(progress, total, message));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_progress_reported_only_inside_a_scoped_call() {
+        let (sink, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let params = serde_json::json!({ "name": "getRecentActivity", "_meta": { "progressToken": "abc" } });
+
+        report_progress(1, None, "outside any call");
+        let reporter = ProgressReporter::from_request(Some(&params), Some(&sink));
+        with_progress(reporter, async { report_progress(2, Some(5), "Fetched 2 of 5 transactions") }).await;
+        drop(sink);
+
+        let JsonRpcMessage::Notification(notification) = receiver.recv().await.unwrap() else {
+            panic!("expected a notification");
+        };
+        assert_eq!(notification.method, "notifications/progress");
+        assert_eq!(
+            notification.params.unwrap(),
+            serde_json::json!({ "progressToken": "abc", "progress": 2, "total": 5, "message": "Fetched 2 of 5 transactions" })
+        );
+        assert!(receiver.recv().await.is_none());
+
+        // No token, or a token of the wrong type, means no reporter
+        let (sink, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        assert!(ProgressReporter::from_request(Some(&serde_json::json!({ "name": "x" })), Some(&sink)).is_none());
+        let params = serde_json::json!({ "_meta": { "progressToken": { "id": 1 } } });
+        assert!(ProgressReporter::from_request(Some(&params), Some(&sink)).is_none());
+    }
+}
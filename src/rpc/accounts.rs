@@ -1,17 +1,24 @@
 use crate::cache::with_cache;
 use crate::error::{McpError, McpResult};
+use crate::rpc::single_flight::RPC_SINGLE_FLIGHT;
 use crate::logging::{log_rpc_request_start, log_rpc_request_success, log_rpc_request_failure, new_request_id};
+use crate::validation::account_encoding_from_name;
 use serde_json::Value;
-use solana_account_decoder::UiAccountEncoding;
+use solana_account_decoder::{encode_ui_account, UiAccountData, UiAccountEncoding, UiDataSliceConfig, MAX_BASE58_BYTES};
 use solana_client::{
     nonblocking::rpc_client::RpcClient,
     rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
     rpc_filter::RpcFilterType,
+    rpc_request::RpcRequest,
+    rpc_response::{OptionalContext, Response, RpcKeyedAccount},
 };
+use solana_loader_v3_interface::state::UpgradeableLoaderState;
 use solana_sdk::{
+    account::Account,
     commitment_config::CommitmentConfig,
     pubkey::Pubkey,
 };
+use solana_sdk_ids::bpf_loader_upgradeable;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -33,7 +40,16 @@ pub async fn get_balance_cached(
 }
 
 /// Get account balance for a given public key
+///
+/// Concurrent identical calls to the same endpoint share one upstream request.
 pub async fn get_balance(client: &RpcClient, pubkey: &Pubkey) -> McpResult<Value> {
+    let params = serde_json::json!({ "pubkey": pubkey.to_string(), "commitment": client.commitment().commitment });
+    RPC_SINGLE_FLIGHT
+        .run("getBalance", &client.url(), &params, || fetch_balance(client, pubkey))
+        .await
+}
+
+async fn fetch_balance(client: &RpcClient, pubkey: &Pubkey) -> McpResult<Value> {
     let request_id = new_request_id();
     let start_time = Instant::now();
     let method = "getBalance";
@@ -81,6 +97,59 @@ pub async fn get_balance(client: &RpcClient, pubkey: &Pubkey) -> McpResult<Value
     }
 }
 
+/// Get account balance for a given public key at the given commitment
+pub async fn get_balance_with_commitment(
+    client: &RpcClient,
+    pubkey: &Pubkey,
+    commitment: CommitmentConfig,
+) -> McpResult<Value> {
+    let request_id = new_request_id();
+    let start_time = Instant::now();
+    let method = "getBalance";
+
+    log_rpc_request_start(
+        request_id,
+        method,
+        Some(&client.url()),
+        Some(&format!("pubkey: {pubkey}, commitment: {commitment:?}")),
+    );
+
+    match client.get_balance_with_commitment(pubkey, commitment).await {
+        Ok(response) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let result = serde_json::json!({ "balance": response.value });
+
+            log_rpc_request_success(
+                request_id,
+                method,
+                duration,
+                Some("balance retrieved"),
+                None,
+            );
+
+            Ok(result)
+        }
+        Err(e) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let error = McpError::from(e)
+                .with_request_id(request_id)
+                .with_method(method)
+                .with_rpc_url(client.url());
+
+            log_rpc_request_failure(
+                request_id,
+                method,
+                error.error_type(),
+                duration,
+                Some(&error.to_log_value()),
+                None,
+            );
+
+            Err(error)
+        }
+    }
+}
+
 /// Get account information for a given public key with caching support
 pub async fn get_account_info_cached(
     client: &RpcClient,
@@ -99,7 +168,16 @@ pub async fn get_account_info_cached(
 }
 
 /// Get account information for a given public key
+///
+/// Concurrent identical calls to the same endpoint share one upstream request.
 pub async fn get_account_info(client: &RpcClient, pubkey: &Pubkey) -> McpResult<Value> {
+    let params = serde_json::json!({ "pubkey": pubkey.to_string(), "commitment": client.commitment().commitment });
+    RPC_SINGLE_FLIGHT
+        .run("getAccountInfo", &client.url(), &params, || fetch_account_info(client, pubkey))
+        .await
+}
+
+async fn fetch_account_info(client: &RpcClient, pubkey: &Pubkey) -> McpResult<Value> {
     let request_id = new_request_id();
     let start_time = Instant::now();
     let method = "getAccountInfo";
@@ -111,16 +189,18 @@ pub async fn get_account_info(client: &RpcClient, pubkey: &Pubkey) -> McpResult<
         Some(&format!("pubkey: {pubkey}")),
     );
 
-    match client.get_account(pubkey).await {
-        Ok(account) => {
+    // get_account turns a missing account into an error; like the RPC API,
+    // report it as a null value and keep errors for genuine failures
+    match client.get_account_with_commitment(pubkey, client.commitment()).await {
+        Ok(response) => {
             let duration = start_time.elapsed().as_millis() as u64;
-            let result = serde_json::json!({ "account": account });
+            let result = serde_json::json!({ "account": response.value });
             
             log_rpc_request_success(
                 request_id,
                 method,
                 duration,
-                Some("account info retrieved"),
+                Some(if response.value.is_some() { "account info retrieved" } else { "account not found" }),
                 None,
             );
             
@@ -148,11 +228,31 @@ pub async fn get_account_info(client: &RpcClient, pubkey: &Pubkey) -> McpResult<
 }
 
 /// Get account information with configuration options
+///
+/// Concurrent identical calls to the same endpoint share one upstream request.
 pub async fn get_account_info_with_config(
     client: &RpcClient,
     pubkey: &Pubkey,
     commitment: Option<CommitmentConfig>,
     encoding: Option<UiAccountEncoding>,
+) -> McpResult<Value> {
+    let params = serde_json::json!({
+        "pubkey": pubkey.to_string(),
+        "commitment": commitment.unwrap_or_else(|| client.commitment()).commitment,
+        "encoding": encoding,
+    });
+    RPC_SINGLE_FLIGHT
+        .run("getAccountInfo", &client.url(), &params, || {
+            fetch_account_info_with_config(client, pubkey, commitment, encoding)
+        })
+        .await
+}
+
+async fn fetch_account_info_with_config(
+    client: &RpcClient,
+    pubkey: &Pubkey,
+    commitment: Option<CommitmentConfig>,
+    encoding: Option<UiAccountEncoding>,
 ) -> McpResult<Value> {
     let request_id = new_request_id();
     let start_time = Instant::now();
@@ -166,15 +266,21 @@ pub async fn get_account_info_with_config(
     );
 
     let config = RpcAccountInfoConfig {
-        encoding,
+        // RpcClient can only decode binary encodings back into an Account, so
+        // fetch as base64 and render the requested encoding locally
+        encoding: Some(UiAccountEncoding::Base64),
         commitment,
         data_slice: None,
         min_context_slot: None,
     };
 
     match client.get_account_with_config(pubkey, config).await {
-        Ok(account) => {
+        Ok(response) => {
             let duration = start_time.elapsed().as_millis() as u64;
+            // Same shape as get_account_info; get_account_info_and_context keeps the slot
+            let account = response
+                .value
+                .map(|account| render_account(pubkey, &account, encoding));
             let result = serde_json::json!({ "account": account });
             
             log_rpc_request_success(
@@ -208,6 +314,308 @@ pub async fn get_account_info_with_config(
     }
 }
 
+/// Renders a fetched account as a UiAccount in `encoding`, or in the
+/// get_account_info shape when no encoding is requested
+///
+/// Nodes refuse base58 for data over `MAX_BASE58_BYTES`, so such accounts are
+/// rendered as base64 instead, with a `warning` saying why.
+fn render_account(pubkey: &Pubkey, account: &Account, encoding: Option<UiAccountEncoding>) -> Value {
+    match encoding {
+        Some(UiAccountEncoding::Base58 | UiAccountEncoding::Binary) if account.data.len() > MAX_BASE58_BYTES => {
+            let mut rendered = serde_json::json!(encode_ui_account(pubkey, account, UiAccountEncoding::Base64, None, None));
+            rendered["warning"] = Value::String(format!(
+                "Account data is {} bytes, over the {MAX_BASE58_BYTES}-byte base58 limit; returned base64 instead",
+                account.data.len()
+            ));
+            rendered
+        }
+        Some(encoding) => serde_json::json!(encode_ui_account(pubkey, account, encoding, None, None)),
+        None => serde_json::json!(account),
+    }
+}
+
+/// Renders account data in each of the requested encodings
+///
+/// # Arguments
+/// * `pubkey` - Account public key (used for jsonParsed decoding)
+/// * `account` - Account fetched from the RPC node
+/// * `encodings` - Encoding names (base58, base64, base64+zstd, jsonParsed)
+///
+/// # Returns
+/// * `Value` - Object keyed by encoding name; encodings that cannot be
+///   rendered map to `{ "error": ... }` instead of failing the whole request
+pub fn render_account_encodings(pubkey: &Pubkey, account: &Account, encodings: &[String]) -> Value {
+    let mut rendered = serde_json::Map::new();
+
+    for name in encodings {
+        let Some(encoding) = account_encoding_from_name(name) else {
+            rendered.insert(
+                name.clone(),
+                serde_json::json!({ "error": format!("Unsupported encoding: {name}") }),
+            );
+            continue;
+        };
+
+        let value = match encode_ui_account(pubkey, account, encoding, None, None).data {
+            UiAccountData::Json(parsed) => serde_json::json!(parsed),
+            UiAccountData::Binary(_, actual) if actual != encoding => serde_json::json!({
+                "error": format!("Account data could not be rendered as {name}")
+            }),
+            UiAccountData::Binary(data, _) | UiAccountData::LegacyBinary(data) => {
+                if data.starts_with("error:") {
+                    serde_json::json!({ "error": data })
+                } else {
+                    Value::String(data)
+                }
+            }
+        };
+        rendered.insert(name.clone(), value);
+    }
+
+    Value::Object(rendered)
+}
+
+/// Get account information rendered in several encodings from a single fetch
+///
+/// The fetch uses `commitment`, or the client's configured commitment when
+/// none is given.
+pub async fn get_account_info_multi_encoding(
+    client: &RpcClient,
+    pubkey: &Pubkey,
+    encodings: &[String],
+    commitment: Option<CommitmentConfig>,
+) -> McpResult<Value> {
+    let request_id = new_request_id();
+    let start_time = Instant::now();
+    let method = "getAccountInfo";
+
+    log_rpc_request_start(
+        request_id,
+        method,
+        Some(&client.url()),
+        Some(&format!("pubkey: {pubkey}, encodings: {encodings:?}, commitment: {commitment:?}")),
+    );
+
+    let commitment = commitment.unwrap_or_else(|| client.commitment());
+    match client.get_account_with_commitment(pubkey, commitment).await {
+        Ok(response) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let account = response.value.map(|account| {
+                serde_json::json!({
+                    "lamports": account.lamports,
+                    "owner": account.owner.to_string(),
+                    "executable": account.executable,
+                    "rentEpoch": account.rent_epoch,
+                    "space": account.data.len(),
+                    "data": render_account_encodings(pubkey, &account, encodings)
+                })
+            });
+            let result = serde_json::json!({ "account": account });
+
+            log_rpc_request_success(
+                request_id,
+                method,
+                duration,
+                Some(&format!("account info rendered in {} encodings", encodings.len())),
+                None,
+            );
+
+            Ok(result)
+        }
+        Err(e) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let error = McpError::from(e)
+                .with_request_id(request_id)
+                .with_method(method)
+                .with_rpc_url(client.url());
+
+            log_rpc_request_failure(
+                request_id,
+                method,
+                error.error_type(),
+                duration,
+                Some(&error.to_log_value()),
+                None,
+            );
+
+            Err(error)
+        }
+    }
+}
+
+/// Describes whether an account is an executable program
+///
+/// # Returns
+/// * `Value` - `{ exists, executable, owner, programDataAddress? }`, where
+///   `programDataAddress` is set for upgradeable-loader programs
+pub fn describe_program_account(account: Option<&Account>) -> Value {
+    let Some(account) = account else {
+        return serde_json::json!({ "exists": false, "executable": false });
+    };
+
+    let mut result = serde_json::json!({
+        "exists": true,
+        "executable": account.executable,
+        "owner": account.owner.to_string(),
+    });
+
+    if account.executable && bpf_loader_upgradeable::check_id(&account.owner) {
+        if let Ok(UpgradeableLoaderState::Program { programdata_address }) =
+            bincode::deserialize::<UpgradeableLoaderState>(&account.data)
+        {
+            result["programDataAddress"] = Value::String(programdata_address.to_string());
+        }
+    }
+
+    result
+}
+
+/// Check whether an address is an executable program
+pub async fn is_program(client: &RpcClient, pubkey: &Pubkey) -> McpResult<Value> {
+    let request_id = new_request_id();
+    let start_time = Instant::now();
+    let method = "isProgram";
+
+    log_rpc_request_start(
+        request_id,
+        method,
+        Some(&client.url()),
+        Some(&format!("pubkey: {pubkey}")),
+    );
+
+    match client.get_account_with_commitment(pubkey, client.commitment()).await {
+        Ok(response) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let result = describe_program_account(response.value.as_ref());
+
+            log_rpc_request_success(
+                request_id,
+                method,
+                duration,
+                Some("program status retrieved"),
+                Some(&client.url()),
+            );
+
+            Ok(result)
+        }
+        Err(e) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let error = McpError::from(e)
+                .with_request_id(request_id)
+                .with_method(method)
+                .with_rpc_url(client.url());
+
+            log_rpc_request_failure(
+                request_id,
+                method,
+                error.error_type(),
+                duration,
+                Some(&error.to_log_value()),
+                Some(&client.url()),
+            );
+
+            Err(error)
+        }
+    }
+}
+
+/// Get the lamport balances of many accounts, in input order
+///
+/// Pubkeys are fetched with getMultipleAccounts in `MAX_MULTIPLE_ACCOUNTS`-sized
+/// chunks sent concurrently, with a zero-length data slice so only the account
+/// metadata is transferred.
+///
+/// # Arguments
+/// * `null_if_missing` - Report accounts that do not exist as `null` rather than 0
+///
+/// # Returns
+/// * `McpResult<Value>` - `{context: {slot}, balances: [{pubkey, lamports}]}`
+///   where `slot` is the oldest slot any chunk was read at
+pub async fn get_balances(
+    client: &RpcClient,
+    pubkeys: &[Pubkey],
+    commitment: Option<CommitmentConfig>,
+    null_if_missing: bool,
+) -> McpResult<Value> {
+    let request_id = new_request_id();
+    let start_time = Instant::now();
+    let method = "getBalances";
+
+    if pubkeys.is_empty() {
+        let error = McpError::validation("At least one pubkey is required")
+            .with_request_id(request_id)
+            .with_method(method)
+            .with_parameter("pubkeys");
+
+        return Err(error);
+    }
+
+    log_rpc_request_start(
+        request_id,
+        method,
+        Some(&client.url()),
+        Some(&format!("pubkeys_count: {}, commitment: {commitment:?}", pubkeys.len())),
+    );
+
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment,
+        data_slice: Some(UiDataSliceConfig { offset: 0, length: 0 }),
+        min_context_slot: None,
+    };
+    let chunks = pubkeys
+        .chunks(crate::validation::MAX_MULTIPLE_ACCOUNTS)
+        .map(|chunk| client.get_multiple_accounts_with_config(chunk, config.clone()));
+
+    match futures_util::future::try_join_all(chunks).await {
+        Ok(responses) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let slot = responses.iter().map(|response| response.context.slot).min().unwrap_or_default();
+            let balances: Vec<Value> = pubkeys
+                .iter()
+                .zip(responses.into_iter().flat_map(|response| response.value))
+                .map(|(pubkey, account)| {
+                    let lamports = match account {
+                        Some(account) => Value::from(account.lamports),
+                        None if null_if_missing => Value::Null,
+                        None => Value::from(0),
+                    };
+                    serde_json::json!({ "pubkey": pubkey.to_string(), "lamports": lamports })
+                })
+                .collect();
+            let result = serde_json::json!({ "context": { "slot": slot }, "balances": balances });
+
+            log_rpc_request_success(
+                request_id,
+                method,
+                duration,
+                Some(&format!("{} balances retrieved", pubkeys.len())),
+                Some(&client.url()),
+            );
+
+            Ok(result)
+        }
+        Err(e) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let error = McpError::from(e)
+                .with_request_id(request_id)
+                .with_method(method)
+                .with_rpc_url(client.url());
+
+            log_rpc_request_failure(
+                request_id,
+                method,
+                error.error_type(),
+                duration,
+                Some(&error.to_log_value()),
+                None,
+            );
+
+            Err(error)
+        }
+    }
+}
+
 /// Get multiple accounts information
 pub async fn get_multiple_accounts(client: &RpcClient, pubkeys: &[Pubkey]) -> McpResult<Value> {
     let request_id = new_request_id();
@@ -295,14 +703,21 @@ pub async fn get_multiple_accounts_with_config(
     );
 
     let config = RpcAccountInfoConfig {
-        encoding,
+        // Rendered locally for the same reason as get_account_info_with_config
+        encoding: Some(UiAccountEncoding::Base64),
         commitment,
         data_slice: None,
         min_context_slot: None,
     };
     match client.get_multiple_accounts_with_config(pubkeys, config).await {
-        Ok(accounts) => {
+        Ok(response) => {
             let duration = start_time.elapsed().as_millis() as u64;
+            // Same shape as get_multiple_accounts; get_multiple_accounts_and_context keeps the slot
+            let accounts: Vec<Option<Value>> = pubkeys
+                .iter()
+                .zip(response.value)
+                .map(|(pubkey, account)| account.map(|account| render_account(pubkey, &account, encoding)))
+                .collect();
             let result = serde_json::json!({ "accounts": accounts });
             
             log_rpc_request_success(
@@ -452,10 +867,261 @@ pub async fn get_program_accounts_with_config(
     }
 }
 
+/// Default number of accounts returned per getProgramAccounts page
+pub const DEFAULT_PROGRAM_ACCOUNTS_PAGE_SIZE: usize = 1000;
+
+/// Maximum number of accounts returned per getProgramAccounts page
+pub const MAX_PROGRAM_ACCOUNTS_PAGE_SIZE: usize = 10_000;
+
+/// Sorts accounts by pubkey and returns the page following `cursor`
+///
+/// # Arguments
+/// * `accounts` - Full set of program accounts
+/// * `limit` - Maximum number of accounts in the page
+/// * `cursor` - Pubkey of the last account on the previous page, if any
+///
+/// # Returns
+/// * `(Vec<(Pubkey, T)>, Option<Pubkey>)` - The page and the cursor for the next page
+pub fn paginate_program_accounts<T>(
+    mut accounts: Vec<(Pubkey, T)>,
+    limit: usize,
+    cursor: Option<&Pubkey>,
+) -> (Vec<(Pubkey, T)>, Option<Pubkey>) {
+    accounts.sort_by_key(|(pubkey, _)| *pubkey);
+
+    let start = match cursor {
+        Some(cursor) => accounts.partition_point(|(pubkey, _)| pubkey <= cursor),
+        None => 0,
+    };
+
+    let mut page: Vec<_> = accounts.into_iter().skip(start).take(limit + 1).collect();
+    let next_cursor = if page.len() > limit {
+        page.truncate(limit);
+        page.last().map(|(pubkey, _)| *pubkey)
+    } else {
+        None
+    };
+
+    (page, next_cursor)
+}
+
+/// Get a page of program accounts ordered by pubkey
+///
+/// The matching account set is fetched once, in the requested encoding
+/// (default base64) and narrowed by any `filters`, then sorted
+/// deterministically by pubkey. The returned `nextCursor` is opaque to callers
+/// and only stable within a slot, since accounts may be created or closed
+/// between requests.
+pub async fn get_program_accounts_paged(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    commitment: Option<CommitmentConfig>,
+    encoding: Option<UiAccountEncoding>,
+    filters: Option<Vec<RpcFilterType>>,
+    limit: Option<usize>,
+    cursor: Option<&str>,
+) -> McpResult<Value> {
+    let request_id = new_request_id();
+    let start_time = Instant::now();
+    let method = "getProgramAccounts";
+
+    log_rpc_request_start(
+        request_id,
+        method,
+        Some(&client.url()),
+        Some(&format!("program_id: {program_id}, encoding: {encoding:?}, limit: {limit:?}, cursor: {cursor:?}")),
+    );
+
+    let limit = limit
+        .unwrap_or(DEFAULT_PROGRAM_ACCOUNTS_PAGE_SIZE)
+        .clamp(1, MAX_PROGRAM_ACCOUNTS_PAGE_SIZE);
+
+    let cursor = match cursor.map(|c| c.parse::<Pubkey>()).transpose() {
+        Ok(cursor) => cursor,
+        Err(_) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let error = McpError::validation("Invalid cursor")
+                .with_request_id(request_id)
+                .with_method(method)
+                .with_parameter("cursor");
+
+            log_rpc_request_failure(
+                request_id,
+                method,
+                error.error_type(),
+                duration,
+                Some(&error.to_log_value()),
+                Some(&client.url()),
+            );
+
+            return Err(error);
+        }
+    };
+
+    let config = RpcProgramAccountsConfig {
+        filters,
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(encoding.unwrap_or(UiAccountEncoding::Base64)),
+            commitment: Some(commitment.unwrap_or_else(|| client.commitment())),
+            data_slice: None,
+            min_context_slot: None,
+        },
+        with_context: None,
+        sort_results: None,
+    };
+
+    // Sent raw so accounts come back in the requested encoding rather than decoded
+    let params = serde_json::json!([program_id.to_string(), config]);
+    match client
+        .send::<OptionalContext<Vec<RpcKeyedAccount>>>(RpcRequest::GetProgramAccounts, params)
+        .await
+    {
+        Ok(response) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let accounts: Vec<_> = response
+                .parse_value()
+                .into_iter()
+                .filter_map(|keyed| Some((keyed.pubkey.parse::<Pubkey>().ok()?, keyed.account)))
+                .collect();
+            let total = accounts.len();
+            let (page, next_cursor) = paginate_program_accounts(accounts, limit, cursor.as_ref());
+            let page: Vec<RpcKeyedAccount> = page
+                .into_iter()
+                .map(|(pubkey, account)| RpcKeyedAccount { pubkey: pubkey.to_string(), account })
+                .collect();
+
+            log_rpc_request_success(
+                request_id,
+                method,
+                duration,
+                Some(&format!("{} of {} program accounts returned", page.len(), total)),
+                Some(&client.url()),
+            );
+
+            Ok(serde_json::json!({
+                "accounts": page,
+                "totalAccounts": total,
+                "nextCursor": next_cursor.map(|c| c.to_string())
+            }))
+        }
+        Err(e) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let error = McpError::from(e)
+                .with_request_id(request_id)
+                .with_method(method)
+                .with_rpc_url(client.url());
+
+            log_rpc_request_failure(
+                request_id,
+                method,
+                error.error_type(),
+                duration,
+                Some(&error.to_log_value()),
+                Some(&client.url()),
+            );
+
+            Err(error)
+        }
+    }
+}
+
+/// Warning attached to accounts the node could not decode as jsonParsed
+const UNPARSED_ACCOUNT_WARNING: &str =
+    "Node has no jsonParsed decoder for this account; data is returned as base64";
+
+/// Get program accounts decoded with jsonParsed
+///
+/// Accounts of programs the node knows how to decode (SPL Token, Stake, ...)
+/// come back as parsed JSON. For any other account the node falls back to
+/// base64, which is passed through with a `warning` field on that entry.
+pub async fn get_program_accounts_parsed(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    commitment: Option<CommitmentConfig>,
+    filters: Option<Vec<RpcFilterType>>,
+) -> McpResult<Value> {
+    let request_id = new_request_id();
+    let start_time = Instant::now();
+    let method = "getProgramAccounts";
+
+    log_rpc_request_start(
+        request_id,
+        method,
+        Some(&client.url()),
+        Some(&format!("program_id: {program_id}, encoding: jsonParsed, commitment: {commitment:?}")),
+    );
+
+    let config = RpcProgramAccountsConfig {
+        filters,
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::JsonParsed),
+            commitment,
+            data_slice: None,
+            min_context_slot: None,
+        },
+        with_context: None,
+        sort_results: None,
+    };
+
+    // RpcClient::get_program_accounts_with_config decodes accounts back to raw
+    // bytes, so the request is sent directly to keep the parsed data
+    let params = serde_json::json!([program_id.to_string(), config]);
+    match client.send::<Vec<RpcKeyedAccount>>(RpcRequest::GetProgramAccounts, params).await {
+        Ok(accounts) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let mut unparsed = 0;
+            let accounts: Vec<Value> = accounts
+                .into_iter()
+                .map(|keyed| {
+                    let parsed = matches!(keyed.account.data, UiAccountData::Json(_));
+                    let mut entry = serde_json::json!(keyed);
+                    if !parsed {
+                        unparsed += 1;
+                        entry["warning"] = Value::from(UNPARSED_ACCOUNT_WARNING);
+                    }
+                    entry
+                })
+                .collect();
+
+            log_rpc_request_success(
+                request_id,
+                method,
+                duration,
+                Some(&format!("{} parsed program accounts retrieved, {unparsed} unparsed", accounts.len())),
+                Some(&client.url()),
+            );
+
+            Ok(serde_json::json!({
+                "accounts": accounts,
+                "unparsedAccounts": unparsed
+            }))
+        }
+        Err(e) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let error = McpError::from(e)
+                .with_request_id(request_id)
+                .with_method(method)
+                .with_rpc_url(client.url());
+
+            log_rpc_request_failure(
+                request_id,
+                method,
+                error.error_type(),
+                duration,
+                Some(&error.to_log_value()),
+                Some(&client.url()),
+            );
+
+            Err(error)
+        }
+    }
+}
+
 /// Get the largest accounts by balance
 pub async fn get_largest_accounts(
     client: &RpcClient,
     filter: Option<solana_client::rpc_config::RpcLargestAccountsFilter>,
+    commitment: Option<CommitmentConfig>,
 ) -> McpResult<Value> {
     let request_id = new_request_id();
     let start_time = Instant::now();
@@ -465,11 +1131,11 @@ pub async fn get_largest_accounts(
         request_id,
         method,
         Some(&client.url()),
-        Some(&format!("filter: {filter:?}")),
+        Some(&format!("filter: {filter:?}, commitment: {commitment:?}")),
     );
 
     let config = solana_client::rpc_config::RpcLargestAccountsConfig {
-        commitment: None,
+        commitment,
         filter,
         sort_results: None, // Use default sorting behavior
     };
@@ -566,6 +1232,7 @@ pub async fn get_minimum_balance_for_rent_exemption(
 pub async fn get_account_info_and_context(
     client: &RpcClient,
     pubkey: &Pubkey,
+    commitment: Option<CommitmentConfig>,
 ) -> McpResult<Value> {
     let request_id = new_request_id();
     let start_time = Instant::now();
@@ -575,10 +1242,10 @@ pub async fn get_account_info_and_context(
         request_id,
         method,
         Some(&client.url()),
-        Some(&format!("pubkey: {pubkey}")),
+        Some(&format!("pubkey: {pubkey}, commitment: {commitment:?}")),
     );
 
-    match client.get_account_with_commitment(pubkey, CommitmentConfig::confirmed()).await {
+    match client.get_account_with_commitment(pubkey, commitment.unwrap_or_else(|| client.commitment())).await {
         Ok(response) => {
             let duration = start_time.elapsed().as_millis() as u64;
             let result = serde_json::json!({
@@ -623,6 +1290,7 @@ pub async fn get_account_info_and_context(
 pub async fn get_balance_and_context(
     client: &RpcClient,
     pubkey: &Pubkey,
+    commitment: Option<CommitmentConfig>,
 ) -> McpResult<Value> {
     let request_id = new_request_id();
     let start_time = Instant::now();
@@ -632,10 +1300,10 @@ pub async fn get_balance_and_context(
         request_id,
         method,
         Some(&client.url()),
-        Some(&format!("pubkey: {pubkey}")),
+        Some(&format!("pubkey: {pubkey}, commitment: {commitment:?}")),
     );
 
-    match client.get_balance_with_commitment(pubkey, CommitmentConfig::confirmed()).await {
+    match client.get_balance_with_commitment(pubkey, commitment.unwrap_or_else(|| client.commitment())).await {
         Ok(response) => {
             let duration = start_time.elapsed().as_millis() as u64;
             let result = serde_json::json!({
@@ -680,6 +1348,7 @@ pub async fn get_balance_and_context(
 pub async fn get_multiple_accounts_and_context(
     client: &RpcClient,
     pubkeys: &[Pubkey],
+    commitment: Option<CommitmentConfig>,
 ) -> McpResult<Value> {
     let request_id = new_request_id();
     let start_time = Instant::now();
@@ -689,10 +1358,13 @@ pub async fn get_multiple_accounts_and_context(
         request_id,
         method,
         Some(&client.url()),
-        Some(&format!("pubkeys: {} accounts", pubkeys.len())),
+        Some(&format!("pubkeys: {} accounts, commitment: {:?}", pubkeys.len(), commitment)),
     );
 
-    match client.get_multiple_accounts_with_commitment(pubkeys, CommitmentConfig::confirmed()).await {
+    match client
+        .get_multiple_accounts_with_commitment(pubkeys, commitment.unwrap_or_else(|| client.commitment()))
+        .await
+    {
         Ok(response) => {
             let duration = start_time.elapsed().as_millis() as u64;
             let result = serde_json::json!({
@@ -733,7 +1405,18 @@ pub async fn get_multiple_accounts_and_context(
     }
 }
 
+/// Formats a program accounts response, preserving the context slot
+pub fn format_program_accounts_with_context(response: Response<Vec<RpcKeyedAccount>>) -> Value {
+    serde_json::json!({
+        "context": { "slot": response.context.slot },
+        "accounts": response.value
+    })
+}
+
 /// Get program accounts with context (slot information)
+///
+/// `with_context` is always forced on so the slot the accounts were read at
+/// is returned alongside them. Caller-supplied filters are passed through.
 pub async fn get_program_accounts_and_context(
     client: &RpcClient,
     program_id: &Pubkey,
@@ -762,21 +1445,24 @@ pub async fn get_program_accounts_and_context(
         sort_results: None,
     };
 
-    let final_config = config.unwrap_or(default_config);
+    let mut final_config = config.unwrap_or(default_config);
+    final_config.with_context = Some(true);
 
-    match client.get_program_accounts_with_config(program_id, final_config).await {
-        Ok(accounts) => {
+    let params = serde_json::json!([program_id.to_string(), final_config]);
+    match client
+        .send::<Response<Vec<RpcKeyedAccount>>>(RpcRequest::GetProgramAccounts, params)
+        .await
+    {
+        Ok(response) => {
             let duration = start_time.elapsed().as_millis() as u64;
-            
-            let result = serde_json::json!({
-                "accounts": accounts
-            });
+            let count = response.value.len();
+            let result = format_program_accounts_with_context(response);
             
             log_rpc_request_success(
                 request_id,
                 method,
                 duration,
-                Some(&format!("{} program accounts with context retrieved", accounts.len())),
+                Some(&format!("{count} program accounts with context retrieved")),
                 Some(&client.url()),
             );
             
@@ -6,8 +6,9 @@ use solana_client::{
         RpcBlockConfig, RpcBlockProductionConfig, RpcBlockProductionConfigRange,
         RpcGetVoteAccountsConfig, RpcLeaderScheduleConfig,
     },
+    rpc_request::RpcRequest,
 };
-use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::{clock::UnixTimestamp, commitment_config::CommitmentConfig, pubkey::Pubkey};
 use solana_transaction_status::{TransactionDetails, UiTransactionEncoding};
 
 pub async fn get_slot(client: &RpcClient) -> Result<Value> {
@@ -25,8 +26,18 @@ pub async fn get_slot_with_commitment(
     Ok(serde_json::json!({ "slot": slot }))
 }
 
+/// Largest `limit` the getSlotLeaders RPC method accepts
+pub const MAX_SLOT_LEADERS_LIMIT: u64 = 5000;
+
+/// Get the ordered leaders for `limit` slots starting at `start_slot`
+///
+/// `limit` is clamped to [`MAX_SLOT_LEADERS_LIMIT`].
 pub async fn get_slot_leaders(client: &RpcClient, start_slot: u64, limit: u64) -> Result<Value> {
-    let leaders = client.get_slot_leaders(start_slot, limit).await?;
+    let leaders = client
+        .get_slot_leaders(start_slot, limit.min(MAX_SLOT_LEADERS_LIMIT))
+        .await?;
+    // Pubkey serializes as raw bytes, so render base58 explicitly
+    let leaders: Vec<String> = leaders.iter().map(|leader| leader.to_string()).collect();
     Ok(serde_json::json!({ "leaders": leaders }))
 }
 
@@ -48,12 +59,24 @@ pub async fn get_block_with_config(
         transaction_details,
         rewards,
         commitment,
-        max_supported_transaction_version: None,
+        // Without a version, blocks containing v0 transactions are rejected
+        max_supported_transaction_version: Some(0),
     };
     let block = client.get_block_with_config(slot, config).await?;
     Ok(serde_json::json!({ "block": block }))
 }
 
+/// Get the estimated production time of a block as a unix timestamp
+///
+/// `timestamp` is `null` when the node has no time recorded for the slot.
+pub async fn get_block_time(client: &RpcClient, slot: u64) -> Result<Value> {
+    // RpcClient::get_block_time turns a null timestamp into an error
+    let timestamp: Option<UnixTimestamp> = client
+        .send(RpcRequest::GetBlockTime, serde_json::json!([slot]))
+        .await?;
+    Ok(serde_json::json!({ "timestamp": timestamp }))
+}
+
 pub async fn get_block_height(client: &RpcClient) -> Result<Value> {
     let height = client.get_block_height().await?;
     Ok(serde_json::json!({ "height": height }))
@@ -67,22 +90,50 @@ pub async fn get_block_height_with_commitment(
     Ok(serde_json::json!({ "height": height }))
 }
 
+/// Get leader slots and produced blocks per validator
+///
+/// # Arguments
+/// * `identity` - Only report this validator identity
+/// * `range` - First and optional last slot; the RPC node defaults to the
+///   current epoch when omitted, and to its latest slot when `last_slot` is
+/// * `commitment` - Commitment level, or the client's default
+///
+/// # Returns
+/// * `Result<Value>` - `{range, byIdentity}` where each identity maps to
+///   `{leaderSlots, blocksProduced}`
 pub async fn get_block_production(
     client: &RpcClient,
-    identity: Option<String>,
-    first_slot: Option<u64>,
-    last_slot: Option<u64>,
+    identity: Option<&Pubkey>,
+    range: Option<(u64, Option<u64>)>,
+    commitment: Option<CommitmentConfig>,
 ) -> Result<Value> {
     let config = RpcBlockProductionConfig {
-        identity: identity.as_ref().map(|s| s.parse()).transpose()?,
-        range: first_slot.map(|start| RpcBlockProductionConfigRange {
-            first_slot: start,
-            last_slot: Some(last_slot.unwrap_or(start + 10)),
+        identity: identity.map(|identity| identity.to_string()),
+        range: range.map(|(first_slot, last_slot)| RpcBlockProductionConfigRange {
+            first_slot,
+            last_slot,
         }),
-        commitment: None,
+        commitment,
     };
-    let production = client.get_block_production_with_config(config).await?;
-    Ok(serde_json::json!({ "production": production }))
+    let production = client.get_block_production_with_config(config).await?.value;
+
+    let by_identity: serde_json::Map<String, Value> = production
+        .by_identity
+        .into_iter()
+        .map(|(identity, (leader_slots, blocks_produced))| {
+            (
+                identity,
+                serde_json::json!({ "leaderSlots": leader_slots, "blocksProduced": blocks_produced }),
+            )
+        })
+        .collect();
+    Ok(serde_json::json!({
+        "range": {
+            "firstSlot": production.range.first_slot,
+            "lastSlot": production.range.last_slot,
+        },
+        "byIdentity": by_identity,
+    }))
 }
 
 pub async fn get_blocks(
@@ -106,6 +157,51 @@ pub async fn get_blocks_with_commitment(
     Ok(serde_json::json!({ "blocks": blocks }))
 }
 
+/// Widest slot range a node serves in one getBlocks request
+pub const MAX_GET_BLOCKS_RANGE: u64 = 500_000;
+
+/// Get confirmed blocks over a range of any width, in ascending slot order
+///
+/// The range is walked in sub-ranges of at most `page_slots` slots (capped at
+/// [`MAX_GET_BLOCKS_RANGE`]), one getBlocks request each. When `end_slot` is
+/// omitted the scan runs to the current slot at `commitment`.
+///
+/// # Returns
+/// * `Result<Value>` - `{blocks, requests}` where `requests` counts the
+///   getBlocks calls made
+pub async fn get_blocks_paged(
+    client: &RpcClient,
+    start_slot: u64,
+    end_slot: Option<u64>,
+    page_slots: u64,
+    commitment: Option<CommitmentConfig>,
+) -> Result<Value> {
+    let commitment = commitment.unwrap_or_else(|| client.commitment());
+    let end_slot = match end_slot {
+        Some(end_slot) => end_slot,
+        None => client.get_slot_with_commitment(commitment).await?,
+    };
+    let page_slots = page_slots.clamp(1, MAX_GET_BLOCKS_RANGE);
+
+    let mut blocks = Vec::new();
+    let mut requests = 0;
+    let mut page_start = start_slot;
+    while page_start <= end_slot {
+        let page_end = page_start.saturating_add(page_slots - 1).min(end_slot);
+        blocks.extend(
+            client
+                .get_blocks_with_commitment(page_start, Some(page_end), commitment)
+                .await?,
+        );
+        requests += 1;
+        let Some(next_start) = page_end.checked_add(1) else {
+            break;
+        };
+        page_start = next_start;
+    }
+    Ok(serde_json::json!({ "blocks": blocks, "requests": requests }))
+}
+
 pub async fn get_blocks_with_limit(
     client: &RpcClient,
     start_slot: u64,
@@ -127,16 +223,27 @@ pub async fn get_blocks_with_limit_and_commitment(
     Ok(serde_json::json!({ "blocks": blocks }))
 }
 
+/// Get the leader schedule for the epoch containing `slot` (or the current epoch)
+///
+/// # Returns
+/// * `Result<Value>` - `{schedule}` mapping identities to slot indexes within
+///   the epoch, restricted to `identity` when given; `null` for unknown epochs
 pub async fn get_leader_schedule(
     client: &RpcClient,
     slot: Option<u64>,
-    identity: Option<String>,
+    identity: Option<&Pubkey>,
+    commitment: Option<CommitmentConfig>,
 ) -> Result<Value> {
     let config = RpcLeaderScheduleConfig {
-        identity: identity.as_ref().map(|s| s.parse()).transpose()?,
-        commitment: None,
+        identity: identity.map(|identity| identity.to_string()),
+        commitment,
     };
-    let schedule = client.get_leader_schedule_with_config(slot, config).await?;
+    let mut schedule = client.get_leader_schedule_with_config(slot, config).await?;
+    // Nodes honor the identity filter, but don't rely on it
+    if let (Some(schedule), Some(identity)) = (schedule.as_mut(), identity) {
+        let identity = identity.to_string();
+        schedule.retain(|leader, _| *leader == identity);
+    }
     Ok(serde_json::json!({ "schedule": schedule }))
 }
 
@@ -155,12 +262,21 @@ pub async fn get_vote_accounts(client: &RpcClient) -> Result<Value> {
     Ok(serde_json::json!({ "accounts": accounts }))
 }
 
+/// Get current and delinquent vote accounts with configuration options
+///
+/// # Arguments
+/// * `min_stake` - Drop validators whose activated stake (lamports) is below
+///   this after fetching, so callers need not receive the full list
+///
+/// # Returns
+/// * `Result<Value>` - `{current, delinquent}`, as returned by the RPC node
 pub async fn get_vote_accounts_with_config(
     client: &RpcClient,
     commitment: Option<CommitmentConfig>,
     vote_pubkey: Option<String>,
     keep_unstaked_delinquents: Option<bool>,
     delinquent_slot_distance: Option<u64>,
+    min_stake: Option<u64>,
 ) -> Result<Value> {
     let config = RpcGetVoteAccountsConfig {
         commitment,
@@ -168,8 +284,15 @@ pub async fn get_vote_accounts_with_config(
         keep_unstaked_delinquents,
         delinquent_slot_distance,
     };
-    let accounts = client.get_vote_accounts_with_config(config).await?;
-    Ok(serde_json::json!({ "accounts": accounts }))
+    let mut accounts = client.get_vote_accounts_with_config(config).await?;
+    if let Some(min_stake) = min_stake {
+        accounts.current.retain(|account| account.activated_stake >= min_stake);
+        accounts.delinquent.retain(|account| account.activated_stake >= min_stake);
+    }
+    Ok(serde_json::json!({
+        "current": accounts.current,
+        "delinquent": accounts.delinquent,
+    }))
 }
 
 pub async fn get_first_available_block(client: &RpcClient) -> Result<Value> {
@@ -179,7 +302,7 @@ pub async fn get_first_available_block(client: &RpcClient) -> Result<Value> {
 
 pub async fn get_genesis_hash(client: &RpcClient) -> Result<Value> {
     let hash = client.get_genesis_hash().await?;
-    Ok(serde_json::json!({ "hash": hash }))
+    Ok(serde_json::json!({ "hash": hash.to_string() }))
 }
 /// Get confirmed block (deprecated version of getBlock)
 pub async fn get_confirmed_block(client: &RpcClient, slot: u64) -> Result<Value> {
@@ -0,0 +1,237 @@
+//! Detects which version-gated RPC methods the configured node serves
+//!
+//! Older nodes lack methods added since (e.g. `isBlockhashValid` before 1.9)
+//! and Agave 2.0 removed the long-deprecated ones (e.g. `getRecentBlockhash`).
+//! The node's `getVersion` is probed once and cached so tools/call can reject
+//! such methods up front instead of surfacing a confusing RPC failure.
+//!
+//! SVM networks other than Solana may leave out whole method groups, which no
+//! version table covers, so `probe_method` asks a node about one method directly.
+
+use crate::server::ServerState;
+use crate::tools::NETWORK_PROBE_TIMEOUT;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// How long a failed probe is remembered before it is retried
+const PROBE_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+type Version = (u32, u32, u32);
+
+/// RPC methods with a minimum version (added in) or maximum (removed in)
+const METHOD_VERSIONS: &[(&str, Option<Version>, Option<Version>)] = &[
+    ("getLatestBlockhash", Some((1, 9, 0)), None),
+    ("isBlockhashValid", Some((1, 9, 0)), None),
+    ("getFeeForMessage", Some((1, 9, 0)), None),
+    ("getHighestSnapshotSlot", Some((1, 9, 0)), None),
+    ("getRecentPrioritizationFees", Some((1, 14, 0)), None),
+    ("getConfirmedBlock", None, Some((2, 0, 0))),
+    ("getConfirmedBlocks", None, Some((2, 0, 0))),
+    ("getConfirmedBlocksWithLimit", None, Some((2, 0, 0))),
+    ("getConfirmedSignaturesForAddress2", None, Some((2, 0, 0))),
+    ("getConfirmedTransaction", None, Some((2, 0, 0))),
+    ("getFeeCalculatorForBlockhash", None, Some((2, 0, 0))),
+    ("getFeeRateGovernor", None, Some((2, 0, 0))),
+    ("getFees", None, Some((2, 0, 0))),
+    ("getRecentBlockhash", None, Some((2, 0, 0))),
+    ("getSnapshotSlot", None, Some((2, 0, 0))),
+    ("getStakeActivation", None, Some((2, 0, 0))),
+];
+
+/// Returns true if support for `method` depends on the node version
+pub fn is_version_gated(method: &str) -> bool {
+    METHOD_VERSIONS.iter().any(|(name, _, _)| *name == method)
+}
+
+/// Version information reported by the node's getVersion
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeCapabilities {
+    /// The `solana-core` version string
+    pub version: String,
+    /// The node's feature set identifier
+    pub feature_set: Option<u32>,
+    parsed: Option<Version>,
+}
+
+impl NodeCapabilities {
+    pub fn new(version: impl Into<String>, feature_set: Option<u32>) -> Self {
+        let version = version.into();
+        Self { parsed: parse_version(&version), version, feature_set }
+    }
+
+    /// Explains why the node cannot serve `method`
+    ///
+    /// # Returns
+    /// * `Option<String>` - `None` when the method is supported, not version
+    ///   gated, or the node version is unrecognized
+    pub fn unsupported_reason(&self, method: &str) -> Option<String> {
+        let version = self.parsed?;
+        let (_, added_in, removed_in) = METHOD_VERSIONS.iter().find(|(name, _, _)| *name == method)?;
+        if let Some(added_in) = added_in.filter(|added_in| version < *added_in) {
+            return Some(format!(
+                "{method} requires solana-core {} or newer (node runs {})",
+                format_version(added_in),
+                self.version
+            ));
+        }
+        if let Some(removed_in) = removed_in.filter(|removed_in| version >= *removed_in) {
+            return Some(format!(
+                "{method} was removed in solana-core {} (node runs {})",
+                format_version(removed_in),
+                self.version
+            ));
+        }
+        None
+    }
+}
+
+/// Parses "major.minor.patch", ignoring any pre-release or build suffix
+///
+/// Major version 0 is reported by non-Agave clients such as Firedancer, whose
+/// numbering does not follow solana-core, so it is treated as unrecognized.
+fn parse_version(version: &str) -> Option<Version> {
+    let mut parts = version.split(|c: char| !c.is_ascii_digit()).map(str::parse::<u32>);
+    let parsed = (parts.next()?.ok()?, parts.next()?.ok()?, parts.next()?.ok()?);
+    (parsed.0 > 0).then_some(parsed)
+}
+
+fn format_version((major, minor, patch): Version) -> String {
+    format!("{major}.{minor}.{patch}")
+}
+
+#[derive(Default)]
+enum ProbeState {
+    #[default]
+    Unprobed,
+    Known(Arc<NodeCapabilities>),
+    Failed(Instant),
+}
+
+/// The cached getVersion probe of the primary RPC node
+#[derive(Default)]
+pub struct CapabilityCache {
+    state: Mutex<ProbeState>,
+    /// Held while probing so concurrent lookups share one getVersion call
+    probing: tokio::sync::Mutex<()>,
+}
+
+impl CapabilityCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the node's capabilities, probing `client` if not yet known
+    ///
+    /// A failed probe returns `None` (nothing is rejected) and is retried
+    /// after a minute. Callers arriving while a probe runs wait for its result
+    /// instead of sending their own.
+    pub async fn get_or_probe(&self, client: &RpcClient) -> Option<Arc<NodeCapabilities>> {
+        if let Some(known) = self.cached() {
+            return known;
+        }
+        let _probing = self.probing.lock().await;
+        if let Some(known) = self.cached() {
+            return known;
+        }
+
+        match tokio::time::timeout(NETWORK_PROBE_TIMEOUT, client.get_version()).await {
+            Ok(Ok(version)) => {
+                let capabilities = Arc::new(NodeCapabilities::new(version.solana_core, version.feature_set));
+                log::info!("RPC node runs solana-core {}", capabilities.version);
+                *self.lock() = ProbeState::Known(capabilities.clone());
+                Some(capabilities)
+            }
+            outcome => {
+                let error = match outcome {
+                    Ok(Err(e)) => e.to_string(),
+                    _ => format!("timed out after {}s", NETWORK_PROBE_TIMEOUT.as_secs()),
+                };
+                log::warn!("RPC node version probe failed, not gating methods: {error}");
+                *self.lock() = ProbeState::Failed(Instant::now());
+                None
+            }
+        }
+    }
+
+    /// Returns the settled probe outcome, or `None` when a probe is due
+    fn cached(&self) -> Option<Option<Arc<NodeCapabilities>>> {
+        match &*self.lock() {
+            ProbeState::Known(capabilities) => Some(Some(capabilities.clone())),
+            ProbeState::Failed(at) if at.elapsed() < PROBE_RETRY_AFTER => Some(None),
+            _ => None,
+        }
+    }
+
+    /// Forgets the probe result so the next lookup probes again
+    pub fn reset(&self) {
+        *self.lock() = ProbeState::Unprobed;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ProbeState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// JSON-RPC code a node returns for a method it does not implement
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// More positional params than any RPC method accepts
+const PROBE_PARAM_COUNT: usize = 16;
+
+/// Asks the node whether it implements `method`
+///
+/// The request carries more positional params than any method takes, so a
+/// node that knows the method rejects it while parsing, before doing any work.
+/// Only "method not found" counts as unsupported.
+///
+/// # Returns
+/// * `Option<bool>` - `None` when the node could not be reached in time
+pub async fn probe_method(client: &RpcClient, method: &'static str) -> Option<bool> {
+    use solana_client::{client_error::ClientErrorKind, rpc_request::{RpcError, RpcRequest}};
+
+    let params = serde_json::Value::Array(vec![serde_json::Value::Null; PROBE_PARAM_COUNT]);
+    let request = client.send::<serde_json::Value>(RpcRequest::Custom { method }, params);
+    match tokio::time::timeout(NETWORK_PROBE_TIMEOUT, request).await {
+        Ok(Ok(_)) => Some(true),
+        Ok(Err(e)) => match e.kind() {
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => Some(*code != METHOD_NOT_FOUND),
+            ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => None,
+            _ => Some(true),
+        },
+        Err(_) => None,
+    }
+}
+
+/// Probes the primary RPC node so the first version-gated call need not wait
+pub async fn probe_node_capabilities(state: Arc<RwLock<ServerState>>) {
+    let (node_capabilities, rpc_client) = {
+        let state_guard = state.read().await;
+        (state_guard.node_capabilities.clone(), state_guard.rpc_client.clone())
+    };
+    node_capabilities.get_or_probe(&rpc_client).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_reason_by_version() {
+        let old = NodeCapabilities::new("1.8.16", Some(1));
+        assert!(old.unsupported_reason("isBlockhashValid").unwrap().contains("requires solana-core 1.9.0"));
+        assert_eq!(old.unsupported_reason("getRecentBlockhash"), None);
+
+        let agave = NodeCapabilities::new("2.1.11", Some(1));
+        assert_eq!(agave.unsupported_reason("isBlockhashValid"), None);
+        assert!(agave.unsupported_reason("getRecentBlockhash").unwrap().contains("removed in solana-core 2.0.0"));
+        assert_eq!(agave.unsupported_reason("getBalance"), None);
+
+        // Unrecognized numbering never rejects anything
+        for version in ["0.405.20218", "unknown"] {
+            assert_eq!(NodeCapabilities::new(version, None).unsupported_reason("getRecentBlockhash"), None);
+        }
+        assert_eq!(parse_version("1.18.26-rc1"), Some((1, 18, 26)));
+    }
+}
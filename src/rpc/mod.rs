@@ -2,7 +2,10 @@
 
 pub mod accounts;
 pub mod blocks;
+pub mod capabilities;
 pub mod missing_methods;
+pub mod names;
+pub mod single_flight;
 pub mod system;
 pub mod tokens;
 pub mod transactions;
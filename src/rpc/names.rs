@@ -0,0 +1,134 @@
+//! Solana Name Service (.sol domain) resolution
+
+use crate::error::{McpError, McpResult};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{hash::hashv, pubkey, pubkey::Pubkey};
+
+/// SPL Name Service program that owns every name record
+pub const NAME_SERVICE_PROGRAM_ID: Pubkey = pubkey!("namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX");
+
+/// Name record of the `.sol` top-level domain, parent of every second-level name
+pub const SOL_TLD_ACCOUNT: Pubkey = pubkey!("58PwtjSDuFHuUkYjH9BYnnQKHfwo9reZhC2zMJv9JPkx");
+
+/// Prefix hashed together with a name to seed its record address
+const HASH_PREFIX: &str = "SPL Name Service";
+
+/// Name records start with a header of parent, owner and class pubkeys
+const NAME_RECORD_HEADER_LEN: usize = 96;
+
+/// Returns true if the value looks like a .sol name rather than a base58 pubkey
+///
+/// Base58 never contains a dot, so the two forms cannot be confused.
+pub fn is_sol_domain(value: &str) -> bool {
+    value.trim().to_ascii_lowercase().ends_with(".sol")
+}
+
+/// Derives the address of a name record under `parent`
+pub fn derive_name_account(name: &str, parent: &Pubkey) -> Pubkey {
+    let hashed_name = hashv(&[HASH_PREFIX.as_bytes(), name.as_bytes()]);
+    // Domains are unclassed, so the class seed is the default pubkey
+    Pubkey::find_program_address(
+        &[hashed_name.as_ref(), Pubkey::default().as_ref(), parent.as_ref()],
+        &NAME_SERVICE_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Derives the name record address for a `.sol` domain or one-level subdomain
+///
+/// # Arguments
+/// * `domain` - Name such as `bonfida.sol` or `dex.bonfida.sol`
+/// * `field` - Argument name reported back on failure
+///
+/// # Errors
+/// * The name is not a `.sol` domain, has empty labels, or nests deeper than
+///   one subdomain
+pub fn domain_name_account(domain: &str, field: &str) -> McpResult<Pubkey> {
+    let normalized = domain.trim().to_ascii_lowercase();
+    let invalid = || {
+        McpError::validation(format!(
+            "Invalid {field} '{domain}': expected a name like 'example.sol' or 'sub.example.sol'"
+        ))
+        .with_parameter(field)
+    };
+
+    let labels: Vec<&str> = normalized.strip_suffix(".sol").ok_or_else(invalid)?.split('.').collect();
+    if labels.iter().any(|label| label.is_empty() || label.chars().any(char::is_whitespace)) {
+        return Err(invalid());
+    }
+
+    match labels.as_slice() {
+        [name] => Ok(derive_name_account(name, &SOL_TLD_ACCOUNT)),
+        // Subdomain records are hashed with a leading NUL under their parent's record
+        [sub, name] => Ok(derive_name_account(
+            &format!("\0{sub}"),
+            &derive_name_account(name, &SOL_TLD_ACCOUNT),
+        )),
+        _ => Err(invalid()),
+    }
+}
+
+/// Resolves a `.sol` domain to the pubkey that owns it
+///
+/// # Arguments
+/// * `client` - RPC client for the network the name is registered on
+/// * `domain` - Name such as `bonfida.sol`
+/// * `field` - Argument name reported back on failure
+///
+/// # Returns
+/// * `McpResult<(Pubkey, Pubkey)>` - The name record address and its owner
+///
+/// # Errors
+/// * Validation error naming `field` when the domain is malformed, has no
+///   name record, or the record is not a live name service account
+/// * RPC errors when the record cannot be fetched
+pub async fn resolve_domain(client: &RpcClient, domain: &str, field: &str) -> McpResult<(Pubkey, Pubkey)> {
+    let name_account = domain_name_account(domain, field)?;
+    let not_registered = || {
+        McpError::validation(format!("Domain '{domain}' is not registered")).with_parameter(field)
+    };
+
+    let account = client
+        .get_account_with_commitment(&name_account, client.commitment())
+        .await
+        .map_err(|e| McpError::from(e).with_method("resolveDomain").with_rpc_url(client.url()))?
+        .value
+        .ok_or_else(not_registered)?;
+    if account.owner != NAME_SERVICE_PROGRAM_ID || account.data.len() < NAME_RECORD_HEADER_LEN {
+        return Err(not_registered());
+    }
+
+    let owner = Pubkey::try_from(&account.data[32..64]).map_err(|_| not_registered())?;
+    // Deleted records keep their account but have their owner zeroed
+    if owner == Pubkey::default() {
+        return Err(not_registered());
+    }
+    Ok((name_account, owner))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_name_account() {
+        // Well-known record address of bonfida.sol
+        let bonfida = pubkey!("Crf8hzfthWGbGbLTVCiqRqV5MVnbpHB1L9KQMd6gsinb");
+        assert_eq!(domain_name_account("bonfida.sol", "domain").unwrap(), bonfida);
+        assert_eq!(domain_name_account(" Bonfida.SOL ", "domain").unwrap(), bonfida);
+
+        let sub = domain_name_account("dex.bonfida.sol", "domain").unwrap();
+        assert_eq!(sub, derive_name_account("\0dex", &bonfida));
+
+        for invalid in ["bonfida", ".sol", "a..sol", "a.b.c.sol"] {
+            let err = domain_name_account(invalid, "domain").unwrap_err();
+            assert_eq!(err.json_rpc_code(), -32602, "{invalid} accepted");
+        }
+    }
+
+    #[test]
+    fn test_is_sol_domain() {
+        assert!(is_sol_domain("bonfida.sol"));
+        assert!(!is_sol_domain("11111111111111111111111111111111"));
+    }
+}
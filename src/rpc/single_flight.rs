@@ -0,0 +1,188 @@
+//! Coalesces concurrent identical upstream RPC calls
+//!
+//! When several callers ask for the same method and params against the same
+//! endpoint at once, only the first (the leader) goes upstream; the others
+//! wait for and share its result. Nothing is kept once the call finishes, so
+//! this complements `crate::cache` rather than replacing it.
+
+use crate::error::McpResult;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use tokio::sync::watch;
+
+/// Shared result slot of one in-flight call; `None` until the leader finishes
+type CallResult = watch::Receiver<Option<McpResult<Value>>>;
+
+/// In-flight upstream calls keyed by endpoint, method and params
+#[derive(Default)]
+pub struct SingleFlight {
+    calls: DashMap<u64, CallResult>,
+}
+
+/// Process-wide single-flight group used by the rpc functions
+pub static RPC_SINGLE_FLIGHT: Lazy<SingleFlight> = Lazy::new(SingleFlight::new);
+
+impl SingleFlight {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of calls currently in flight
+    pub fn in_flight(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Runs `f` unless an identical call is already in flight, then shares its result
+    ///
+    /// If the leader is dropped before finishing (e.g. its tool call was
+    /// cancelled), each waiter falls back to running `f` itself.
+    ///
+    /// # Arguments
+    /// * `method` - The RPC method name
+    /// * `endpoint` - The RPC URL the call goes to; calls to different endpoints are never merged
+    /// * `params` - The parameters identifying the call
+    /// * `f` - The upstream call
+    pub async fn run<F, Fut>(&self, method: &str, endpoint: &str, params: &Value, f: F) -> McpResult<Value>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = McpResult<Value>>,
+    {
+        let key = call_key(method, endpoint, params);
+        let leader = match self.calls.entry(key) {
+            Entry::Occupied(call) => Err(call.get().clone()),
+            Entry::Vacant(slot) => {
+                let (tx, rx) = watch::channel(None);
+                slot.insert(rx);
+                Ok(tx)
+            }
+        };
+
+        let tx = match leader {
+            Ok(tx) => tx,
+            Err(mut rx) => {
+                log::debug!("Joining in-flight {method} call");
+                if let Ok(shared) = rx.wait_for(Option::is_some).await {
+                    if let Some(result) = shared.clone() {
+                        return result;
+                    }
+                }
+                return f().await;
+            }
+        };
+
+        let guard = InFlight { calls: &self.calls, key };
+        let result = f().await;
+        drop(guard);
+        tx.send_replace(Some(result.clone()));
+        result
+    }
+}
+
+/// Removes a call from the group when its leader finishes or is dropped
+struct InFlight<'a> {
+    calls: &'a DashMap<u64, CallResult>,
+    key: u64,
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.calls.remove(&self.key);
+    }
+}
+
+fn call_key(method: &str, endpoint: &str, params: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    endpoint.hash(&mut hasher);
+    method.hash(&mut hasher);
+    params.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::McpError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_identical_calls_share_one_upstream_call() {
+        let group = Arc::new(SingleFlight::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let params = serde_json::json!({ "pubkey": "11111111111111111111111111111111" });
+
+        let callers: Vec<_> = (0..8)
+            .map(|_| {
+                let (group, calls, params) = (group.clone(), calls.clone(), params.clone());
+                tokio::spawn(async move {
+                    group
+                        .run("getAccountInfo", "http://rpc", &params, || async {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            Ok(serde_json::json!({ "account": null }))
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for caller in callers {
+            assert_eq!(caller.await.unwrap().unwrap(), serde_json::json!({ "account": null }));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(group.in_flight(), 0);
+
+        // Different endpoints are separate calls, and errors are shared too
+        let failing = |endpoint: &'static str| {
+            let (group, calls, params) = (group.clone(), calls.clone(), params.clone());
+            tokio::spawn(async move {
+                group
+                    .run("getAccountInfo", endpoint, &params, || async {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Err(McpError::network("upstream down"))
+                    })
+                    .await
+            })
+        };
+        let results = [failing("http://a"), failing("http://a"), failing("http://b")];
+        for result in results {
+            assert!(result.await.unwrap().is_err());
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_waiters_recover_when_leader_is_dropped() {
+        let group = Arc::new(SingleFlight::new());
+        let params = Value::Null;
+
+        let leader_group = group.clone();
+        let leader = tokio::spawn(async move {
+            leader_group
+                .run("getBalance", "http://rpc", &Value::Null, std::future::pending)
+                .await
+        });
+        while group.in_flight() == 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        let waiter_group = group.clone();
+        let waiter = tokio::spawn(async move {
+            waiter_group
+                .run("getBalance", "http://rpc", &params, || async { Ok(serde_json::json!({ "balance": 1 })) })
+                .await
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        leader.abort();
+
+        let result = tokio::time::timeout(Duration::from_secs(1), waiter).await.unwrap().unwrap();
+        assert_eq!(result.unwrap(), serde_json::json!({ "balance": 1 }));
+        assert_eq!(group.in_flight(), 0);
+    }
+}
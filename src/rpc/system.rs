@@ -1,16 +1,24 @@
 use crate::cache::with_cache;
 use crate::error::{McpError, McpResult};
 use crate::logging::{log_rpc_request_start, log_rpc_request_success, log_rpc_request_failure, new_request_id};
+use crate::progress::report_progress;
+use base64::Engine;
+use futures_util::StreamExt;
 use serde_json::Value;
 use solana_client::{
     nonblocking::rpc_client::RpcClient,
-    rpc_config::RpcRequestAirdropConfig,
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::{RpcEpochConfig, RpcRequestAirdropConfig, RpcTransactionConfig},
+    rpc_request::RpcRequest,
+    rpc_response::{Response, RpcInflationGovernor, RpcInflationReward},
 };
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     pubkey::Pubkey,
     message::Message,
+    signature::Signature,
 };
+use solana_transaction_status::UiTransactionEncoding;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -141,7 +149,8 @@ pub async fn get_identity(client: &RpcClient) -> McpResult<Value> {
     match client.get_identity().await {
         Ok(identity) => {
             let duration = start_time.elapsed().as_millis() as u64;
-            let result = serde_json::json!({ "identity": identity });
+            // Pubkey serializes as raw bytes, so render base58 explicitly
+            let result = serde_json::json!({ "identity": identity.to_string() });
             
             log_rpc_request_success(
                 request_id,
@@ -224,7 +233,7 @@ pub async fn get_cluster_nodes(client: &RpcClient) -> McpResult<Value> {
 }
 
 /// Get current epoch information
-pub async fn get_epoch_info(client: &RpcClient) -> McpResult<Value> {
+pub async fn get_epoch_info(client: &RpcClient, commitment: Option<CommitmentConfig>) -> McpResult<Value> {
     let request_id = new_request_id();
     let start_time = Instant::now();
     let method = "getEpochInfo";
@@ -233,10 +242,13 @@ pub async fn get_epoch_info(client: &RpcClient) -> McpResult<Value> {
         request_id,
         method,
         Some(&client.url()),
-        None,
+        Some(&format!("commitment: {commitment:?}")),
     );
 
-    match client.get_epoch_info().await {
+    match client
+        .get_epoch_info_with_commitment(commitment.unwrap_or_else(|| client.commitment()))
+        .await
+    {
         Ok(epoch_info) => {
             let duration = start_time.elapsed().as_millis() as u64;
             let result = serde_json::json!({ "epoch_info": epoch_info });
@@ -322,7 +334,10 @@ pub async fn get_epoch_schedule(client: &RpcClient) -> McpResult<Value> {
 }
 
 /// Get inflation governor information
-pub async fn get_inflation_governor(client: &RpcClient) -> McpResult<Value> {
+pub async fn get_inflation_governor(
+    client: &RpcClient,
+    commitment: Option<CommitmentConfig>,
+) -> McpResult<Value> {
     let request_id = new_request_id();
     let start_time = Instant::now();
     let method = "getInflationGovernor";
@@ -331,10 +346,18 @@ pub async fn get_inflation_governor(client: &RpcClient) -> McpResult<Value> {
         request_id,
         method,
         Some(&client.url()),
-        None,
+        Some(&format!("commitment: {commitment:?}")),
     );
 
-    match client.get_inflation_governor().await {
+    // RpcClient::get_inflation_governor always uses the client's commitment
+    let response: Result<RpcInflationGovernor, _> = client
+        .send(
+            RpcRequest::GetInflationGovernor,
+            serde_json::json!([commitment.unwrap_or_else(|| client.commitment())]),
+        )
+        .await;
+
+    match response {
         Ok(governor) => {
             let duration = start_time.elapsed().as_millis() as u64;
             let result = serde_json::json!({ "governor": governor });
@@ -420,10 +443,14 @@ pub async fn get_inflation_rate(client: &RpcClient) -> McpResult<Value> {
 }
 
 /// Get inflation rewards for accounts
+///
+/// `rewards` is positional: entry `i` belongs to `addresses[i]` and is `null`
+/// when that address earned no reward in the epoch.
 pub async fn get_inflation_reward(
     client: &RpcClient,
     addresses: &[Pubkey],
     epoch: Option<u64>,
+    commitment: Option<CommitmentConfig>,
 ) -> McpResult<Value> {
     let request_id = new_request_id();
     let start_time = Instant::now();
@@ -433,10 +460,21 @@ pub async fn get_inflation_reward(
         request_id,
         method,
         Some(&client.url()),
-        Some(&format!("addresses_count: {}, epoch: {:?}", addresses.len(), epoch)),
+        Some(&format!("addresses_count: {}, epoch: {:?}, commitment: {:?}", addresses.len(), epoch, commitment)),
     );
 
-    match client.get_inflation_reward(addresses, epoch).await {
+    // RpcClient::get_inflation_reward always uses the client's commitment
+    let addresses_param: Vec<String> = addresses.iter().map(|address| address.to_string()).collect();
+    let config = RpcEpochConfig {
+        epoch,
+        commitment: Some(commitment.unwrap_or_else(|| client.commitment())),
+        min_context_slot: None,
+    };
+    let response: Result<Vec<Option<RpcInflationReward>>, _> = client
+        .send(RpcRequest::GetInflationReward, serde_json::json!([addresses_param, config]))
+        .await;
+
+    match response {
         Ok(rewards) => {
             let duration = start_time.elapsed().as_millis() as u64;
             let result = serde_json::json!({ "rewards": rewards });
@@ -475,8 +513,15 @@ pub async fn get_inflation_reward(
 pub async fn get_minimum_balance_for_rent_exemption(
     client: &RpcClient,
     data_len: usize,
+    commitment: Option<CommitmentConfig>,
 ) -> McpResult<Value> {
-    let lamports = client.get_minimum_balance_for_rent_exemption(data_len).await?;
+    // RpcClient::get_minimum_balance_for_rent_exemption always uses the client's commitment
+    let lamports: u64 = client
+        .send(
+            RpcRequest::GetMinimumBalanceForRentExemption,
+            serde_json::json!([data_len, commitment.unwrap_or_else(|| client.commitment())]),
+        )
+        .await?;
     Ok(serde_json::json!({ "lamports": lamports }))
 }
 
@@ -507,7 +552,7 @@ pub async fn request_airdrop(
         client,
         async {
             let signature = client.request_airdrop(pubkey, lamports).await?;
-            Ok::<Value, crate::error::McpError>(serde_json::json!({ "signature": signature }))
+            Ok::<Value, crate::error::McpError>(serde_json::json!({ "signature": signature.to_string() }))
         },
         &params_summary
     )
@@ -527,7 +572,7 @@ pub async fn request_airdrop_with_config(
     let signature = client
         .request_airdrop_with_config(pubkey, lamports, config)
         .await?;
-    Ok(serde_json::json!({ "signature": signature }))
+    Ok(serde_json::json!({ "signature": signature.to_string() }))
 }
 
 pub async fn get_stake_minimum_delegation(client: &RpcClient) -> McpResult<Value> {
@@ -576,13 +621,71 @@ pub async fn get_latest_blockhash_with_commitment(
     }))
 }
 
+/// Get the fee the network would charge for a message
+///
+/// `fee` is in lamports, or `null` when the message's recent blockhash has
+/// expired.
 pub async fn get_fee_for_message(
     client: &RpcClient,
     message: &Message,
+    commitment: Option<CommitmentConfig>,
 ) -> McpResult<Value> {
+    let request_id = new_request_id();
+    let start_time = Instant::now();
+    let method = "getFeeForMessage";
+    
+    log_rpc_request_start(
+        request_id,
+        method,
+        Some(&client.url()),
+        Some(&format!("blockhash: {}, commitment: {:?}", message.recent_blockhash, commitment)),
+    );
 
-    let fee = client.get_fee_for_message(message).await?;
-    Ok(serde_json::json!({ "fee": fee }))
+    // RpcClient::get_fee_for_message turns an expired blockhash into an error
+    let serialized = bincode::serialize(message)
+        .map_err(|e| McpError::server(format!("Failed to serialize message: {e}")))?;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(serialized);
+    let response: Result<Response<Option<u64>>, _> = client
+        .send(
+            RpcRequest::GetFeeForMessage,
+            serde_json::json!([encoded, commitment.unwrap_or_else(|| client.commitment())]),
+        )
+        .await;
+
+    match response {
+        Ok(response) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let result = serde_json::json!({ "fee": response.value });
+            
+            log_rpc_request_success(
+                request_id,
+                method,
+                duration,
+                Some("message fee retrieved"),
+                Some(&client.url()),
+            );
+            
+            Ok(result)
+        }
+        Err(e) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let error = McpError::from(e)
+                .with_request_id(request_id)
+                .with_method(method)
+                .with_rpc_url(client.url());
+            
+            log_rpc_request_failure(
+                request_id,
+                method,
+                error.error_type(),
+                duration,
+                Some(&error.to_log_value()),
+                Some(&client.url()),
+            );
+            
+            Err(error)
+        }
+    }
 }
 
 /// Check if a blockhash is still valid for submitting transactions
@@ -1120,6 +1223,193 @@ pub async fn get_recent_performance_samples(
     }
 }
 
+/// Default number of landed transactions sampled by `getHistoricalFeeStats`
+pub const DEFAULT_FEE_SAMPLE_SIZE: usize = 25;
+
+/// Upper bound on transactions sampled by `getHistoricalFeeStats`
+pub const MAX_FEE_SAMPLE_SIZE: usize = 100;
+
+/// Base fee charged per transaction signature
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// getTransaction requests `getHistoricalFeeStats` keeps in flight at once
+const FEE_SAMPLE_FETCH_CONCURRENCY: usize = 8;
+
+/// Summarize a set of priority fees (in lamports) into distribution statistics
+pub fn compute_fee_stats(fees: &[u64]) -> Value {
+    if fees.is_empty() {
+        return serde_json::json!({ "count": 0 });
+    }
+
+    let mut sorted = fees.to_vec();
+    sorted.sort_unstable();
+
+    // Nearest-rank percentile
+    let percentile = |p: u64| {
+        let rank = (p as usize * sorted.len()).div_ceil(100).max(1);
+        sorted[rank - 1]
+    };
+    let sum: u128 = sorted.iter().map(|fee| *fee as u128).sum();
+
+    serde_json::json!({
+        "count": sorted.len(),
+        "min": sorted[0],
+        "max": sorted[sorted.len() - 1],
+        "mean": (sum / sorted.len() as u128) as u64,
+        "median": percentile(50),
+        "percentiles": {
+            "p25": percentile(25),
+            "p50": percentile(50),
+            "p75": percentile(75),
+            "p90": percentile(90),
+            "p95": percentile(95)
+        }
+    })
+}
+
+/// Sample recently landed transactions touching the given accounts and
+/// report the distribution of priority fees they paid
+///
+/// Each address contributes an equal share of the sample, and the shares are
+/// interleaved so no single account dominates it.
+pub async fn get_historical_fee_stats(
+    client: &RpcClient,
+    addresses: &[Pubkey],
+    sample_size: usize,
+    commitment: Option<CommitmentConfig>,
+) -> McpResult<Value> {
+    let request_id = new_request_id();
+    let start_time = Instant::now();
+    let method = "getHistoricalFeeStats";
+    let sample_size = sample_size.clamp(1, MAX_FEE_SAMPLE_SIZE);
+    let per_address = sample_size.div_ceil(addresses.len().max(1));
+    let commitment = commitment.unwrap_or_else(CommitmentConfig::confirmed);
+
+    log_rpc_request_start(
+        request_id,
+        method,
+        Some(&client.url()),
+        Some(&format!("addresses: {}, sampleSize: {}", addresses.len(), sample_size)),
+    );
+
+    let mut per_address_signatures: Vec<Vec<Signature>> = Vec::with_capacity(addresses.len());
+    let address_count = addresses.len() as u64;
+    for (scanned, address) in (1..).zip(addresses) {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before: None,
+            until: None,
+            limit: Some(per_address),
+            commitment: Some(commitment),
+        };
+        match client.get_signatures_for_address_with_config(address, config).await {
+            Ok(statuses) => {
+                per_address_signatures.push(
+                    statuses
+                        .iter()
+                        .filter_map(|status| status.signature.parse::<Signature>().ok())
+                        .collect(),
+                );
+                report_progress(scanned, None, &format!("Listed signatures for {scanned} of {address_count} addresses"));
+            }
+            Err(e) => {
+                let duration = start_time.elapsed().as_millis() as u64;
+                let error = McpError::from(e)
+                    .with_request_id(request_id)
+                    .with_method(method)
+                    .with_rpc_url(client.url());
+
+                log_rpc_request_failure(
+                    request_id,
+                    method,
+                    error.error_type(),
+                    duration,
+                    Some(&error.to_log_value()),
+                    Some(&client.url()),
+                );
+
+                return Err(error);
+            }
+        }
+    }
+    let signatures = interleave_signatures(per_address_signatures, sample_size);
+
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(commitment),
+        max_supported_transaction_version: Some(0),
+    };
+    // Progress continues from the address scan, so it keeps increasing
+    let sampled = signatures.len() as u64;
+    let fetches = signatures
+        .iter()
+        .map(|signature| client.get_transaction_with_config(signature, config));
+    let transactions: Vec<_> = futures_util::stream::iter(fetches)
+        .buffer_unordered(FEE_SAMPLE_FETCH_CONCURRENCY)
+        .enumerate()
+        .map(|(index, transaction)| {
+            let done = index as u64 + 1;
+            report_progress(
+                address_count + done,
+                Some(address_count + sampled),
+                &format!("Fetched {done} of {sampled} transactions"),
+            );
+            transaction
+        })
+        .collect()
+        .await;
+
+    // Transactions that cannot be fetched or decoded are left out of the sample
+    let priority_fees: Vec<u64> = transactions
+        .into_iter()
+        .filter_map(|tx| tx.ok())
+        .filter_map(|tx| {
+            let fee = tx.transaction.meta.as_ref()?.fee;
+            let signature_count = tx.transaction.transaction.decode()?.signatures.len() as u64;
+            Some(fee.saturating_sub(signature_count * LAMPORTS_PER_SIGNATURE))
+        })
+        .collect();
+
+    let duration = start_time.elapsed().as_millis() as u64;
+    let result = serde_json::json!({
+        "sampledSignatures": signatures.len(),
+        "priorityFeeLamports": compute_fee_stats(&priority_fees)
+    });
+
+    log_rpc_request_success(
+        request_id,
+        method,
+        duration,
+        Some(&format!("{} landed transactions sampled", priority_fees.len())),
+        Some(&client.url()),
+    );
+
+    Ok(result)
+}
+
+/// Merges per-address signature lists round-robin, dropping duplicates,
+/// until `limit` signatures are taken
+pub fn interleave_signatures(per_address: Vec<Vec<Signature>>, limit: usize) -> Vec<Signature> {
+    let mut lists: Vec<_> = per_address.into_iter().map(Vec::into_iter).collect();
+    let mut signatures = Vec::new();
+    while signatures.len() < limit {
+        let mut progressed = false;
+        for list in &mut lists {
+            let Some(signature) = list.next() else { continue };
+            progressed = true;
+            if !signatures.contains(&signature) {
+                signatures.push(signature);
+                if signatures.len() == limit {
+                    break;
+                }
+            }
+        }
+        if !progressed {
+            break;
+        }
+    }
+    signatures
+}
+
 /// Get recent prioritization fees
 pub async fn get_recent_prioritization_fees(
     client: &RpcClient,
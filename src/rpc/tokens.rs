@@ -1,17 +1,91 @@
+use crate::progress::report_progress;
 use anyhow::Result;
 use serde_json::Value;
-use solana_account_decoder::UiAccountEncoding;
-use solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::TokenAccountsFilter};
-use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use solana_account_decoder::{encode_ui_account, UiAccountData, UiAccountEncoding};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::TokenAccountsFilter, rpc_response::RpcKeyedAccount};
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 use spl_token::id as spl_token_program_id;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::{Account as TokenAccount, Mint};
+use std::collections::BTreeMap;
 
-pub async fn get_token_accounts_by_owner(client: &RpcClient, owner: &Pubkey) -> Result<Value> {
+/// Token-2022 program id
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = spl_token_2022::ID;
+
+/// Returns true if the program id is the Token or Token-2022 program
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == spl_token_program_id() || *program_id == TOKEN_2022_PROGRAM_ID
+}
+
+/// Resolves an optional token program id, defaulting to the classic Token program
+///
+/// # Errors
+/// * The program id is neither the Token nor the Token-2022 program
+pub fn resolve_token_program_id(program_id: Option<&Pubkey>) -> Result<Pubkey> {
+    let program_id = program_id.copied().unwrap_or_else(spl_token_program_id);
+    if !is_token_program(&program_id) {
+        return Err(anyhow::anyhow!(
+            "Unsupported token program {}. Must be the Token or Token-2022 program",
+            program_id
+        ));
+    }
+    Ok(program_id)
+}
+
+/// Decodes Token-2022 extensions (transfer fees, interest-bearing config, etc.)
+///
+/// # Returns
+/// * `Option<Value>` - The jsonParsed extension list, or None if the account
+///   has no extensions or cannot be parsed
+pub fn decode_token_2022_extensions(address: &Pubkey, account: &Account) -> Option<Value> {
+    if account.owner != TOKEN_2022_PROGRAM_ID {
+        return None;
+    }
+    match encode_ui_account(address, account, UiAccountEncoding::JsonParsed, None, None).data {
+        UiAccountData::Json(parsed) => parsed.parsed["info"].get("extensions").cloned(),
+        _ => None,
+    }
+}
+
+/// Fetches an account and verifies it is owned by the expected token program
+async fn get_token_program_account(
+    client: &RpcClient,
+    address: &Pubkey,
+    program_id: &Pubkey,
+    commitment: CommitmentConfig,
+) -> Result<Account> {
+    let account = client
+        .get_account_with_commitment(address, commitment)
+        .await?
+        .value
+        .ok_or_else(|| anyhow::anyhow!("Account {} not found", address))?;
+    if account.owner != *program_id {
+        return Err(anyhow::anyhow!(
+            "Account {} is owned by {}, not token program {}",
+            address,
+            account.owner,
+            program_id
+        ));
+    }
+    Ok(account)
+}
+
+pub async fn get_token_accounts_by_owner(
+    client: &RpcClient,
+    owner: &Pubkey,
+    program_id: Option<&Pubkey>,
+    commitment: Option<CommitmentConfig>,
+) -> Result<Value> {
+    let program_id = resolve_token_program_id(program_id)?;
     let accounts = client
-        .get_token_accounts_by_owner(
+        .get_token_accounts_by_owner_with_commitment(
             owner,
-            TokenAccountsFilter::ProgramId(spl_token_program_id()),
+            TokenAccountsFilter::ProgramId(program_id),
+            commitment.unwrap_or_else(|| client.commitment()),
         )
-        .await?;
+        .await?
+        .value;
     Ok(serde_json::json!({ "accounts": accounts }))
 }
 
@@ -19,10 +93,13 @@ pub async fn get_token_accounts_by_owner_with_commitment(
     client: &RpcClient,
     owner: &Pubkey,
     filter: TokenAccountsFilter,
-    _commitment: CommitmentConfig,
+    commitment: CommitmentConfig,
     _encoding: Option<UiAccountEncoding>,
 ) -> Result<Value> {
-    let accounts = client.get_token_accounts_by_owner(owner, filter).await?;
+    let accounts = client
+        .get_token_accounts_by_owner_with_commitment(owner, filter, commitment)
+        .await?
+        .value;
     Ok(serde_json::json!({ "accounts": accounts }))
 }
 
@@ -41,12 +118,13 @@ pub async fn get_token_accounts_by_delegate_with_commitment(
     client: &RpcClient,
     delegate: &Pubkey,
     filter: TokenAccountsFilter,
-    _commitment: CommitmentConfig,
+    commitment: CommitmentConfig,
     _encoding: Option<UiAccountEncoding>,
 ) -> Result<Value> {
     let accounts = client
-        .get_token_accounts_by_delegate(delegate, filter)
-        .await?;
+        .get_token_accounts_by_delegate_with_commitment(delegate, filter, commitment)
+        .await?
+        .value;
     Ok(serde_json::json!({ "accounts": accounts }))
 }
 
@@ -55,6 +133,29 @@ pub async fn get_token_supply(client: &RpcClient, mint: &Pubkey) -> Result<Value
     Ok(serde_json::json!({ "supply": supply }))
 }
 
+/// Get token supply for a mint of the given token program
+///
+/// For Token-2022 mints the decoded mint extensions are included.
+pub async fn get_token_supply_for_program(
+    client: &RpcClient,
+    mint: &Pubkey,
+    program_id: Option<&Pubkey>,
+    commitment: Option<CommitmentConfig>,
+) -> Result<Value> {
+    let program_id = resolve_token_program_id(program_id)?;
+    let commitment = commitment.unwrap_or_else(|| client.commitment());
+    if program_id != TOKEN_2022_PROGRAM_ID {
+        return get_token_supply_with_commitment(client, mint, commitment).await;
+    }
+
+    let mint_account = get_token_program_account(client, mint, &program_id, commitment).await?;
+    let supply = client.get_token_supply_with_commitment(mint, commitment).await?.value;
+    Ok(serde_json::json!({
+        "supply": supply,
+        "extensions": decode_token_2022_extensions(mint, &mint_account).unwrap_or(Value::Array(vec![]))
+    }))
+}
+
 pub async fn get_token_supply_with_commitment(
     client: &RpcClient,
     mint: &Pubkey,
@@ -62,7 +163,8 @@ pub async fn get_token_supply_with_commitment(
 ) -> Result<Value> {
     let supply = client
         .get_token_supply_with_commitment(mint, commitment)
-        .await?;
+        .await?
+        .value;
     Ok(serde_json::json!({ "supply": supply }))
 }
 
@@ -87,6 +189,32 @@ pub async fn get_token_account_balance(client: &RpcClient, account: &Pubkey) ->
     Ok(serde_json::json!({ "balance": balance }))
 }
 
+/// Get token account balance for an account of the given token program
+///
+/// For Token-2022 accounts the decoded account extensions are included.
+pub async fn get_token_account_balance_for_program(
+    client: &RpcClient,
+    account: &Pubkey,
+    program_id: Option<&Pubkey>,
+    commitment: Option<CommitmentConfig>,
+) -> Result<Value> {
+    let program_id = resolve_token_program_id(program_id)?;
+    let commitment = commitment.unwrap_or_else(|| client.commitment());
+    if program_id != TOKEN_2022_PROGRAM_ID {
+        return get_token_account_balance_with_commitment(client, account, commitment).await;
+    }
+
+    let token_account = get_token_program_account(client, account, &program_id, commitment).await?;
+    let balance = client
+        .get_token_account_balance_with_commitment(account, commitment)
+        .await?
+        .value;
+    Ok(serde_json::json!({
+        "balance": balance,
+        "extensions": decode_token_2022_extensions(account, &token_account).unwrap_or(Value::Array(vec![]))
+    }))
+}
+
 pub async fn get_token_account_balance_with_commitment(
     client: &RpcClient,
     account: &Pubkey,
@@ -94,11 +222,16 @@ pub async fn get_token_account_balance_with_commitment(
 ) -> Result<Value> {
     let balance = client
         .get_token_account_balance_with_commitment(account, commitment)
-        .await?;
+        .await?
+        .value;
     Ok(serde_json::json!({ "balance": balance }))
 }
 
-pub async fn get_token_accounts_by_mint(client: &RpcClient, mint: &Pubkey) -> Result<Value> {
+pub async fn get_token_accounts_by_mint(
+    client: &RpcClient,
+    mint: &Pubkey,
+    commitment: Option<CommitmentConfig>,
+) -> Result<Value> {
     // Use getProgramAccounts to find all token accounts for a specific mint
     let accounts = client
         .get_program_accounts_with_config(
@@ -111,6 +244,7 @@ pub async fn get_token_accounts_by_mint(client: &RpcClient, mint: &Pubkey) -> Re
                 ]),
                 account_config: solana_client::rpc_config::RpcAccountInfoConfig {
                     encoding: Some(UiAccountEncoding::Base64),
+                    commitment,
                     ..Default::default()
                 },
                 ..Default::default()
@@ -119,3 +253,147 @@ pub async fn get_token_accounts_by_mint(client: &RpcClient, mint: &Pubkey) -> Re
         .await?;
     Ok(serde_json::json!({ "accounts": accounts }))
 }
+
+/// Decodes a token account, returning its mint and raw amount
+///
+/// # Errors
+/// * The account is not owned by the token program or has an invalid layout
+pub fn decode_token_account(address: &Pubkey, owner: &Pubkey, data: &[u8]) -> Result<(Pubkey, u64)> {
+    if !is_token_program(owner) {
+        return Err(anyhow::anyhow!("Account {} is not a token account", address));
+    }
+    let account = StateWithExtensions::<TokenAccount>::unpack(data)
+        .map_err(|_| anyhow::anyhow!("Account {} is not an initialized token account", address))?;
+    Ok((account.base.mint, account.base.amount))
+}
+
+/// Formats a raw token amount with its mint decimals
+pub fn token_ui_balance(amount: u64, decimals: u8) -> Value {
+    serde_json::json!({
+        "amount": amount.to_string(),
+        "decimals": decimals,
+        "uiAmount": spl_token::amount_to_ui_amount(amount, decimals),
+        "uiAmountString": spl_token::amount_to_ui_amount_string_trimmed(amount, decimals),
+    })
+}
+
+/// Get a token account balance together with its mint decimals
+pub async fn get_token_ui_balance(client: &RpcClient, account: &Pubkey) -> Result<Value> {
+    let token_account = client.get_account(account).await?;
+    let (mint, amount) = decode_token_account(account, &token_account.owner, &token_account.data)?;
+
+    let mint_account = client.get_account(&mint).await?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&mint_account.data)
+        .map_err(|_| anyhow::anyhow!("Mint {} could not be decoded", mint))?;
+
+    let mut balance = token_ui_balance(amount, mint_state.base.decimals);
+    balance["mint"] = Value::String(mint.to_string());
+    Ok(balance)
+}
+
+/// Reads the mint and raw amount of a token account returned by getTokenAccountsByOwner
+///
+/// The client requests jsonParsed; binary data is decoded as a fallback.
+fn keyed_token_amount(keyed: &RpcKeyedAccount) -> Result<(Pubkey, u64)> {
+    let undecodable = || anyhow::anyhow!("Token account {} could not be decoded", keyed.pubkey);
+    if let UiAccountData::Json(parsed) = &keyed.account.data {
+        let info = &parsed.parsed["info"];
+        let mint = info["mint"].as_str().and_then(|mint| mint.parse().ok()).ok_or_else(undecodable)?;
+        let amount = info["tokenAmount"]["amount"]
+            .as_str()
+            .and_then(|amount| amount.parse().ok())
+            .ok_or_else(undecodable)?;
+        return Ok((mint, amount));
+    }
+    let account: Account = keyed.account.decode().ok_or_else(undecodable)?;
+    let address = keyed.pubkey.parse().map_err(|_| undecodable())?;
+    decode_token_account(&address, &account.owner, &account.data)
+}
+
+/// Get a wallet's token holdings summed per mint
+///
+/// Queries the given token program, or both Token and Token-2022 when none is
+/// given, then fetches the decimals of every held mint in batches of 100.
+/// Zero-balance accounts are skipped unless `include_zero` is set.
+///
+/// # Returns
+/// * `Result<Value>` - `{ owner, holdings: [{ mint, uiAmount, rawAmount, decimals }] }`
+///   sorted by mint
+pub async fn get_wallet_token_holdings(
+    client: &RpcClient,
+    owner: &Pubkey,
+    program_id: Option<&Pubkey>,
+    include_zero: bool,
+) -> Result<Value> {
+    let program_ids = match program_id {
+        Some(program_id) => vec![resolve_token_program_id(Some(program_id))?],
+        None => vec![spl_token_program_id(), TOKEN_2022_PROGRAM_ID],
+    };
+
+    let program_count = program_ids.len() as u64;
+    let mut totals: BTreeMap<Pubkey, u64> = BTreeMap::new();
+    for (scanned, program_id) in (1..).zip(program_ids) {
+        let accounts = client
+            .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program_id))
+            .await?;
+        for keyed in &accounts {
+            let (mint, amount) = keyed_token_amount(keyed)?;
+            if amount == 0 && !include_zero {
+                continue;
+            }
+            let total = totals.entry(mint).or_default();
+            *total = total.saturating_add(amount);
+        }
+        report_progress(scanned, None, &format!("Listed token accounts for {scanned} of {program_count} token programs"));
+    }
+
+    let mints: Vec<Pubkey> = totals.keys().copied().collect();
+    let mut holdings = Vec::with_capacity(mints.len());
+    let batches = mints.chunks(crate::validation::MAX_MULTIPLE_ACCOUNTS);
+    let batch_count = batches.len() as u64;
+    for (decoded, batch) in (1..).zip(batches) {
+        let mint_accounts = client.get_multiple_accounts(batch).await?;
+        for (mint, mint_account) in batch.iter().zip(mint_accounts) {
+            let decimals = mint_account
+                .and_then(|account| StateWithExtensions::<Mint>::unpack(&account.data).ok().map(|m| m.base.decimals))
+                .ok_or_else(|| anyhow::anyhow!("Mint {} could not be decoded", mint))?;
+            let amount = totals[mint];
+            holdings.push(serde_json::json!({
+                "mint": mint.to_string(),
+                "uiAmount": spl_token::amount_to_ui_amount(amount, decimals),
+                "rawAmount": amount.to_string(),
+                "decimals": decimals,
+            }));
+        }
+        report_progress(
+            program_count + decoded,
+            Some(program_count + batch_count),
+            &format!("Decoded {decoded} of {batch_count} mint batches"),
+        );
+    }
+
+    Ok(serde_json::json!({
+        "owner": owner.to_string(),
+        "holdings": holdings,
+    }))
+}
+
+/// Derive the associated token account address for a wallet and mint
+///
+/// This is a pure derivation and performs no RPC call. Both the legacy
+/// Token program and Token-2022 are supported via `token_program_id`.
+pub fn get_associated_token_address(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: Option<&Pubkey>,
+) -> Result<Value> {
+    let token_program_id = resolve_token_program_id(token_program_id)?;
+
+    let address = get_associated_token_address_with_program_id(owner, mint, &token_program_id);
+    Ok(serde_json::json!({
+        "address": address.to_string(),
+        "owner": owner.to_string(),
+        "mint": mint.to_string(),
+        "tokenProgramId": token_program_id.to_string(),
+    }))
+}
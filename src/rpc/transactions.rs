@@ -1,48 +1,315 @@
 use crate::error::{McpError, McpResult};
 use crate::logging::{log_rpc_request_start, log_rpc_request_success, log_rpc_request_failure, new_request_id};
+use crate::progress::report_progress;
+use crate::validation::MAX_SIGNATURE_STATUSES;
 use anyhow::Result;
 use base64::Engine;
+use futures_util::StreamExt;
 use serde_json::Value;
 use solana_client::{
+    client_error::ClientError,
     nonblocking::rpc_client::RpcClient,
     rpc_client::GetConfirmedSignaturesForAddress2Config,
     rpc_config::{
         RpcSendTransactionConfig, RpcSimulateTransactionAccountsConfig,
         RpcSimulateTransactionConfig, RpcTransactionConfig,
     },
+    rpc_response::RpcConfirmedTransactionStatusWithSignature,
 };
 use solana_sdk::{
     commitment_config::{CommitmentConfig, CommitmentLevel},
+    instruction::CompiledInstruction,
+    message::AccountKeys,
     pubkey::Pubkey,
     signature::Signature,
     transaction::Transaction,
 };
-use solana_transaction_status::UiTransactionEncoding;
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, parse_instruction, EncodedConfirmedTransactionWithStatusMeta,
+    EncodedTransaction, TransactionStatus, UiInstruction, UiMessage, UiParsedInstruction, UiTransactionEncoding,
+};
 use std::time::Instant;
 
+/// Transaction version requested when the caller does not pick one
+///
+/// Without it the node rejects every versioned transaction, so default to the
+/// newest version rather than legacy-only.
+pub const DEFAULT_MAX_TRANSACTION_VERSION: u8 = 0;
+
+/// RPC error code for a transaction newer than `maxSupportedTransactionVersion`
+const UNSUPPORTED_TRANSACTION_VERSION_CODE: i64 = -32015;
+
+/// Reports a transaction newer than the requested version as a validation error
+///
+/// The node's own message only tells the caller to retry with a config
+/// parameter; this names the transaction's version and the argument to raise.
+/// Other errors pass through unchanged.
+fn map_transaction_version_error(err: ClientError, max_supported_transaction_version: u8) -> anyhow::Error {
+    use solana_client::{client_error::ClientErrorKind, rpc_request::RpcError};
+
+    let ClientErrorKind::RpcError(RpcError::RpcResponseError {
+        code: UNSUPPORTED_TRANSACTION_VERSION_CODE,
+        message,
+        ..
+    }) = err.kind()
+    else {
+        return err.into();
+    };
+    // The node reports the version as "Transaction version (N) is not supported ..."
+    let version = message
+        .split_once('(')
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map(|(version, _)| version);
+    let message = match version {
+        Some(version) => format!(
+            "Transaction is version {version}, newer than maxSupportedTransactionVersion \
+             {max_supported_transaction_version}; retry with maxSupportedTransactionVersion {version}"
+        ),
+        None => format!(
+            "Transaction is newer than maxSupportedTransactionVersion {max_supported_transaction_version}"
+        ),
+    };
+    McpError::validation(message)
+        .with_parameter("maxSupportedTransactionVersion")
+        .into()
+}
+
 pub async fn get_transaction(client: &RpcClient, signature: &Signature) -> Result<Value> {
+    get_transaction_with_config(client, signature, UiTransactionEncoding::Json, None, None).await
+}
+
+/// Fetch a transaction in the requested encoding
+///
+/// `max_supported_transaction_version` defaults to
+/// [`DEFAULT_MAX_TRANSACTION_VERSION`] so versioned transactions are returned.
+pub async fn get_transaction_with_config(
+    client: &RpcClient,
+    signature: &Signature,
+    encoding: UiTransactionEncoding,
+    commitment: Option<CommitmentConfig>,
+    max_supported_transaction_version: Option<u8>,
+) -> Result<Value> {
+    let max_supported_transaction_version =
+        max_supported_transaction_version.unwrap_or(DEFAULT_MAX_TRANSACTION_VERSION);
+    let config = RpcTransactionConfig {
+        encoding: Some(encoding),
+        commitment,
+        max_supported_transaction_version: Some(max_supported_transaction_version),
+    };
     let tx = client
-        .get_transaction(signature, UiTransactionEncoding::Json)
-        .await?;
+        .get_transaction_with_config(signature, config)
+        .await
+        .map_err(|e| map_transaction_version_error(e, max_supported_transaction_version))?;
     Ok(serde_json::json!({ "transaction": tx }))
 }
 
-pub async fn get_transaction_with_config(
+/// Fetch a transaction and optionally decode its top-level instructions
+///
+/// The transaction is returned in the requested encoding. When
+/// `parse_instructions` is set, a `parsedInstructions` array is added with
+/// System, SPL Token, Associated Token and Memo instructions decoded into
+/// `{program, programId, type, info}`; other programs pass through raw.
+pub async fn get_transaction_parsed(
     client: &RpcClient,
     signature: &Signature,
     encoding: UiTransactionEncoding,
     commitment: Option<CommitmentConfig>,
     max_supported_transaction_version: Option<u8>,
+    parse_instructions: bool,
 ) -> Result<Value> {
+    let max_supported_transaction_version =
+        max_supported_transaction_version.unwrap_or(DEFAULT_MAX_TRANSACTION_VERSION);
     let config = RpcTransactionConfig {
         encoding: Some(encoding),
         commitment,
-        max_supported_transaction_version,
+        max_supported_transaction_version: Some(max_supported_transaction_version),
     };
     let tx = client
         .get_transaction_with_config(signature, config)
+        .await
+        .map_err(|e| map_transaction_version_error(e, max_supported_transaction_version))?;
+
+    let mut result = serde_json::json!({ "transaction": tx });
+    if parse_instructions {
+        result["parsedInstructions"] = match parse_transaction_instructions(&tx) {
+            Some(instructions) => Value::Array(instructions),
+            None => Value::Null,
+        };
+    }
+    Ok(result)
+}
+
+/// Decode the top-level instructions of a fetched transaction
+///
+/// Works for every encoding. Binary and raw JSON instructions are decoded
+/// here; `jsonParsed` ones, already decoded by the node, are reshaped into
+/// the same form. Returns `None` only when the transaction cannot be decoded.
+pub fn parse_transaction_instructions(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Option<Vec<Value>> {
+    let (mut account_keys, instructions) = match &tx.transaction.transaction {
+        EncodedTransaction::Json(ui_tx) => match &ui_tx.message {
+            UiMessage::Raw(message) => {
+                let keys = message
+                    .account_keys
+                    .iter()
+                    .map(|key| key.parse::<Pubkey>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()?;
+                let instructions = message
+                    .instructions
+                    .iter()
+                    .map(|ix| {
+                        Some(CompiledInstruction {
+                            program_id_index: ix.program_id_index,
+                            accounts: ix.accounts.clone(),
+                            data: bs58::decode(&ix.data).into_vec().ok()?,
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                (keys, instructions)
+            }
+            UiMessage::Parsed(message) => {
+                // Parsed account keys already include lookup-table addresses
+                let keys = message
+                    .account_keys
+                    .iter()
+                    .map(|key| key.pubkey.parse::<Pubkey>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()?;
+                return message
+                    .instructions
+                    .iter()
+                    .map(|ix| reshape_ui_instruction(ix, &keys))
+                    .collect();
+            }
+        },
+        encoded => {
+            let versioned = encoded.decode()?;
+            (
+                versioned.message.static_account_keys().to_vec(),
+                versioned.message.instructions().to_vec(),
+            )
+        }
+    };
+
+    // v0 transactions reference lookup-table accounts after the static keys
+    if let Some(meta) = &tx.transaction.meta {
+        if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+            for key in loaded.writable.iter().chain(loaded.readonly.iter()) {
+                account_keys.push(key.parse().ok()?);
+            }
+        }
+    }
+
+    Some(parse_compiled_instructions(&account_keys, &instructions))
+}
+
+/// Brings one instruction of a `jsonParsed` transaction into the shape
+/// `parse_compiled_instructions` returns
+fn reshape_ui_instruction(ix: &UiInstruction, account_keys: &[Pubkey]) -> Option<Value> {
+    match ix {
+        UiInstruction::Compiled(ix) => {
+            let compiled = CompiledInstruction {
+                program_id_index: ix.program_id_index,
+                accounts: ix.accounts.clone(),
+                data: bs58::decode(&ix.data).into_vec().ok()?,
+            };
+            parse_compiled_instructions(account_keys, &[compiled]).pop()
+        }
+        UiInstruction::Parsed(UiParsedInstruction::Parsed(parsed)) => Some(parsed_instruction_value(
+            parsed.program.clone(),
+            parsed.program_id.clone(),
+            parsed.parsed.clone(),
+        )),
+        // The node has no parser for this program, and neither do we
+        UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(raw)) => Some(serde_json::json!({
+            "program": "unknown",
+            "programId": raw.program_id,
+            "accounts": raw.accounts,
+            "data": raw.data
+        })),
+    }
+}
+
+/// Splits a parser's `{type, info}` output into the decoded instruction form
+fn parsed_instruction_value(program: String, program_id: String, parsed: Value) -> Value {
+    let (instruction_type, info) = match parsed {
+        Value::Object(mut map) if map.contains_key("type") => (
+            map.remove("type").unwrap_or(Value::Null),
+            map.remove("info").unwrap_or(Value::Null),
+        ),
+        // Memo instructions parse to the bare memo string
+        other => (Value::String(program.clone()), other),
+    };
+    serde_json::json!({
+        "program": program,
+        "programId": program_id,
+        "type": instruction_type,
+        "info": info
+    })
+}
+
+/// Decode compiled instructions against the transaction's account keys
+///
+/// Instructions for programs without a parser, or whose data fails to
+/// decode, are returned with their raw accounts and base58 data.
+pub fn parse_compiled_instructions(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+) -> Vec<Value> {
+    let keys = AccountKeys::new(account_keys, None);
+
+    instructions
+        .iter()
+        .map(|ix| {
+            let Some(program_id) = keys.get(ix.program_id_index as usize) else {
+                return serde_json::json!({
+                    "program": "unknown",
+                    "programIdIndex": ix.program_id_index,
+                    "data": bs58::encode(&ix.data).into_string()
+                });
+            };
+
+            match parse_instruction::parse(program_id, ix, &keys, None) {
+                Ok(parsed) => parsed_instruction_value(parsed.program, parsed.program_id, parsed.parsed),
+                Err(e) => {
+                    let accounts: Vec<String> = ix
+                        .accounts
+                        .iter()
+                        .filter_map(|index| keys.get(*index as usize))
+                        .map(|key| key.to_string())
+                        .collect();
+                    let mut raw = serde_json::json!({
+                        "program": "unknown",
+                        "programId": program_id.to_string(),
+                        "accounts": accounts,
+                        "data": bs58::encode(&ix.data).into_string()
+                    });
+                    if !matches!(e, parse_instruction::ParseInstructionError::ProgramNotParsable) {
+                        raw["parseError"] = Value::String(e.to_string());
+                    }
+                    raw
+                }
+            }
+        })
+        .collect()
+}
+
+/// Check whether a transaction has reached the requested commitment
+pub async fn confirm_transaction(
+    client: &RpcClient,
+    signature: &Signature,
+    commitment: CommitmentConfig,
+) -> Result<Value> {
+    let response = client
+        .confirm_transaction_with_commitment(signature, commitment)
         .await?;
-    Ok(serde_json::json!({ "transaction": tx }))
+    Ok(serde_json::json!({
+        "context": { "slot": response.context.slot },
+        "confirmed": response.value,
+        "commitment": commitment.commitment
+    }))
 }
 
 pub async fn get_signatures_for_address(
@@ -51,12 +318,13 @@ pub async fn get_signatures_for_address(
     before: Option<Signature>,
     until: Option<Signature>,
     limit: Option<u64>,
+    commitment: Option<CommitmentConfig>,
 ) -> Result<Value> {
     let config = GetConfirmedSignaturesForAddress2Config {
         before,
         until,
         limit: limit.map(|l| l as usize),
-        commitment: None,
+        commitment,
     };
     let signatures = client
         .get_signatures_for_address_with_config(address, config)
@@ -94,7 +362,7 @@ pub async fn send_transaction(
         )
         .await?;
 
-    Ok(serde_json::json!({ "signature": signature }))
+    Ok(serde_json::json!({ "signature": signature.to_string() }))
 }
 
 pub async fn send_transaction_with_config(
@@ -131,7 +399,7 @@ pub async fn send_transaction_with_config(
         )
         .await?;
 
-    Ok(serde_json::json!({ "signature": signature }))
+    Ok(serde_json::json!({ "signature": signature.to_string() }))
 }
 
 pub async fn simulate_transaction(
@@ -197,6 +465,69 @@ pub async fn simulate_transaction_with_config(
     Ok(serde_json::json!({ "result": result }))
 }
 
+/// Largest compute unit limit a transaction may request
+pub const MAX_COMPUTE_UNIT_LIMIT: u64 = 1_400_000;
+
+/// Simulates a transaction to measure its compute units and suggest a limit
+///
+/// Signatures are not verified and the blockhash is replaced, so unsigned or
+/// stale transactions can be measured before they are finalized.
+///
+/// # Arguments
+/// * `margin_percent` - Headroom added on top of the consumed units
+///
+/// # Returns
+/// * `Result<Value>` - `{unitsConsumed, recommendedComputeUnitLimit,
+///   marginPercent}`, or `{err, unitsConsumed, logs}` when the simulation fails
+pub async fn estimate_compute_units(
+    client: &RpcClient,
+    transaction_data: &str,
+    encoding: &str,
+    commitment: Option<CommitmentConfig>,
+    margin_percent: u64,
+) -> Result<Value> {
+    let wire_transaction = match encoding {
+        "base58" => bs58::decode(transaction_data).into_vec()?,
+        "base64" => base64::engine::general_purpose::STANDARD.decode(transaction_data)?,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Invalid encoding. Must be base58 or base64"
+            ))
+        }
+    };
+
+    let tx: Transaction = bincode::deserialize(&wire_transaction)?;
+
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment,
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let simulation = client.simulate_transaction_with_config(&tx, config).await?.value;
+
+    if let Some(err) = simulation.err {
+        return Ok(serde_json::json!({
+            "err": err,
+            "unitsConsumed": simulation.units_consumed,
+            "logs": simulation.logs.unwrap_or_default(),
+        }));
+    }
+    let units_consumed = simulation
+        .units_consumed
+        .ok_or_else(|| anyhow::anyhow!("RPC node did not report unitsConsumed for the simulation"))?;
+    let recommended = units_consumed
+        .saturating_mul(100 + margin_percent)
+        .div_ceil(100)
+        .min(MAX_COMPUTE_UNIT_LIMIT);
+
+    Ok(serde_json::json!({
+        "unitsConsumed": units_consumed,
+        "recommendedComputeUnitLimit": recommended,
+        "marginPercent": margin_percent,
+    }))
+}
+
 pub async fn get_block_time(client: &RpcClient, slot: u64) -> Result<Value> {
     let timestamp = client.get_block_time(slot).await?;
     Ok(serde_json::json!({ "timestamp": timestamp }))
@@ -261,7 +592,15 @@ pub async fn get_confirmed_signatures_for_address_2(
     get_signatures_for_address(client, address, before, until, limit).await
 }
 
-/// Get signature statuses for a list of transaction signatures
+/// Get statuses for a list of signatures
+///
+/// Signatures are split into chunks of [`MAX_SIGNATURE_STATUSES`], the RPC
+/// per-request limit, which are fetched concurrently and reassembled in
+/// request order. `context.slot` is the lowest slot any chunk was served at.
+///
+/// With `search_transaction_history`, each chunk is first checked against the
+/// recent status cache and only the signatures missing from it are re-queried
+/// with the much slower ledger history search.
 pub async fn get_signature_statuses(
     client: &RpcClient,
     signatures: &[String],
@@ -270,12 +609,16 @@ pub async fn get_signature_statuses(
     let request_id = new_request_id();
     let start_time = Instant::now();
     let method = "getSignatureStatuses";
+    let search_transaction_history = search_transaction_history.unwrap_or(false);
     
     log_rpc_request_start(
         request_id,
         method,
         Some(&client.url()),
-        Some(&format!("signatures: {} to check", signatures.len())),
+        Some(&format!(
+            "signatures: {} to check, search_transaction_history: {search_transaction_history}",
+            signatures.len()
+        )),
     );
 
     // Parse signature strings to Signature objects
@@ -305,18 +648,20 @@ pub async fn get_signature_statuses(
         }
     };
 
-    let _config = solana_client::rpc_config::RpcSignatureStatusConfig {
-        search_transaction_history: search_transaction_history.unwrap_or(false),
-    };
+    let chunks = signature_objects
+        .chunks(MAX_SIGNATURE_STATUSES)
+        .map(|chunk| get_signature_status_chunk(client, chunk, search_transaction_history));
 
-    match client.get_signature_statuses_with_history(&signature_objects).await {
-        Ok(response) => {
+    match futures_util::future::try_join_all(chunks).await {
+        Ok(responses) => {
             let duration = start_time.elapsed().as_millis() as u64;
+            let slot = responses.iter().map(|(slot, _)| *slot).min().unwrap_or_default();
+            let statuses: Vec<_> = responses.into_iter().flat_map(|(_, statuses)| statuses).collect();
             let result = serde_json::json!({
                 "context": {
-                    "slot": response.context.slot
+                    "slot": slot
                 },
-                "value": response.value
+                "value": statuses
             });
             
             log_rpc_request_success(
@@ -349,3 +694,203 @@ pub async fn get_signature_statuses(
         }
     }
 }
+
+/// Fetches statuses for at most [`MAX_SIGNATURE_STATUSES`] signatures
+///
+/// # Returns
+/// * `(slot, statuses)` - The lowest context slot of the requests made and
+///   one status per signature, in order
+async fn get_signature_status_chunk(
+    client: &RpcClient,
+    signatures: &[Signature],
+    search_transaction_history: bool,
+) -> Result<(u64, Vec<Option<TransactionStatus>>), ClientError> {
+    let recent = client.get_signature_statuses(signatures).await?;
+    let mut slot = recent.context.slot;
+    let mut statuses = recent.value;
+
+    let missing: Vec<usize> = statuses
+        .iter()
+        .enumerate()
+        .filter_map(|(index, status)| status.is_none().then_some(index))
+        .collect();
+    if search_transaction_history && !missing.is_empty() {
+        let missing_signatures: Vec<Signature> = missing.iter().map(|&index| signatures[index]).collect();
+        let history = client.get_signature_statuses_with_history(&missing_signatures).await?;
+        slot = slot.min(history.context.slot);
+        for (index, status) in missing.into_iter().zip(history.value) {
+            statuses[index] = status;
+        }
+    }
+
+    Ok((slot, statuses))
+}
+
+/// Default number of transactions summarized by `getRecentActivity`
+pub const DEFAULT_ACTIVITY_LIMIT: usize = 10;
+
+/// Upper bound on transactions summarized by `getRecentActivity`, which
+/// issues one getTransaction per signature
+pub const MAX_ACTIVITY_LIMIT: usize = 50;
+
+/// getTransaction requests `getRecentActivity` keeps in flight at once
+const ACTIVITY_FETCH_CONCURRENCY: usize = 8;
+
+/// Programs invoked by a transaction, top-level first, without duplicates
+///
+/// Needs a binary encoding; JSON-encoded transactions yield no programs.
+fn involved_programs(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<String> {
+    let Some(versioned) = tx.transaction.transaction.decode() else {
+        return Vec::new();
+    };
+    let meta = tx.transaction.meta.as_ref();
+
+    let mut account_keys: Vec<String> = versioned
+        .message
+        .static_account_keys()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    if let Some(OptionSerializer::Some(loaded)) = meta.map(|meta| &meta.loaded_addresses) {
+        account_keys.extend(loaded.writable.iter().chain(loaded.readonly.iter()).cloned());
+    }
+
+    let mut program_indexes: Vec<u8> = versioned
+        .message
+        .instructions()
+        .iter()
+        .map(|ix| ix.program_id_index)
+        .collect();
+    if let Some(OptionSerializer::Some(inner)) = meta.map(|meta| &meta.inner_instructions) {
+        for ix in inner.iter().flat_map(|set| &set.instructions) {
+            if let UiInstruction::Compiled(ix) = ix {
+                program_indexes.push(ix.program_id_index);
+            }
+        }
+    }
+
+    let mut programs: Vec<String> = Vec::new();
+    for key in program_indexes.into_iter().filter_map(|index| account_keys.get(index as usize)) {
+        if !programs.contains(key) {
+            programs.push(key.clone());
+        }
+    }
+    programs
+}
+
+/// Summarizes one signature of an address's history
+///
+/// Slot, time and outcome come from the signature listing, so a transaction
+/// that could not be fetched is still reported, with a null fee and no programs.
+fn summarize_activity(
+    status: &RpcConfirmedTransactionStatusWithSignature,
+    tx: Option<&EncodedConfirmedTransactionWithStatusMeta>,
+) -> Value {
+    serde_json::json!({
+        "signature": status.signature,
+        "slot": status.slot,
+        "blockTime": status.block_time,
+        "success": status.err.is_none(),
+        "err": status.err,
+        "fee": tx.and_then(|tx| tx.transaction.meta.as_ref()).map(|meta| meta.fee),
+        "programs": tx.map(involved_programs).unwrap_or_default()
+    })
+}
+
+/// Fetch an address's most recent transactions and summarize each one
+///
+/// Lists up to `limit` signatures (capped at [`MAX_ACTIVITY_LIMIT`]), fetches
+/// the transactions with bounded concurrency and returns them oldest first.
+pub async fn get_recent_activity(
+    client: &RpcClient,
+    address: &Pubkey,
+    limit: usize,
+    commitment: Option<CommitmentConfig>,
+) -> McpResult<Value> {
+    let request_id = new_request_id();
+    let start_time = Instant::now();
+    let method = "getRecentActivity";
+    let limit = limit.clamp(1, MAX_ACTIVITY_LIMIT);
+    let commitment = commitment.unwrap_or_else(CommitmentConfig::confirmed);
+
+    log_rpc_request_start(
+        request_id,
+        method,
+        Some(&client.url()),
+        Some(&format!("address: {address}, limit: {limit}")),
+    );
+
+    let config = GetConfirmedSignaturesForAddress2Config {
+        before: None,
+        until: None,
+        limit: Some(limit),
+        commitment: Some(commitment),
+    };
+    let mut statuses = match client.get_signatures_for_address_with_config(address, config).await {
+        Ok(statuses) => statuses,
+        Err(e) => {
+            let duration = start_time.elapsed().as_millis() as u64;
+            let error = McpError::from(e)
+                .with_request_id(request_id)
+                .with_method(method)
+                .with_rpc_url(client.url());
+
+            log_rpc_request_failure(
+                request_id,
+                method,
+                error.error_type(),
+                duration,
+                Some(&error.to_log_value()),
+                Some(&client.url()),
+            );
+
+            return Err(error);
+        }
+    };
+    // Signatures are listed newest first
+    statuses.truncate(limit);
+    statuses.reverse();
+
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(commitment),
+        max_supported_transaction_version: Some(0),
+    };
+    let total = statuses.len() as u64;
+    report_progress(0, Some(total), &format!("Found {total} signatures"));
+    let signatures: Vec<Option<Signature>> = statuses.iter().map(|status| status.signature.parse().ok()).collect();
+    let fetches = signatures.into_iter().map(|signature| async move {
+        client.get_transaction_with_config(&signature?, config).await.ok()
+    });
+    let transactions: Vec<Option<EncodedConfirmedTransactionWithStatusMeta>> = futures_util::stream::iter(fetches)
+        .buffered(ACTIVITY_FETCH_CONCURRENCY)
+        .enumerate()
+        .map(|(index, transaction)| {
+            let done = index as u64 + 1;
+            report_progress(done, Some(total), &format!("Fetched {done} of {total} transactions"));
+            transaction
+        })
+        .collect()
+        .await;
+
+    let fetched = transactions.iter().filter(|tx| tx.is_some()).count();
+    let activity: Vec<Value> = statuses
+        .iter()
+        .zip(&transactions)
+        .map(|(status, tx)| summarize_activity(status, tx.as_ref()))
+        .collect();
+
+    let duration = start_time.elapsed().as_millis() as u64;
+    log_rpc_request_success(
+        request_id,
+        method,
+        duration,
+        Some(&format!("{fetched} of {} transactions fetched", activity.len())),
+        Some(&client.url()),
+    );
+
+    Ok(serde_json::json!({
+        "address": address.to_string(),
+        "transactions": activity
+    }))
+}
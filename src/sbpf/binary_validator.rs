@@ -1,6 +1,6 @@
 use super::errors::SbpfError;
-use super::types::BinaryMetadata;
-use goblin::elf::Elf;
+use super::types::{BinaryMetadata, SectionLayout};
+use goblin::elf::{header::ET_DYN, Elf};
 
 const MAX_BINARY_SIZE: usize = 512 * 1024 * 1024; // 512MB
 const MIN_BINARY_SIZE: usize = 64;
@@ -50,20 +50,50 @@ impl BinaryValidator {
         }
 
         // 5. Extract sections
-        let sections: Vec<String> = elf
+        let section_layout: Vec<SectionLayout> = elf
             .section_headers
             .iter()
             .filter_map(|sh| {
-                elf.shdr_strtab
-                    .get_at(sh.sh_name)
-                    .map(|s| s.to_string())
+                let name = elf.shdr_strtab.get_at(sh.sh_name)?;
+                Some(SectionLayout {
+                    name: name.to_string(),
+                    address: format!("0x{:x}", sh.sh_addr),
+                    offset: sh.sh_offset,
+                    size: sh.sh_size,
+                })
             })
             .collect();
+        let sections: Vec<String> = section_layout.iter().map(|s| s.name.clone()).collect();
 
         // 6. Validate has required sections
-        let has_text = sections.iter().any(|s| s == ".text");
-        if !has_text {
-            errors.push("Missing .text section".to_string());
+        let text = elf
+            .section_headers
+            .iter()
+            .find(|sh| elf.shdr_strtab.get_at(sh.sh_name) == Some(".text"));
+        match text {
+            None => errors.push("Missing .text section".to_string()),
+            Some(text) => {
+                if !text.vm_range().contains(&(elf.header.e_entry as usize)) {
+                    errors.push(format!(
+                        "Entrypoint 0x{:x} is outside the .text section",
+                        elf.header.e_entry
+                    ));
+                }
+            }
+        }
+
+        // 7. Check the properties the Solana program loader insists on
+        if !elf.is_64 {
+            errors.push("ELF class is not 64-bit".to_string());
+        }
+        if !elf.little_endian {
+            errors.push("ELF data encoding is not little-endian".to_string());
+        }
+        if elf.header.e_type != ET_DYN {
+            errors.push(format!(
+                "ELF type is {} (expected ET_DYN shared object)",
+                goblin::elf::header::et_to_str(elf.header.e_type)
+            ));
         }
 
         // Return metadata
@@ -71,7 +101,9 @@ impl BinaryValidator {
             size_bytes: data.len(),
             architecture: "BPF".to_string(),
             entrypoint: format!("0x{:x}", elf.header.e_entry),
+            loadable: errors.is_empty(),
             sections,
+            section_layout,
             errors,
         })
     }
@@ -111,6 +143,23 @@ mod tests {
         assert!(matches!(result, Err(SbpfError::NotElfFile)));
     }
 
+    #[test]
+    fn test_header_only_elf_is_not_loadable() {
+        let mut data = vec![0u8; 1024];
+        data[0..4].copy_from_slice(&[0x7F, 0x45, 0x4C, 0x46]);
+        data[4] = 2; // 64-bit
+        data[5] = 1; // little-endian
+        data[6] = 1; // ELF version
+        data[16] = 0x02; // ET_EXEC
+        data[18] = 0xF7; // EM_BPF
+
+        let metadata = BinaryValidator::validate(&data).unwrap();
+        assert!(!metadata.loadable);
+        assert!(metadata.section_layout.is_empty());
+        assert!(metadata.errors.iter().any(|e| e == "Missing .text section"));
+        assert!(metadata.errors.iter().any(|e| e.contains("ET_DYN")));
+    }
+
     #[test]
     fn test_size_check() {
         assert!(BinaryValidator::check_size(&vec![0u8; 32]).is_err());
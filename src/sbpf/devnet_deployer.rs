@@ -3,8 +3,26 @@ use super::errors::SbpfError;
 use super::types::DevnetDeployResponse;
 use base64::engine::general_purpose::STANDARD as BASE64;
 use base64::Engine;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_loader_v3_interface::{instruction as loader_instruction, state::UpgradeableLoaderState};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+};
 
-const DEFAULT_DEVNET_URL: &str = "https://api.devnet.solana.com";
+/// Default public devnet RPC endpoint
+pub const DEFAULT_DEVNET_URL: &str = "https://api.devnet.solana.com";
+
+/// Genesis hash identifying Solana devnet
+pub const DEVNET_GENESIS_HASH: &str = "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG";
+
+/// Program bytes carried by each buffer write transaction
+const WRITE_CHUNK_SIZE: usize = 900;
+
+/// Buffer write transactions submitted concurrently
+const WRITE_BATCH_SIZE: usize = 16;
 
 /// Provides deployment guidance and validation for Solana devnet
 pub struct DevnetDeployer {
@@ -85,14 +103,259 @@ solana program deploy {temp_file}
             } else {
                 Some(metadata.errors.join("; "))
             },
+            cost_lamports: None,
         })
     }
 }
 
+impl DevnetDeployer {
+    /// Deploy a program to devnet through the upgradeable BPF loader
+    ///
+    /// The payer funds the buffer, program and program-data accounts and
+    /// becomes the upgrade authority. The endpoint is checked against the
+    /// devnet genesis hash first so mainnet funds are never spent, and a
+    /// binary the loader would reject is refused before any transaction.
+    pub async fn deploy(
+        &self,
+        binary: Vec<u8>,
+        payer: &Keypair,
+    ) -> Result<DevnetDeployResponse, SbpfError> {
+        let metadata = BinaryValidator::validate(&binary)?;
+        if !metadata.loadable || !metadata.errors.is_empty() {
+            return Err(SbpfError::InvalidBinary(format!(
+                "Binary is not deployable: {}",
+                metadata.errors.join("; ")
+            )));
+        }
+        let client = RpcClient::new_with_commitment(self.rpc_url.clone(), CommitmentConfig::confirmed());
+
+        let genesis_hash = client
+            .get_genesis_hash()
+            .await
+            .map_err(|e| SbpfError::DeploymentError(format!("Failed to reach RPC: {}", e)))?;
+        if genesis_hash.to_string() != DEVNET_GENESIS_HASH {
+            return Err(SbpfError::DeploymentError(format!(
+                "Configured RPC is not Solana devnet (genesis hash {})",
+                genesis_hash
+            )));
+        }
+
+        let program_len = binary.len();
+        let buffer_lamports =
+            Self::rent_exempt(&client, UpgradeableLoaderState::size_of_buffer(program_len)).await?;
+        let program_lamports =
+            Self::rent_exempt(&client, UpgradeableLoaderState::size_of_program()).await?;
+        let programdata_lamports =
+            Self::rent_exempt(&client, UpgradeableLoaderState::size_of_programdata(program_len))
+                .await?;
+
+        let balance_before = client
+            .get_balance(&payer.pubkey())
+            .await
+            .map_err(|e| SbpfError::DeploymentError(format!("Failed to fetch payer balance: {}", e)))?;
+        // The buffer is drained into the program-data account, so rent is paid once
+        let required = program_lamports + programdata_lamports.max(buffer_lamports);
+        if balance_before < required {
+            return Err(SbpfError::InvalidPayer(format!(
+                "Payer {} has {} lamports but deployment needs at least {} plus fees",
+                payer.pubkey(),
+                balance_before,
+                required
+            )));
+        }
+
+        let buffer = Keypair::new();
+        let program = Keypair::new();
+        log::info!(
+            "Deploying {} byte program {} to devnet via buffer {}",
+            program_len,
+            program.pubkey(),
+            buffer.pubkey()
+        );
+
+        // 1. Create and initialize the buffer account
+        let create_buffer = loader_instruction::create_buffer(
+            &payer.pubkey(),
+            &buffer.pubkey(),
+            &payer.pubkey(),
+            buffer_lamports,
+            program_len,
+        )
+        .map_err(|e| SbpfError::DeploymentError(format!("Failed to build buffer instructions: {}", e)))?;
+        Self::send(&client, &create_buffer, &[payer, &buffer]).await?;
+
+        // A failure past this point must not strand the buffer's rent
+        let signature = match Self::write_and_deploy(&client, &binary, payer, &buffer, &program, program_lamports).await {
+            Ok(signature) => signature,
+            Err(e) => return Err(Self::reclaim_buffer(&client, payer, &buffer, e).await),
+        };
+
+        let cost_lamports = match client.get_balance(&payer.pubkey()).await {
+            Ok(balance_after) => Some(balance_before.saturating_sub(balance_after)),
+            Err(e) => {
+                log::warn!("Failed to fetch payer balance after deployment: {}", e);
+                None
+            }
+        };
+
+        log::info!("Deployed program {} with signature {}", program.pubkey(), signature);
+
+        Ok(DevnetDeployResponse {
+            program_id: program.pubkey().to_string(),
+            signature: signature.to_string(),
+            deployed: true,
+            size_bytes: program_len,
+            network: "devnet".to_string(),
+            rpc_url: self.rpc_url.clone(),
+            cli_commands: None,
+            binary_valid: true,
+            validation_notes: None,
+            cost_lamports,
+        })
+    }
+
+    /// Write the program bytes into the buffer and deploy the program from it
+    async fn write_and_deploy(
+        client: &RpcClient,
+        binary: &[u8],
+        payer: &Keypair,
+        buffer: &Keypair,
+        program: &Keypair,
+        program_lamports: u64,
+    ) -> Result<Signature, SbpfError> {
+        let program_len = binary.len();
+
+        // 2. Write the program bytes into the buffer
+        let writes: Vec<Instruction> = binary
+            .chunks(WRITE_CHUNK_SIZE)
+            .enumerate()
+            .map(|(index, chunk)| {
+                loader_instruction::write(
+                    &buffer.pubkey(),
+                    &payer.pubkey(),
+                    (index * WRITE_CHUNK_SIZE) as u32,
+                    chunk.to_vec(),
+                )
+            })
+            .collect();
+        let payer_only = [payer];
+        for batch in writes.chunks(WRITE_BATCH_SIZE) {
+            let sends = batch
+                .iter()
+                .map(|write| Self::send(client, std::slice::from_ref(write), &payer_only));
+            futures_util::future::try_join_all(sends).await?;
+        }
+
+        // 3. Deploy the program from the buffer
+        #[allow(deprecated)]
+        let deploy = loader_instruction::deploy_with_max_program_len(
+            &payer.pubkey(),
+            &program.pubkey(),
+            &buffer.pubkey(),
+            &payer.pubkey(),
+            program_lamports,
+            program_len,
+        )
+        .map_err(|e| SbpfError::DeploymentError(format!("Failed to build deploy instructions: {}", e)))?;
+        Self::send(client, &deploy, &[payer, program]).await
+    }
+
+    /// Close a buffer left behind by a failed deployment, returning its lamports to the payer
+    ///
+    /// The returned error always names the buffer so a buffer that could not be
+    /// closed can still be reclaimed with `solana program close`.
+    async fn reclaim_buffer(
+        client: &RpcClient,
+        payer: &Keypair,
+        buffer: &Keypair,
+        error: SbpfError,
+    ) -> SbpfError {
+        let reason = match error {
+            SbpfError::DeploymentError(msg) => msg,
+            other => other.to_string(),
+        };
+        let close = loader_instruction::close(&buffer.pubkey(), &payer.pubkey(), &payer.pubkey());
+        match Self::send(client, &[close], &[payer]).await {
+            Ok(_) => {
+                log::warn!("Deployment failed, closed buffer {}", buffer.pubkey());
+                SbpfError::DeploymentError(format!(
+                    "{}; buffer {} was closed and its lamports returned to the payer",
+                    reason,
+                    buffer.pubkey()
+                ))
+            }
+            Err(close_error) => {
+                log::error!("Deployment failed and buffer {} could not be closed: {}", buffer.pubkey(), close_error);
+                SbpfError::DeploymentError(format!(
+                    "{}; buffer {} still holds the payer's lamports (close failed: {})",
+                    reason,
+                    buffer.pubkey(),
+                    close_error
+                ))
+            }
+        }
+    }
+
+    /// Minimum lamports for an account of `size` bytes to be rent exempt
+    async fn rent_exempt(client: &RpcClient, size: usize) -> Result<u64, SbpfError> {
+        client
+            .get_minimum_balance_for_rent_exemption(size)
+            .await
+            .map_err(|e| SbpfError::DeploymentError(format!("Failed to fetch rent: {}", e)))
+    }
+
+    /// Sign and submit one transaction, waiting for confirmation
+    async fn send(
+        client: &RpcClient,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Signature, SbpfError> {
+        let blockhash = client
+            .get_latest_blockhash()
+            .await
+            .map_err(|e| SbpfError::DeploymentError(format!("Failed to fetch blockhash: {}", e)))?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            blockhash,
+        );
+        client
+            .send_and_confirm_transaction(&transaction)
+            .await
+            .map_err(|e| SbpfError::DeploymentError(e.to_string()))
+    }
+}
+
+/// Parse a payer keypair given as base64-encoded 64 secret-key bytes
+pub fn parse_payer_keypair(encoded: &str) -> Result<Keypair, SbpfError> {
+    let bytes = BASE64
+        .decode(encoded)
+        .map_err(|e| SbpfError::InvalidPayer(format!("Invalid base64: {}", e)))?;
+    Keypair::try_from(bytes.as_slice())
+        .map_err(|e| SbpfError::InvalidPayer(format!("Invalid keypair bytes: {}", e)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_deploy_rejects_unloadable_binary_before_sending() {
+        let mut binary = vec![0u8; 1024];
+        binary[0..4].copy_from_slice(&[0x7F, 0x45, 0x4C, 0x46]);
+        binary[4] = 2; // 64-bit
+        binary[5] = 1; // little-endian
+        binary[6] = 1; // ELF version
+        binary[16] = 0x02; // ET_EXEC
+        binary[18] = 0xF7; // EM_BPF
+
+        // An unreachable endpoint proves validation fails before any RPC call
+        let deployer = DevnetDeployer::new(Some("http://127.0.0.1:1".to_string()));
+        let result = deployer.deploy(binary, &Keypair::new()).await;
+        assert!(matches!(result, Err(SbpfError::InvalidBinary(msg)) if msg.contains("Missing .text section")));
+    }
+
     #[test]
     fn test_deployer_creation() {
         let deployer = DevnetDeployer::new(None);
@@ -102,4 +365,17 @@ mod tests {
         let deployer2 = DevnetDeployer::new(Some(custom_url.clone()));
         assert_eq!(deployer2.rpc_url, custom_url);
     }
+
+    #[test]
+    fn test_parse_payer_keypair() {
+        let keypair = Keypair::new();
+        let encoded = BASE64.encode(keypair.to_bytes());
+        assert_eq!(parse_payer_keypair(&encoded).unwrap().pubkey(), keypair.pubkey());
+
+        assert!(matches!(parse_payer_keypair("!!"), Err(SbpfError::InvalidPayer(_))));
+        assert!(matches!(
+            parse_payer_keypair(&BASE64.encode([0u8; 10])),
+            Err(SbpfError::InvalidPayer(_))
+        ));
+    }
 }
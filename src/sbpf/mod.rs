@@ -13,9 +13,12 @@ pub mod vm_wrapper;
 
 // Re-export main types
 pub use binary_validator::BinaryValidator;
-pub use devnet_deployer::DevnetDeployer;
+pub use devnet_deployer::{parse_payer_keypair, DevnetDeployer};
 pub use errors::SbpfError;
-pub use security_scanner::{SecurityScanResult, SecurityScanner, Severity, Vulnerability};
-pub use test_executor::TestExecutor;
+pub use security_scanner::{
+    ScanOptions, SecurityScanResult, SecurityScanner, Severity, Vulnerability,
+    DEFAULT_DIVISION_THRESHOLD,
+};
+pub use test_executor::{TestExecutor, MAX_TEST_COMPUTE_UNITS, TEST_WALL_CLOCK_LIMIT};
 pub use types::*;
 pub use vm_wrapper::SbpfVmWrapper;
@@ -13,6 +13,23 @@ pub enum Severity {
     Info,
 }
 
+impl Severity {
+    fn rank(&self) -> u8 {
+        match self {
+            Severity::Critical => 4,
+            Severity::High => 3,
+            Severity::Medium => 2,
+            Severity::Low => 1,
+            Severity::Info => 0,
+        }
+    }
+
+    /// Whether this severity is the same as or more severe than `min`
+    pub fn at_least(&self, min: &Severity) -> bool {
+        self.rank() >= min.rank()
+    }
+}
+
 /// A detected security vulnerability
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vulnerability {
@@ -39,11 +56,46 @@ pub struct SecurityScanResult {
     pub passed: bool,
 }
 
+impl SecurityScanResult {
+    /// Drop vulnerabilities below `min` from the list
+    ///
+    /// Counts and risk_score are left as computed over the full set.
+    pub fn retain_min_severity(&mut self, min: &Severity) {
+        self.vulnerabilities.retain(|v| v.severity.at_least(min));
+    }
+}
+
+/// Division/modulo instruction count above which the scan reports a performance finding
+pub const DEFAULT_DIVISION_THRESHOLD: usize = 50;
+
+/// Tunable thresholds for the heuristic checks
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// Report "Excessive division operations" when .text holds more DIV/MOD instructions than this
+    pub division_threshold: usize,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            division_threshold: DEFAULT_DIVISION_THRESHOLD,
+        }
+    }
+}
+
 pub struct SecurityScanner;
 
 impl SecurityScanner {
     /// Perform comprehensive security scan on sBPF binary
     pub fn scan(data: &[u8]) -> Result<SecurityScanResult, SbpfError> {
+        Self::scan_with_options(data, &ScanOptions::default())
+    }
+
+    /// Perform the security scan with custom heuristic thresholds
+    pub fn scan_with_options(
+        data: &[u8],
+        options: &ScanOptions,
+    ) -> Result<SecurityScanResult, SbpfError> {
         // Parse ELF
         if data.len() < 4 || &data[0..4] != &[0x7F, 0x45, 0x4C, 0x46] {
             return Err(SbpfError::NotElfFile);
@@ -60,7 +112,7 @@ impl SecurityScanner {
         Self::check_sections(&mut vulnerabilities, &elf);
         Self::check_symbols(&mut vulnerabilities, &elf);
         Self::check_relocations(&mut vulnerabilities, &elf);
-        Self::check_code_patterns(&mut vulnerabilities, data);
+        Self::check_code_patterns(&mut vulnerabilities, data, &elf, options);
         Self::check_solana_security(&mut vulnerabilities, data);
         Self::check_arithmetic_safety(&mut vulnerabilities, data);
         Self::check_account_validation(&mut vulnerabilities, data);
@@ -286,7 +338,12 @@ impl SecurityScanner {
         }
     }
 
-    fn check_code_patterns(vulnerabilities: &mut Vec<Vulnerability>, data: &[u8]) {
+    fn check_code_patterns(
+        vulnerabilities: &mut Vec<Vulnerability>,
+        data: &[u8],
+        elf: &Elf,
+        options: &ScanOptions,
+    ) {
         // Scan for common vulnerability patterns in the binary
 
         // 1. Look for panic/abort patterns (common in Rust debug builds)
@@ -314,22 +371,17 @@ impl SecurityScanner {
         }
 
         // 3. Check for unoptimized division/modulo (expensive in BPF)
-        // BPF div/mod instructions are opcode 0x3* and 0x9*
-        let mut div_count = 0;
-        for window in data.windows(8) {
-            // Look for BPF division instructions (simplified heuristic)
-            if window[0] == 0x37 || window[0] == 0x97 {
-                div_count += 1;
-            }
-        }
+        let div_count = Self::text_section(data, elf)
+            .map(Self::count_div_mod_instructions)
+            .unwrap_or(0);
 
-        if div_count > 50 {
+        if div_count > options.division_threshold {
             vulnerabilities.push(Vulnerability {
                 severity: Severity::Medium,
                 category: "Performance".to_string(),
                 title: "Excessive division operations".to_string(),
                 description: format!(
-                    "Detected approximately {} division/modulo operations. These are expensive in BPF (~20 compute units each).",
+                    "Detected {} division/modulo instructions in .text. These are expensive in BPF (~20 compute units each).",
                     div_count
                 ),
                 recommendation: "Consider using bit shifts for power-of-2 divisions or precomputing values.".to_string(),
@@ -546,6 +598,60 @@ impl SecurityScanner {
         }
     }
 
+    fn text_section<'a>(data: &'a [u8], elf: &Elf) -> Option<&'a [u8]> {
+        let text = elf
+            .section_headers
+            .iter()
+            .find(|sh| elf.shdr_strtab.get_at(sh.sh_name) == Some(".text"))?;
+        data.get(text.file_range()?)
+    }
+
+    /// Count genuine ALU/ALU64 DIV and MOD instructions in a .text section
+    ///
+    /// Instructions are decoded on their 8-byte boundaries: the opcode must be
+    /// in the ALU or ALU64 class with a DIV/MOD operation, the destination must
+    /// be a writable register (r0-r9) and the source register must be valid for
+    /// the operand kind, so stray data bytes that merely look like an opcode are
+    /// not counted.
+    fn count_div_mod_instructions(text: &[u8]) -> usize {
+        const BPF_ALU: u8 = 0x04;
+        const BPF_ALU64: u8 = 0x07;
+        const BPF_X: u8 = 0x08;
+        const BPF_DIV: u8 = 0x30;
+        const BPF_MOD: u8 = 0x90;
+        const LD_DW_IMM: u8 = 0x18;
+
+        let mut count = 0;
+        let mut slots = text.chunks_exact(8);
+        while let Some(insn) = slots.next() {
+            let opcode = insn[0];
+            if opcode == LD_DW_IMM {
+                // lddw occupies two slots; the second holds the upper immediate
+                slots.next();
+                continue;
+            }
+
+            let class = opcode & 0x07;
+            let op = opcode & 0xf0;
+            if !matches!(class, BPF_ALU | BPF_ALU64) || !matches!(op, BPF_DIV | BPF_MOD) {
+                continue;
+            }
+
+            let dst = insn[1] & 0x0f;
+            let src = insn[1] >> 4;
+            let imm = i32::from_le_bytes([insn[4], insn[5], insn[6], insn[7]]);
+            let operands_valid = if opcode & BPF_X != 0 {
+                src <= 10
+            } else {
+                src == 0 && imm != 0
+            };
+            if dst <= 9 && operands_valid {
+                count += 1;
+            }
+        }
+        count
+    }
+
     fn contains_pattern(data: &[u8], pattern: &[u8]) -> bool {
         data.windows(pattern.len()).any(|window| window == pattern)
     }
@@ -562,6 +668,120 @@ mod tests {
         assert!(matches!(result, Err(SbpfError::NotElfFile)));
     }
 
+    #[test]
+    fn test_retain_min_severity_keeps_counts() {
+        let finding = |severity| Vulnerability {
+            severity,
+            category: "Test".to_string(),
+            title: "Test".to_string(),
+            description: String::new(),
+            recommendation: String::new(),
+            location: None,
+        };
+        let mut result = SecurityScanResult {
+            binary_size: 0,
+            vulnerabilities: vec![finding(Severity::Critical), finding(Severity::Medium), finding(Severity::Info)],
+            critical_count: 1,
+            high_count: 0,
+            medium_count: 1,
+            low_count: 0,
+            info_count: 1,
+            risk_score: 46,
+            passed: false,
+        };
+
+        result.retain_min_severity(&Severity::Medium);
+        assert_eq!(result.vulnerabilities.len(), 2);
+        assert_eq!(result.info_count, 1);
+        assert_eq!(result.risk_score, 46);
+    }
+
+    /// Build a minimal ELF64 whose only sections are .text and .shstrtab
+    fn elf_with_text(text: &[u8]) -> Vec<u8> {
+        let shstrtab = b"\0.text\0.shstrtab\0";
+        let text_offset = 64;
+        let strtab_offset = text_offset + text.len();
+        let shoff = (strtab_offset + shstrtab.len() + 7) & !7;
+
+        let mut elf = vec![0u8; shoff + 3 * 64];
+        elf[0..4].copy_from_slice(&[0x7F, 0x45, 0x4C, 0x46]);
+        elf[4] = 2; // 64-bit
+        elf[5] = 1; // little-endian
+        elf[6] = 1; // ELF version
+        elf[16..18].copy_from_slice(&3u16.to_le_bytes()); // ET_DYN
+        elf[18..20].copy_from_slice(&0xF7u16.to_le_bytes());
+        elf[20..24].copy_from_slice(&1u32.to_le_bytes());
+        elf[24..32].copy_from_slice(&(text_offset as u64).to_le_bytes());
+        elf[40..48].copy_from_slice(&(shoff as u64).to_le_bytes());
+        elf[52..54].copy_from_slice(&64u16.to_le_bytes());
+        elf[58..60].copy_from_slice(&64u16.to_le_bytes());
+        elf[60..62].copy_from_slice(&3u16.to_le_bytes());
+        elf[62..64].copy_from_slice(&2u16.to_le_bytes());
+        elf[text_offset..strtab_offset].copy_from_slice(text);
+        elf[strtab_offset..strtab_offset + shstrtab.len()].copy_from_slice(shstrtab);
+
+        let mut section = |index: usize, name: u32, kind: u32, flags: u64, offset: usize, size: usize| {
+            let sh = shoff + index * 64;
+            elf[sh..sh + 4].copy_from_slice(&name.to_le_bytes());
+            elf[sh + 4..sh + 8].copy_from_slice(&kind.to_le_bytes());
+            elf[sh + 8..sh + 16].copy_from_slice(&flags.to_le_bytes());
+            elf[sh + 16..sh + 24].copy_from_slice(&(offset as u64).to_le_bytes());
+            elf[sh + 24..sh + 32].copy_from_slice(&(offset as u64).to_le_bytes());
+            elf[sh + 32..sh + 40].copy_from_slice(&(size as u64).to_le_bytes());
+        };
+        section(1, 1, 1, 0x6, text_offset, text.len()); // .text: PROGBITS, ALLOC|EXECINSTR
+        section(2, 7, 3, 0, strtab_offset, shstrtab.len()); // .shstrtab: STRTAB
+        elf
+    }
+
+    fn division_finding(result: &SecurityScanResult) -> Option<&Vulnerability> {
+        result
+            .vulnerabilities
+            .iter()
+            .find(|v| v.title == "Excessive division operations")
+    }
+
+    #[test]
+    fn test_counts_only_genuine_div_mod_instructions() {
+        let text: Vec<[u8; 8]> = vec![
+            [0x37, 0x01, 0, 0, 0x0a, 0, 0, 0], // div64 r1, 10
+            [0x3f, 0x21, 0, 0, 0, 0, 0, 0],    // div64 r1, r2
+            [0x9c, 0x43, 0, 0, 0, 0, 0, 0],    // mod32 r3, r4
+            [0x97, 0x05, 0, 0, 0x03, 0, 0, 0], // mod64 r5, 3
+            [0x34, 0x06, 0, 0, 0x02, 0, 0, 0], // div32 r6, 2
+            [0x37, 0x01, 0, 0, 0, 0, 0, 0],    // div64 r1, 0 (rejected by the verifier)
+            [0x37, 0x0a, 0, 0, 0x02, 0, 0, 0], // div64 r10, 2 (frame pointer is read-only)
+            [0x18, 0x01, 0, 0, 0, 0, 0, 0],    // lddw r1, ...
+            [0x37, 0x01, 0, 0, 0x0a, 0, 0, 0], // ... second lddw slot, never decoded
+            [0x07, 0x01, 0, 0, 0x01, 0, 0, 0], // add64 r1, 1
+            [0x95, 0, 0, 0, 0, 0, 0, 0],       // exit
+        ];
+        let text: Vec<u8> = text.concat();
+        assert_eq!(SecurityScanner::count_div_mod_instructions(&text), 5);
+
+        let binary = elf_with_text(&text);
+        let result =
+            SecurityScanner::scan_with_options(&binary, &ScanOptions { division_threshold: 4 })
+                .unwrap();
+        let finding = division_finding(&result).expect("5 divisions exceed a threshold of 4");
+        assert!(finding.description.starts_with("Detected 5 division/modulo instructions"));
+
+        let result =
+            SecurityScanner::scan_with_options(&binary, &ScanOptions { division_threshold: 5 })
+                .unwrap();
+        assert!(division_finding(&result).is_none());
+    }
+
+    #[test]
+    fn test_div_opcode_bytes_outside_text_are_ignored() {
+        let mut binary = elf_with_text(&[0x95, 0, 0, 0, 0, 0, 0, 0]);
+        binary.extend(std::iter::repeat([0x37u8, 0x01, 0, 0, 0x0a, 0, 0, 0]).take(100).flatten());
+
+        let result = SecurityScanner::scan_with_options(&binary, &ScanOptions { division_threshold: 0 })
+            .unwrap();
+        assert!(division_finding(&result).is_none());
+    }
+
     #[test]
     fn test_contains_pattern() {
         let data = b"Hello world panicked at something";
@@ -1,12 +1,19 @@
 use super::binary_validator::BinaryValidator;
 use super::errors::SbpfError;
-use super::types::{TestParams, TestResult};
+use super::types::{InstructionSpec, TestParams, TestResult};
 use super::vm_wrapper::{parse_pubkey, SbpfVmWrapper};
 use base64::engine::general_purpose::STANDARD as BASE64;
 use base64::Engine;
 use solana_sdk::account::Account;
 use solana_sdk::instruction::AccountMeta;
 use std::collections::HashMap;
+use std::time::Duration;
+
+/// Upper bound on compute units a single test transaction may consume
+pub const MAX_TEST_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// Upper bound on how long a test may run before the caller gives up
+pub const TEST_WALL_CLOCK_LIMIT: Duration = Duration::from_secs(10);
 
 /// Executes sBPF program tests
 pub struct TestExecutor {
@@ -22,7 +29,19 @@ impl TestExecutor {
     }
 
     /// Execute a complete test from parameters
+    ///
+    /// A transaction the runtime rejects is returned as an `ExecutionError`;
+    /// `execute_test_with_limits` reports it as `success: false` instead.
     pub async fn execute_test(&self, params: TestParams) -> Result<TestResult, SbpfError> {
+        let result = self.run_test(params).await?;
+        match &result.error {
+            Some(error) if !result.success => Err(SbpfError::ExecutionError(error.clone())),
+            _ => Ok(result),
+        }
+    }
+
+    /// Validate, deploy and execute a test, keeping a failed run's logs
+    async fn run_test(&self, params: TestParams) -> Result<TestResult, SbpfError> {
         // 1. Validate binary
         let metadata = BinaryValidator::validate(&params.binary)?;
         log::info!(
@@ -80,21 +99,34 @@ impl TestExecutor {
 
         log::info!("Setup {} test accounts", initial_accounts.len());
 
-        // 4. Decode instruction data
-        let instruction_data = if let Some(data_b64) = params.instruction_data {
-            BASE64
-                .decode(&data_b64)
-                .map_err(|e| SbpfError::Base64Error(e))?
+        // 4. Decode instructions
+        let instructions = if params.instructions.is_empty() {
+            let instruction_data = if let Some(data_b64) = params.instruction_data {
+                BASE64
+                    .decode(&data_b64)
+                    .map_err(|e| SbpfError::Base64Error(e))?
+            } else {
+                vec![]
+            };
+            log::info!("Instruction data: {} bytes", instruction_data.len());
+            vec![(account_metas, instruction_data)]
         } else {
-            vec![]
+            params
+                .instructions
+                .iter()
+                .map(|spec| Self::resolve_instruction(spec, &account_metas))
+                .collect::<Result<Vec<_>, _>>()?
         };
 
-        log::info!("Instruction data: {} bytes", instruction_data.len());
-
         // 5. Execute test
         let result = self
             .vm
-            .test_program(&program_id, account_metas, instruction_data, initial_accounts)
+            .execute_instructions(
+                &program_id,
+                instructions,
+                initial_accounts,
+                params.compute_unit_limit,
+            )
             .await?;
 
         log::info!(
@@ -107,12 +139,79 @@ impl TestExecutor {
         Ok(result)
     }
 
+    /// Execute a test on a blocking thread, giving up after `wall_clock_limit`
+    ///
+    /// The compute-unit limit is clamped to `MAX_TEST_COMPUTE_UNITS` so the VM
+    /// always terminates; the wall-clock limit bounds how long the caller waits.
+    pub async fn execute_test_with_limits(
+        self,
+        mut params: TestParams,
+        wall_clock_limit: Duration,
+    ) -> Result<TestResult, SbpfError> {
+        params.compute_unit_limit = Some(clamp_compute_unit_limit(params.compute_unit_limit));
+
+        // liteSVM executes synchronously, so run it off the async workers
+        let handle = tokio::runtime::Handle::current();
+        run_with_wall_clock_limit(move || handle.block_on(self.run_test(params)), wall_clock_limit).await
+    }
+
+    /// Build the account metas and data for one instruction fixture
+    fn resolve_instruction(
+        spec: &InstructionSpec,
+        fixtures: &[AccountMeta],
+    ) -> Result<(Vec<AccountMeta>, Vec<u8>), SbpfError> {
+        let data = match &spec.data {
+            Some(data_b64) => BASE64.decode(data_b64)?,
+            None => vec![],
+        };
+
+        let metas = match &spec.accounts {
+            None => fixtures.to_vec(),
+            Some(pubkeys) => pubkeys
+                .iter()
+                .map(|key| {
+                    let pubkey = parse_pubkey(key)?;
+                    fixtures
+                        .iter()
+                        .find(|meta| meta.pubkey == pubkey)
+                        .cloned()
+                        .ok_or_else(|| SbpfError::InvalidParameter {
+                            parameter: "instructions".to_string(),
+                            reason: format!("Account {} is not one of the test fixtures", key),
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+
+        Ok((metas, data))
+    }
+
     /// Validate a binary without executing
     pub fn validate_only(binary: &[u8]) -> Result<super::types::BinaryMetadata, SbpfError> {
         BinaryValidator::validate(binary)
     }
 }
 
+/// Caps a requested compute-unit limit at `MAX_TEST_COMPUTE_UNITS`, the default
+fn clamp_compute_unit_limit(requested: Option<u32>) -> u32 {
+    requested.unwrap_or(MAX_TEST_COMPUTE_UNITS).min(MAX_TEST_COMPUTE_UNITS)
+}
+
+/// Runs `test` on a blocking thread, giving up after `wall_clock_limit`
+async fn run_with_wall_clock_limit<F>(test: F, wall_clock_limit: Duration) -> Result<TestResult, SbpfError>
+where
+    F: FnOnce() -> Result<TestResult, SbpfError> + Send + 'static,
+{
+    match tokio::time::timeout(wall_clock_limit, tokio::task::spawn_blocking(test)).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => Err(SbpfError::ExecutionError(format!("Test task failed: {}", e))),
+        Err(_) => Err(SbpfError::ExecutionError(format!(
+            "Test exceeded wall-clock limit of {}ms",
+            wall_clock_limit.as_millis()
+        ))),
+    }
+}
+
 impl Default for TestExecutor {
     fn default() -> Self {
         Self::new()
@@ -131,6 +230,27 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_compute_unit_limit_is_clamped() {
+        assert_eq!(clamp_compute_unit_limit(None), MAX_TEST_COMPUTE_UNITS);
+        assert_eq!(clamp_compute_unit_limit(Some(u32::MAX)), MAX_TEST_COMPUTE_UNITS);
+        assert_eq!(clamp_compute_unit_limit(Some(5_000)), 5_000);
+    }
+
+    #[tokio::test]
+    async fn test_wall_clock_limit_stops_waiting() {
+        let slow = || {
+            std::thread::sleep(Duration::from_millis(200));
+            Err(SbpfError::ExecutionError("finished".to_string()))
+        };
+        match run_with_wall_clock_limit(slow, Duration::from_millis(20)).await {
+            Err(SbpfError::ExecutionError(message)) => {
+                assert_eq!(message, "Test exceeded wall-clock limit of 20ms")
+            }
+            other => panic!("expected a wall-clock error, got {:?}", other.map(|r| r.success)),
+        }
+    }
+
     #[tokio::test]
     async fn test_executor_creation() {
         let _executor = TestExecutor::new();
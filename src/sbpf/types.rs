@@ -16,6 +16,25 @@ pub struct TestParams {
     /// Signers for the transaction
     #[serde(default)]
     pub signers: Vec<String>,  // pubkey strings
+
+    /// Instructions to execute in one transaction (overrides `instruction_data` when non-empty)
+    #[serde(default)]
+    pub instructions: Vec<InstructionSpec>,
+
+    /// Compute-unit limit for the transaction (runtime default when unset)
+    #[serde(default)]
+    pub compute_unit_limit: Option<u32>,
+}
+
+/// Instruction fixture for a multi-instruction test
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionSpec {
+    /// Instruction data (base64-encoded)
+    pub data: Option<String>,
+
+    /// Fixture account pubkeys passed to the instruction, in order (all fixtures if omitted)
+    pub accounts: Option<Vec<String>>,
 }
 
 /// Account specification for testing
@@ -58,6 +77,10 @@ pub struct TestResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub return_value: Option<u64>,
 
+    /// Raw return data from the program (base64-encoded)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_data: Option<String>,
+
     /// Compute units consumed
     pub compute_units: u64,
 
@@ -101,14 +124,36 @@ pub struct BinaryMetadata {
     /// Entry point address
     pub entrypoint: String,
 
+    /// Whether the binary passes every structural check the program loader applies
+    pub loadable: bool,
+
     /// ELF sections found
     pub sections: Vec<String>,
 
+    /// Address, file offset and size of each named section
+    pub section_layout: Vec<SectionLayout>,
+
     /// Validation errors (if any)
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub errors: Vec<String>,
 }
 
+/// Placement of a single ELF section
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionLayout {
+    /// Section name (e.g. ".text")
+    pub name: String,
+
+    /// Virtual address the section is mapped at
+    pub address: String,
+
+    /// Offset of the section within the file
+    pub offset: u64,
+
+    /// Size of the section in bytes
+    pub size: u64,
+}
+
 /// Response from deploying a program locally
 #[derive(Debug, Clone, Serialize)]
 pub struct DeployResponse {
@@ -153,6 +198,10 @@ pub struct DevnetDeployResponse {
     /// Any validation warnings or notes
     #[serde(skip_serializing_if = "Option::is_none")]
     pub validation_notes: Option<String>,
+
+    /// Lamports spent by the payer on rent and fees (actual deployments only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_lamports: Option<u64>,
 }
 
 /// Parameters for devnet deployment
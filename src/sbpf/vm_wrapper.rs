@@ -1,8 +1,11 @@
 use super::errors::SbpfError;
 use super::types::{AccountChange, DeployResponse, TestResult};
 use litesvm::LiteSVM;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use solana_sdk::{
     account::Account as SdkAccount,
+    compute_budget::ComputeBudgetInstruction,
     instruction::AccountMeta,
     pubkey::Pubkey as SdkPubkey,
     signature::{Keypair as SdkKeypair, Signer as SdkSigner},
@@ -117,6 +120,28 @@ impl SbpfVmWrapper {
         account_metas: Vec<AccountMeta>,
         instruction_data: Vec<u8>,
         initial_accounts: HashMap<SdkPubkey, SdkAccount>,
+    ) -> Result<TestResult, SbpfError> {
+        self.execute_instructions(
+            program_id,
+            vec![(account_metas, instruction_data)],
+            initial_accounts,
+            None,
+        )
+        .await
+    }
+
+    /// Execute one or more instructions against a program in a single transaction
+    ///
+    /// When `compute_unit_limit` is set, a compute-budget instruction caps the
+    /// transaction so a runaway program is halted by the runtime. A transaction
+    /// the runtime rejects is reported as `success: false` with its logs rather
+    /// than as an error.
+    pub async fn execute_instructions(
+        &self,
+        program_id: &SdkPubkey,
+        instructions: Vec<(Vec<AccountMeta>, Vec<u8>)>,
+        initial_accounts: HashMap<SdkPubkey, SdkAccount>,
+        compute_unit_limit: Option<u32>,
     ) -> Result<TestResult, SbpfError> {
         let mut vm = self.vm.lock().await;
 
@@ -152,27 +177,45 @@ impl SbpfVmWrapper {
             ));
         }
 
-        log::info!("Creating instruction for program: {}", program_id);
+        log::info!(
+            "Creating {} instruction(s) for program: {}",
+            instructions.len(),
+            program_id
+        );
 
-        // Create instruction using litesvm types
-        let account_metas_lite: Vec<solana_instruction::AccountMeta> = account_metas
-            .iter()
-            .map(|am| solana_instruction::AccountMeta {
-                pubkey: sdk_pubkey_to_litesvm(&am.pubkey),
-                is_signer: am.is_signer,
-                is_writable: am.is_writable,
-            })
-            .collect();
+        let mut lite_instructions = Vec::with_capacity(instructions.len() + 1);
+        if let Some(limit) = compute_unit_limit {
+            let budget = ComputeBudgetInstruction::set_compute_unit_limit(limit);
+            lite_instructions.push(solana_instruction::Instruction {
+                program_id: sdk_pubkey_to_litesvm(&budget.program_id),
+                accounts: vec![],
+                data: budget.data,
+            });
+        }
 
-        log::debug!("Instruction accounts: {}", account_metas_lite.len());
+        // Create instructions using litesvm types
+        for (account_metas, instruction_data) in instructions {
+            let account_metas_lite: Vec<solana_instruction::AccountMeta> = account_metas
+                .iter()
+                .map(|am| solana_instruction::AccountMeta {
+                    pubkey: sdk_pubkey_to_litesvm(&am.pubkey),
+                    is_signer: am.is_signer,
+                    is_writable: am.is_writable,
+                })
+                .collect();
 
-        let instruction = solana_instruction::Instruction {
-            program_id: program_id_lite,
-            accounts: account_metas_lite,
-            data: instruction_data.clone(),
-        };
+            log::debug!(
+                "Instruction accounts: {}, data length: {} bytes",
+                account_metas_lite.len(),
+                instruction_data.len()
+            );
 
-        log::debug!("Instruction data length: {} bytes", instruction_data.len());
+            lite_instructions.push(solana_instruction::Instruction {
+                program_id: program_id_lite,
+                accounts: account_metas_lite,
+                data: instruction_data,
+            });
+        }
 
         // Create and sign transaction
         let payer = LiteKeypair::new();
@@ -186,7 +229,7 @@ impl SbpfVmWrapper {
 
         // Build message and transaction
         let message = LiteMessage::new_with_blockhash(
-            &[instruction],
+            &lite_instructions,
             Some(&payer_pubkey),
             &recent_blockhash,
         );
@@ -194,10 +237,15 @@ impl SbpfVmWrapper {
         let mut transaction = LiteTransaction::new_unsigned(message);
         transaction.sign(&[&payer], recent_blockhash);
 
-        // Process transaction
-        let result = vm
-            .send_transaction(transaction)
-            .map_err(|e| SbpfError::ExecutionError(format!("{:?}", e)))?;
+        // Process transaction, keeping the logs of a failed run for diagnosis
+        let (result, error) = match vm.send_transaction(transaction) {
+            Ok(meta) => (meta, None),
+            Err(failed) => {
+                log::info!("Test transaction failed: {:?}", failed.err);
+                (failed.meta, Some(format!("{:?}", failed.err)))
+            }
+        };
+        let success = error.is_none();
 
         // Extract logs
         let logs: Vec<String> = result
@@ -235,10 +283,6 @@ impl SbpfVmWrapper {
             }
         }
 
-        // Transaction was successful if we got here (send_transaction returned Ok)
-        let success = true;
-        let error = None;
-
         // Extract compute units from metadata
         let compute_units = result.compute_units_consumed;
 
@@ -250,10 +294,13 @@ impl SbpfVmWrapper {
         } else {
             None
         };
+        let return_data = (!result.return_data.data.is_empty())
+            .then(|| BASE64.encode(&result.return_data.data));
 
         Ok(TestResult {
             success,
             return_value,
+            return_data,
             compute_units,
             logs,
             account_changes,
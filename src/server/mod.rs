@@ -1,14 +1,45 @@
 use crate::cache::RpcCache;
-use crate::transport::{JsonRpcMessage, JsonRpcNotification, JsonRpcVersion, Transport};
+use crate::progress::ProgressSink;
+use crate::rpc::capabilities::CapabilityCache;
+use crate::transport::{JsonRpcMessage, JsonRpcNotification, JsonRpcVersion, StdioFraming, Transport};
 use crate::validation::sanitize_for_logging;
 use crate::{Config, CustomStdioTransport};
 use anyhow::Result;
+use dashmap::DashMap;
+use serde_json::Value;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{RwLock, Semaphore};
+use tokio::task::AbortHandle;
+
+/// In-flight tool calls keyed by session and JSON-RPC request id
+pub type InFlightCalls = Arc<DashMap<String, AbortHandle>>;
+
+/// Session of requests handled outside any [`with_call_session`] scope,
+/// i.e. the single stdio client
+pub const LOCAL_SESSION: &str = "local";
+
+tokio::task_local! {
+    /// Client session the request currently being handled belongs to
+    static CALL_SESSION: String;
+}
+
+/// Runs `future` with its requests attributed to `session`, so request ids
+/// only match in-flight calls and cancellations from the same client
+pub async fn with_call_session<F: std::future::Future>(session: String, future: F) -> F::Output {
+    CALL_SESSION.scope(session, future).await
+}
+
+/// The session of the request being handled, or `LOCAL_SESSION` outside one
+pub fn current_call_session() -> String {
+    CALL_SESSION
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| LOCAL_SESSION.to_string())
+}
 
 /// Server state containing RPC clients and configuration
 ///
@@ -16,7 +47,9 @@ use tokio::sync::RwLock;
 /// and server configuration. Thread-safe through Arc<RwLock<>> wrapper.
 pub struct ServerState {
     /// Primary Solana RPC client (kept for backwards compatibility)
-    pub rpc_client: RpcClient,
+    ///
+    /// Shared so callers can keep using it after releasing the state lock.
+    pub rpc_client: Arc<RpcClient>,
     /// Pool of RPC clients for round-robin load balancing
     pub rpc_clients: Vec<RpcClient>,
     /// Round-robin counter for client selection
@@ -31,6 +64,21 @@ pub struct ServerState {
     pub protocol_version: String,
     /// RPC response cache
     pub cache: Arc<RpcCache>,
+    /// Limits the number of concurrent upstream RPC calls
+    pub rpc_semaphore: Arc<Semaphore>,
+    /// Abort handles for running tool calls, used by `notifications/cancelled`
+    pub in_flight_calls: InFlightCalls,
+    /// Number of tools/call requests currently in flight
+    pub in_flight_requests: Arc<AtomicUsize>,
+    /// Whether tools may read files from the local filesystem (stdio transport only)
+    pub local_file_access: bool,
+    /// Cached getVersion probe of the primary RPC node
+    pub node_capabilities: Arc<CapabilityCache>,
+    /// Delivers progress notifications mid-request (stdio transport only)
+    pub progress_sink: Option<ProgressSink>,
+    /// x402 payments settled by this server and the results they paid for
+    #[cfg(feature = "x402")]
+    pub settled_payments: Arc<crate::x402::SettledPayments>,
 }
 
 impl ServerState {
@@ -47,58 +95,33 @@ impl ServerState {
     /// - Uses validated configuration with HTTPS enforcement
     pub fn new(config: Config) -> Self {
         let commitment = Self::parse_commitment(&config.commitment);
+        let timeout = Duration::from_secs(config.rpc_timeout_seconds);
 
         // Create RPC clients pool for round-robin
-        let mut rpc_clients = Vec::new();
-        for url in &config.rpc_urls {
-            log::info!(
-                "Creating RPC client for: {}",
-                sanitize_for_logging(url)
-            );
-            let client = RpcClient::new_with_commitment(url.clone(), commitment);
-            rpc_clients.push(client);
-        }
-
-        // Fallback to single rpc_url if rpc_urls is empty (backwards compatibility)
-        if rpc_clients.is_empty() {
-            log::info!(
-                "Creating RPC client for: {}",
-                sanitize_for_logging(&config.rpc_url)
-            );
-            let client = RpcClient::new_with_commitment(config.rpc_url.clone(), commitment);
-            rpc_clients.push(client);
-        }
+        let rpc_clients = Self::build_rpc_pool(&config, commitment, timeout);
 
         // Keep first client as primary for backwards compatibility
-        let rpc_client = RpcClient::new_with_commitment(
+        let rpc_client = Arc::new(Self::build_rpc_client(
             if !config.rpc_urls.is_empty() {
                 config.rpc_urls[0].clone()
             } else {
                 config.rpc_url.clone()
             },
-            commitment
-        );
+            commitment,
+            timeout,
+        ));
 
         log::info!("RPC pool created with {} endpoint(s)", rpc_clients.len());
 
         // Create RPC clients for enabled SVM networks
-        let mut svm_clients = HashMap::new();
-        for (network_id, network) in &config.svm_networks {
-            if network.enabled {
-                log::info!(
-                    "Creating SVM client for network '{}': {}",
-                    network_id,
-                    sanitize_for_logging(&network.rpc_url)
-                );
-                let client =
-                    RpcClient::new_with_commitment(network.rpc_url.clone(), commitment);
-                svm_clients.insert(network_id.clone(), client);
-            }
-        }
+        let svm_clients = Self::build_svm_clients(&config, commitment, timeout);
 
         // Create cache with config
         let cache = Arc::new(RpcCache::new(config.cache.clone()));
 
+        // Create concurrency limiter for upstream RPC calls
+        let rpc_semaphore = Arc::new(Semaphore::new(config.max_concurrent_rpc));
+
         Self {
             rpc_client,
             rpc_clients,
@@ -108,6 +131,35 @@ impl ServerState {
             config,
             initialized: false,
             cache,
+            rpc_semaphore,
+            in_flight_calls: Arc::new(DashMap::new()),
+            in_flight_requests: Arc::new(AtomicUsize::new(0)),
+            local_file_access: false,
+            node_capabilities: Arc::new(CapabilityCache::new()),
+            progress_sink: None,
+            #[cfg(feature = "x402")]
+            settled_payments: Arc::new(crate::x402::SettledPayments::new()),
+        }
+    }
+
+    /// Key under which a session's request id is tracked in `in_flight_calls`
+    ///
+    /// Uses the JSON form of the id so numeric `1` and string `"1"` stay distinct.
+    pub fn request_key(session: &str, request_id: &Value) -> String {
+        format!("{session}/{request_id}")
+    }
+
+    /// Aborts the running tool call for a request id of `session`
+    ///
+    /// # Returns
+    /// * `bool` - Whether a matching in-flight call was found
+    pub fn cancel_tool_call(&self, session: &str, request_id: &Value) -> bool {
+        match self.in_flight_calls.remove(&Self::request_key(session, request_id)) {
+            Some((_, handle)) => {
+                handle.abort();
+                true
+            }
+            None => false,
         }
     }
 
@@ -124,6 +176,13 @@ impl ServerState {
         &self.rpc_clients[index]
     }
 
+    /// Returns the client the next `get_next_rpc_client` call will hand out
+    /// without advancing the rotation
+    pub fn peek_next_rpc_client(&self) -> &RpcClient {
+        let index = self.rpc_client_index.load(Ordering::Relaxed) % self.rpc_clients.len();
+        &self.rpc_clients[index]
+    }
+
     /// Updates the server configuration and recreates clients as needed
     ///
     /// # Arguments
@@ -134,32 +193,36 @@ impl ServerState {
     /// - Recreates clients with new URLs securely
     pub fn update_config(&mut self, new_config: Config) {
         let commitment = Self::parse_commitment(&new_config.commitment);
+        let timeout = Duration::from_secs(new_config.rpc_timeout_seconds);
 
-        // Update main RPC client if URL changed
-        if self.config.rpc_url != new_config.rpc_url {
+        // Update main RPC client if URL, timeout or commitment changed
+        let client_settings_changed = self.config.rpc_timeout_seconds != new_config.rpc_timeout_seconds
+            || self.config.commitment != new_config.commitment;
+        if self.config.rpc_url != new_config.rpc_url || client_settings_changed {
             log::info!(
                 "Updating main RPC client to: {}",
                 sanitize_for_logging(&new_config.rpc_url)
             );
-            self.rpc_client =
-                RpcClient::new_with_commitment(new_config.rpc_url.clone(), commitment);
+            self.rpc_client = Arc::new(Self::build_rpc_client(new_config.rpc_url.clone(), commitment, timeout));
         }
 
-        // Update SVM clients
-        self.svm_clients.clear();
-        for (network_id, network) in &new_config.svm_networks {
-            if network.enabled {
-                log::info!(
-                    "Creating/updating SVM client for network '{}': {}",
-                    network_id,
-                    sanitize_for_logging(&network.rpc_url)
-                );
-                let client =
-                    RpcClient::new_with_commitment(network.rpc_url.clone(), commitment);
-                self.svm_clients.insert(network_id.clone(), client);
-            }
+        // Rebuild the round-robin pool if its endpoints changed
+        if self.config.rpc_urls != new_config.rpc_urls
+            || (new_config.rpc_urls.is_empty() && self.config.rpc_url != new_config.rpc_url)
+            || client_settings_changed
+        {
+            self.rpc_clients = Self::build_rpc_pool(&new_config, commitment, timeout);
+            log::info!("RPC pool rebuilt with {} endpoint(s)", self.rpc_clients.len());
+        }
+
+        // A different node may run a different version
+        if self.config.rpc_url != new_config.rpc_url || self.config.rpc_urls != new_config.rpc_urls {
+            self.node_capabilities.reset();
         }
 
+        // Update SVM clients
+        self.svm_clients = Self::build_svm_clients(&new_config, commitment, timeout);
+
         // Update cache if config changed
         if self.config.cache.enabled != new_config.cache.enabled
             || self.config.cache.max_entries != new_config.cache.max_entries
@@ -170,6 +233,15 @@ impl ServerState {
             self.cache = Arc::new(RpcCache::new(new_config.cache.clone()));
         }
 
+        // Recreate concurrency limiter if the limit changed
+        if self.config.max_concurrent_rpc != new_config.max_concurrent_rpc {
+            log::info!(
+                "Updating RPC concurrency limit to {}",
+                new_config.max_concurrent_rpc
+            );
+            self.rpc_semaphore = Arc::new(Semaphore::new(new_config.max_concurrent_rpc));
+        }
+
         self.config = new_config;
     }
 
@@ -186,6 +258,64 @@ impl ServerState {
             .collect()
     }
 
+    /// Creates the round-robin pool from `rpc_urls`, falling back to `rpc_url`
+    pub(crate) fn build_rpc_pool(config: &Config, commitment: CommitmentConfig, timeout: Duration) -> Vec<RpcClient> {
+        let mut rpc_clients = Vec::new();
+        for url in &config.rpc_urls {
+            log::info!(
+                "Creating RPC client for: {}",
+                sanitize_for_logging(url)
+            );
+            let client = Self::build_rpc_client(url.clone(), commitment, timeout);
+            rpc_clients.push(client);
+        }
+
+        // Fallback to single rpc_url if rpc_urls is empty (backwards compatibility)
+        if rpc_clients.is_empty() {
+            log::info!(
+                "Creating RPC client for: {}",
+                sanitize_for_logging(&config.rpc_url)
+            );
+            let client = Self::build_rpc_client(config.rpc_url.clone(), commitment, timeout);
+            rpc_clients.push(client);
+        }
+        rpc_clients
+    }
+
+    /// Creates a client for each enabled SVM network
+    ///
+    /// A network's own `commitment` takes precedence over the global `commitment`.
+    fn build_svm_clients(
+        config: &Config,
+        commitment: CommitmentConfig,
+        timeout: Duration,
+    ) -> HashMap<String, RpcClient> {
+        let mut svm_clients = HashMap::new();
+        for (network_id, network) in &config.svm_networks {
+            if network.enabled {
+                let commitment = network.commitment.as_deref().map_or(commitment, Self::parse_commitment);
+                log::info!(
+                    "Creating SVM client for network '{}' ({:?}): {}",
+                    network_id,
+                    commitment.commitment,
+                    sanitize_for_logging(&network.rpc_url)
+                );
+                let client = Self::build_rpc_client(network.rpc_url.clone(), commitment, timeout);
+                svm_clients.insert(network_id.clone(), client);
+            }
+        }
+        svm_clients
+    }
+
+    /// Creates an RPC client whose HTTP requests give up after `timeout`
+    ///
+    /// The timeout bounds each HTTP attempt. The client retries HTTP 429
+    /// responses itself (up to 5 times, honoring `Retry-After`), and each retry
+    /// gets a fresh timeout, so a rate-limited call can outlast it.
+    fn build_rpc_client(url: String, commitment: CommitmentConfig, timeout: Duration) -> RpcClient {
+        RpcClient::new_with_timeout_and_commitment(url, timeout, commitment)
+    }
+
     /// Parses commitment string into CommitmentConfig
     ///
     /// # Arguments
@@ -193,7 +323,7 @@ impl ServerState {
     ///
     /// # Returns
     /// * `CommitmentConfig` - Parsed commitment configuration
-    fn parse_commitment(commitment_str: &str) -> CommitmentConfig {
+    pub(crate) fn parse_commitment(commitment_str: &str) -> CommitmentConfig {
         match commitment_str {
             "processed" => CommitmentConfig::processed(),
             "confirmed" => CommitmentConfig::confirmed(),
@@ -213,6 +343,9 @@ impl ServerState {
 /// Initializes the server with configuration validation, sets up transport,
 /// starts the metrics HTTP server, sends protocol negotiation, and starts the main message loop.
 ///
+/// # Arguments
+/// * `framing` - Stdio framing to use instead of the configured `stdio_framing`
+///
 /// # Returns
 /// * `Result<()>` - Ok if server shuts down cleanly, Err on critical errors
 ///
@@ -220,7 +353,7 @@ impl ServerState {
 /// - Validates configuration before starting
 /// - Uses secure transport with proper error handling
 /// - Implements graceful shutdown on connection close
-pub async fn start_server() -> Result<()> {
+pub async fn start_server(framing: Option<StdioFraming>) -> Result<()> {
     log::info!("Starting Solana MCP server...");
 
     // Initialize Prometheus metrics
@@ -239,13 +372,44 @@ pub async fn start_server() -> Result<()> {
         config.protocol_version
     );
 
-    let state = Arc::new(RwLock::new(ServerState::new(config.clone())));
+    let transport = Arc::new(CustomStdioTransport::with_framing(framing.unwrap_or(config.stdio_framing)));
+
+    // The stdio client runs on the same machine, so local file paths are meaningful
+    let mut server_state = ServerState::new(config.clone());
+    server_state.local_file_access = true;
+    server_state.progress_sink = Some(spawn_notification_forwarder(transport.clone()));
+    let state = Arc::new(RwLock::new(server_state));
+
+    if config.watch_config {
+        if let Err(e) = crate::config_watcher::start_config_watcher_task("config.json".into(), state.clone()) {
+            log::error!("Config hot reload disabled: {e:#}");
+        }
+    }
+
+    let _network_health_handle = crate::network_health::start_network_health_task(state.clone()).await;
+    tokio::spawn(crate::rpc::capabilities::probe_node_capabilities(state.clone()));
+
+    // Start metrics HTTP server on port 8080 (or metrics_bind_addr) in background
+    let _metrics_handle = crate::http_server::start_metrics_server_task_with_config(8080, Arc::new(config.clone()));
+    log::info!(
+        "Started metrics server on {}",
+        config.metrics_bind_addr.as_deref().unwrap_or("port 8080")
+    );
+
+    announce(&transport, &config.protocol_version)?;
+    // A fixed line on stderr that test harnesses can wait for instead of sleeping
+    eprintln!("{READY_MARKER}");
+    serve(transport.clone(), state).await;
 
-    // Start metrics HTTP server on port 8080 in background
-    let _metrics_handle = crate::http_server::start_metrics_server_task(8080);
-    log::info!("Started metrics server on port 8080");
+    log::info!("Solana MCP server stopped");
+    Ok(())
+}
+
+/// Printed to stderr once the stdio server accepts protocol messages
+pub const READY_MARKER: &str = "MCP_SERVER_READY";
 
-    let transport = CustomStdioTransport::new();
+/// Opens `transport` and sends the protocol version notification
+fn announce(transport: &CustomStdioTransport, protocol_version: &str) -> Result<()> {
     transport.open().map_err(|e| {
         log::error!("Failed to open transport: {e}");
         e
@@ -255,41 +419,58 @@ pub async fn start_server() -> Result<()> {
     // Send initial protocol version notification
     log::info!(
         "Sending protocol version notification: {}",
-        config.protocol_version
+        protocol_version
     );
     transport
         .send(&JsonRpcMessage::Notification(JsonRpcNotification {
             jsonrpc: JsonRpcVersion::V2,
             method: "protocol".to_string(),
             params: Some(serde_json::json!({
-                "version": config.protocol_version.clone()
+                "version": protocol_version
             })),
         }))
         .map_err(|e| {
             log::error!("Failed to send protocol notification: {e}");
             e
         })?;
+    Ok(())
+}
 
+/// Answers messages on `transport` until the client disconnects
+///
+/// Requests run in their own tasks and reply when they finish, so the loop
+/// keeps reading while a slow tool call is in flight and a
+/// `notifications/cancelled` for it can arrive. `initialize` is answered
+/// before reading on, since the requests after it depend on its outcome.
+/// Calls still running at disconnect are allowed to finish.
+async fn serve(transport: Arc<CustomStdioTransport>, state: Arc<RwLock<ServerState>>) {
     // Start message loop with proper error handling
     log::info!("Starting message loop");
+    let mut in_flight = tokio::task::JoinSet::new();
     loop {
-        match transport.receive() {
+        // Reap finished requests so the set only holds running ones
+        while in_flight.try_join_next().is_some() {}
+
+        // Reading stdin blocks, so keep it off the runtime's worker threads
+        let receiver = transport.clone();
+        let received = match tokio::task::spawn_blocking(move || receiver.receive()).await {
+            Ok(received) => received,
+            Err(e) => {
+                log::error!("Transport reader failed: {e}");
+                break;
+            }
+        };
+        match received {
             Ok(message) => {
                 // Handle message without logging sensitive content
                 log::debug!("Received message of type: {}", get_message_type(&message));
 
-                match handle_message(message, state.clone()).await {
-                    Ok(response) => {
-                        log::debug!("Sending response");
-                        if let Err(e) = transport.send(&response) {
-                            log::error!("Failed to send response: {e}");
-                            break;
-                        }
-                    }
-                    Err(e) => {
-                        log::error!("Error handling message: {e}");
-                        // Continue processing other messages
-                    }
+                let initialize = is_initialize(&message);
+                let reply = reply_to(message, state.clone(), transport.clone());
+                if initialize {
+                    reply.await;
+                } else {
+                    in_flight.spawn(reply);
                 }
             }
             Err(e) => {
@@ -304,16 +485,142 @@ pub async fn start_server() -> Result<()> {
             }
         }
     }
+    while in_flight.join_next().await.is_some() {}
 
     log::info!("Closing transport");
     if let Err(e) = transport.close() {
         log::warn!("Error closing transport: {e}");
     }
+}
 
-    log::info!("Solana MCP server stopped");
+fn is_initialize(message: &JsonRpcMessage) -> bool {
+    matches!(message, JsonRpcMessage::Request(request) if request.method == "initialize")
+}
+
+/// Handles one message and sends its response on `transport`
+async fn reply_to(
+    message: JsonRpcMessage,
+    state: Arc<RwLock<ServerState>>,
+    transport: Arc<CustomStdioTransport>,
+) {
+    match handle_message(message, state).await {
+        Ok(response) => {
+            log::debug!("Sending response");
+            if let Err(e) = transport.send(&response) {
+                log::error!("Failed to send response: {e}");
+            }
+        }
+        Err(e) => {
+            log::error!("Error handling message: {e}");
+            // Continue processing other messages
+        }
+    }
+}
+
+/// How long the self-test waits for the server to answer by default
+pub const SELF_TEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Collects what the self-test's in-process transport writes
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Checks that the server answers `initialize` and `tools/list`
+///
+/// The server is run as in stdio mode, but over an in-process transport that
+/// carries the two requests, so no client or network is needed. Background
+/// tasks (metrics server, health probes, config watcher) are not started.
+///
+/// # Arguments
+/// * `timeout` - How long both requests may take together
+///
+/// # Returns
+/// * `Result<()>` - Ok if both requests succeeded in time, Err describing the
+///   first failure otherwise
+pub async fn run_self_test(timeout: Duration) -> Result<()> {
+    crate::metrics::init_prometheus_metrics()
+        .map_err(|e| anyhow::anyhow!("Failed to initialize Prometheus metrics: {}", e))?;
+    let config = Config::load()?;
+
+    let requests = [
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": config.protocol_version,
+                "capabilities": {},
+                "clientInfo": { "name": "self-test", "version": env!("CARGO_PKG_VERSION") }
+            }
+        }),
+        serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+    ];
+    let input = requests.iter().map(|request| format!("{request}\n")).collect::<String>();
+    let output = SharedBuffer::default();
+    let transport = Arc::new(CustomStdioTransport::from_io(
+        std::io::Cursor::new(input.into_bytes()),
+        output.clone(),
+        StdioFraming::Line,
+    ));
+
+    let mut server_state = ServerState::new(config.clone());
+    server_state.local_file_access = true;
+    let state = Arc::new(RwLock::new(server_state));
+    announce(&transport, &config.protocol_version)?;
+    tokio::time::timeout(timeout, serve(transport.clone(), state))
+        .await
+        .map_err(|_| anyhow::anyhow!("Self-test timed out after {}s", timeout.as_secs_f64()))?;
+
+    let output = output.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    let responses = String::from_utf8(output)?
+        .lines()
+        .map(serde_json::from_str::<Value>)
+        .collect::<Result<Vec<_>, _>>()?;
+    for (id, method) in [(1, "initialize"), (2, "tools/list")] {
+        let response = responses
+            .iter()
+            .find(|response| response["id"] == id)
+            .ok_or_else(|| anyhow::anyhow!("Self-test: no response to {method}"))?;
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("Self-test: {method} failed: {error}");
+        }
+    }
+    let tools = responses
+        .iter()
+        .find(|response| response["id"] == 2)
+        .and_then(|response| response["result"]["tools"].as_array())
+        .map_or(0, Vec::len);
+    if tools == 0 {
+        anyhow::bail!("Self-test: tools/list returned no tools");
+    }
+
+    log::info!("Self-test passed: initialize succeeded and tools/list returned {tools} tools");
     Ok(())
 }
 
+/// Writes notifications raised while a request is being handled, such as
+/// tool progress, to the transport as they arrive
+fn spawn_notification_forwarder(transport: Arc<CustomStdioTransport>) -> ProgressSink {
+    let (sink, mut notifications) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Some(notification) = notifications.recv().await {
+            if let Err(e) = transport.send(&notification) {
+                log::error!("Failed to send notification: {e}");
+            }
+        }
+    });
+    sink
+}
+
 /// Handles a received message and returns appropriate response
 async fn handle_message(
     message: JsonRpcMessage,
@@ -331,3 +638,83 @@ fn get_message_type(message: &JsonRpcMessage) -> &'static str {
         JsonRpcMessage::Notification(_) => "notification",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+
+    /// Blocking reader over one end of a tokio duplex
+    ///
+    /// The transport reads inside `spawn_blocking`, so blocking on the runtime
+    /// handle here does not stall the server.
+    struct DuplexReader(DuplexStream, tokio::runtime::Handle);
+
+    impl std::io::Read for DuplexReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let Self(stream, handle) = self;
+            handle.block_on(stream.read(buf))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stdio_cancellation_reaches_pending_call() {
+        let mut config: Config = serde_json::from_value(serde_json::json!({
+            "rpc_url": "http://127.0.0.1:1",
+            "commitment": "confirmed",
+            "protocol_version": crate::protocol::LATEST_PROTOCOL_VERSION,
+        }))
+        .unwrap();
+        config.max_concurrent_rpc = 1;
+        config.timeouts.rpc_permit_wait_ms = 60_000;
+        let mut server_state = ServerState::new(config);
+        server_state.initialized = true;
+        let state = Arc::new(RwLock::new(server_state));
+
+        // Hold the only permit so the tool call stays pending until cancelled
+        let semaphore = state.read().await.rpc_semaphore.clone();
+        let _held = semaphore.acquire_owned().await.unwrap();
+
+        let (mut writer, reader) = tokio::io::duplex(4096);
+        let output = SharedBuffer::default();
+        let transport = Arc::new(CustomStdioTransport::from_io(
+            std::io::BufReader::new(DuplexReader(reader, tokio::runtime::Handle::current())),
+            output.clone(),
+            StdioFraming::Line,
+        ));
+        let server = tokio::spawn(serve(transport, state.clone()));
+
+        let call = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": { "name": "getHealth", "arguments": {} }
+        });
+        writer.write_all(format!("{call}\n").as_bytes()).await.unwrap();
+        let in_flight = state.read().await.in_flight_calls.clone();
+        while in_flight.is_empty() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        let cancel = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/cancelled",
+            "params": { "requestId": 7 }
+        });
+        writer.write_all(format!("{cancel}\n").as_bytes()).await.unwrap();
+        drop(writer);
+
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("serve should finish once the call is cancelled")
+            .unwrap();
+        let output = output.0.lock().unwrap().clone();
+        let response = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .find(|response| response["id"] == 7)
+            .expect("the cancelled call should be answered");
+        assert_eq!(response["error"]["code"], -32800);
+    }
+}
@@ -1,22 +1,38 @@
 use crate::protocol::{
-    Implementation, InitializeRequest, InitializeResponse, Resource, ResourcesListResponse,
-    ServerCapabilities, ToolDefinition, ToolsListResponse, LATEST_PROTOCOL_VERSION,
+    Implementation, InitializeRequest, InitializeResponse, ReadResourceRequest, Resource, ResourceContents,
+    ResourcesListResponse, ResourcesReadResponse, ServerCapabilities, ToolDefinition, ToolsListResponse,
+    LATEST_PROTOCOL_VERSION,
 };
-use crate::server::ServerState;
+use crate::error::{McpError, McpResult};
+use crate::config::Config;
+use crate::server::{InFlightCalls, ServerState};
 use crate::transport::{JsonRpcError, JsonRpcMessage, JsonRpcResponse, JsonRpcVersion};
 use crate::validation::{
-    sanitize_for_logging, validate_network_id, validate_network_name, validate_rpc_url,
+    account_encoding_from_name, parse_account_encoding, parse_commitment, parse_program_account_filters,
+    sanitize_for_logging,
+    validate_network_id, validate_network_name, validate_array_len,
+    validate_pubkey, validate_rpc_url_with_allowlist, validate_signature,
+    validate_write_commitment, MAX_ACCOUNT_DATA_SIZE, MAX_BALANCES_TOTAL, MAX_MULTIPLE_ACCOUNTS,
+    MAX_FEE_STATS_ADDRESSES, MAX_SIGNATURE_STATUSES_TOTAL,
+    WRITE_COMMITMENT_TOOLS,
 };
 use crate::SvmNetwork;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use base64::Engine;
 use reqwest;
 use serde::Deserialize;
 use serde_json::Value;
-use solana_sdk::commitment_config::CommitmentConfig;
+use solana_account_decoder::UiAccountEncoding;
+use solana_sdk::{
+    commitment_config::{CommitmentConfig, CommitmentLevel},
+    pubkey::Pubkey,
+};
+use solana_transaction_status::{TransactionDetails, UiTransactionEncoding};
 
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, RwLock};
+use tokio::task::AbortHandle;
 use url::Url;
 
 /// Creates a success response for JSON-RPC requests
@@ -72,13 +88,41 @@ pub fn create_error_response(
     })
 }
 
+/// Creates an error response carrying structured `data`
+///
+/// # Arguments
+/// * `code` - Error code following JSON-RPC 2.0 specification
+/// * `message` - Human-readable error message
+/// * `id` - The request ID to match the response to
+/// * `data` - Machine-readable details about the failure
+///
+/// # Returns
+/// * `JsonRpcMessage` - Formatted error response
+pub fn create_error_response_with_data(
+    code: i32,
+    message: String,
+    id: Value,
+    data: Value,
+) -> JsonRpcMessage {
+    log::error!("Creating error response: {message} (code: {code})");
+    JsonRpcMessage::Response(JsonRpcResponse {
+        jsonrpc: JsonRpcVersion::V2,
+        id,
+        result: None,
+        error: Some(JsonRpcError {
+            code,
+            message,
+            data: Some(data),
+        }),
+    })
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct CancelledParams {
-    #[allow(dead_code)]
-    request_id: i64,
-    #[allow(dead_code)]
-    reason: String,
+    request_id: Value,
+    #[serde(default)]
+    reason: Option<String>,
 }
 
 pub async fn handle_initialize(
@@ -138,7 +182,7 @@ pub async fn handle_initialize(
                     "docs": {
                         "name": "Documentation",
                         "description": "Solana API documentation",
-                        "uri": "https://docs.solana.com/developing/clients/jsonrpc-api",
+                        "uri": SOLANA_RPC_DOCS_URL,
                         "mimeType": "text/html"
                     }
                 })),
@@ -169,7 +213,9 @@ pub async fn handle_cancelled(
 ) -> Result<JsonRpcMessage> {
     log::info!("Handling cancelled request");
     if let Some(params) = params {
-        let _cancel_params: CancelledParams = serde_json::from_value(params)?;
+        let cancel_params: CancelledParams = serde_json::from_value(params)?;
+        cancel_in_flight_call(&cancel_params, state);
+        // Success is returned whether or not a matching call was still running
         Ok(create_success_response(
             Value::Null,
             id.unwrap_or(Value::Null),
@@ -185,9 +231,243 @@ pub async fn handle_cancelled(
     }
 }
 
-pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Result<JsonRpcMessage> {
+/// Remote Solana JSON-RPC documentation advertised as the "docs" resource
+const SOLANA_RPC_DOCS_URL: &str = "https://docs.solana.com/developing/clients/jsonrpc-api";
+
+/// URI prefix of the markdown documentation bundled by `crate::docs`
+const DOCS_RESOURCE_SCHEME: &str = "docs://";
+
+/// URI prefix of the per-method RPC documentation from `crate::docs::rpc`
+const RPC_DOCS_RESOURCE_PREFIX: &str = "mcp://docs/rpc/";
+
+/// How long resources/read waits for the remote Solana documentation
+const REMOTE_DOCS_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Handles resources/read for the documentation resources
+///
+/// `docs://<name>` returns the bundled markdown from `crate::docs::get_all_docs`
+/// and `mcp://docs/rpc/<method>` a method's rendered `RpcMethodDoc`; the
+/// advertised Solana documentation URL is fetched on demand. Any other URI
+/// fails with -32002 (resource not found).
+pub async fn handle_resources_read(params: Option<Value>, id: Value) -> Result<JsonRpcMessage> {
+    log::info!("Handling resources/read request");
+    let Some(request) = params.and_then(|params| serde_json::from_value::<ReadResourceRequest>(params).ok()) else {
+        return Ok(create_error_response(-32602, "Invalid params: missing uri".to_string(), id, None));
+    };
+
+    let (text, mime_type) = if let Some(name) = request.uri.strip_prefix(DOCS_RESOURCE_SCHEME) {
+        match crate::docs::get_all_docs().remove(name) {
+            Some(markdown) => (markdown, "text/markdown"),
+            None => return Ok(resource_not_found(&request.uri, id)),
+        }
+    } else if let Some(method) = request.uri.strip_prefix(RPC_DOCS_RESOURCE_PREFIX) {
+        match crate::docs::rpc::get_rpc_method_docs().get(method) {
+            Some(doc) => (doc.to_markdown(method), "text/markdown"),
+            None => return Ok(resource_not_found(&request.uri, id)),
+        }
+    } else if request.uri == SOLANA_RPC_DOCS_URL {
+        match fetch_remote_docs().await {
+            Ok(html) => (html, "text/html"),
+            Err(e) => {
+                return Ok(create_error_response(
+                    -32603,
+                    format!("Failed to fetch {}: {e:#}", request.uri),
+                    id,
+                    None,
+                ))
+            }
+        }
+    } else {
+        return Ok(resource_not_found(&request.uri, id));
+    };
+
+    let response = ResourcesReadResponse {
+        contents: vec![ResourceContents {
+            uri: Url::parse(&request.uri)?,
+            mime_type: Some(mime_type.to_string()),
+            text: Some(text),
+        }],
+        meta: None,
+    };
+    Ok(create_success_response(serde_json::to_value(response)?, id))
+}
+
+fn resource_not_found(uri: &str, id: Value) -> JsonRpcMessage {
+    create_error_response_with_data(
+        -32002,
+        format!("Resource not found: {uri}"),
+        id,
+        serde_json::json!({ "uri": uri }),
+    )
+}
+
+/// Fetches the remote Solana JSON-RPC documentation page
+async fn fetch_remote_docs() -> Result<String> {
+    let client = reqwest::Client::builder()
+        .timeout(REMOTE_DOCS_FETCH_TIMEOUT)
+        .build()?;
+    let response = client.get(SOLANA_RPC_DOCS_URL).send().await?.error_for_status()?;
+    Ok(response.text().await?)
+}
+
+/// Aborts the in-flight tool call named by a cancellation, if still running
+fn cancel_in_flight_call(params: &CancelledParams, state: &ServerState) {
+    let reason = params.reason.as_deref().unwrap_or("no reason given");
+    if state.cancel_tool_call(&crate::server::current_call_session(), &params.request_id) {
+        log::info!("Cancelled in-flight request {} ({reason})", params.request_id);
+    } else {
+        log::debug!("No in-flight request {} to cancel ({reason})", params.request_id);
+    }
+}
+
+/// Removes a tool call's own entry from `in_flight_calls` when dropped,
+/// aborting the call if its caller stopped waiting for it
+struct InFlightCall {
+    calls: InFlightCalls,
+    key: String,
+    handle: AbortHandle,
+}
+
+impl Drop for InFlightCall {
+    fn drop(&mut self) {
+        // Aborting a finished task is a no-op
+        self.handle.abort();
+        // A later call may have reused the id once this one was cancelled
+        self.calls.remove_if(&self.key, |_, handle| handle.id() == self.handle.id());
+    }
+}
+
+/// Runs a tools/call as an abortable task tracked by session and request id
+///
+/// The task is registered in `ServerState::in_flight_calls` for its lifetime
+/// so a later `notifications/cancelled` from the same session can abort it
+/// mid-RPC. A request id already in flight in the session is rejected.
+async fn run_cancellable_tools_call(
+    params: Option<Value>,
+    id: Value,
+    state: Arc<RwLock<ServerState>>,
+) -> Result<JsonRpcMessage> {
+    let calls = state.read().await.in_flight_calls.clone();
+    let key = ServerState::request_key(&crate::server::current_call_session(), &id);
+
+    let task = match calls.entry(key.clone()) {
+        dashmap::mapref::entry::Entry::Occupied(_) => {
+            return Ok(create_error_response(
+                -32600,
+                format!("Request id {id} is already in flight"),
+                id,
+                None,
+            ));
+        }
+        dashmap::mapref::entry::Entry::Vacant(slot) => {
+            // Task-locals do not cross the spawn, so carry the audit actor over explicitly
+            let actor = crate::logging::current_audit_actor();
+            let task = tokio::spawn(crate::logging::with_audit_actor(
+                actor,
+                handle_tools_call(params, Some(id.clone()), state),
+            ));
+            slot.insert(task.abort_handle());
+            task
+        }
+    };
+    let _in_flight = InFlightCall {
+        calls,
+        key: key.clone(),
+        handle: task.abort_handle(),
+    };
+
+    match task.await {
+        Ok(response) => response,
+        Err(e) if e.is_cancelled() => {
+            log::info!("Tool call {key} was cancelled");
+            Ok(create_error_response(
+                -32800,
+                "Request cancelled".to_string(),
+                id,
+                None,
+            ))
+        }
+        Err(e) => Err(anyhow::anyhow!("Tool call task failed: {}", e)),
+    }
+}
+
+/// Tools that manage SVM networks and are gated by `allow_network_mutation`
+pub const NETWORK_MUTATION_TOOLS: &[&str] = &[
+    "listSvmNetworks",
+    "enableSvmNetwork",
+    "disableSvmNetwork",
+    "setNetworkRpcUrl",
+];
+
+pub async fn handle_tools_list(id: Option<Value>, state: &ServerState) -> Result<JsonRpcMessage> {
     log::info!("Handling tools/list request");
-    let tools = vec![
+    let mut tools = TOOL_DEFINITIONS.clone();
+
+    // Advertise only what tools/call would run
+    tools.retain(|tool| check_tool_policy(&tool.name, &state.config).is_none());
+
+    let tools_len = tools.len();
+    log::debug!("Returning {tools_len} tools");
+
+    let response = ToolsListResponse {
+        tools,
+        next_cursor: None,
+        meta: None,
+    };
+
+    Ok(create_success_response(
+        serde_json::to_value(response).unwrap(),
+        id.unwrap_or(Value::Null),
+    ))
+}
+
+/// Whether `name` is a tool this server defines
+pub fn is_known_tool(name: &str) -> bool {
+    TOOL_DEFINITIONS.iter().any(|tool| tool.name == name)
+}
+
+/// Tool definitions with descriptions enriched from `crate::docs::rpc`, built once
+static TOOL_DEFINITIONS: once_cell::sync::Lazy<Vec<ToolDefinition>> = once_cell::sync::Lazy::new(|| {
+    let method_docs = crate::docs::rpc::get_rpc_method_docs();
+    let mut tools = tool_definitions();
+    for tool in &mut tools {
+        if let Some(doc) = method_docs.get(&tool.name) {
+            tool.description = Some(enrich_tool_description(tool.description.as_deref().unwrap_or_default(), doc));
+        }
+    }
+    tools
+});
+
+/// Appends a method's documented description, parameters and first example
+///
+/// A documented description that merely extends the one-liner replaces it.
+/// The example is the raw Solana RPC request, so it is labelled as such; the
+/// tool itself takes the named arguments of its input schema.
+fn enrich_tool_description(summary: &str, doc: &crate::docs::RpcMethodDoc) -> String {
+    let mut description = if doc.description.starts_with(summary.trim_end_matches('.')) {
+        doc.description.clone()
+    } else {
+        format!("{summary}\n\n{}", doc.description)
+    };
+    if !doc.request_params.is_empty() {
+        description.push_str("\n\nParameters:");
+        for param in &doc.request_params {
+            description.push('\n');
+            description.push_str(&param.to_markdown());
+        }
+    }
+    if let Some(example) = doc.examples.first() {
+        description.push_str(&format!(
+            "\n\nUnderlying Solana RPC request ({}):\n```json\n{}\n```",
+            example.description, example.request
+        ));
+    }
+    description
+}
+
+/// Definitions and input schemas of every tool the server implements
+fn tool_definitions() -> Vec<ToolDefinition> {
+    vec![
         ToolDefinition {
             name: "getAccountInfo".to_string(),
             description: Some("Returns all information associated with the account".to_string()),
@@ -207,6 +487,14 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                         "type": "string",
                         "description": "Encoding format",
                         "enum": ["base58", "base64", "jsonParsed"]
+                    },
+                    "encodings": {
+                        "type": "array",
+                        "description": "Return the account data in each of these encodings from a single fetch",
+                        "items": {
+                            "type": "string",
+                            "enum": ["base58", "base64", "base64+zstd", "jsonParsed"]
+                        }
                     }
                 },
                 "required": ["pubkey"]
@@ -231,9 +519,35 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                 "required": ["pubkey"]
             }),
         },
+        ToolDefinition {
+            name: "getBalances".to_string(),
+            description: Some("Returns the lamport balances of up to 1000 accounts in input order, fetched with getMultipleAccounts in chunks of 100 instead of one getBalance per account".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "pubkeys": {
+                        "type": "array",
+                        "items": {
+                            "type": "string"
+                        },
+                        "description": "Array of account public keys (base58 encoded)"
+                    },
+                    "nullIfMissing": {
+                        "type": "boolean",
+                        "description": "Report accounts that do not exist as null instead of 0 (default: false)"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
+                    }
+                },
+                "required": ["pubkeys"]
+            }),
+        },
         ToolDefinition {
             name: "getProgramAccounts".to_string(),
-            description: Some("Returns all accounts owned by the program".to_string()),
+            description: Some("Returns a page of accounts owned by the program, sorted by pubkey, with a nextCursor for the following page".to_string()),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -254,6 +568,23 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                                 "enum": ["processed", "confirmed", "finalized"]
                             }
                         }
+                    },
+                    "filters": {
+                        "type": "array",
+                        "description": "Optional memcmp/dataSize filters to apply",
+                        "items": {
+                            "type": "object"
+                        }
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of accounts per page (default 1000, max 10000)",
+                        "minimum": 1,
+                        "maximum": 10000
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque cursor from a previous page's nextCursor (stable only within a slot)"
                     }
                 },
                 "required": ["programId"]
@@ -272,6 +603,21 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     "commitment": {
                         "type": "string",
                         "enum": ["processed", "confirmed", "finalized"]
+                    },
+                    "encoding": {
+                        "type": "string",
+                        "enum": ["json", "jsonParsed", "base58", "base64"],
+                        "description": "Transaction encoding (default: json)"
+                    },
+                    "parseInstructions": {
+                        "type": "boolean",
+                        "description": "Decode System, SPL Token, Associated Token and Memo instructions into {program, type, info}; other programs are returned raw"
+                    },
+                    "maxSupportedTransactionVersion": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": 255,
+                        "description": "Maximum transaction version to return (default: 0)"
                     }
                 },
                 "required": ["signature"]
@@ -285,6 +631,28 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                 "properties": {}
             }),
         },
+        ToolDefinition {
+            name: "getClusterStatus".to_string(),
+            description: Some("Returns slot, block height, epoch info and node health in one call, fetched concurrently and cached for cluster_status_cache_ttl_ms. Fields that fail to load are returned as {error} while the rest are still populated".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        ToolDefinition {
+            name: "getHealthDetailed".to_string(),
+            description: Some("Returns node health together with the node's processed slot, the highest slot known to the cluster and the lag between them. healthy is false when the node reports unhealthy or lags more than maxSlotLag slots".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "maxSlotLag": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "Largest slot lag still reported as healthy (default: 150)"
+                    }
+                }
+            }),
+        },
         ToolDefinition {
             name: "getVersion".to_string(),
             description: Some("Returns the current Solana version".to_string()),
@@ -331,6 +699,11 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                         "type": "string",
                         "description": "Filter by account type",
                         "enum": ["circulating", "nonCirculating"]
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
                     }
                 }
             }),
@@ -343,7 +716,14 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                 "properties": {
                     "dataSize": {
                         "type": "integer",
-                        "description": "Size of account data in bytes"
+                        "description": "Size of account data in bytes",
+                        "minimum": 0,
+                        "maximum": MAX_ACCOUNT_DATA_SIZE
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
                     }
                 },
                 "required": ["dataSize"]
@@ -424,6 +804,10 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                         "type": "integer",
                         "description": "End slot (optional)"
                     },
+                    "scan": {
+                        "type": "boolean",
+                        "description": "Page through a range wider than max_block_range_slots in bounded requests (default: false)"
+                    },
                     "commitment": {
                         "type": "string",
                         "enum": ["processed", "confirmed", "finalized"]
@@ -491,6 +875,7 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                 "properties": {
                     "commitment": {
                         "type": "string",
+                        "description": "Commitment level",
                         "enum": ["processed", "confirmed", "finalized"]
                     }
                 }
@@ -518,6 +903,11 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     "until": {
                         "type": "string",
                         "description": "Search until this signature"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["confirmed", "finalized"]
                     }
                 },
                 "required": ["address"]
@@ -546,11 +936,35 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     "maxRetries": {
                         "type": "integer",
                         "description": "Maximum retries"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Preflight commitment level",
+                        "enum": ["confirmed", "finalized"]
                     }
                 },
                 "required": ["transaction"]
             }),
         },
+        ToolDefinition {
+            name: "confirmTransaction".to_string(),
+            description: Some("Checks whether a transaction has reached the requested commitment. Only 'confirmed' or 'finalized' are accepted".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "signature": {
+                        "type": "string",
+                        "description": "Transaction signature (base58 encoded)"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "enum": ["confirmed", "finalized"],
+                        "description": "Commitment level to confirm against (default: confirmed)"
+                    }
+                },
+                "required": ["signature"]
+            }),
+        },
         ToolDefinition {
             name: "simulateTransaction".to_string(),
             description: Some("Simulate sending a transaction".to_string()),
@@ -571,6 +985,40 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                         "type": "boolean",
                         "description": "Verify signatures"
                     },
+                    "replaceRecentBlockhash": {
+                        "type": "boolean",
+                        "description": "Replace the recent blockhash with the latest one (conflicts with sigVerify)"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "enum": ["processed", "confirmed", "finalized"]
+                    }
+                },
+                "required": ["transaction"]
+            }),
+        },
+        ToolDefinition {
+            name: "estimateComputeUnits".to_string(),
+            description: Some("Simulates a transaction and returns the compute units it consumed plus a recommended compute unit limit with a safety margin. Signatures are not verified and the blockhash is replaced. When the simulation fails, returns its err and logs instead".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "transaction": {
+                        "type": "string",
+                        "description": "Transaction data"
+                    },
+                    "encoding": {
+                        "type": "string",
+                        "description": "Encoding of transaction data",
+                        "enum": ["base58", "base64"],
+                        "default": "base64"
+                    },
+                    "marginPercent": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": 1000,
+                        "description": "Headroom added to the consumed units, in percent (default: 10)"
+                    },
                     "commitment": {
                         "type": "string",
                         "enum": ["processed", "confirmed", "finalized"]
@@ -596,7 +1044,7 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     },
                     "programId": {
                         "type": "string",
-                        "description": "Token program ID (base58 encoded)"
+                        "description": "Token program ID: Token (default) or Token-2022 (base58 encoded)"
                     },
                     "commitment": {
                         "type": "string",
@@ -620,6 +1068,10 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                         "type": "string",
                         "description": "Token mint (base58 encoded)"
                     },
+                    "programId": {
+                        "type": "string",
+                        "description": "Token program ID: Token (default) or Token-2022 (base58 encoded)"
+                    },
                     "commitment": {
                         "type": "string",
                         "enum": ["processed", "confirmed", "finalized"]
@@ -638,6 +1090,10 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                         "type": "string",
                         "description": "Token account (base58 encoded)"
                     },
+                    "programId": {
+                        "type": "string",
+                        "description": "Token program ID: Token (default) or Token-2022 (base58 encoded)"
+                    },
                     "commitment": {
                         "type": "string",
                         "enum": ["processed", "confirmed", "finalized"]
@@ -647,19 +1103,105 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
             }),
         },
         ToolDefinition {
-            name: "getAccountOwner".to_string(),
-            description: Some("Returns the owner of an account".to_string()),
+            name: "getTokenUiBalance".to_string(),
+            description: Some("Returns a token account balance with mint decimals and human-readable uiAmount".to_string()),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
-                    "pubkey": {
-                        "type": "string",
-                        "description": "Account public key (base58 encoded)"
-                    },
-                    "commitment": {
+                    "account": {
                         "type": "string",
-                        "description": "Commitment level",
-                        "enum": ["processed", "confirmed", "finalized"]
+                        "description": "Token account (base58 encoded)"
+                    }
+                },
+                "required": ["account"]
+            }),
+        },
+        ToolDefinition {
+            name: "getWalletTokenHoldings".to_string(),
+            description: Some("Returns a wallet's token holdings summed per mint, with decimals and human-readable uiAmount".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "owner": {
+                        "type": "string",
+                        "description": "Wallet public key (base58 encoded)"
+                    },
+                    "programId": {
+                        "type": "string",
+                        "description": "Token program ID to query: Token or Token-2022 (default: both)"
+                    },
+                    "includeZero": {
+                        "type": "boolean",
+                        "description": "Keep zero-balance token accounts (default: false)"
+                    }
+                },
+                "required": ["owner"]
+            }),
+        },
+        ToolDefinition {
+            name: "isProgram".to_string(),
+            description: Some("Returns whether an address is an executable program, its owner, and its ProgramData address for upgradeable programs".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "pubkey": {
+                        "type": "string",
+                        "description": "Account public key (base58 encoded)"
+                    }
+                },
+                "required": ["pubkey"]
+            }),
+        },
+        ToolDefinition {
+            name: "resolveDomain".to_string(),
+            description: Some("Resolves a .sol domain to the pubkey that owns it. Tools taking a pubkey also accept .sol names directly".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "domain": {
+                        "type": "string",
+                        "description": "Domain name, e.g. 'bonfida.sol' or 'dex.bonfida.sol'"
+                    }
+                },
+                "required": ["domain"]
+            }),
+        },
+        ToolDefinition {
+            name: "getAssociatedTokenAddress".to_string(),
+            description: Some("Derives the associated token account address for a wallet and mint (no RPC call)".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "owner": {
+                        "type": "string",
+                        "description": "Wallet public key (base58 encoded)"
+                    },
+                    "mint": {
+                        "type": "string",
+                        "description": "Token mint (base58 encoded)"
+                    },
+                    "tokenProgramId": {
+                        "type": "string",
+                        "description": "Token program ID: Token (default) or Token-2022 (base58 encoded)"
+                    }
+                },
+                "required": ["owner", "mint"]
+            }),
+        },
+        ToolDefinition {
+            name: "getAccountOwner".to_string(),
+            description: Some("Returns the owner of an account".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "pubkey": {
+                        "type": "string",
+                        "description": "Account public key (base58 encoded)"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
                     }
                 },
                 "required": ["pubkey"]
@@ -704,7 +1246,7 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     },
                     "limit": {
                         "type": "integer",
-                        "description": "Limit number of results"
+                        "description": "Number of slots to return leaders for (clamped to 5000)"
                     }
                 },
                 "required": ["startSlot", "limit"]
@@ -726,7 +1268,12 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     },
                     "lastSlot": {
                         "type": "integer",
-                        "description": "Last slot to query"
+                        "description": "Last slot to query (requires firstSlot; defaults to the latest slot)"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
                     }
                 }
             }),
@@ -748,6 +1295,14 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     "keepUnstakedDelinquents": {
                         "type": "boolean",
                         "description": "Keep unstaked delinquents"
+                    },
+                    "delinquentSlotDistance": {
+                        "type": "integer",
+                        "description": "Slots behind the tip before a validator counts as delinquent"
+                    },
+                    "minStake": {
+                        "type": "integer",
+                        "description": "Only return validators with at least this much activated stake, in lamports"
                     }
                 }
             }),
@@ -764,7 +1319,12 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     },
                     "identity": {
                         "type": "string",
-                        "description": "Validator identity (base58 encoded)"
+                        "description": "Only return this validator's slots (base58 encoded)"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
                     }
                 }
             }),
@@ -791,7 +1351,13 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
             description: Some("Returns current inflation governor".to_string()),
             input_schema: serde_json::json!({
                 "type": "object",
-                "properties": {}
+                "properties": {
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
+                    }
+                }
             }),
         },
         ToolDefinition {
@@ -817,7 +1383,12 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     },
                     "epoch": {
                         "type": "integer",
-                        "description": "Epoch number"
+                        "description": "Epoch number (defaults to the previous epoch)"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
                     }
                 },
                 "required": ["addresses"]
@@ -883,6 +1454,11 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                         "type": "string",
                         "enum": ["base58", "base64"],
                         "default": "base64"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
                     }
                 },
                 "required": ["message"]
@@ -962,6 +1538,7 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                 "properties": {
                     "commitment": {
                         "type": "string",
+                        "description": "Commitment level",
                         "enum": ["processed", "confirmed", "finalized"]
                     }
                 }
@@ -990,7 +1567,9 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     },
                     "maxSupportedTransactionVersion": {
                         "type": "integer",
-                        "description": "Maximum transaction version to return"
+                        "minimum": 0,
+                        "maximum": 255,
+                        "description": "Maximum transaction version to return (default: 0)"
                     }
                 },
                 "required": ["signature"]
@@ -1120,6 +1699,10 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     "endSlot": {
                         "type": "integer",
                         "description": "End slot (optional)"
+                    },
+                    "scan": {
+                        "type": "boolean",
+                        "description": "Page through a range wider than max_block_range_slots in bounded requests (default: false)"
                     }
                 },
                 "required": ["startSlot"]
@@ -1170,6 +1753,11 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     "pubkey": {
                         "type": "string",
                         "description": "Account public key (base58 encoded)"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
                     }
                 },
                 "required": ["pubkey"]
@@ -1184,6 +1772,11 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                     "pubkey": {
                         "type": "string",
                         "description": "Account public key (base58 encoded)"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
                     }
                 },
                 "required": ["pubkey"]
@@ -1201,6 +1794,11 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                             "type": "string"
                         },
                         "description": "Array of account public keys (base58 encoded)"
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
                     }
                 },
                 "required": ["pubkeys"]
@@ -1222,11 +1820,47 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                         "items": {
                             "type": "object"
                         }
+                    },
+                    "encoding": {
+                        "type": "string",
+                        "description": "Account data encoding (default: default_account_encoding, else base64)",
+                        "enum": ["base58", "base64", "base64+zstd", "jsonParsed"]
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
                     }
                 },
                 "required": ["program_id"]
             }),
         },
+        ToolDefinition {
+            name: "getProgramAccountsParsed".to_string(),
+            description: Some("Returns all accounts owned by the program decoded with jsonParsed; accounts the node cannot decode fall back to base64 with a warning".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "programId": {
+                        "type": "string",
+                        "description": "Program public key (base58 encoded)"
+                    },
+                    "filters": {
+                        "type": "array",
+                        "description": "Optional memcmp/dataSize filters to apply",
+                        "items": {
+                            "type": "object"
+                        }
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level",
+                        "enum": ["processed", "confirmed", "finalized"]
+                    }
+                },
+                "required": ["programId"]
+            }),
+        },
         ToolDefinition {
             name: "getRecentPerformanceSamples".to_string(),
             description: Some("Returns recent performance samples from the cluster".to_string()),
@@ -1256,6 +1890,103 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                 }
             }),
         },
+        ToolDefinition {
+            name: "decodeProgramError".to_string(),
+            description: Some("Decodes a custom program error code from a failed transaction into a human-readable message for System, SPL Token, Token-2022, Associated Token and Anchor programs".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "programId": {
+                        "type": "string",
+                        "description": "Program that returned the error (base58 encoded)"
+                    },
+                    "code": {
+                        "type": ["integer", "string"],
+                        "description": "Custom error code as a number or hex string (e.g. 1 or \"0x1\")"
+                    }
+                },
+                "required": ["programId", "code"]
+            }),
+        },
+        ToolDefinition {
+            name: "decodeAccountData".to_string(),
+            description: Some("Decodes raw base64 account data offline for known layouts: SPL Token and Token-2022 accounts and mints, stake, nonce, vote and other native program accounts".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "data": {
+                        "type": "string",
+                        "description": "Raw account data (base64 encoded)"
+                    },
+                    "ownerProgram": {
+                        "type": "string",
+                        "description": "Program that owns the account (base58 encoded)"
+                    },
+                    "pubkey": {
+                        "type": "string",
+                        "description": "Account address, needed only for sysvar and config accounts (optional)"
+                    },
+                    "decimals": {
+                        "type": "integer",
+                        "description": "Mint decimals, required for SPL token accounts (optional)"
+                    }
+                },
+                "required": ["data", "ownerProgram"]
+            }),
+        },
+        ToolDefinition {
+            name: "getHistoricalFeeStats".to_string(),
+            description: Some("Samples recently landed transactions for the given accounts and reports the distribution of priority fees they paid".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "addresses": {
+                        "type": "array",
+                        "items": {
+                            "type": "string"
+                        },
+                        "description": "Array of account addresses (base58 encoded, max: 128)"
+                    },
+                    "sampleSize": {
+                        "type": "integer",
+                        "description": "Number of transactions to sample (default: 25, max: 100)",
+                        "minimum": 1,
+                        "maximum": 100
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level (default: confirmed)",
+                        "enum": ["confirmed", "finalized"]
+                    }
+                },
+                "required": ["addresses"]
+            }),
+        },
+        ToolDefinition {
+            name: "getRecentActivity".to_string(),
+            description: Some("Summarizes an address's most recent transactions, oldest first: signature, slot, block time, fee, success and the programs invoked".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "address": {
+                        "type": "string",
+                        "description": "Account address (base58 encoded)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Number of recent transactions to summarize (default: 10, max: 50)",
+                        "minimum": 1,
+                        "maximum": 50
+                    },
+                    "commitment": {
+                        "type": "string",
+                        "description": "Commitment level (default: confirmed)",
+                        "enum": ["confirmed", "finalized"]
+                    }
+                },
+                "required": ["address"]
+            }),
+        },
         ToolDefinition {
             name: "getSignatureStatuses".to_string(),
             description: Some("Returns signature statuses for transaction signatures".to_string()),
@@ -1267,11 +1998,15 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                         "items": {
                             "type": "string"
                         },
-                        "description": "Array of transaction signatures (base58 encoded)"
+                        "description": "Array of transaction signatures (base58 encoded), at most 2560"
+                    },
+                    "searchTransactionHistory": {
+                        "type": "boolean",
+                        "description": "Search ledger history for signatures missing from the recent status cache; much slower (default: false)"
                     },
                     "search_transaction_history": {
                         "type": "boolean",
-                        "description": "Search transaction history (default: false)"
+                        "description": "Deprecated alias of searchTransactionHistory"
                     }
                 },
                 "required": ["signatures"]
@@ -1620,7 +2355,45 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
         // Network Management Methods
         ToolDefinition {
             name: "listSvmNetworks".to_string(),
-            description: Some("List all available SVM networks from awesome-svm repository".to_string()),
+            description: Some("List all available SVM networks from awesome-svm repository. The list is cached for svm_networks_cache_ttl_seconds (default 5 minutes). If a refresh fails or times out, the last successfully fetched list is returned as {networks, cached: true, error}".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "serveCached": {
+                        "type": "boolean",
+                        "description": "Serve the last successfully fetched list when the repository is unreachable (default: true)"
+                    }
+                }
+            }),
+        },
+        ToolDefinition {
+            name: "getEnabledNetworks".to_string(),
+            description: Some("List locally enabled SVM networks with their id, name, sanitized RPC URL and a freshly probed health status (reachable/unreachable with latency)".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        ToolDefinition {
+            name: "probeNetworkMethods".to_string(),
+            description: Some("Probe which upstream RPC methods a network implements and report which tools will work against it. Each method is checked with a request the node rejects while parsing, so no real work is done. Results are cached per network for network_methods_cache_ttl_seconds".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "networkId": {
+                        "type": "string",
+                        "description": "Enabled SVM network to probe (defaults to the primary RPC endpoint)"
+                    },
+                    "refresh": {
+                        "type": "boolean",
+                        "description": "Ignore any cached result and probe again (default: false)"
+                    }
+                }
+            }),
+        },
+        ToolDefinition {
+            name: "getServerConfig".to_string(),
+            description: Some("Return the server's effective configuration (protocol version, commitment, networks, timeouts, limits) with RPC URLs reduced to scheme and host and secrets redacted".to_string()),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {}
@@ -1744,22 +2517,82 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
             }),
         },
         ToolDefinition {
-            name: "validateSbpfBinary".to_string(),
-            description: Some("Validate an sBPF binary without execution".to_string()),
+            name: "runSbpfTest".to_string(),
+            description: Some("Execute an sBPF program under liteSVM with account and instruction fixtures, returning logs, compute units consumed, return data and success/failure. Runs are capped at 1.4M compute units and 10 seconds".to_string()),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
                     "programBinary": {
                         "type": "string",
                         "description": "Base64-encoded sBPF program binary (ELF format)"
-                    }
-                },
-                "required": ["programBinary"]
-            }),
-        },
-        ToolDefinition {
-            name: "deploySbpfProgramLocal".to_string(),
-            description: Some("Deploy an sBPF program to local VM (returns program ID)".to_string()),
+                    },
+                    "programPath": {
+                        "type": "string",
+                        "description": "Path to a local .so file instead of programBinary (stdio mode only)"
+                    },
+                    "accounts": {
+                        "type": "array",
+                        "description": "Account fixtures loaded into the VM before execution",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "pubkey": { "type": "string", "description": "Account public key (base58)" },
+                                "lamports": { "type": "number", "description": "Account lamports balance" },
+                                "data": { "type": "string", "description": "Account data (base64-encoded)" },
+                                "owner": { "type": "string", "description": "Account owner program ID (base58)" },
+                                "executable": { "type": "boolean", "description": "Whether the account is executable" },
+                                "isSigner": { "type": "boolean", "description": "Whether the account is a signer" },
+                                "isWritable": { "type": "boolean", "description": "Whether the account is writable" }
+                            },
+                            "required": ["pubkey"]
+                        }
+                    },
+                    "instructions": {
+                        "type": "array",
+                        "description": "Instructions to execute in order within one transaction",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "data": { "type": "string", "description": "Instruction data (base64-encoded)" },
+                                "accounts": {
+                                    "type": "array",
+                                    "description": "Fixture pubkeys passed to this instruction, in order (default: all fixtures)",
+                                    "items": { "type": "string" }
+                                }
+                            }
+                        }
+                    },
+                    "instructionData": {
+                        "type": "string",
+                        "description": "Single instruction's data (base64-encoded), used when instructions is omitted"
+                    },
+                    "computeUnitLimit": {
+                        "type": "integer",
+                        "description": "Compute-unit limit for the run (default and maximum: 1400000)"
+                    }
+                }
+            }),
+        },
+        ToolDefinition {
+            name: "validateSbpfBinary".to_string(),
+            description: Some("Structurally validate an sBPF binary without execution; returns whether it is a loadable Solana program, its entrypoint, section layout and any structural errors".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "programBinary": {
+                        "type": "string",
+                        "description": "Base64-encoded sBPF program binary (ELF format)"
+                    },
+                    "programPath": {
+                        "type": "string",
+                        "description": "Path to a local .so file instead of programBinary (stdio mode only)"
+                    }
+                }
+            }),
+        },
+        ToolDefinition {
+            name: "deploySbpfProgramLocal".to_string(),
+            description: Some("Deploy an sBPF program to local VM (returns program ID)".to_string()),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -1789,9 +2622,63 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                 "required": ["programBinary"]
             }),
         },
+        ToolDefinition {
+            name: "deploySbpfToDevnet".to_string(),
+            description: Some("Deploy an sBPF program to Solana devnet through the upgradeable loader. Spends devnet SOL from the supplied or configured payer and returns the program id, deployment signature and cost in lamports".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "programBinary": {
+                        "type": "string",
+                        "description": "Base64-encoded sBPF program binary (ELF format)"
+                    },
+                    "programPath": {
+                        "type": "string",
+                        "description": "Path to a local .so file instead of programBinary (stdio mode only)"
+                    },
+                    "payerKeypair": {
+                        "type": "string",
+                        "description": "Base64-encoded 64-byte payer keypair (defaults to sbpf_deploy.payer_keypair_path)"
+                    }
+                }
+            }),
+        },
+        ToolDefinition {
+            name: "scanSbpfBinary".to_string(),
+            description: Some("Run the sBPF security scanner over a program binary and return risk_score, per-severity counts and the vulnerability list".to_string()),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "programBinary": {
+                        "type": "string",
+                        "description": "Base64-encoded sBPF program binary (ELF format)"
+                    },
+                    "programPath": {
+                        "type": "string",
+                        "description": "Path to a local .so file instead of programBinary (stdio mode only)"
+                    },
+                    "minSeverity": {
+                        "type": "string",
+                        "enum": ["critical", "high", "medium", "low", "info"],
+                        "description": "Only list vulnerabilities at or above this severity; counts and risk_score still cover every finding"
+                    },
+                    "summaryOnly": {
+                        "type": "boolean",
+                        "description": "Return only the per-severity counts, risk_score and passed flag",
+                        "default": false
+                    },
+                    "divisionThreshold": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "Report excessive division when .text contains more DIV/MOD instructions than this",
+                        "default": crate::sbpf::DEFAULT_DIVISION_THRESHOLD
+                    }
+                }
+            }),
+        },
         ToolDefinition {
             name: "securityScanSbpfBinary".to_string(),
-            description: Some("Perform comprehensive security scan on sBPF program to detect vulnerabilities and risks".to_string()),
+            description: Some("Alias of scanSbpfBinary. Perform comprehensive security scan on sBPF program to detect vulnerabilities and risks".to_string()),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -1847,30 +2734,367 @@ pub async fn handle_tools_list(id: Option<Value>, _state: &ServerState) -> Resul
                 "properties": {}
             }),
         },
-    ];
+    ]
+}
 
-    let tools_len = tools.len();
-    log::debug!("Returning {tools_len} tools");
+/// Concurrency permit for an in-flight tool call, tracked by the in-flight gauge
+struct InFlightPermit {
+    _permit: OwnedSemaphorePermit,
+}
 
-    let response = ToolsListResponse {
-        tools,
-        next_cursor: None,
-        meta: None,
-    };
+impl InFlightPermit {
+    fn new(permit: OwnedSemaphorePermit) -> Self {
+        crate::metrics::PROMETHEUS_METRICS.inc_in_flight();
+        Self { _permit: permit }
+    }
+}
 
-    Ok(create_success_response(
-        serde_json::to_value(response).unwrap(),
-        id.unwrap_or(Value::Null),
-    ))
+impl Drop for InFlightPermit {
+    fn drop(&mut self) {
+        crate::metrics::PROMETHEUS_METRICS.dec_in_flight();
+    }
+}
+
+/// Suggested client back-off when the server rejects a call as busy
+const BUSY_RETRY_AFTER_MS: u64 = 1000;
+
+/// Slot in the global in-flight request ceiling, released on drop
+struct InFlightRequestSlot {
+    counter: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl InFlightRequestSlot {
+    /// Claims a slot without waiting, or returns `None` when at the ceiling
+    fn try_acquire(counter: Arc<std::sync::atomic::AtomicUsize>, limit: usize) -> Option<Self> {
+        counter
+            .fetch_update(
+                std::sync::atomic::Ordering::AcqRel,
+                std::sync::atomic::Ordering::Acquire,
+                |current| (current < limit).then_some(current + 1),
+            )
+            .ok()
+            .map(|_| Self { counter })
+    }
+}
+
+impl Drop for InFlightRequestSlot {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, std::sync::atomic::Ordering::AcqRel);
+    }
 }
 
 /// Handles the tools/call MCP method to execute a specific tool
+///
+/// Argument validation failures raised while dispatching are reported as
+/// `-32602` invalid params naming the offending field. Any other `McpError`
+/// carries `{errorType, requestId, method}` in the error's `data` so failures
+/// can be matched to server logs.
 pub async fn handle_tools_call(
     params: Option<Value>,
     id: Option<Value>,
     state: Arc<RwLock<ServerState>>,
+) -> Result<JsonRpcMessage> {
+    let tool_name = params
+        .as_ref()
+        .and_then(|p| p.get("name"))
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let compress = params.as_ref().and_then(|p| p["arguments"]["compress"].as_bool()) == Some(true);
+    let (max_response_bytes, compression_threshold_bytes, progress) = {
+        let state_guard = state.read().await;
+        (
+            state_guard.config.max_response_bytes,
+            state_guard.config.compression_threshold_bytes,
+            crate::progress::ProgressReporter::from_request(params.as_ref(), state_guard.progress_sink.as_ref()),
+        )
+    };
+
+    // Refuse disabled tools up front, so a gated call that would be refused is never charged
+    if !tool_name.is_empty() {
+        if let Some(message) = check_tool_policy(&tool_name, &state.read().await.config) {
+            log::warn!("Blocked tool {tool_name}: {message}");
+            return Ok(create_error_response(-32601, message, id.unwrap_or(Value::Null), None));
+        }
+    }
+
+    // Dry runs send nothing upstream and are never charged
+    #[cfg(feature = "x402")]
+    let dry_run = params.as_ref().and_then(|p| p["arguments"]["dryRun"].as_bool()) == Some(true);
+    #[cfg(feature = "x402")]
+    let arguments = params.as_ref().and_then(|p| p.get("arguments")).cloned().unwrap_or(Value::Null);
+    #[cfg(feature = "x402")]
+    let payment = match dry_run {
+        true => None,
+        false => match enforce_tool_payment(params.as_ref(), &arguments, &id, &state).await {
+            Ok(payment) => payment,
+            Err(response) => return Ok(response),
+        },
+    };
+
+    let response = match crate::progress::with_progress(progress, execute_tools_call(params, id.clone(), state.clone())).await {
+        Err(e) => match e.downcast_ref::<McpError>() {
+            Some(error @ McpError::Validation { message, .. }) => {
+                log::warn!("Rejected invalid tool params: {message}");
+                Ok(create_error_response_with_data(
+                    -32602,
+                    format!("Invalid params: {message}"),
+                    id.unwrap_or(Value::Null),
+                    error.to_error_data(),
+                ))
+            }
+            Some(error) => {
+                log::error!("Tool execution failed: {e:#}");
+                Ok(create_error_response_with_data(
+                    error.json_rpc_code(),
+                    format!("Tool execution failed: {e:#}"),
+                    id.unwrap_or(Value::Null),
+                    error.to_error_data(),
+                ))
+            }
+            None => Err(e),
+        },
+        response => response,
+    };
+    let response = match compress {
+        true => response.map(|r| compress_result(r, compression_threshold_bytes)),
+        false => response,
+    };
+    let response = limit_response_size(response?, &tool_name, max_response_bytes);
+
+    // Charge only for a result that is delivered, so after the size limit had its say
+    #[cfg(feature = "x402")]
+    if let Some((payload, requirements)) = payment.filter(|_| response.is_success()) {
+        return Ok(settle_tool_payment(response, &tool_name, &arguments, &payload, &requirements, &state).await);
+    }
+    Ok(response)
+}
+
+/// Explains why the server is configured not to run `tool_name`
+///
+/// Covers the tool allow and deny lists (see `Config::is_tool_enabled`),
+/// `allow_network_mutation` and `sbpf_deploy.enabled`. tools/list hides
+/// every tool this refuses, so clients never discover a tool that always fails.
+///
+/// # Returns
+/// * `Option<String>` - The -32601 error message, `None` when the tool may run
+fn check_tool_policy(tool_name: &str, config: &Config) -> Option<String> {
+    if !config.is_tool_enabled(tool_name) {
+        return Some(format!("Method disabled: {tool_name} is not enabled on this server"));
+    }
+
+    if !config.allow_network_mutation && NETWORK_MUTATION_TOOLS.contains(&tool_name) {
+        return Some(format!("Method disabled: {tool_name} requires allow_network_mutation"));
+    }
+
+    if tool_name == "deploySbpfToDevnet" && !config.sbpf_deploy.enabled {
+        return Some(format!("Method disabled: {tool_name} requires sbpf_deploy.enabled"));
+    }
+    None
+}
+
+/// Encoding tag of a result compressed for a `compress: true` call
+pub const COMPRESSED_RESULT_ENCODING: &str = "base64+zstd";
+
+/// Replaces a successful result larger than `threshold` bytes with its zstd compression
+///
+/// The result becomes `{encoding: "base64+zstd", data}`; a `_meta` it carried
+/// is kept uncompressed alongside so clients can still read timing and
+/// settlement details without decoding.
+fn compress_result(response: JsonRpcMessage, threshold: usize) -> JsonRpcMessage {
+    let JsonRpcMessage::Response(mut response) = response else {
+        return response;
+    };
+    let Some(result) = response.result.as_mut() else {
+        return JsonRpcMessage::Response(response);
+    };
+    match serde_json::to_vec(result) {
+        Ok(bytes) if bytes.len() > threshold => {}
+        _ => return JsonRpcMessage::Response(response),
+    }
+
+    let meta = result.as_object_mut().and_then(|map| map.remove("_meta"));
+    let compressed = serde_json::to_vec(result)
+        .map_err(std::io::Error::other)
+        .and_then(|bytes| zstd::encode_all(bytes.as_slice(), zstd::DEFAULT_COMPRESSION_LEVEL));
+    match compressed {
+        Ok(compressed) => {
+            let mut wrapped = serde_json::json!({
+                "encoding": COMPRESSED_RESULT_ENCODING,
+                "data": base64::engine::general_purpose::STANDARD.encode(compressed),
+            });
+            if let Some(meta) = meta {
+                wrapped["_meta"] = meta;
+            }
+            *result = wrapped;
+        }
+        Err(e) => {
+            // Sending the result uncompressed still answers the call
+            log::warn!("Failed to compress tool result, sending it uncompressed: {e}");
+            if let (Some(map), Some(meta)) = (result.as_object_mut(), meta) {
+                map.insert("_meta".to_string(), meta);
+            }
+        }
+    }
+    JsonRpcMessage::Response(response)
+}
+
+/// JSON-RPC error code for a tool result larger than `max_response_bytes`
+pub const RESPONSE_TOO_LARGE_CODE: i32 = -32011;
+
+/// Replaces a successful response whose serialized size exceeds `max_bytes`
+///
+/// Transmitting a result of tens of megabytes can exceed MCP client limits and
+/// stall the transport, so the client gets a -32011 error with the actual
+/// size and a hint on narrowing the query instead. `max_bytes` of 0 disables
+/// the check.
+fn limit_response_size(response: JsonRpcMessage, tool_name: &str, max_bytes: usize) -> JsonRpcMessage {
+    if max_bytes == 0 || !response.is_success() {
+        return response;
+    }
+    let size = match serde_json::to_vec(&response) {
+        Ok(bytes) if bytes.len() > max_bytes => bytes.len(),
+        _ => return response,
+    };
+    let JsonRpcMessage::Response(response) = response else {
+        return response;
+    };
+
+    log::warn!("Dropping {size}-byte result of {tool_name}: exceeds max_response_bytes ({max_bytes})");
+    create_error_response_with_data(
+        RESPONSE_TOO_LARGE_CODE,
+        format!("Response too large: {size} bytes exceeds the {max_bytes}-byte limit"),
+        response.id,
+        serde_json::json!({
+            "tool": tool_name,
+            "sizeBytes": size,
+            "maxBytes": max_bytes,
+            "hint": narrowing_hint(tool_name),
+        }),
+    )
+}
+
+/// Suggests how to shrink an oversized result from `tool_name`
+fn narrowing_hint(tool_name: &str) -> &'static str {
+    match tool_name {
+        "getProgramAccounts" => "Lower limit and page with cursor, or add dataSize/memcmp filters",
+        "getProgramAccountsParsed" => "Add dataSize/memcmp filters",
+        "getTokenAccountsByMint" => "Use getProgramAccounts with the token program, a memcmp filter on the mint and a lower limit",
+        "getBlock" => "Set transactionDetails to \"signatures\" or \"none\" and rewards to false",
+        "getMultipleAccounts" | "getMultipleAccountsAndContext" => "Request fewer pubkeys per call",
+        "getSignaturesForAddress" => "Lower limit and page with before",
+        "getRecentActivity" | "getBlocksWithLimit" => "Lower limit",
+        "getHistoricalFeeStats" => "Lower sampleSize or pass fewer addresses",
+        "getBlocks" => "Request a smaller slot range",
+        _ => "Narrow the query, for example with filters, a lower limit or a smaller range",
+    }
+}
+
+/// Verifies payment for tools gated by `x402.tool_payments`
+///
+/// `Err` carries the response to send instead of running the tool: Payment
+/// Required when no payload was attached, Invalid Payment when it was malformed,
+/// the facilitator rejected it or it already paid for a different call, and
+/// the recorded result when it already paid for this identical call.
+/// `Ok(None)` means the tool is free; `Ok(Some(_))` carries the verified
+/// payment, which is settled by [`settle_tool_payment`] only once the tool has
+/// succeeded.
+#[cfg(feature = "x402")]
+async fn enforce_tool_payment(
+    params: Option<&Value>,
+    arguments: &Value,
+    id: &Option<Value>,
+    state: &Arc<RwLock<ServerState>>,
+) -> std::result::Result<Option<(crate::x402::PaymentPayload, crate::x402::PaymentRequirements)>, JsonRpcMessage> {
+    use crate::x402::{
+        build_tool_payment_required, create_invalid_payment_response,
+        create_payment_required_response, extract_payment_payload, verify_payment,
+    };
+
+    let Some(tool_name) = params.and_then(|p| p.get("name")).and_then(|v| v.as_str()) else {
+        return Ok(None);
+    };
+    let (x402, settled_payments) = {
+        let state_guard = state.read().await;
+        if state_guard.config.x402.tool_payment(tool_name).is_none() {
+            return Ok(None);
+        }
+        (state_guard.config.x402.clone(), state_guard.settled_payments.clone())
+    };
+    let Some(requirements) = x402.tool_payment(tool_name) else {
+        return Ok(None);
+    };
+    let request_id = id.clone().unwrap_or(Value::Null);
+
+    match extract_payment_payload(params.and_then(|p| p.get("_meta"))) {
+        Err(e) => Err(create_invalid_payment_response(e.to_string(), request_id)),
+        Ok(None) => Err(create_payment_required_response(
+            build_tool_payment_required(tool_name, requirements),
+            request_id,
+        )),
+        Ok(Some(payload)) => match settled_payments.replay(&payload, tool_name, requirements, arguments) {
+            // A retry of a call that was charged and answered gets the same answer
+            Ok(Some(result)) => {
+                log::info!("Replaying settled result for {tool_name}");
+                Err(create_success_response(result, request_id))
+            }
+            Ok(None) => match verify_payment(&payload, requirements, &x402).await {
+                Ok(()) => Ok(Some((payload, requirements.clone()))),
+                Err(e) => Err(create_invalid_payment_response(e.to_string(), request_id)),
+            },
+            Err(e) => Err(create_invalid_payment_response(e.to_string(), request_id)),
+        },
+    }
+}
+
+/// Settles a verified payment and attaches the proof to the tool's result
+///
+/// Called only with a successful response. If settlement fails the result is
+/// withheld and an Invalid Payment error is returned instead, so a result is
+/// never served without payment. A settled result is recorded so an identical
+/// retry is answered without charging again.
+#[cfg(feature = "x402")]
+async fn settle_tool_payment(
+    response: JsonRpcMessage,
+    tool_name: &str,
+    arguments: &Value,
+    payload: &crate::x402::PaymentPayload,
+    requirements: &crate::x402::PaymentRequirements,
+    state: &Arc<RwLock<ServerState>>,
+) -> JsonRpcMessage {
+    let (x402, settled_payments) = {
+        let state_guard = state.read().await;
+        (state_guard.config.x402.clone(), state_guard.settled_payments.clone())
+    };
+    let JsonRpcMessage::Response(response) = response else {
+        return response;
+    };
+    match crate::x402::settle_payment(payload, requirements, &x402).await {
+        Ok(settlement) => {
+            log::info!("Payment for {tool_name} settled in transaction {}", settlement.transaction);
+            match crate::x402::attach_settlement_meta(JsonRpcMessage::Response(response), &settlement) {
+                JsonRpcMessage::Response(JsonRpcResponse { id, result: Some(result), .. }) => {
+                    match settled_payments.record(payload, tool_name, requirements, arguments, result) {
+                        Ok(result) => create_success_response(result, id),
+                        // A racing call with other arguments recorded this payment first
+                        Err(e) => crate::x402::create_invalid_payment_response(e.to_string(), id),
+                    }
+                }
+                other => other,
+            }
+        }
+        Err(e) => crate::x402::create_invalid_payment_response(e.to_string(), response.id),
+    }
+}
+
+/// Dispatches a tools/call request to the matching tool implementation
+async fn execute_tools_call(
+    params: Option<Value>,
+    id: Option<Value>,
+    state: Arc<RwLock<ServerState>>,
 ) -> Result<JsonRpcMessage> {
     log::info!("Handling tools/call request");
+    let started = std::time::Instant::now();
     
     let params = params.ok_or_else(|| anyhow::anyhow!("Missing params"))?;
     
@@ -1879,77 +3103,330 @@ pub async fn handle_tools_call(
         .and_then(|v| v.as_str())
         .ok_or_else(|| anyhow::anyhow!("Missing tool name parameter"))?;
         
-    let arguments = params.get("arguments").cloned().unwrap_or(serde_json::json!({}));
+    let mut arguments = params.get("arguments").cloned().unwrap_or(serde_json::json!({}));
     
     log::info!("Executing tool: {tool_name}");
-    
+
+    // Reject unrecognized commitment levels for every tool rather than defaulting
+    let commitment = parse_commitment(arguments.get("commitment"), "commitment")?;
+
+    let (semaphore, permit_wait, _in_flight_slot) = {
+        let state_guard = state.read().await;
+
+        // Reject immediately rather than queueing once the global ceiling is hit
+        let Some(slot) = InFlightRequestSlot::try_acquire(
+            state_guard.in_flight_requests.clone(),
+            state_guard.config.max_in_flight_requests,
+        ) else {
+            log::warn!("Global in-flight request limit reached, rejecting tool: {tool_name}");
+            return Ok(create_error_response_with_data(
+                -32005,
+                format!("Server busy: too many in-flight requests, retry after {BUSY_RETRY_AFTER_MS}ms"),
+                id.unwrap_or(Value::Null),
+                serde_json::json!({ "retryAfterMs": BUSY_RETRY_AFTER_MS }),
+            ));
+        };
+
+        (
+            state_guard.rpc_semaphore.clone(),
+            Duration::from_millis(state_guard.config.timeouts.rpc_permit_wait_ms),
+            slot,
+        )
+    };
+
+    // Writes must not be confirmed against reorg-prone processed data, and no
+    // tool may run at a commitment level its schema does not advertise
+    if let Some(commitment) = arguments.get("commitment").and_then(|v| v.as_str()) {
+        let rejected = if WRITE_COMMITMENT_TOOLS.contains(&tool_name) {
+            validate_write_commitment(commitment).err().map(|e| e.to_string())
+        } else {
+            unadvertised_commitment(tool_name, commitment)
+        };
+        if let Some(reason) = rejected {
+            return Ok(create_error_response(
+                -32602,
+                format!("Invalid params: {reason}"),
+                id.unwrap_or(Value::Null),
+                None,
+            ));
+        }
+    }
+
+    // Dry runs stop after validation, before taking an RPC permit or resolving .sol names
+    if arguments.get("dryRun").and_then(|v| v.as_bool()) == Some(true) {
+        let plan = {
+            let state_guard = state.read().await;
+            explain_tool_call(tool_name, &arguments, commitment, &state_guard)?
+        };
+        return Ok(match plan {
+            Some(plan) => create_success_response(plan, id.unwrap_or(Value::Null)),
+            None => create_error_response(
+                -32601,
+                format!("Tool not found: {tool_name}"),
+                id.unwrap_or(Value::Null),
+                None,
+            ),
+        });
+    }
+
+    // Pubkey arguments may name a .sol domain instead of a base58 key
+    resolve_domain_arguments(&mut arguments, &state).await?;
+
+    // Reject methods the configured node is too old or too new to serve
+    let gated_methods: Vec<&str> = planned_rpc_methods(tool_name)
+        .into_iter()
+        .filter(|method| crate::rpc::capabilities::is_version_gated(method))
+        .collect();
+    // deploySbpfToDevnet talks to its own endpoint rather than the configured node
+    if !gated_methods.is_empty() && tool_name != "deploySbpfToDevnet" {
+        // Probe without the state lock so a slow node cannot stall config reloads
+        let (node_capabilities, rpc_client) = {
+            let state_guard = state.read().await;
+            (state_guard.node_capabilities.clone(), state_guard.rpc_client.clone())
+        };
+        let capabilities = node_capabilities.get_or_probe(&rpc_client).await;
+        let unsupported = capabilities.as_ref().and_then(|capabilities| {
+            gated_methods
+                .iter()
+                .find_map(|method| capabilities.unsupported_reason(method).map(|reason| (*method, reason)))
+        });
+        if let (Some(capabilities), Some((method, reason))) = (capabilities.as_ref(), unsupported) {
+            log::warn!("Blocked tool {tool_name}: {reason}");
+            return Ok(create_error_response_with_data(
+                -32601,
+                format!("Method not supported by configured node: {reason}"),
+                id.unwrap_or(Value::Null),
+                serde_json::json!({
+                    "method": method,
+                    "nodeVersion": capabilities.version,
+                    "featureSet": capabilities.feature_set,
+                }),
+            ));
+        }
+    }
+
+    // Apply backpressure by bounding concurrent upstream RPC calls
+    let _in_flight = match tokio::time::timeout(permit_wait, semaphore.acquire_owned()).await {
+        Ok(Ok(permit)) => InFlightPermit::new(permit),
+        _ => {
+            log::warn!("RPC concurrency limit reached, rejecting tool: {tool_name}");
+            return Ok(create_error_response(
+                -32005,
+                "Server busy: too many concurrent RPC requests".to_string(),
+                id.unwrap_or(Value::Null),
+                None,
+            ));
+        }
+    };
+
+    // Capture the endpoint before the tool advances the round-robin rotation
+    let timing_endpoint = {
+        let state_guard = state.read().await;
+        state_guard
+            .config
+            .include_timing
+            .then(|| planned_endpoint(tool_name, &state_guard).map(|url| sanitize_for_logging(&url)))
+    };
+
     // Execute the specific tool based on the tool name
     let result = match tool_name {
         "getHealth" => {
             let state_guard = state.read().await;
             crate::rpc::system::get_health(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Health check failed: {}", e))
+                .context("Health check failed")
+        }
+        "getClusterStatus" => get_cluster_status(state.clone()).await,
+        "getHealthDetailed" => {
+            let max_slot_lag = optional_u64_argument(&arguments, "maxSlotLag")?.unwrap_or(DEFAULT_MAX_HEALTHY_SLOT_LAG);
+            get_health_detailed(state.clone(), max_slot_lag).await
         }
         "getVersion" => {
             let state_guard = state.read().await;
             crate::rpc::system::get_version(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Version check failed: {}", e))
+                .context("Version check failed")
         }
         "getBalance" => {
             let pubkey_str = arguments
                 .get("pubkey")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing pubkey parameter"))?;
-            let pubkey = Pubkey::try_from(pubkey_str)?;
+                .ok_or_else(|| McpError::validation("Missing pubkey parameter").with_parameter("pubkey"))?;
+            let pubkey = validate_pubkey(pubkey_str, "pubkey")?;
             
             let state_guard = state.read().await;
-            crate::rpc::accounts::get_balance(state_guard.get_next_rpc_client(), &pubkey).await
-                .map_err(|e| anyhow::anyhow!("Get balance failed: {}", e))
+            let client = state_guard.get_next_rpc_client();
+            match commitment {
+                Some(commitment) => crate::rpc::accounts::get_balance_with_commitment(client, &pubkey, commitment).await,
+                None => crate::rpc::accounts::get_balance(client, &pubkey).await,
+            }
+            .context("Get balance failed")
+        }
+        "getBalances" => {
+            let pubkeys_array = arguments
+                .get("pubkeys")
+                .and_then(|v| v.as_array())
+                .ok_or_else(|| McpError::validation("Missing pubkeys parameter").with_parameter("pubkeys"))?;
+            validate_array_len(pubkeys_array.len(), MAX_BALANCES_TOTAL, "pubkeys")?;
+
+            let mut pubkeys = Vec::new();
+            for pubkey_val in pubkeys_array {
+                let pubkey_str = pubkey_val
+                    .as_str()
+                    .ok_or_else(|| McpError::validation("Invalid pubkey in array").with_parameter("pubkeys"))?;
+                pubkeys.push(validate_pubkey(pubkey_str, "pubkeys")?);
+            }
+            let null_if_missing = arguments.get("nullIfMissing").and_then(|v| v.as_bool()).unwrap_or(false);
+
+            let state_guard = state.read().await;
+            crate::rpc::accounts::get_balances(state_guard.get_next_rpc_client(), &pubkeys, commitment, null_if_missing)
+                .await
+                .context("Get balances failed")
         }
         "getAccountInfo" => {
             let pubkey_str = arguments
                 .get("pubkey")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing pubkey parameter"))?;
-            let pubkey = Pubkey::try_from(pubkey_str)?;
+                .ok_or_else(|| McpError::validation("Missing pubkey parameter").with_parameter("pubkey"))?;
+            let pubkey = validate_pubkey(pubkey_str, "pubkey")?;
             
+            let encodings: Option<Vec<String>> = match arguments.get("encodings") {
+                None | Some(Value::Null) => None,
+                Some(value) => Some(serde_json::from_value(value.clone()).map_err(|_| {
+                    McpError::validation("Invalid encodings: expected an array of strings")
+                        .with_parameter("encodings")
+                })?),
+            };
+
             let state_guard = state.read().await;
-            crate::rpc::accounts::get_account_info(state_guard.get_next_rpc_client(), &pubkey).await
-                .map_err(|e| anyhow::anyhow!("Get account info failed: {}", e))
+            let encoding = account_encoding_or_default(&arguments, &state_guard.config)?;
+            let client = state_guard.get_next_rpc_client();
+            match (encodings, commitment, encoding) {
+                (Some(encodings), commitment, _) => crate::rpc::accounts::get_account_info_multi_encoding(
+                    client,
+                    &pubkey,
+                    &encodings,
+                    commitment,
+                )
+                .await,
+                (None, None, None) => crate::rpc::accounts::get_account_info(client, &pubkey).await,
+                (None, commitment, encoding) => {
+                    crate::rpc::accounts::get_account_info_with_config(client, &pubkey, commitment, encoding).await
+                }
+            }
+            .context("Get account info failed")
         }
         "getAccountOwner" => {
             let pubkey_str = arguments
                 .get("pubkey")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing pubkey parameter"))?;
-            let pubkey = Pubkey::try_from(pubkey_str)?;
+                .ok_or_else(|| McpError::validation("Missing pubkey parameter").with_parameter("pubkey"))?;
+            let pubkey = validate_pubkey(pubkey_str, "pubkey")?;
             
             let state_guard = state.read().await;
-            let account_info = crate::rpc::accounts::get_account_info(state_guard.get_next_rpc_client(), &pubkey).await
-                .map_err(|e| anyhow::anyhow!("Get account info failed: {}", e))?;
+            let account_info = crate::rpc::accounts::get_account_info_with_config(
+                state_guard.get_next_rpc_client(),
+                &pubkey,
+                commitment,
+                None,
+            )
+            .await
+            .context("Get account info failed")?;
             
             // Extract owner from account info
             Ok(serde_json::json!({
-                "owner": account_info.get("owner").unwrap_or(&serde_json::Value::Null)
+                "owner": account_info["account"].get("owner").unwrap_or(&serde_json::Value::Null)
+            }))
+        }
+        "isProgram" => {
+            let pubkey_str = arguments
+                .get("pubkey")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing pubkey parameter").with_parameter("pubkey"))?;
+            let pubkey = validate_pubkey(pubkey_str, "pubkey")?;
+
+            let state_guard = state.read().await;
+            crate::rpc::accounts::is_program(state_guard.get_next_rpc_client(), &pubkey).await
+                .context("Program check failed")
+        }
+        "resolveDomain" => {
+            let domain = arguments
+                .get("domain")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing domain parameter").with_parameter("domain"))?;
+
+            let (name_account, owner, cached) = resolve_domain_cached(&state, domain, "domain").await?;
+            Ok(serde_json::json!({
+                "domain": domain.trim().to_ascii_lowercase(),
+                "nameAccount": name_account.to_string(),
+                "owner": owner.to_string(),
+                "cached": cached,
             }))
         }
         "getMultipleAccounts" => {
             let pubkeys_array = arguments
                 .get("pubkeys")
                 .and_then(|v| v.as_array())
-                .ok_or_else(|| anyhow::anyhow!("Missing pubkeys parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing pubkeys parameter").with_parameter("pubkeys"))?;
+            validate_array_len(pubkeys_array.len(), MAX_MULTIPLE_ACCOUNTS, "pubkeys")?;
 
             let mut pubkeys = Vec::new();
             for pubkey_val in pubkeys_array {
                 let pubkey_str = pubkey_val
                     .as_str()
-                    .ok_or_else(|| anyhow::anyhow!("Invalid pubkey in array"))?;
-                pubkeys.push(Pubkey::try_from(pubkey_str)?);
+                    .ok_or_else(|| McpError::validation("Invalid pubkey in array").with_parameter("pubkeys"))?;
+                pubkeys.push(validate_pubkey(pubkey_str, "pubkeys")?);
             }
 
             let state_guard = state.read().await;
-            crate::rpc::accounts::get_multiple_accounts(state_guard.get_next_rpc_client(), &pubkeys).await
-                .map_err(|e| anyhow::anyhow!("Get multiple accounts failed: {}", e))
+            let encoding = account_encoding_or_default(&arguments, &state_guard.config)?;
+            let client = state_guard.get_next_rpc_client();
+            match (commitment, encoding) {
+                (None, None) => crate::rpc::accounts::get_multiple_accounts(client, &pubkeys).await,
+                (commitment, encoding) => {
+                    crate::rpc::accounts::get_multiple_accounts_with_config(client, &pubkeys, commitment, encoding).await
+                }
+            }
+            .context("Get multiple accounts failed")
+        }
+        "getProgramAccounts" => {
+            let program_id_str = arguments
+                .get("programId")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing programId parameter").with_parameter("programId"))?;
+            let program_id = validate_pubkey(program_id_str, "programId")?;
+            let commitment = match arguments.get("config").and_then(|c| c.get("commitment")) {
+                Some(value) => parse_commitment(Some(value), "config.commitment")?,
+                None => commitment,
+            };
+            let filters = parse_program_account_filters(arguments.get("filters"), "filters")?;
+            let limit = match optional_u64_argument(&arguments, "limit")? {
+                Some(limit) if limit == 0 || limit > crate::rpc::accounts::MAX_PROGRAM_ACCOUNTS_PAGE_SIZE as u64 => {
+                    return Err(McpError::validation(format!(
+                        "limit must be between 1 and {}, got {limit}",
+                        crate::rpc::accounts::MAX_PROGRAM_ACCOUNTS_PAGE_SIZE
+                    ))
+                    .with_parameter("limit")
+                    .into());
+                }
+                limit => limit.map(|limit| limit as usize),
+            };
+            let cursor = arguments.get("cursor").and_then(|v| v.as_str());
+
+            let state_guard = state.read().await;
+            let encoding = match arguments.get("config").and_then(|c| c.get("encoding")) {
+                Some(value) => parse_account_encoding(Some(value), "config.encoding")?,
+                None => account_encoding_or_default(&arguments, &state_guard.config)?,
+            };
+            crate::rpc::accounts::get_program_accounts_paged(
+                state_guard.get_next_rpc_client(),
+                &program_id,
+                commitment,
+                encoding,
+                filters,
+                limit,
+                cursor,
+            )
+            .await
+            .context("Get program accounts failed")
         }
         "getSlot" => {
             log::info!("getSlot: About to acquire state lock");
@@ -1957,190 +3434,402 @@ pub async fn handle_tools_call(
             log::info!("getSlot: State lock acquired");
             let client = state_guard.get_next_rpc_client();
             log::info!("getSlot: Got RPC client, about to call get_slot");
-            crate::rpc::blocks::get_slot(client).await
+            match commitment {
+                Some(commitment) => crate::rpc::blocks::get_slot_with_commitment(client, commitment).await,
+                None => crate::rpc::blocks::get_slot(client).await,
+            }
         }
         "getTransactionCount" => {
             let state_guard = state.read().await;
-            crate::rpc::system::get_transaction_count(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Get transaction count failed: {}", e))
+            let client = state_guard.get_next_rpc_client();
+            match commitment {
+                Some(commitment) => crate::rpc::system::get_transaction_count_with_commitment(client, commitment).await,
+                None => crate::rpc::system::get_transaction_count(client).await,
+            }
+            .context("Get transaction count failed")
         }
         "getLatestBlockhash" => {
             let state_guard = state.read().await;
-            crate::rpc::system::get_latest_blockhash(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Get latest blockhash failed: {}", e))
+            let client = state_guard.get_next_rpc_client();
+            match commitment {
+                Some(commitment) => crate::rpc::system::get_latest_blockhash_with_commitment(client, commitment).await,
+                None => crate::rpc::system::get_latest_blockhash(client).await,
+            }
+            .context("Get latest blockhash failed")
         }
         "getEpochInfo" => {
             let state_guard = state.read().await;
-            crate::rpc::system::get_epoch_info(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Get epoch info failed: {}", e))
+            crate::rpc::system::get_epoch_info(state_guard.get_next_rpc_client(), commitment).await
+                .context("Get epoch info failed")
+        }
+        "getIdentity" => {
+            let state_guard = state.read().await;
+            crate::rpc::system::get_identity(state_guard.get_next_rpc_client()).await
+                .context("Get identity failed")
+        }
+        "getInflationGovernor" => {
+            let state_guard = state.read().await;
+            crate::rpc::system::get_inflation_governor(state_guard.get_next_rpc_client(), commitment).await
+                .context("Get inflation governor failed")
+        }
+        "getInflationRate" => {
+            let state_guard = state.read().await;
+            crate::rpc::system::get_inflation_rate(state_guard.get_next_rpc_client()).await
+                .context("Get inflation rate failed")
+        }
+        "getEpochSchedule" => {
+            let state_guard = state.read().await;
+            crate::rpc::system::get_epoch_schedule(state_guard.get_next_rpc_client()).await
+                .context("Get epoch schedule failed")
+        }
+        "getStakeMinimumDelegation" => {
+            let state_guard = state.read().await;
+            let client = state_guard.get_next_rpc_client();
+            match commitment {
+                Some(commitment) => {
+                    crate::rpc::system::get_stake_minimum_delegation_with_commitment(client, commitment).await
+                }
+                None => crate::rpc::system::get_stake_minimum_delegation(client).await,
+            }
+            .context("Get stake minimum delegation failed")
+        }
+        "getBlockProduction" => {
+            let identity = arguments
+                .get("identity")
+                .and_then(|v| v.as_str())
+                .map(|identity| validate_pubkey(identity, "identity"))
+                .transpose()?;
+
+            let first_slot = optional_u64_argument(&arguments, "firstSlot")?;
+            let last_slot = optional_u64_argument(&arguments, "lastSlot")?;
+            let range = match (first_slot, last_slot) {
+                (None, None) => None,
+                (None, Some(_)) => {
+                    return Err(McpError::validation("lastSlot requires firstSlot")
+                        .with_parameter("firstSlot")
+                        .into())
+                }
+                (Some(first), Some(last)) if last < first => {
+                    return Err(McpError::validation(format!(
+                        "lastSlot {last} must be greater than or equal to firstSlot {first}"
+                    ))
+                    .with_parameter("lastSlot")
+                    .into())
+                }
+                (Some(first), last) => Some((first, last)),
+            };
+
+            let state_guard = state.read().await;
+            crate::rpc::blocks::get_block_production(
+                state_guard.get_next_rpc_client(),
+                identity.as_ref(),
+                range,
+                commitment,
+            )
+            .await
+            .context("Get block production failed")
+        }
+        "getVoteAccounts" => {
+            let vote_pubkey = arguments
+                .get("votePubkey")
+                .and_then(|v| v.as_str())
+                .map(|pubkey| validate_pubkey(pubkey, "votePubkey"))
+                .transpose()?;
+            let keep_unstaked_delinquents = arguments.get("keepUnstakedDelinquents").and_then(|v| v.as_bool());
+            let delinquent_slot_distance = optional_u64_argument(&arguments, "delinquentSlotDistance")?;
+            let min_stake = optional_u64_argument(&arguments, "minStake")?;
+
+            let state_guard = state.read().await;
+            crate::rpc::blocks::get_vote_accounts_with_config(
+                state_guard.get_next_rpc_client(),
+                commitment,
+                vote_pubkey.map(|pubkey| pubkey.to_string()),
+                keep_unstaked_delinquents,
+                delinquent_slot_distance,
+                min_stake,
+            )
+            .await
+            .context("Get vote accounts failed")
+        }
+        "getLeaderSchedule" => {
+            let slot = optional_u64_argument(&arguments, "slot")?;
+            let identity = arguments
+                .get("identity")
+                .and_then(|v| v.as_str())
+                .map(|identity| validate_pubkey(identity, "identity"))
+                .transpose()?;
+
+            let state_guard = state.read().await;
+            crate::rpc::blocks::get_leader_schedule(
+                state_guard.get_next_rpc_client(),
+                slot,
+                identity.as_ref(),
+                commitment,
+            )
+            .await
+            .context("Get leader schedule failed")
+        }
+        "getSlotLeaders" => {
+            let required_positive = |field: &str| -> McpResult<u64> {
+                match optional_u64_argument(&arguments, field)? {
+                    Some(0) => Err(McpError::validation(format!("{field} must be greater than zero"))
+                        .with_parameter(field)),
+                    Some(value) => Ok(value),
+                    None => Err(McpError::validation(format!("Missing {field} parameter")).with_parameter(field)),
+                }
+            };
+            let start_slot = required_positive("startSlot")?;
+            let limit = required_positive("limit")?;
+
+            let state_guard = state.read().await;
+            crate::rpc::blocks::get_slot_leaders(state_guard.get_next_rpc_client(), start_slot, limit)
+                .await
+                .context("Get slot leaders failed")
+        }
+        "getSupply" => {
+            let state_guard = state.read().await;
+            let client = state_guard.get_next_rpc_client();
+            match commitment {
+                Some(commitment) => crate::rpc::system::get_supply_with_commitment(client, commitment).await,
+                None => crate::rpc::system::get_supply(client).await,
+            }
+            .context("Get supply failed")
+        }
+        "getLargestAccounts" => {
+            use solana_client::rpc_config::RpcLargestAccountsFilter;
+
+            let filter = match arguments.get("filter") {
+                None | Some(Value::Null) => None,
+                Some(value) => Some(match value.as_str() {
+                    Some("circulating") => RpcLargestAccountsFilter::Circulating,
+                    Some("nonCirculating") => RpcLargestAccountsFilter::NonCirculating,
+                    _ => {
+                        return Err(McpError::validation(format!(
+                            "Invalid filter {value}: expected 'circulating' or 'nonCirculating'"
+                        ))
+                        .with_parameter("filter")
+                        .into())
+                    }
+                }),
+            };
+
+            let state_guard = state.read().await;
+            crate::rpc::accounts::get_largest_accounts(state_guard.get_next_rpc_client(), filter, commitment).await
+                .context("Get largest accounts failed")
         }
         "getClusterNodes" => {
             let state_guard = state.read().await;
             crate::rpc::system::get_cluster_nodes(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Get cluster nodes failed: {}", e))
+                .context("Get cluster nodes failed")
         }
         // New critical missing methods
         "isBlockhashValid" => {
             let blockhash = arguments.get("blockhash")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing blockhash parameter"))?;
-            let commitment = arguments.get("commitment")
-                .and_then(|v| v.as_str())
-                .map(|c| match c {
-                    "processed" => CommitmentConfig::processed(),
-                    "confirmed" => CommitmentConfig::confirmed(),
-                    "finalized" => CommitmentConfig::finalized(),
-                    _ => CommitmentConfig::finalized(),
-                });
-
+                .ok_or_else(|| McpError::validation("Missing blockhash parameter").with_parameter("blockhash"))?;
             let state_guard = state.read().await;
             crate::rpc::system::is_blockhash_valid(state_guard.get_next_rpc_client(), blockhash, commitment).await
-                .map_err(|e| anyhow::anyhow!("Check blockhash validity failed: {}", e))
+                .context("Check blockhash validity failed")
         }
-        "getSlotLeader" => {
-            let commitment = arguments.get("commitment")
-                .and_then(|v| v.as_str())
-                .map(|c| match c {
-                    "processed" => CommitmentConfig::processed(),
-                    "confirmed" => CommitmentConfig::confirmed(),
-                    "finalized" => CommitmentConfig::finalized(),
-                    _ => CommitmentConfig::finalized(),
-                });
+        "getFeeForMessage" => {
+            let message = decode_message_argument(&arguments)?;
+            let state_guard = state.read().await;
+            crate::rpc::system::get_fee_for_message(state_guard.get_next_rpc_client(), &message, commitment).await
+                .context("Get fee for message failed")
+        }
+        "getMinimumBalanceForRentExemption" => {
+            let data_size = required_u64_argument(&arguments, "dataSize")?;
+            if data_size > MAX_ACCOUNT_DATA_SIZE as u64 {
+                return Err(McpError::validation(format!(
+                    "Invalid dataSize: {data_size} exceeds the {MAX_ACCOUNT_DATA_SIZE}-byte account size limit"
+                ))
+                .with_parameter("dataSize")
+                .into());
+            }
 
+            let state_guard = state.read().await;
+            crate::rpc::system::get_minimum_balance_for_rent_exemption(
+                state_guard.get_next_rpc_client(),
+                data_size as usize,
+                commitment,
+            )
+            .await
+            .context("Get minimum balance for rent exemption failed")
+        }
+        "getSlotLeader" => {
             let state_guard = state.read().await;
             crate::rpc::system::get_slot_leader(state_guard.get_next_rpc_client(), commitment).await
-                .map_err(|e| anyhow::anyhow!("Get slot leader failed: {}", e))
+                .context("Get slot leader failed")
         }
         "minimumLedgerSlot" => {
             let state_guard = state.read().await;
             crate::rpc::system::minimum_ledger_slot(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Get minimum ledger slot failed: {}", e))
+                .context("Get minimum ledger slot failed")
         }
         "getMaxRetransmitSlot" => {
             let state_guard = state.read().await;
             crate::rpc::system::get_max_retransmit_slot(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Get max retransmit slot failed: {}", e))
+                .context("Get max retransmit slot failed")
         }
         "getMaxShredInsertSlot" => {
             let state_guard = state.read().await;
             crate::rpc::system::get_max_shred_insert_slot(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Get max shred insert slot failed: {}", e))
+                .context("Get max shred insert slot failed")
         }
         "getHighestSnapshotSlot" => {
             let state_guard = state.read().await;
             crate::rpc::system::get_highest_snapshot_slot(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Get highest snapshot slot failed: {}", e))
+                .context("Get highest snapshot slot failed")
         }
         // Deprecated methods
         "getRecentBlockhash" => {
             let state_guard = state.read().await;
             crate::rpc::system::get_recent_blockhash(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Get recent blockhash failed: {}", e))
+                .context("Get recent blockhash failed")
         }
         "getFees" => {
             let state_guard = state.read().await;
             crate::rpc::system::get_fees(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Get fees failed: {}", e))
+                .context("Get fees failed")
         }
         "getConfirmedBlock" => {
             let state_guard = state.read().await;
             let slot = arguments.get("slot").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing slot parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing slot parameter").with_parameter("slot"))?;
             crate::rpc::blocks::get_confirmed_block(state_guard.get_next_rpc_client(), slot).await
-                .map_err(|e| anyhow::anyhow!("Get confirmed block failed: {}", e))
+                .context("Get confirmed block failed")
         }
         "getConfirmedTransaction" => {
             let state_guard = state.read().await;
             let signature_str = arguments.get("signature").and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing signature parameter"))?;
-            let signature = signature_str.parse()?;
+                .ok_or_else(|| McpError::validation("Missing signature parameter").with_parameter("signature"))?;
+            let signature = validate_signature(signature_str, "signature")?;
             crate::rpc::transactions::get_confirmed_transaction(state_guard.get_next_rpc_client(), &signature).await
-                .map_err(|e| anyhow::anyhow!("Get confirmed transaction failed: {}", e))
+                .context("Get confirmed transaction failed")
         }
         "getConfirmedBlocks" => {
             let state_guard = state.read().await;
             let start_slot = arguments.get("startSlot").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing startSlot parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing startSlot parameter").with_parameter("startSlot"))?;
             let end_slot = arguments.get("endSlot").and_then(|v| v.as_u64());
-            crate::rpc::blocks::get_confirmed_blocks(state_guard.get_next_rpc_client(), start_slot, end_slot).await
-                .map_err(|e| anyhow::anyhow!("Get confirmed blocks failed: {}", e))
+            let max_slots = state_guard.config.max_block_range_slots;
+            let client = state_guard.get_next_rpc_client();
+            if scan_argument(&arguments) {
+                crate::rpc::blocks::get_blocks_paged(client, start_slot, end_slot, max_slots, None).await
+            } else {
+                validate_block_range(start_slot, end_slot, max_slots)?;
+                crate::rpc::blocks::get_confirmed_blocks(client, start_slot, end_slot).await
+            }
+            .context("Get confirmed blocks failed")
         }
         "getConfirmedBlocksWithLimit" => {
             let state_guard = state.read().await;
             let start_slot = arguments.get("startSlot").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing startSlot parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing startSlot parameter").with_parameter("startSlot"))?;
             let limit = arguments.get("limit").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing limit parameter"))? as usize;
+                .ok_or_else(|| McpError::validation("Missing limit parameter").with_parameter("limit"))? as usize;
             crate::rpc::blocks::get_confirmed_blocks_with_limit(state_guard.get_next_rpc_client(), start_slot, limit).await
-                .map_err(|e| anyhow::anyhow!("Get confirmed blocks with limit failed: {}", e))
+                .context("Get confirmed blocks with limit failed")
         }
         "getConfirmedSignaturesForAddress2" => {
             let state_guard = state.read().await;
             let address_str = arguments.get("address").and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing address parameter"))?;
-            let address = Pubkey::try_from(address_str)?;
+                .ok_or_else(|| McpError::validation("Missing address parameter").with_parameter("address"))?;
+            let address = validate_pubkey(address_str, "address")?;
             let limit = arguments.get("limit").and_then(|v| v.as_u64());
             crate::rpc::transactions::get_confirmed_signatures_for_address_2(state_guard.get_next_rpc_client(), &address, None, None, limit).await
-                .map_err(|e| anyhow::anyhow!("Get confirmed signatures for address failed: {}", e))
+                .context("Get confirmed signatures for address failed")
         }
         "getAccountInfoAndContext" => {
             let state_guard = state.read().await;
             let pubkey: String = arguments.get("pubkey")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing pubkey parameter"))?
+                .ok_or_else(|| McpError::validation("Missing pubkey parameter").with_parameter("pubkey"))?
                 .to_string();
             
-            let parsed_pubkey = pubkey.parse::<solana_sdk::pubkey::Pubkey>()
-                .map_err(|e| anyhow::anyhow!("Invalid pubkey: {}", e))?;
+            let parsed_pubkey = validate_pubkey(&pubkey, "pubkey")?;
             
-            crate::rpc::accounts::get_account_info_and_context(state_guard.get_next_rpc_client(), &parsed_pubkey)
+            crate::rpc::accounts::get_account_info_and_context(state_guard.get_next_rpc_client(), &parsed_pubkey, commitment)
                 .await
-                .map_err(|e| anyhow::anyhow!("Get account info with context failed: {}", e))
+                .context("Get account info with context failed")
         }
         "getBalanceAndContext" => {
             let state_guard = state.read().await;
             let pubkey: String = arguments.get("pubkey")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing pubkey parameter"))?
+                .ok_or_else(|| McpError::validation("Missing pubkey parameter").with_parameter("pubkey"))?
                 .to_string();
             
-            let parsed_pubkey = pubkey.parse::<solana_sdk::pubkey::Pubkey>()
-                .map_err(|e| anyhow::anyhow!("Invalid pubkey: {}", e))?;
+            let parsed_pubkey = validate_pubkey(&pubkey, "pubkey")?;
             
-            crate::rpc::accounts::get_balance_and_context(state_guard.get_next_rpc_client(), &parsed_pubkey)
+            crate::rpc::accounts::get_balance_and_context(state_guard.get_next_rpc_client(), &parsed_pubkey, commitment)
                 .await
-                .map_err(|e| anyhow::anyhow!("Get balance with context failed: {}", e))
+                .context("Get balance with context failed")
         }
         "getMultipleAccountsAndContext" => {
             let state_guard = state.read().await;
             let pubkeys: Vec<String> = arguments.get("pubkeys")
                 .and_then(|v| serde_json::from_value(v.clone()).ok())
-                .ok_or_else(|| anyhow::anyhow!("Missing or invalid pubkeys parameter"))?;
-            
-            let parsed_pubkeys: Result<Vec<_>, _> = pubkeys.iter()
-                .map(|key| key.parse::<solana_sdk::pubkey::Pubkey>())
-                .collect();
+                .ok_or_else(|| McpError::validation("Missing or invalid pubkeys parameter").with_parameter("pubkeys"))?;
+            validate_array_len(pubkeys.len(), MAX_MULTIPLE_ACCOUNTS, "pubkeys")?;
             
-            let parsed_pubkeys = parsed_pubkeys
-                .map_err(|e| anyhow::anyhow!("Invalid pubkey: {}", e))?;
+            let parsed_pubkeys = pubkeys.iter()
+                .map(|key| validate_pubkey(key, "pubkeys"))
+                .collect::<Result<Vec<_>, _>>()?;
             
-            crate::rpc::accounts::get_multiple_accounts_and_context(state_guard.get_next_rpc_client(), &parsed_pubkeys)
-                .await
-                .map_err(|e| anyhow::anyhow!("Get multiple accounts with context failed: {}", e))
+            crate::rpc::accounts::get_multiple_accounts_and_context(
+                state_guard.get_next_rpc_client(),
+                &parsed_pubkeys,
+                commitment,
+            )
+            .await
+            .context("Get multiple accounts with context failed")
         }
         "getProgramAccountsAndContext" => {
             let state_guard = state.read().await;
             let program_id: String = arguments.get("program_id")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing program_id parameter"))?
+                .ok_or_else(|| McpError::validation("Missing program_id parameter").with_parameter("program_id"))?
                 .to_string();
             
-            let parsed_program_id = program_id.parse::<solana_sdk::pubkey::Pubkey>()
-                .map_err(|e| anyhow::anyhow!("Invalid program_id: {}", e))?;
+            let parsed_program_id = validate_pubkey(&program_id, "program_id")?;
             
-            crate::rpc::accounts::get_program_accounts_and_context(state_guard.get_next_rpc_client(), &parsed_program_id, None)
+            let filters = parse_program_account_filters(arguments.get("filters"), "filters")?;
+            let encoding = account_encoding_or_default(&arguments, &state_guard.config)?;
+            let client = state_guard.get_next_rpc_client();
+            let config = solana_client::rpc_config::RpcProgramAccountsConfig {
+                filters,
+                account_config: solana_client::rpc_config::RpcAccountInfoConfig {
+                    encoding: Some(encoding.unwrap_or(UiAccountEncoding::Base64)),
+                    commitment: Some(commitment.unwrap_or_else(|| client.commitment())),
+                    data_slice: None,
+                    min_context_slot: None,
+                },
+                with_context: Some(true),
+                sort_results: None,
+            };
+
+            crate::rpc::accounts::get_program_accounts_and_context(client, &parsed_program_id, Some(config))
                 .await
-                .map_err(|e| anyhow::anyhow!("Get program accounts with context failed: {}", e))
+                .context("Get program accounts with context failed")
+        }
+        "getProgramAccountsParsed" => {
+            let program_id_str = arguments
+                .get("programId")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing programId parameter").with_parameter("programId"))?;
+            let program_id = validate_pubkey(program_id_str, "programId")?;
+            let filters = parse_program_account_filters(arguments.get("filters"), "filters")?;
+
+            let state_guard = state.read().await;
+            crate::rpc::accounts::get_program_accounts_parsed(
+                state_guard.get_next_rpc_client(),
+                &program_id,
+                commitment,
+                filters,
+            )
+            .await
+            .context("Get parsed program accounts failed")
         }
         "getRecentPerformanceSamples" => {
             let state_guard = state.read().await;
@@ -2150,79 +3839,292 @@ pub async fn handle_tools_call(
             
             crate::rpc::system::get_recent_performance_samples(state_guard.get_next_rpc_client(), limit)
                 .await
-                .map_err(|e| anyhow::anyhow!("Get recent performance samples failed: {}", e))
+                .context("Get recent performance samples failed")
         }
         "getRecentPrioritizationFees" => {
             let state_guard = state.read().await;
             let addresses: Option<Vec<String>> = arguments.get("addresses")
                 .and_then(|v| serde_json::from_value(v.clone()).ok());
+            for address in addresses.iter().flatten() {
+                validate_pubkey(address, "addresses")?;
+            }
             
             crate::rpc::system::get_recent_prioritization_fees(state_guard.get_next_rpc_client(), addresses)
                 .await
-                .map_err(|e| anyhow::anyhow!("Get recent prioritization fees failed: {}", e))
+                .context("Get recent prioritization fees failed")
+        }
+        "decodeProgramError" => {
+            let program_id_str = arguments
+                .get("programId")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing programId parameter").with_parameter("programId"))?;
+            let program_id = validate_pubkey(program_id_str, "programId")?;
+            let code = arguments
+                .get("code")
+                .and_then(crate::program_errors::parse_error_code)
+                .ok_or_else(|| McpError::validation("Missing or invalid code parameter").with_parameter("code"))?;
+
+            Ok(crate::program_errors::decode_program_error(&program_id, code))
+        }
+        "decodeAccountData" => {
+            let data = arguments
+                .get("data")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing data parameter").with_parameter("data"))?;
+            let data = base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .map_err(|e| McpError::validation(format!("Invalid base64 data: {e}")).with_parameter("data"))?;
+            let owner_str = arguments
+                .get("ownerProgram")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing ownerProgram parameter").with_parameter("ownerProgram"))?;
+            let owner = validate_pubkey(owner_str, "ownerProgram")?;
+            let pubkey = match arguments.get("pubkey").and_then(|v| v.as_str()) {
+                Some(pubkey) => Some(validate_pubkey(pubkey, "pubkey")?),
+                None => None,
+            };
+            let decimals = optional_u64_argument(&arguments, "decimals")?
+                .map(|decimals| {
+                    u8::try_from(decimals).map_err(|_| {
+                        McpError::validation(format!("Invalid decimals: {decimals}")).with_parameter("decimals")
+                    })
+                })
+                .transpose()?;
+
+            Ok(crate::account_layouts::decode_account_data(&owner, &data, pubkey.as_ref(), decimals)?)
+        }
+        "getInflationReward" => {
+            let addresses_array = arguments
+                .get("addresses")
+                .and_then(|v| v.as_array())
+                .ok_or_else(|| McpError::validation("Missing addresses parameter").with_parameter("addresses"))?;
+            if addresses_array.is_empty() {
+                return Err(McpError::validation("At least one address is required")
+                    .with_parameter("addresses")
+                    .into());
+            }
+            validate_array_len(addresses_array.len(), MAX_MULTIPLE_ACCOUNTS, "addresses")?;
+
+            let mut addresses = Vec::new();
+            for address_val in addresses_array {
+                let address_str = address_val
+                    .as_str()
+                    .ok_or_else(|| McpError::validation("Invalid address in array").with_parameter("addresses"))?;
+                addresses.push(validate_pubkey(address_str, "addresses")?);
+            }
+            let epoch = optional_u64_argument(&arguments, "epoch")?;
+
+            let state_guard = state.read().await;
+            crate::rpc::system::get_inflation_reward(state_guard.get_next_rpc_client(), &addresses, epoch, commitment)
+                .await
+                .context("Get inflation reward failed")
+        }
+        "getHistoricalFeeStats" => {
+            let addresses_array = arguments
+                .get("addresses")
+                .and_then(|v| v.as_array())
+                .ok_or_else(|| McpError::validation("Missing addresses parameter").with_parameter("addresses"))?;
+            validate_array_len(addresses_array.len(), MAX_FEE_STATS_ADDRESSES, "addresses")?;
+
+            let mut addresses = Vec::new();
+            for address_val in addresses_array {
+                let address_str = address_val
+                    .as_str()
+                    .ok_or_else(|| McpError::validation("Invalid address in array").with_parameter("addresses"))?;
+                addresses.push(validate_pubkey(address_str, "addresses")?);
+            }
+
+            let sample_size = match optional_u64_argument(&arguments, "sampleSize")? {
+                None => crate::rpc::system::DEFAULT_FEE_SAMPLE_SIZE,
+                Some(size) if (1..=crate::rpc::system::MAX_FEE_SAMPLE_SIZE as u64).contains(&size) => size as usize,
+                Some(size) => {
+                    return Err(McpError::validation(format!(
+                        "sampleSize must be between 1 and {}, got {size}",
+                        crate::rpc::system::MAX_FEE_SAMPLE_SIZE
+                    ))
+                    .with_parameter("sampleSize")
+                    .into());
+                }
+            };
+
+            let state_guard = state.read().await;
+            crate::rpc::system::get_historical_fee_stats(state_guard.get_next_rpc_client(), &addresses, sample_size, commitment)
+                .await
+                .context("Get historical fee stats failed")
+        }
+        "getRecentActivity" => {
+            let address_str = arguments
+                .get("address")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing address parameter").with_parameter("address"))?;
+            let address = validate_pubkey(address_str, "address")?;
+            let limit = arguments
+                .get("limit")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as usize)
+                .unwrap_or(crate::rpc::transactions::DEFAULT_ACTIVITY_LIMIT);
+            // Transaction history is not served at processed commitment
+            if commitment.is_some_and(|c| c.commitment == CommitmentLevel::Processed) {
+                return Err(McpError::validation("getRecentActivity accepts only 'confirmed' or 'finalized' commitment")
+                    .with_parameter("commitment")
+                    .into());
+            }
+
+            let state_guard = state.read().await;
+            crate::rpc::transactions::get_recent_activity(state_guard.get_next_rpc_client(), &address, limit, commitment)
+                .await
+                .context("Get recent activity failed")
         }
         "getStakeActivation" => {
             let state_guard = state.read().await;
             let pubkey: String = arguments.get("pubkey")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing pubkey parameter"))?
+                .ok_or_else(|| McpError::validation("Missing pubkey parameter").with_parameter("pubkey"))?
                 .to_string();
-            
-            let commitment = arguments.get("commitment")
-                .and_then(|v| v.as_str())
-                .and_then(|s| match s {
-                    "processed" => Some(solana_sdk::commitment_config::CommitmentConfig::processed()),
-                    "confirmed" => Some(solana_sdk::commitment_config::CommitmentConfig::confirmed()),
-                    "finalized" => Some(solana_sdk::commitment_config::CommitmentConfig::finalized()),
-                    _ => None,
-                });
+            validate_pubkey(&pubkey, "pubkey")?;
             
             crate::rpc::missing_methods::get_stake_activation(state_guard.get_next_rpc_client(), &pubkey, commitment)
                 .await
-                .map_err(|e| anyhow::anyhow!("Get stake activation failed: {}", e))
+                .context("Get stake activation failed")
         }
         "getSignatureStatuses" => {
             let signatures_array = arguments
                 .get("signatures")
                 .and_then(|v| v.as_array())
-                .ok_or_else(|| anyhow::anyhow!("Missing signatures parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing signatures parameter").with_parameter("signatures"))?;
+            validate_array_len(signatures_array.len(), MAX_SIGNATURE_STATUSES_TOTAL, "signatures")?;
 
             let mut signatures = Vec::new();
             for sig_val in signatures_array {
                 let sig_str = sig_val
                     .as_str()
-                    .ok_or_else(|| anyhow::anyhow!("Invalid signature in array"))?;
-                signatures.push(sig_str.parse()?);
+                    .ok_or_else(|| McpError::validation("Invalid signature in array").with_parameter("signatures"))?;
+                validate_signature(sig_str, "signatures")?;
+                signatures.push(sig_str.to_string());
             }
 
+            // searchTransactionHistory matches the RPC name; the snake_case form is kept for older clients
             let search_transaction_history = arguments
-                .get("search_transaction_history")
+                .get("searchTransactionHistory")
+                .or_else(|| arguments.get("search_transaction_history"))
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
 
             let state_guard = state.read().await;
             crate::rpc::transactions::get_signature_statuses(state_guard.get_next_rpc_client(), &signatures, Some(search_transaction_history)).await
-                .map_err(|e| anyhow::anyhow!("Get signature statuses failed: {}", e))
+                .context("Get signature statuses failed")
+        }
+        "getBlock" => {
+            let slot = required_u64_argument(&arguments, "slot")?;
+            let encoding = transaction_encoding_argument(&arguments)?;
+            let transaction_details = match arguments.get("transactionDetails") {
+                None | Some(Value::Null) => None,
+                Some(value) => Some(match value.as_str() {
+                    Some("full") => TransactionDetails::Full,
+                    Some("signatures") => TransactionDetails::Signatures,
+                    Some("none") => TransactionDetails::None,
+                    _ => {
+                        return Err(McpError::validation(format!(
+                            "Invalid transactionDetails {value}: expected full, signatures or none"
+                        ))
+                        .with_parameter("transactionDetails")
+                        .into())
+                    }
+                }),
+            };
+            let rewards = arguments.get("rewards").and_then(|v| v.as_bool());
+
+            let state_guard = state.read().await;
+            crate::rpc::blocks::get_block_with_config(
+                state_guard.get_next_rpc_client(),
+                slot,
+                Some(encoding),
+                transaction_details,
+                rewards,
+                commitment,
+            )
+            .await
+            .context("Get block failed")
+        }
+        "getBlockHeight" => {
+            let state_guard = state.read().await;
+            let client = state_guard.get_next_rpc_client();
+            match commitment {
+                Some(commitment) => crate::rpc::blocks::get_block_height_with_commitment(client, commitment).await,
+                None => crate::rpc::blocks::get_block_height(client).await,
+            }
+            .context("Get block height failed")
+        }
+        "getBlocks" => {
+            let start_slot = required_u64_argument(&arguments, "startSlot")?;
+            let end_slot = optional_u64_argument(&arguments, "endSlot")?;
+
+            let state_guard = state.read().await;
+            let max_slots = state_guard.config.max_block_range_slots;
+            let client = state_guard.get_next_rpc_client();
+            if scan_argument(&arguments) {
+                crate::rpc::blocks::get_blocks_paged(client, start_slot, end_slot, max_slots, commitment).await
+            } else {
+                validate_block_range(start_slot, end_slot, max_slots)?;
+                match commitment {
+                    Some(commitment) => {
+                        crate::rpc::blocks::get_blocks_with_commitment(client, start_slot, end_slot, commitment).await
+                    }
+                    None => crate::rpc::blocks::get_blocks(client, start_slot, end_slot).await,
+                }
+            }
+            .context("Get blocks failed")
+        }
+        "getBlocksWithLimit" => {
+            let start_slot = required_u64_argument(&arguments, "startSlot")?;
+            let limit = required_u64_argument(&arguments, "limit")? as usize;
+
+            let state_guard = state.read().await;
+            let client = state_guard.get_next_rpc_client();
+            match commitment {
+                Some(commitment) => {
+                    crate::rpc::blocks::get_blocks_with_limit_and_commitment(client, start_slot, limit, commitment)
+                        .await
+                }
+                None => crate::rpc::blocks::get_blocks_with_limit(client, start_slot, limit).await,
+            }
+            .context("Get blocks with limit failed")
+        }
+        "getFirstAvailableBlock" => {
+            let state_guard = state.read().await;
+            crate::rpc::blocks::get_first_available_block(state_guard.get_next_rpc_client()).await
+                .context("Get first available block failed")
+        }
+        "getGenesisHash" => {
+            let state_guard = state.read().await;
+            crate::rpc::blocks::get_genesis_hash(state_guard.get_next_rpc_client()).await
+                .context("Get genesis hash failed")
+        }
+        "getBlockTime" => {
+            let slot = required_u64_argument(&arguments, "slot")?;
+
+            let state_guard = state.read().await;
+            crate::rpc::blocks::get_block_time(state_guard.get_next_rpc_client(), slot).await
+                .context("Get block time failed")
         }
         // Manual RPC methods for missing functionality
         "getBlockCommitment" => {
-            let slot = arguments.get("slot").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing slot parameter"))?;
+            let slot = required_u64_argument(&arguments, "slot")?;
 
             let state_guard = state.read().await;
             crate::rpc::missing_methods::get_block_commitment(state_guard.get_next_rpc_client(), slot).await
-                .map_err(|e| anyhow::anyhow!("Get block commitment failed: {}", e))
+                .context("Get block commitment failed")
         }
         "getSnapshotSlot" => {
             let state_guard = state.read().await;
             crate::rpc::missing_methods::get_snapshot_slot(state_guard.get_next_rpc_client()).await
-                .map_err(|e| anyhow::anyhow!("Get snapshot slot failed: {}", e))
+                .context("Get snapshot slot failed")
         }
         // WebSocket subscription methods  
         "accountSubscribe" => {
             let pubkey_str = arguments.get("pubkey").and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing pubkey parameter"))?;
-            let _pubkey = pubkey_str.parse::<solana_sdk::pubkey::Pubkey>()?;
+                .ok_or_else(|| McpError::validation("Missing pubkey parameter").with_parameter("pubkey"))?;
+            let _pubkey = validate_pubkey(pubkey_str, "pubkey")?;
             let _commitment = arguments.get("commitment").and_then(|v| v.as_str());
             let _encoding = arguments.get("encoding").and_then(|v| v.as_str());
 
@@ -2234,7 +4136,7 @@ pub async fn handle_tools_call(
         }
         "accountUnsubscribe" => {
             let _subscription_id = arguments.get("subscription_id").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing subscription_id parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing subscription_id parameter").with_parameter("subscription_id"))?;
 
             Ok(serde_json::json!({
                 "success": false, 
@@ -2255,7 +4157,7 @@ pub async fn handle_tools_call(
         }
         "blockUnsubscribe" => {
             let _subscription_id = arguments.get("subscription_id").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing subscription_id parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing subscription_id parameter").with_parameter("subscription_id"))?;
 
             Ok(serde_json::json!({
                 "success": false,
@@ -2273,7 +4175,7 @@ pub async fn handle_tools_call(
         }
         "logsUnsubscribe" => {
             let _subscription_id = arguments.get("subscription_id").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing subscription_id parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing subscription_id parameter").with_parameter("subscription_id"))?;
 
             Ok(serde_json::json!({
                 "success": false,
@@ -2282,8 +4184,8 @@ pub async fn handle_tools_call(
         }
         "programSubscribe" => {
             let program_id_str = arguments.get("program_id").and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing program_id parameter"))?;
-            let _program_id = program_id_str.parse::<solana_sdk::pubkey::Pubkey>()?;
+                .ok_or_else(|| McpError::validation("Missing program_id parameter").with_parameter("program_id"))?;
+            let _program_id = validate_pubkey(program_id_str, "program_id")?;
             let _commitment = arguments.get("commitment").and_then(|v| v.as_str());
             let _encoding = arguments.get("encoding").and_then(|v| v.as_str());
             let _filters = arguments.get("filters");
@@ -2295,7 +4197,7 @@ pub async fn handle_tools_call(
         }
         "programUnsubscribe" => {
             let _subscription_id = arguments.get("subscription_id").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing subscription_id parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing subscription_id parameter").with_parameter("subscription_id"))?;
 
             Ok(serde_json::json!({
                 "success": false,
@@ -2310,7 +4212,7 @@ pub async fn handle_tools_call(
         }
         "rootUnsubscribe" => {
             let _subscription_id = arguments.get("subscription_id").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing subscription_id parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing subscription_id parameter").with_parameter("subscription_id"))?;
 
             Ok(serde_json::json!({
                 "success": false,
@@ -2319,8 +4221,8 @@ pub async fn handle_tools_call(
         }
         "signatureSubscribe" => {
             let signature_str = arguments.get("signature").and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing signature parameter"))?;
-            let _signature = signature_str.parse::<solana_sdk::signature::Signature>()?;
+                .ok_or_else(|| McpError::validation("Missing signature parameter").with_parameter("signature"))?;
+            let _signature = validate_signature(signature_str, "signature")?;
             let _commitment = arguments.get("commitment").and_then(|v| v.as_str());
             let _enable_received_notification = arguments.get("enableReceivedNotification").and_then(|v| v.as_bool());
 
@@ -2331,7 +4233,7 @@ pub async fn handle_tools_call(
         }
         "signatureUnsubscribe" => {
             let _subscription_id = arguments.get("subscription_id").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing subscription_id parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing subscription_id parameter").with_parameter("subscription_id"))?;
 
             Ok(serde_json::json!({
                 "success": false,
@@ -2346,7 +4248,7 @@ pub async fn handle_tools_call(
         }
         "slotUnsubscribe" => {
             let _subscription_id = arguments.get("subscription_id").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing subscription_id parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing subscription_id parameter").with_parameter("subscription_id"))?;
 
             Ok(serde_json::json!({
                 "success": false,
@@ -2361,80 +4263,433 @@ pub async fn handle_tools_call(
         }
         "slotsUpdatesUnsubscribe" => {
             let _subscription_id = arguments.get("subscription_id").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing subscription_id parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing subscription_id parameter").with_parameter("subscription_id"))?;
+
+            Ok(serde_json::json!({
+                "success": false,
+                "status": "WebSocket subscriptions require WebSocket connection mode. Use 'solana-mcp-server websocket --port 8900' to enable real-time subscriptions."
+            }))
+        }
+        "voteSubscribe" => {
+            Ok(serde_json::json!({
+                "subscription_id": 9,
+                "status": "WebSocket subscriptions require WebSocket connection mode. Use 'solana-mcp-server websocket --port 8900' to enable real-time subscriptions."
+            }))
+        }
+        "voteUnsubscribe" => {
+            let _subscription_id = arguments.get("subscription_id").and_then(|v| v.as_u64())
+                .ok_or_else(|| McpError::validation("Missing subscription_id parameter").with_parameter("subscription_id"))?;
+
+            Ok(serde_json::json!({
+                "success": false,
+                "status": "WebSocket subscriptions require WebSocket connection mode. Use 'solana-mcp-server websocket --port 8900' to enable real-time subscriptions."
+            }))
+        }
+        // Network Management Methods
+        "listSvmNetworks" => {
+            let serve_cached = arguments
+                .get("serveCached")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+
+            let ttl = {
+                let state_guard = state.read().await;
+                Duration::from_secs(state_guard.config.svm_networks_cache_ttl_seconds)
+            };
+
+            match crate::tools::list_svm_networks(serve_cached, ttl).await {
+                Ok(networks) => Ok(networks),
+                Err(e) => {
+                    log::error!("List SVM networks failed: {e}");
+                    return Ok(create_error_response_with_data(
+                        -32603,
+                        format!("List SVM networks failed: {e}"),
+                        id.unwrap_or(Value::Null),
+                        e.to_json(),
+                    ));
+                }
+            }
+        }
+        "enableSvmNetwork" => {
+            let network_id = arguments.get("networkId").and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing networkId parameter").with_parameter("networkId"))?;
+            let name = arguments.get("name").and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing name parameter").with_parameter("name"))?;
+            let rpc_url = arguments.get("rpcUrl").and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing rpcUrl parameter").with_parameter("rpcUrl"))?;
+
+            crate::tools::enable_svm_network(state.clone(), network_id, name, rpc_url).await
+                .context("Enable SVM network failed")
+        }
+        "getEnabledNetworks" => {
+            crate::tools::get_enabled_networks(state.clone()).await
+                .context("Get enabled networks failed")
+        }
+        "probeNetworkMethods" => {
+            let network_id = arguments.get("networkId").and_then(|v| v.as_str());
+            let refresh = arguments.get("refresh").and_then(|v| v.as_bool()).unwrap_or(false);
+
+            probe_network_methods(state.clone(), network_id, refresh).await
+                .context("Probe network methods failed")
+        }
+        "getServerConfig" => {
+            let state_guard = state.read().await;
+            Ok(state_guard.config.sanitized())
+        }
+        "disableSvmNetwork" => {
+            let network_id = arguments.get("networkId").and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing networkId parameter").with_parameter("networkId"))?;
+
+            crate::tools::disable_svm_network(state.clone(), network_id).await
+                .context("Disable SVM network failed")
+        }
+        "setNetworkRpcUrl" => {
+            let network_id = arguments.get("networkId").and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing networkId parameter").with_parameter("networkId"))?;
+            let rpc_url = arguments.get("rpcUrl").and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing rpcUrl parameter").with_parameter("rpcUrl"))?;
+
+            crate::tools::set_network_rpc_url(state.clone(), network_id, rpc_url).await
+                .context("Set network RPC URL failed")
+        }
+        "getTokenAccountsByMint" => {
+            let mint_str = arguments
+                .get("mint")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing mint parameter").with_parameter("mint"))?;
+            let mint = validate_pubkey(mint_str, "mint")?;
+
+            let state_guard = state.read().await;
+            crate::rpc::tokens::get_token_accounts_by_mint(state_guard.get_next_rpc_client(), &mint, commitment).await
+                .context("Get token accounts by mint failed")
+        }
+        "getTransaction" => {
+            let signature_str = arguments
+                .get("signature")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing signature parameter").with_parameter("signature"))?;
+            let signature = validate_signature(signature_str, "signature")?;
+
+            let encoding = match arguments.get("encoding").and_then(|v| v.as_str()) {
+                Some("jsonParsed") => solana_transaction_status::UiTransactionEncoding::JsonParsed,
+                Some("base58") => solana_transaction_status::UiTransactionEncoding::Base58,
+                Some("base64") => solana_transaction_status::UiTransactionEncoding::Base64,
+                _ => solana_transaction_status::UiTransactionEncoding::Json,
+            };
+
+            let parse_instructions = arguments
+                .get("parseInstructions")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let max_supported_transaction_version = max_transaction_version_argument(&arguments)?;
+
+            let state_guard = state.read().await;
+            crate::rpc::transactions::get_transaction_parsed(
+                state_guard.get_next_rpc_client(),
+                &signature,
+                encoding,
+                commitment,
+                max_supported_transaction_version,
+                parse_instructions,
+            )
+            .await
+            .context("Get transaction failed")
+        }
+        "getTransactionWithConfig" => {
+            let signature_str = arguments
+                .get("signature")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing signature parameter").with_parameter("signature"))?;
+            let signature = validate_signature(signature_str, "signature")?;
+            let encoding = transaction_encoding_argument(&arguments)?;
+            let max_supported_transaction_version = max_transaction_version_argument(&arguments)?;
+
+            let state_guard = state.read().await;
+            crate::rpc::transactions::get_transaction_with_config(
+                state_guard.get_next_rpc_client(),
+                &signature,
+                encoding,
+                commitment,
+                max_supported_transaction_version,
+            )
+            .await
+            .context("Get transaction with config failed")
+        }
+        "getSignaturesForAddress" => {
+            let address_str = arguments
+                .get("address")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing address parameter").with_parameter("address"))?;
+            let address = validate_pubkey(address_str, "address")?;
+            let before = arguments
+                .get("before")
+                .and_then(|v| v.as_str())
+                .map(|s| validate_signature(s, "before"))
+                .transpose()?;
+            let until = arguments
+                .get("until")
+                .and_then(|v| v.as_str())
+                .map(|s| validate_signature(s, "until"))
+                .transpose()?;
+            let limit = optional_u64_argument(&arguments, "limit")?;
+
+            let state_guard = state.read().await;
+            crate::rpc::transactions::get_signatures_for_address(
+                state_guard.get_next_rpc_client(),
+                &address,
+                before,
+                until,
+                limit,
+                commitment,
+            )
+            .await
+            .context("Get signatures for address failed")
+        }
+        "sendTransaction" => {
+            let (transaction, encoding) = transaction_argument(&arguments)?;
+            let skip_preflight = arguments.get("skipPreflight").and_then(|v| v.as_bool()).unwrap_or(false);
+            let max_retries = optional_u64_argument(&arguments, "maxRetries")?.map(|retries| retries as usize);
+
+            let state_guard = state.read().await;
+            crate::rpc::transactions::send_transaction_with_config(
+                state_guard.get_next_rpc_client(),
+                transaction,
+                encoding,
+                skip_preflight,
+                commitment.map(|commitment| commitment.commitment),
+                max_retries,
+                None,
+            )
+            .await
+            .context("Send transaction failed")
+        }
+        "simulateTransaction" => {
+            let (transaction, encoding) = transaction_argument(&arguments)?;
+            let sig_verify = arguments.get("sigVerify").and_then(|v| v.as_bool()).unwrap_or(false);
+            let replace_recent_blockhash = arguments
+                .get("replaceRecentBlockhash")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if sig_verify && replace_recent_blockhash {
+                return Err(McpError::validation("sigVerify cannot be combined with replaceRecentBlockhash")
+                    .with_parameter("replaceRecentBlockhash")
+                    .into());
+            }
+
+            let state_guard = state.read().await;
+            crate::rpc::transactions::simulate_transaction_with_config(
+                state_guard.get_next_rpc_client(),
+                transaction,
+                encoding,
+                sig_verify,
+                commitment,
+                replace_recent_blockhash,
+                None,
+                None,
+            )
+            .await
+            .context("Simulate transaction failed")
+        }
+        "estimateComputeUnits" => {
+            let (transaction, encoding) = transaction_argument(&arguments)?;
+            let margin_percent = optional_u64_argument(&arguments, "marginPercent")?.unwrap_or(DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT);
+            if margin_percent > MAX_COMPUTE_UNIT_MARGIN_PERCENT {
+                return Err(McpError::validation(format!(
+                    "marginPercent must be at most {MAX_COMPUTE_UNIT_MARGIN_PERCENT}"
+                ))
+                .with_parameter("marginPercent")
+                .into());
+            }
+
+            let state_guard = state.read().await;
+            crate::rpc::transactions::estimate_compute_units(
+                state_guard.get_next_rpc_client(),
+                transaction,
+                encoding,
+                commitment,
+                margin_percent,
+            )
+            .await
+            .context("Estimate compute units failed")
+        }
+        "confirmTransaction" => {
+            let signature_str = arguments
+                .get("signature")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing signature parameter").with_parameter("signature"))?;
+            let signature = validate_signature(signature_str, "signature")?;
+
+            let commitment = commitment.unwrap_or_else(CommitmentConfig::confirmed);
+
+            let state_guard = state.read().await;
+            crate::rpc::transactions::confirm_transaction(state_guard.get_next_rpc_client(), &signature, commitment).await
+                .context("Confirm transaction failed")
+        }
+        "getTokenAccountsByOwner" => {
+            let owner_str = arguments
+                .get("owner")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing owner parameter").with_parameter("owner"))?;
+            let owner = validate_pubkey(owner_str, "owner")?;
+            let program_id = arguments
+                .get("programId")
+                .and_then(|v| v.as_str())
+                .map(|s| validate_pubkey(s, "programId"))
+                .transpose()?;
+
+            let state_guard = state.read().await;
+            crate::rpc::tokens::get_token_accounts_by_owner(state_guard.get_next_rpc_client(), &owner, program_id.as_ref(), commitment).await
+                .context("Get token accounts by owner failed")
+        }
+        "requestAirdrop" => {
+            let pubkey_str = arguments
+                .get("pubkey")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing pubkey parameter").with_parameter("pubkey"))?;
+            let pubkey = validate_pubkey(pubkey_str, "pubkey")?;
+            let lamports = required_u64_argument(&arguments, "lamports")?;
 
-            Ok(serde_json::json!({
-                "success": false,
-                "status": "WebSocket subscriptions require WebSocket connection mode. Use 'solana-mcp-server websocket --port 8900' to enable real-time subscriptions."
-            }))
+            let state_guard = state.read().await;
+            crate::rpc::system::request_airdrop(state_guard.get_next_rpc_client(), &pubkey, lamports).await
+                .context("Request airdrop failed")
         }
-        "voteSubscribe" => {
-            Ok(serde_json::json!({
-                "subscription_id": 9,
-                "status": "WebSocket subscriptions require WebSocket connection mode. Use 'solana-mcp-server websocket --port 8900' to enable real-time subscriptions."
-            }))
+        "getTokenAccountsByDelegate" => {
+            use solana_client::rpc_request::TokenAccountsFilter;
+
+            let delegate_str = arguments
+                .get("delegate")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing delegate parameter").with_parameter("delegate"))?;
+            let delegate = validate_pubkey(delegate_str, "delegate")?;
+            let mint = arguments
+                .get("mint")
+                .and_then(|v| v.as_str())
+                .map(|s| validate_pubkey(s, "mint"))
+                .transpose()?;
+            let program_id = arguments
+                .get("programId")
+                .and_then(|v| v.as_str())
+                .map(|s| validate_pubkey(s, "programId"))
+                .transpose()?;
+            // A mint already pins the token program, so it takes precedence
+            let filter = match mint {
+                Some(mint) => TokenAccountsFilter::Mint(mint),
+                None => TokenAccountsFilter::ProgramId(
+                    crate::rpc::tokens::resolve_token_program_id(program_id.as_ref())
+                        .map_err(|e| McpError::validation(e.to_string()).with_parameter("programId"))?,
+                ),
+            };
+
+            let state_guard = state.read().await;
+            crate::rpc::tokens::get_token_accounts_by_delegate(state_guard.get_next_rpc_client(), &delegate, filter).await
+                .context("Get token accounts by delegate failed")
         }
-        "voteUnsubscribe" => {
-            let _subscription_id = arguments.get("subscription_id").and_then(|v| v.as_u64())
-                .ok_or_else(|| anyhow::anyhow!("Missing subscription_id parameter"))?;
+        "getTokenLargestAccounts" => {
+            let mint_str = arguments
+                .get("mint")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing mint parameter").with_parameter("mint"))?;
+            let mint = validate_pubkey(mint_str, "mint")?;
 
-            Ok(serde_json::json!({
-                "success": false,
-                "status": "WebSocket subscriptions require WebSocket connection mode. Use 'solana-mcp-server websocket --port 8900' to enable real-time subscriptions."
-            }))
+            let state_guard = state.read().await;
+            let client = state_guard.get_next_rpc_client();
+            match commitment {
+                Some(commitment) => {
+                    crate::rpc::tokens::get_token_largest_accounts_with_commitment(client, &mint, commitment).await
+                }
+                None => crate::rpc::tokens::get_token_largest_accounts(client, &mint).await,
+            }
+            .context("Get token largest accounts failed")
         }
-        // Network Management Methods
-        "listSvmNetworks" => {
-            crate::tools::list_svm_networks().await
-                .map_err(|e| anyhow::anyhow!("List SVM networks failed: {}", e))
+        "getTokenSupply" => {
+            let mint_str = arguments
+                .get("mint")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing mint parameter").with_parameter("mint"))?;
+            let mint = validate_pubkey(mint_str, "mint")?;
+            let program_id = arguments
+                .get("programId")
+                .and_then(|v| v.as_str())
+                .map(|s| validate_pubkey(s, "programId"))
+                .transpose()?;
+
+            let state_guard = state.read().await;
+            crate::rpc::tokens::get_token_supply_for_program(state_guard.get_next_rpc_client(), &mint, program_id.as_ref(), commitment).await
+                .context("Get token supply failed")
         }
-        "enableSvmNetwork" => {
-            let network_id = arguments.get("networkId").and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing networkId parameter"))?;
-            let name = arguments.get("name").and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing name parameter"))?;
-            let rpc_url = arguments.get("rpcUrl").and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing rpcUrl parameter"))?;
+        "getTokenAccountBalance" => {
+            let account_str = arguments
+                .get("account")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing account parameter").with_parameter("account"))?;
+            let account = validate_pubkey(account_str, "account")?;
+            let program_id = arguments
+                .get("programId")
+                .and_then(|v| v.as_str())
+                .map(|s| validate_pubkey(s, "programId"))
+                .transpose()?;
 
-            crate::tools::enable_svm_network(state.clone(), network_id, name, rpc_url).await
-                .map_err(|e| anyhow::anyhow!("Enable SVM network failed: {}", e))
+            let state_guard = state.read().await;
+            crate::rpc::tokens::get_token_account_balance_for_program(state_guard.get_next_rpc_client(), &account, program_id.as_ref(), commitment).await
+                .context("Get token account balance failed")
         }
-        "disableSvmNetwork" => {
-            let network_id = arguments.get("networkId").and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing networkId parameter"))?;
+        "getTokenUiBalance" => {
+            let account_str = arguments
+                .get("account")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing account parameter").with_parameter("account"))?;
+            let account = validate_pubkey(account_str, "account")?;
 
-            crate::tools::disable_svm_network(state.clone(), network_id).await
-                .map_err(|e| anyhow::anyhow!("Disable SVM network failed: {}", e))
+            let state_guard = state.read().await;
+            crate::rpc::tokens::get_token_ui_balance(state_guard.get_next_rpc_client(), &account).await
+                .context("Get token UI balance failed")
         }
-        "setNetworkRpcUrl" => {
-            let network_id = arguments.get("networkId").and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing networkId parameter"))?;
-            let rpc_url = arguments.get("rpcUrl").and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing rpcUrl parameter"))?;
+        "getWalletTokenHoldings" => {
+            let owner_str = arguments
+                .get("owner")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing owner parameter").with_parameter("owner"))?;
+            let owner = validate_pubkey(owner_str, "owner")?;
+            let program_id = arguments
+                .get("programId")
+                .and_then(|v| v.as_str())
+                .map(|s| validate_pubkey(s, "programId"))
+                .transpose()?;
+            let include_zero = arguments.get("includeZero").and_then(|v| v.as_bool()).unwrap_or(false);
 
-            crate::tools::set_network_rpc_url(state.clone(), network_id, rpc_url).await
-                .map_err(|e| anyhow::anyhow!("Set network RPC URL failed: {}", e))
+            let state_guard = state.read().await;
+            crate::rpc::tokens::get_wallet_token_holdings(state_guard.get_next_rpc_client(), &owner, program_id.as_ref(), include_zero).await
+                .context("Get wallet token holdings failed")
         }
-        "getTokenAccountsByMint" => {
+        "getAssociatedTokenAddress" => {
+            let owner_str = arguments
+                .get("owner")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| McpError::validation("Missing owner parameter").with_parameter("owner"))?;
             let mint_str = arguments
                 .get("mint")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing mint parameter"))?;
-            let mint = Pubkey::try_from(mint_str)?;
+                .ok_or_else(|| McpError::validation("Missing mint parameter").with_parameter("mint"))?;
+            let owner = validate_pubkey(owner_str, "owner")?;
+            let mint = validate_pubkey(mint_str, "mint")?;
+            let token_program_id = arguments
+                .get("tokenProgramId")
+                .and_then(|v| v.as_str())
+                .map(|s| validate_pubkey(s, "tokenProgramId"))
+                .transpose()?;
 
-            let state_guard = state.read().await;
-            crate::rpc::tokens::get_token_accounts_by_mint(state_guard.get_next_rpc_client(), &mint).await
-                .map_err(|e| anyhow::anyhow!("Get token accounts by mint failed: {}", e))
+            crate::rpc::tokens::get_associated_token_address(&owner, &mint, token_program_id.as_ref())
+                .context("Derive associated token address failed")
         }
         "testSbpfProgram" => {
             let binary_b64 = arguments
                 .get("programBinary")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing programBinary parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing programBinary parameter").with_parameter("programBinary"))?;
 
             let binary = base64::engine::general_purpose::STANDARD
                 .decode(binary_b64)
-                .map_err(|e| anyhow::anyhow!("Invalid base64: {}", e))?;
+                .map_err(|e| McpError::validation(format!("Invalid base64: {e}")).with_parameter("programBinary"))?;
 
             let accounts = arguments
                 .get("accounts")
@@ -2445,7 +4700,7 @@ pub async fn handle_tools_call(
                         .collect::<Result<Vec<_>, _>>()
                 })
                 .transpose()
-                .map_err(|e| anyhow::anyhow!("Invalid accounts: {}", e))?
+                .map_err(|e| McpError::validation(format!("Invalid accounts: {e}")).with_parameter("accounts"))?
                 .unwrap_or_default();
 
             let instruction_data = arguments
@@ -2468,51 +4723,94 @@ pub async fn handle_tools_call(
                 accounts,
                 instruction_data,
                 signers,
+                instructions: Vec::new(),
+                compute_unit_limit: None,
             };
 
             let executor = crate::sbpf::TestExecutor::new();
             executor.execute_test(test_params).await
                 .map(|result| serde_json::to_value(result).unwrap())
-                .map_err(|e| anyhow::anyhow!("Test execution failed: {}", e))
+                .context("Test execution failed")
         }
-        "validateSbpfBinary" => {
-            let binary_b64 = arguments
-                .get("programBinary")
+        "runSbpfTest" => {
+            let local_file_access = state.read().await.local_file_access;
+            let binary = load_sbpf_binary(&arguments, local_file_access)?;
+
+            let accounts: Vec<crate::sbpf::AccountSpec> = match arguments.get("accounts") {
+                Some(v) => serde_json::from_value(v.clone()).map_err(|e| {
+                    McpError::validation(format!("Invalid account fixtures: {e}")).with_parameter("accounts")
+                })?,
+                None => Vec::new(),
+            };
+            let instructions: Vec<crate::sbpf::InstructionSpec> = match arguments.get("instructions") {
+                Some(v) => serde_json::from_value(v.clone()).map_err(|e| {
+                    McpError::validation(format!("Invalid instruction fixtures: {e}"))
+                        .with_parameter("instructions")
+                })?,
+                None => Vec::new(),
+            };
+            let instruction_data = arguments
+                .get("instructionData")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing programBinary parameter"))?;
+                .map(|s| s.to_string());
+            let compute_unit_limit = arguments
+                .get("computeUnitLimit")
+                .and_then(|v| v.as_u64())
+                .map(|limit| limit.min(u32::MAX as u64) as u32);
 
-            let binary = base64::engine::general_purpose::STANDARD
-                .decode(binary_b64)
-                .map_err(|e| anyhow::anyhow!("Invalid base64: {}", e))?;
+            let test_params = crate::sbpf::TestParams {
+                binary,
+                accounts,
+                instruction_data,
+                signers: Vec::new(),
+                instructions,
+                compute_unit_limit,
+            };
+
+            let executor = crate::sbpf::TestExecutor::new();
+            match executor
+                .execute_test_with_limits(test_params, crate::sbpf::TEST_WALL_CLOCK_LIMIT)
+                .await
+            {
+                Ok(result) => Ok(serde_json::to_value(result).unwrap()),
+                Err(e) => match McpError::from(e) {
+                    err @ McpError::Validation { .. } => return Err(err.into()),
+                    err => Err(anyhow::anyhow!("Test execution failed: {}", err)),
+                },
+            }
+        }
+        "validateSbpfBinary" => {
+            let local_file_access = state.read().await.local_file_access;
+            let binary = load_sbpf_binary(&arguments, local_file_access)?;
 
-            crate::sbpf::TestExecutor::validate_only(&binary)
-                .map(|metadata| serde_json::to_value(metadata).unwrap())
-                .map_err(|e| anyhow::anyhow!("Validation failed: {}", e))
+            let metadata = crate::sbpf::TestExecutor::validate_only(&binary)
+                .map_err(McpError::from)?;
+            Ok(serde_json::to_value(metadata).unwrap())
         }
         "deploySbpfProgramLocal" => {
             let binary_b64 = arguments
                 .get("programBinary")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing programBinary parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing programBinary parameter").with_parameter("programBinary"))?;
 
             let binary = base64::engine::general_purpose::STANDARD
                 .decode(binary_b64)
-                .map_err(|e| anyhow::anyhow!("Invalid base64: {}", e))?;
+                .map_err(|e| McpError::validation(format!("Invalid base64: {e}")).with_parameter("programBinary"))?;
 
             let vm = crate::sbpf::SbpfVmWrapper::new();
             vm.deploy_program(binary).await
                 .map(|response| serde_json::to_value(response).unwrap())
-                .map_err(|e| anyhow::anyhow!("Deployment failed: {}", e))
+                .context("Deployment failed")
         }
         "prepareDevnetDeploy" => {
             let binary_b64 = arguments
                 .get("programBinary")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing programBinary parameter"))?;
+                .ok_or_else(|| McpError::validation("Missing programBinary parameter").with_parameter("programBinary"))?;
 
             let binary = base64::engine::general_purpose::STANDARD
                 .decode(binary_b64)
-                .map_err(|e| anyhow::anyhow!("Invalid base64: {}", e))?;
+                .map_err(|e| McpError::validation(format!("Invalid base64: {e}")).with_parameter("programBinary"))?;
 
             let rpc_url = arguments
                 .get("rpcUrl")
@@ -2522,21 +4820,84 @@ pub async fn handle_tools_call(
             let deployer = crate::sbpf::DevnetDeployer::new(rpc_url);
             deployer.prepare_deployment(binary).await
                 .map(|response| serde_json::to_value(response).unwrap())
-                .map_err(|e| anyhow::anyhow!("Devnet deployment preparation failed: {}", e))
+                .context("Devnet deployment preparation failed")
         }
-        "securityScanSbpfBinary" => {
-            let binary_b64 = arguments
-                .get("programBinary")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow::anyhow!("Missing programBinary parameter"))?;
+        "deploySbpfToDevnet" => {
+            let (local_file_access, deploy_config) = {
+                let state_guard = state.read().await;
+                (state_guard.local_file_access, state_guard.config.sbpf_deploy.clone())
+            };
+            let binary = load_sbpf_binary(&arguments, local_file_access)?;
 
-            let binary = base64::engine::general_purpose::STANDARD
-                .decode(binary_b64)
-                .map_err(|e| anyhow::anyhow!("Invalid base64: {}", e))?;
+            let payer = match arguments.get("payerKeypair").and_then(|v| v.as_str()) {
+                Some(encoded) => crate::sbpf::parse_payer_keypair(encoded).map_err(McpError::from)?,
+                None => {
+                    let path = deploy_config.payer_keypair_path.as_deref().ok_or_else(|| {
+                        McpError::validation("No payerKeypair supplied and sbpf_deploy.payer_keypair_path is not configured")
+                            .with_parameter("payerKeypair")
+                    })?;
+                    solana_sdk::signature::read_keypair_file(path)
+                        .map_err(|e| anyhow::anyhow!("Failed to read configured payer keypair: {}", e))?
+                }
+            };
 
-            crate::sbpf::SecurityScanner::scan(&binary)
-                .map(|result| serde_json::to_value(result).unwrap())
-                .map_err(|e| anyhow::anyhow!("Security scan failed: {}", e))
+            let deployer = crate::sbpf::DevnetDeployer::new(Some(deploy_config.rpc_url));
+            match deployer.deploy(binary, &payer).await {
+                Ok(response) => Ok(serde_json::to_value(response).unwrap()),
+                Err(e) => match McpError::from(e) {
+                    err @ McpError::Validation { .. } => return Err(err.into()),
+                    err => Err(anyhow::anyhow!("Devnet deployment failed: {}", err)),
+                },
+            }
+        }
+        "scanSbpfBinary" | "securityScanSbpfBinary" => {
+            let local_file_access = state.read().await.local_file_access;
+            let binary = load_sbpf_binary(&arguments, local_file_access)?;
+
+            let min_severity = arguments
+                .get("minSeverity")
+                .map(|v| serde_json::from_value::<crate::sbpf::Severity>(v.clone()))
+                .transpose()
+                .map_err(|_| {
+                    McpError::validation(
+                        "minSeverity must be one of critical, high, medium, low, info",
+                    )
+                    .with_parameter("minSeverity")
+                })?;
+            let summary_only = arguments
+                .get("summaryOnly")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            let mut options = crate::sbpf::ScanOptions::default();
+            if let Some(threshold) = arguments.get("divisionThreshold") {
+                options.division_threshold = threshold
+                    .as_u64()
+                    .ok_or_else(|| {
+                        McpError::validation("divisionThreshold must be a non-negative integer")
+                            .with_parameter("divisionThreshold")
+                    })? as usize;
+            }
+
+            let mut result = crate::sbpf::SecurityScanner::scan_with_options(&binary, &options)
+                .map_err(McpError::from)?;
+
+            if summary_only {
+                Ok(serde_json::json!({
+                    "critical_count": result.critical_count,
+                    "high_count": result.high_count,
+                    "medium_count": result.medium_count,
+                    "low_count": result.low_count,
+                    "info_count": result.info_count,
+                    "risk_score": result.risk_score,
+                    "passed": result.passed
+                }))
+            } else {
+                if let Some(min) = min_severity {
+                    result.retain_min_severity(&min);
+                }
+                Ok(serde_json::to_value(result).unwrap())
+            }
         }
         "getSbpfReadme" => {
             Ok(serde_json::json!({
@@ -2831,57 +5192,812 @@ pub async fn handle_tools_call(
                 }
             }))
         }
-        _ => {
-            return Ok(create_error_response(
-                -32601,
-                format!("Tool not found: {tool_name}"),
-                id.unwrap_or(Value::Null),
-                None,
-            ));
+        _ => {
+            return Ok(create_error_response(
+                -32601,
+                format!("Tool not found: {tool_name}"),
+                id.unwrap_or(Value::Null),
+                None,
+            ));
+        }
+    };
+    
+    match result {
+        Ok(result_value) => {
+            let result_value = match timing_endpoint {
+                Some(rpc_url) => attach_timing_meta(result_value, started.elapsed(), rpc_url),
+                None => result_value,
+            };
+            Ok(create_success_response(result_value, id.unwrap_or(Value::Null)))
+        }
+        // Rendered with their structured data by handle_tools_call
+        Err(e) if e.downcast_ref::<McpError>().is_some() => Err(e),
+        Err(e) => {
+            log::error!("Tool execution failed: {e:#}");
+            Ok(create_error_response(
+                -32603,
+                format!("Tool execution failed: {e:#}"),
+                id.unwrap_or(Value::Null),
+                None,
+            ))
+        }
+    }
+}
+
+/// Largest program binary accepted by the sBPF tools, in bytes
+pub const MAX_SBPF_TOOL_BINARY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Tools answered by the server itself without calling a Solana RPC endpoint
+const LOCAL_TOOLS: &[&str] = &[
+    "decodeAccountData",
+    "decodeProgramError",
+    "getAssociatedTokenAddress",
+    "getEnabledNetworks",
+    "probeNetworkMethods",
+    "getServerConfig",
+    "listSvmNetworks",
+    "enableSvmNetwork",
+    "disableSvmNetwork",
+    "setNetworkRpcUrl",
+    "testSbpfProgram",
+    "runSbpfTest",
+    "validateSbpfBinary",
+    "deploySbpfProgramLocal",
+    "prepareDevnetDeploy",
+    "scanSbpfBinary",
+    "securityScanSbpfBinary",
+    "getSbpfReadme",
+    "getSbpfTutorial",
+    "getSbpfExamples",
+    "getSbpfFaq",
+];
+
+/// Top-level arguments that always carry a single base58 public key
+const PUBKEY_ARGUMENTS: &[&str] = &[
+    "pubkey",
+    "programId",
+    "mint",
+    "owner",
+    "delegate",
+    "address",
+    "account",
+    "identity",
+    "votePubkey",
+    "tokenProgramId",
+];
+
+/// Top-level arguments that carry an array of base58 public keys
+const PUBKEY_ARRAY_ARGUMENTS: &[&str] = &["pubkeys", "addresses"];
+
+/// Upstream Solana RPC methods a tool issues, as reported by dry runs
+///
+/// Most tools map one-to-one onto the RPC method of the same name; composite
+/// tools list every method they call and local tools list none.
+fn planned_rpc_methods(tool_name: &str) -> Vec<&str> {
+    match tool_name {
+        name if LOCAL_TOOLS.contains(&name) => Vec::new(),
+        name if name.ends_with("Subscribe") || name.ends_with("Unsubscribe") => Vec::new(),
+        "getClusterStatus" => vec!["getSlot", "getBlockHeight", "getEpochInfo", "getHealth"],
+        "getHealthDetailed" => vec!["getHealth", "getSlot", "getMaxShredInsertSlot"],
+        "estimateComputeUnits" => vec!["simulateTransaction"],
+        "getBalances" => vec!["getMultipleAccounts"],
+        "getAccountOwner" | "isProgram" | "getTokenUiBalance" | "resolveDomain" => vec!["getAccountInfo"],
+        "getTokenAccountsByMint" | "getProgramAccountsParsed" => vec!["getProgramAccounts"],
+        "getWalletTokenHoldings" => vec!["getTokenAccountsByOwner", "getMultipleAccounts"],
+        "getHistoricalFeeStats" | "getRecentActivity" => vec!["getSignaturesForAddress", "getTransaction"],
+        "deploySbpfToDevnet" => vec![
+            "getGenesisHash",
+            "getBalance",
+            "getMinimumBalanceForRentExemption",
+            "getLatestBlockhash",
+            "sendTransaction",
+        ],
+        name => vec![name.strip_suffix("AndContext").unwrap_or(name)],
+    }
+}
+
+/// Checks arguments against a tool's input schema
+///
+/// Enforces required properties, JSON types and enum values of top-level
+/// properties; nested objects are left to the tool itself.
+fn validate_tool_arguments(arguments: &Value, schema: &Value) -> McpResult<()> {
+    let Some(arguments) = arguments.as_object() else {
+        return Err(McpError::validation("Tool arguments must be an object").with_parameter("arguments"));
+    };
+
+    let required = schema["required"].as_array().into_iter().flatten();
+    for field in required.filter_map(|v| v.as_str()) {
+        if arguments.get(field).map_or(true, Value::is_null) {
+            return Err(McpError::validation(format!("Missing {field} parameter")).with_parameter(field));
+        }
+    }
+
+    for (field, value) in arguments {
+        let Some(property) = schema["properties"].get(field) else {
+            continue;
+        };
+        if value.is_null() {
+            continue;
+        }
+
+        let types: Vec<&str> = match &property["type"] {
+            Value::String(kind) => vec![kind.as_str()],
+            Value::Array(kinds) => kinds.iter().filter_map(|v| v.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        let type_matches = |kind: &&str| match *kind {
+            "string" => value.is_string(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => true,
+        };
+        if !types.is_empty() && !types.iter().any(type_matches) {
+            return Err(McpError::validation(format!(
+                "Invalid {field}: expected {}, got {value}",
+                types.join(" or ")
+            ))
+            .with_parameter(field));
+        }
+
+        if let Some(allowed) = property["enum"].as_array() {
+            if !allowed.contains(value) {
+                return Err(McpError::validation(format!(
+                    "Invalid {field} {value}: must be one of {}",
+                    Value::Array(allowed.clone())
+                ))
+                .with_parameter(field));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the RPC endpoint a tool call would be sent to, `None` for local tools
+///
+/// Peeks at the round-robin rotation without advancing it.
+fn planned_endpoint(tool_name: &str, state: &ServerState) -> Option<String> {
+    match tool_name {
+        _ if planned_rpc_methods(tool_name).is_empty() => None,
+        "deploySbpfToDevnet" => Some(state.config.sbpf_deploy.rpc_url.clone()),
+        _ => Some(state.peek_next_rpc_client().url()),
+    }
+}
+
+/// Adds `_meta.durationMs` and `_meta.rpcUrl` to a tool result
+///
+/// Non-object results are wrapped as `{ "value": ... }` so the metadata has
+/// somewhere to live, matching how payment settlements are attached.
+fn attach_timing_meta(result: Value, duration: Duration, rpc_url: Option<String>) -> Value {
+    let mut result = match result {
+        Value::Object(map) => map,
+        other => {
+            let mut map = serde_json::Map::new();
+            map.insert("value".to_string(), other);
+            map
+        }
+    };
+    let meta = result
+        .entry("_meta")
+        .or_insert_with(|| Value::Object(serde_json::Map::new()));
+    if !meta.is_object() {
+        *meta = Value::Object(serde_json::Map::new());
+    }
+    if let Some(meta) = meta.as_object_mut() {
+        meta.insert("durationMs".to_string(), serde_json::json!(duration.as_millis() as u64));
+        meta.insert("rpcUrl".to_string(), serde_json::json!(rpc_url));
+    }
+    Value::Object(result)
+}
+
+/// Explains why a commitment level is outside the `enum` of the tool's schema
+///
+/// Tools whose schema does not restrict `commitment` accept every level.
+fn unadvertised_commitment(tool_name: &str, commitment: &str) -> Option<String> {
+    let levels = TOOL_DEFINITIONS
+        .iter()
+        .find(|tool| tool.name == tool_name)?
+        .input_schema["properties"]["commitment"]["enum"]
+        .as_array()?;
+    if levels.iter().any(|level| level == commitment) {
+        return None;
+    }
+    let levels: Vec<&str> = levels.iter().filter_map(|level| level.as_str()).collect();
+    Some(format!(
+        "Commitment '{commitment}' is not supported by {tool_name}; use one of {}",
+        levels.join(", ")
+    ))
+}
+
+/// Validates a tool call and describes the upstream request it would make
+///
+/// Backs `dryRun: true`: arguments go through the tool's schema and the shared
+/// pubkey, signature, commitment and encoding validators, but nothing is sent
+/// upstream and the round-robin rotation is not advanced. .sol names are not
+/// looked up; they are listed under `unresolvedDomains` instead.
+///
+/// # Returns
+/// * `McpResult<Option<Value>>` - The planned call, `None` for unknown tools,
+///   or the validation error the real call would have raised
+fn explain_tool_call(
+    tool_name: &str,
+    arguments: &Value,
+    commitment: Option<CommitmentConfig>,
+    state: &ServerState,
+) -> McpResult<Option<Value>> {
+    let Some(definition) = TOOL_DEFINITIONS.iter().find(|tool| tool.name == tool_name) else {
+        return Ok(None);
+    };
+    validate_tool_arguments(arguments, &definition.input_schema)?;
+
+    let mut unresolved_domains = Vec::new();
+    for &field in PUBKEY_ARGUMENTS {
+        if let Some(value) = arguments.get(field).and_then(|v| v.as_str()) {
+            if crate::rpc::names::is_sol_domain(value) {
+                unresolved_domains.push(serde_json::json!({ "argument": field, "domain": value }));
+            } else {
+                validate_pubkey(value, field)?;
+            }
+        }
+    }
+    if let Some(signature) = arguments.get("signature").and_then(|v| v.as_str()) {
+        validate_signature(signature, "signature")?;
+    }
+    let max_pubkeys = match tool_name {
+        "getBalances" => MAX_BALANCES_TOTAL,
+        _ => MAX_MULTIPLE_ACCOUNTS,
+    };
+    for (field, max) in [("pubkeys", max_pubkeys), ("signatures", MAX_SIGNATURE_STATUSES_TOTAL)] {
+        if let Some(items) = arguments.get(field).and_then(|v| v.as_array()) {
+            validate_array_len(items.len(), max, field)?;
+        }
+    }
+    for &field in PUBKEY_ARRAY_ARGUMENTS {
+        for item in arguments.get(field).and_then(|v| v.as_array()).into_iter().flatten() {
+            let Some(item) = item.as_str() else { continue };
+            if crate::rpc::names::is_sol_domain(item) {
+                unresolved_domains.push(serde_json::json!({ "argument": field, "domain": item }));
+            } else {
+                validate_pubkey(item, field)?;
+            }
+        }
+    }
+    if let Some(items) = arguments.get("signatures").and_then(|v| v.as_array()) {
+        for item in items.iter().filter_map(|v| v.as_str()) {
+            validate_signature(item, "signatures")?;
+        }
+    }
+    let encoding = match tool_name {
+        "getAccountInfo" | "getMultipleAccounts" | "getProgramAccountsAndContext" => {
+            account_encoding_or_default(arguments, &state.config)?
+        }
+        _ => None,
+    };
+
+    let rpc_methods = planned_rpc_methods(tool_name);
+    let endpoint = planned_endpoint(tool_name, state);
+
+    // Commitment only matters when the call actually reaches an RPC node
+    let planned_commitment = endpoint.as_ref().map(|_| {
+        commitment
+            .map(|c| c.commitment.to_string())
+            .unwrap_or_else(|| state.config.commitment.clone())
+    });
+
+    let mut planned_arguments = arguments.clone();
+    if let Some(map) = planned_arguments.as_object_mut() {
+        map.remove("dryRun");
+    }
+
+    Ok(Some(serde_json::json!({
+        "dryRun": true,
+        "tool": tool_name,
+        "rpcMethods": rpc_methods,
+        "endpoint": endpoint.map(|url| sanitize_for_logging(&url)),
+        "commitment": planned_commitment,
+        "encoding": encoding,
+        "arguments": planned_arguments,
+        "unresolvedDomains": unresolved_domains,
+    })))
+}
+
+/// Reads an optional non-negative integer argument such as a slot or lamport amount
+///
+/// # Returns
+/// * `McpResult<Option<u64>>` - `None` when omitted or null, a validation
+///   error naming `field` for anything but an unsigned integer
+fn optional_u64_argument(arguments: &Value, field: &str) -> McpResult<Option<u64>> {
+    match arguments.get(field) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value.as_u64().map(Some).ok_or_else(|| {
+            McpError::validation(format!("Invalid {field}: expected a non-negative integer, got {value}"))
+                .with_parameter(field)
+        }),
+    }
+}
+
+/// Whether a getBlocks call opted into paging through a wide range with `scan: true`
+fn scan_argument(arguments: &Value) -> bool {
+    arguments.get("scan").and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// Rejects an explicit getBlocks range wider than `max_slots`
+///
+/// Without `endSlot` the node picks the end and enforces its own limit.
+fn validate_block_range(start_slot: u64, end_slot: Option<u64>, max_slots: u64) -> McpResult<()> {
+    let Some(end_slot) = end_slot else {
+        return Ok(());
+    };
+    let span = end_slot.saturating_sub(start_slot).saturating_add(1);
+    if span > max_slots {
+        return Err(McpError::validation(format!(
+            "Slot range {start_slot}-{end_slot} spans {span} slots, more than the {max_slots}-slot limit; \
+             use getBlocksWithLimit or pass scan: true to page through the range"
+        ))
+        .with_parameter("endSlot"));
+    }
+    Ok(())
+}
+
+/// Reads the optional `maxSupportedTransactionVersion` argument
+fn max_transaction_version_argument(arguments: &Value) -> McpResult<Option<u8>> {
+    optional_u64_argument(arguments, "maxSupportedTransactionVersion")?
+        .map(|version| {
+            u8::try_from(version).map_err(|_| {
+                McpError::validation(format!("Invalid maxSupportedTransactionVersion: {version}"))
+                    .with_parameter("maxSupportedTransactionVersion")
+            })
+        })
+        .transpose()
+}
+
+/// Reads a required non-negative integer argument
+fn required_u64_argument(arguments: &Value, field: &str) -> McpResult<u64> {
+    optional_u64_argument(arguments, field)?
+        .ok_or_else(|| McpError::validation(format!("Missing {field} parameter")).with_parameter(field))
+}
+
+/// Resolves the account encoding for a request, falling back to the
+/// configured `default_account_encoding` when the caller omits `encoding`
+fn account_encoding_or_default(arguments: &Value, config: &Config) -> McpResult<Option<UiAccountEncoding>> {
+    match parse_account_encoding(arguments.get("encoding"), "encoding")? {
+        Some(encoding) => Ok(Some(encoding)),
+        None => Ok(config
+            .default_account_encoding
+            .as_deref()
+            .and_then(account_encoding_from_name)),
+    }
+}
+
+/// Decodes a base58 or base64 wire-format argument according to `encoding`
+///
+/// # Arguments
+/// * `arguments` - Tool arguments
+/// * `field` - Name of the encoded argument
+///
+/// # Returns
+/// * `McpResult<(&str, &str, Vec<u8>)>` - The argument as supplied, its
+///   encoding (default base64) and the decoded bytes
+///
+/// # Errors
+/// * Validation error naming `encoding` for an unsupported encoding, or
+///   `field` when it is missing or badly encoded
+fn decode_wire_argument<'a>(arguments: &'a Value, field: &str) -> McpResult<(&'a str, &'a str, Vec<u8>)> {
+    let encoded = arguments
+        .get(field)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| McpError::validation(format!("Missing {field} parameter")).with_parameter(field))?;
+
+    let encoding = arguments.get("encoding").and_then(|v| v.as_str()).unwrap_or("base64");
+    let bytes = match encoding {
+        "base58" => bs58::decode(encoded).into_vec().map_err(|e| e.to_string()),
+        "base64" => base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| e.to_string()),
+        other => {
+            return Err(McpError::validation(format!(
+                "Invalid encoding '{other}': expected base58 or base64"
+            ))
+            .with_parameter("encoding"))
+        }
+    }
+    .map_err(|e| McpError::validation(format!("Invalid {field} encoding: {e}")).with_parameter(field))?;
+
+    Ok((encoded, encoding, bytes))
+}
+
+/// Decodes the `message` argument into a legacy message
+///
+/// # Errors
+/// * Validation error naming `encoding` or `message`, as for
+///   `decode_wire_argument`, or `message` when the bytes are not a valid message
+fn decode_message_argument(arguments: &Value) -> McpResult<solana_sdk::message::Message> {
+    let (_, _, bytes) = decode_wire_argument(arguments, "message")?;
+    bincode::deserialize(&bytes)
+        .map_err(|e| McpError::validation(format!("Invalid message: {e}")).with_parameter("message"))
+}
+
+/// Checks the `transaction` argument decodes into a transaction
+///
+/// # Returns
+/// * `McpResult<(&str, &str)>` - The encoded transaction and its encoding,
+///   as the rpc layer expects them
+fn transaction_argument(arguments: &Value) -> McpResult<(&str, &str)> {
+    let (encoded, encoding, bytes) = decode_wire_argument(arguments, "transaction")?;
+    bincode::deserialize::<solana_sdk::transaction::Transaction>(&bytes).map_err(|e| {
+        McpError::validation(format!("Invalid transaction: {e}")).with_parameter("transaction")
+    })?;
+    Ok((encoded, encoding))
+}
+
+/// Parses the `encoding` argument of block and transaction tools (default json)
+fn transaction_encoding_argument(arguments: &Value) -> McpResult<UiTransactionEncoding> {
+    match arguments.get("encoding") {
+        None | Some(Value::Null) => Ok(UiTransactionEncoding::Json),
+        Some(value) => match value.as_str() {
+            Some("json") => Ok(UiTransactionEncoding::Json),
+            Some("jsonParsed") => Ok(UiTransactionEncoding::JsonParsed),
+            Some("base58") => Ok(UiTransactionEncoding::Base58),
+            Some("base64") => Ok(UiTransactionEncoding::Base64),
+            _ => Err(McpError::validation(format!(
+                "Invalid encoding {value}: expected json, jsonParsed, base58 or base64"
+            ))
+            .with_parameter("encoding")),
+        },
+    }
+}
+
+/// Loads an sBPF binary from `programBinary` (base64) or `programPath`
+///
+/// # Arguments
+/// * `arguments` - Tool arguments
+/// * `local_file_access` - Whether `programPath` may be read from disk
+///
+/// # Returns
+/// * `McpResult<Vec<u8>>` - Binary bytes, or a validation error naming the
+///   offending parameter
+///
+/// # Security
+/// - Rejects oversized input before decoding or reading it in full
+/// - Only reads local files when the client shares the server's machine
+fn load_sbpf_binary(arguments: &Value, local_file_access: bool) -> McpResult<Vec<u8>> {
+    let too_large = |size: usize| crate::sbpf::SbpfError::BinaryTooLarge {
+        size,
+        max: MAX_SBPF_TOOL_BINARY_BYTES,
+    };
+
+    if let Some(encoded) = arguments.get("programBinary").and_then(|v| v.as_str()) {
+        // Base64 expands 3 bytes into 4 characters
+        if encoded.len() / 4 * 3 > MAX_SBPF_TOOL_BINARY_BYTES {
+            return Err(too_large(encoded.len() / 4 * 3).into());
+        }
+        let binary = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(crate::sbpf::SbpfError::from)?;
+        if binary.len() > MAX_SBPF_TOOL_BINARY_BYTES {
+            return Err(too_large(binary.len()).into());
         }
+        return Ok(binary);
+    }
+
+    let Some(path) = arguments.get("programPath").and_then(|v| v.as_str()) else {
+        return Err(McpError::validation("Missing programBinary parameter")
+            .with_parameter("programBinary"));
     };
-    
-    match result {
-        Ok(result_value) => Ok(create_success_response(result_value, id.unwrap_or(Value::Null))),
-        Err(e) => {
-            log::error!("Tool execution failed: {e}");
-            Ok(create_error_response(
-                -32603,
-                format!("Tool execution failed: {e}"),
-                id.unwrap_or(Value::Null),
-                None,
-            ))
+    if !local_file_access {
+        return Err(McpError::validation(
+            "programPath is only supported in stdio mode; send programBinary instead",
+        )
+        .with_parameter("programPath"));
+    }
+
+    let unreadable = |e: std::io::Error| {
+        McpError::validation(format!("Cannot read {path}: {e}")).with_parameter("programPath")
+    };
+    let size = std::fs::metadata(path).map_err(unreadable)?.len() as usize;
+    if size > MAX_SBPF_TOOL_BINARY_BYTES {
+        return Err(too_large(size).into());
+    }
+    std::fs::read(path).map_err(unreadable)
+}
+
+// SVM Network Management Functions
+
+/// Source URL for the awesome-svm network list
+const SVM_NETWORKS_URL: &str =
+    "https://raw.githubusercontent.com/openSVM/awesome-svm/refs/heads/main/svm-networks.json";
+
+/// Upper bound on how long a single awesome-svm fetch may take
+pub const SVM_NETWORKS_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay before the first retry after a failed awesome-svm fetch
+pub const SVM_NETWORKS_BACKOFF_BASE: Duration = Duration::from_secs(5);
+
+/// Upper bound on the retry delay after repeated fetch failures
+pub const SVM_NETWORKS_BACKOFF_MAX: Duration = Duration::from_secs(300);
+
+/// Network list together with the time it was fetched
+#[derive(Debug, Clone)]
+struct CachedSvmNetworks {
+    networks: Value,
+    fetched_at: std::time::Instant,
+}
+
+/// Consecutive fetch failures and when the next fetch may be attempted
+#[derive(Debug, Default)]
+struct SvmNetworksBackoff {
+    consecutive_failures: u32,
+    retry_at: Option<std::time::Instant>,
+    last_error: Option<SvmNetworksFetchError>,
+}
+
+/// Last successfully fetched awesome-svm network list
+///
+/// Fresh entries are served without refetching until the TTL lapses. Failed
+/// refreshes back off exponentially, serving the stale list in the meantime.
+#[derive(Debug, Default)]
+pub struct SvmNetworkListCache {
+    last_good: std::sync::RwLock<Option<CachedSvmNetworks>>,
+    backoff: std::sync::RwLock<SvmNetworksBackoff>,
+}
+
+impl SvmNetworkListCache {
+    /// Creates an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the last successfully fetched list, if any
+    pub fn get(&self) -> Option<Value> {
+        self.last_good
+            .read()
+            .ok()
+            .and_then(|guard| guard.as_ref().map(|cached| cached.networks.clone()))
+    }
+
+    /// Returns the cached list only if it was fetched within `ttl`
+    pub fn get_fresh(&self, ttl: Duration) -> Option<Value> {
+        if ttl.is_zero() {
+            return None;
+        }
+        self.last_good.read().ok().and_then(|guard| {
+            guard
+                .as_ref()
+                .filter(|cached| cached.fetched_at.elapsed() < ttl)
+                .map(|cached| cached.networks.clone())
+        })
+    }
+
+    /// Records a successfully fetched list and clears any backoff
+    pub fn store(&self, networks: Value) {
+        if let Ok(mut guard) = self.last_good.write() {
+            *guard = Some(CachedSvmNetworks {
+                networks,
+                fetched_at: std::time::Instant::now(),
+            });
+        }
+        if let Ok(mut backoff) = self.backoff.write() {
+            *backoff = SvmNetworksBackoff::default();
+        }
+    }
+
+    /// Whether a previously fetched list is available
+    pub fn is_available(&self) -> bool {
+        self.last_good.read().map(|guard| guard.is_some()).unwrap_or(false)
+    }
+
+    /// Records a failed fetch and returns the delay before the next attempt
+    pub fn record_failure(&self, error: &SvmNetworksFetchError) -> Duration {
+        let Ok(mut backoff) = self.backoff.write() else {
+            return SVM_NETWORKS_BACKOFF_BASE;
+        };
+        backoff.consecutive_failures = backoff.consecutive_failures.saturating_add(1);
+        let exponent = backoff.consecutive_failures.saturating_sub(1).min(16);
+        let delay = SVM_NETWORKS_BACKOFF_BASE
+            .saturating_mul(1u32 << exponent)
+            .min(SVM_NETWORKS_BACKOFF_MAX);
+        backoff.retry_at = Some(std::time::Instant::now() + delay);
+        backoff.last_error = Some(error.clone());
+        delay
+    }
+
+    /// Returns the last fetch error while the retry delay has not yet elapsed
+    pub fn backoff_error(&self) -> Option<SvmNetworksFetchError> {
+        let backoff = self.backoff.read().ok()?;
+        match backoff.retry_at {
+            Some(retry_at) if std::time::Instant::now() < retry_at => backoff.last_error.clone(),
+            _ => None,
         }
     }
 }
 
-use solana_sdk::pubkey::Pubkey;
+/// Process-wide caches backing `listSvmNetworks`, keyed on the source URL
+static SVM_NETWORK_LIST_CACHES: once_cell::sync::Lazy<
+    dashmap::DashMap<String, Arc<SvmNetworkListCache>>,
+> = once_cell::sync::Lazy::new(dashmap::DashMap::new);
 
-// SVM Network Management Functions
+/// Returns the shared cache for an awesome-svm list URL
+fn svm_network_list_cache(url: &str) -> Arc<SvmNetworkListCache> {
+    SVM_NETWORK_LIST_CACHES
+        .entry(url.to_string())
+        .or_insert_with(|| Arc::new(SvmNetworkListCache::new()))
+        .clone()
+}
+
+/// Structured failure when the awesome-svm list cannot be fetched
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SvmNetworksFetchError {
+    /// The fetch did not complete within the allotted time and was cancelled
+    #[error("Timed out after {timeout_ms}ms fetching SVM networks")]
+    Timeout { timeout_ms: u64, cached_available: bool },
+    /// The fetch completed with a transport, HTTP or parse error
+    #[error("Failed to fetch SVM networks: {message}")]
+    Failed { message: String, cached_available: bool },
+}
+
+impl SvmNetworksFetchError {
+    /// Whether a previously fetched list could be served instead
+    pub fn cached_available(&self) -> bool {
+        match self {
+            Self::Timeout { cached_available, .. } | Self::Failed { cached_available, .. } => {
+                *cached_available
+            }
+        }
+    }
+
+    /// JSON-RPC error data describing the failure
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Timeout { timeout_ms, cached_available } => serde_json::json!({
+                "reason": "timeout",
+                "timeoutMs": timeout_ms,
+                "cachedAvailable": cached_available
+            }),
+            Self::Failed { message, cached_available } => serde_json::json!({
+                "reason": "fetchFailed",
+                "detail": message,
+                "cachedAvailable": cached_available
+            }),
+        }
+    }
+}
+
+/// Runs an awesome-svm fetch under a timeout, falling back to the cached list
+///
+/// # Arguments
+/// * `fetch` - Future performing the actual fetch
+/// * `timeout` - Maximum time to wait before cancelling the fetch
+/// * `cache` - Cache updated on success, consulted on failure, and backed off after it
+/// * `serve_cached` - Whether to return the cached list when the fetch fails
+///
+/// # Returns
+/// * `Result<Value, SvmNetworksFetchError>` - The fresh list as-is, or
+///   `{networks, cached: true, error}` when served from cache
+pub async fn fetch_svm_networks_with_fallback<F>(
+    fetch: F,
+    timeout: Duration,
+    cache: &SvmNetworkListCache,
+    serve_cached: bool,
+) -> std::result::Result<Value, SvmNetworksFetchError>
+where
+    F: std::future::Future<Output = Result<Value>>,
+{
+    let error = match tokio::time::timeout(timeout, fetch).await {
+        Ok(Ok(networks)) => {
+            cache.store(networks.clone());
+            return Ok(networks);
+        }
+        Ok(Err(e)) => SvmNetworksFetchError::Failed {
+            message: e.to_string(),
+            cached_available: cache.is_available(),
+        },
+        Err(_) => SvmNetworksFetchError::Timeout {
+            timeout_ms: timeout.as_millis() as u64,
+            cached_available: cache.is_available(),
+        },
+    };
+
+    let delay = cache.record_failure(&error);
+    log::warn!("{error}; next SVM networks refresh in {}ms", delay.as_millis());
+
+    if serve_cached {
+        if let Some(networks) = cache.get() {
+            log::warn!("{error}; serving last successfully fetched SVM networks list");
+            return Ok(serde_json::json!({
+                "networks": networks,
+                "cached": true,
+                "error": error.to_json()
+            }));
+        }
+    }
+
+    Err(error)
+}
+
+/// Serves the awesome-svm list from cache while fresh, otherwise refetches
+///
+/// A list fetched within `ttl` is returned without touching the network; a
+/// zero `ttl` always refetches. While a failed refresh is backing off, the
+/// stale list is served (when `serve_cached` allows) instead of retrying, and
+/// with nothing to serve the failure that started the backoff is returned.
+///
+/// # Returns
+/// * `Result<Value, SvmNetworksFetchError>` - Same shapes as
+///   `fetch_svm_networks_with_fallback`
+pub async fn fetch_svm_networks_cached<F>(
+    fetch: F,
+    ttl: Duration,
+    timeout: Duration,
+    cache: &SvmNetworkListCache,
+    serve_cached: bool,
+) -> std::result::Result<Value, SvmNetworksFetchError>
+where
+    F: std::future::Future<Output = Result<Value>>,
+{
+    if let Some(networks) = cache.get_fresh(ttl) {
+        return Ok(networks);
+    }
+
+    if let Some(error) = cache.backoff_error() {
+        if let Some(networks) = cache.get().filter(|_| serve_cached) {
+            log::debug!("SVM networks refresh backing off; serving stale list");
+            return Ok(serde_json::json!({
+                "networks": networks,
+                "cached": true,
+                "error": error.to_json()
+            }));
+        }
+        log::debug!("SVM networks refresh backing off; returning last error");
+        return Err(error);
+    }
+
+    fetch_svm_networks_with_fallback(fetch, timeout, cache, serve_cached).await
+}
 
 /// Fetches the latest list of SVM networks from the awesome-svm repository
 ///
+/// # Arguments
+/// * `serve_cached` - Whether to fall back to the last good list on failure
+/// * `ttl` - How long a fetched list is served without refetching; zero
+///   always fetches fresh
+///
 /// # Returns
-/// * `Result<Value>` - JSON containing available SVM networks
+/// * `Result<Value, SvmNetworksFetchError>` - JSON containing available SVM networks
 ///
 /// # Security
 /// - Uses HTTPS to fetch network list
-/// - Does not cache data to ensure freshness
+/// - Backs off exponentially after failures to avoid hammering GitHub
 /// - Validates response format
-async fn list_svm_networks() -> Result<Value> {
-    let url =
-        "https://raw.githubusercontent.com/openSVM/awesome-svm/refs/heads/main/svm-networks.json";
-    log::info!("Fetching SVM networks from: {}", sanitize_for_logging(url));
+async fn list_svm_networks(
+    serve_cached: bool,
+    ttl: Duration,
+) -> std::result::Result<Value, SvmNetworksFetchError> {
+    fetch_svm_networks_cached(
+        fetch_svm_networks(),
+        ttl,
+        SVM_NETWORKS_FETCH_TIMEOUT,
+        &svm_network_list_cache(SVM_NETWORKS_URL),
+        serve_cached,
+    )
+    .await
+}
+
+/// Performs a single fetch of the awesome-svm network list
+async fn fetch_svm_networks() -> Result<Value> {
+    log::info!("Fetching SVM networks from: {}", sanitize_for_logging(SVM_NETWORKS_URL));
 
     let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
+        .timeout(SVM_NETWORKS_FETCH_TIMEOUT)
         .build()?;
 
     let response = client
-        .get(url)
+        .get(SVM_NETWORKS_URL)
         .send()
         .await
-        .map_err(|e| anyhow::anyhow!("Failed to fetch SVM networks: {}", e))?;
+        .context("Failed to fetch SVM networks")?;
 
     if !response.status().is_success() {
         return Err(anyhow::anyhow!(
@@ -2893,7 +6009,7 @@ async fn list_svm_networks() -> Result<Value> {
     let networks: Value = response
         .json()
         .await
-        .map_err(|e| anyhow::anyhow!("Failed to parse SVM networks JSON: {}", e))?;
+        .context("Failed to parse SVM networks JSON")?;
 
     log::info!("Successfully fetched SVM networks list");
     Ok(networks)
@@ -2908,12 +6024,15 @@ async fn list_svm_networks() -> Result<Value> {
 /// * `rpc_url` - RPC endpoint URL (must be HTTPS)
 ///
 /// # Returns
-/// * `Result<Value>` - Success/error response
+/// * `Result<Value>` - Success/error response; `changed` is false when the
+///   network was already enabled with the same settings, in which case nothing
+///   is written
 ///
 /// # Security
 /// - Validates network ID format
 /// - Validates network name content
 /// - Enforces HTTPS for RPC URL
+/// - Restricts RPC host to `allowed_rpc_hosts` when configured
 /// - Saves configuration atomically
 async fn enable_svm_network(
     state: Arc<RwLock<ServerState>>,
@@ -2922,11 +6041,15 @@ async fn enable_svm_network(
     rpc_url: &str,
 ) -> Result<Value> {
     // Validate inputs
-    validate_network_id(network_id).map_err(|e| anyhow::anyhow!("Invalid network ID: {}", e))?;
+    validate_network_id(network_id)
+        .map_err(|e| McpError::validation(format!("Invalid network ID: {e}")).with_parameter("networkId"))?;
 
-    validate_network_name(name).map_err(|e| anyhow::anyhow!("Invalid network name: {}", e))?;
+    validate_network_name(name)
+        .map_err(|e| McpError::validation(format!("Invalid network name: {e}")).with_parameter("name"))?;
 
-    validate_rpc_url(rpc_url).map_err(|e| anyhow::anyhow!("Invalid RPC URL: {}", e))?;
+    let allowed_hosts = state.read().await.config.allowed_rpc_hosts.clone();
+    validate_rpc_url_with_allowlist(rpc_url, &allowed_hosts)
+        .map_err(|e| McpError::validation(format!("Invalid RPC URL: {e}")).with_parameter("rpcUrl"))?;
 
     log::info!(
         "Enabling SVM network '{}' ({}): {}",
@@ -2937,31 +6060,58 @@ async fn enable_svm_network(
 
     let mut state_guard = state.write().await;
 
+    // Re-enabling a network keeps its commitment override
     let network = SvmNetwork {
         name: name.to_string(),
         rpc_url: rpc_url.to_string(),
         enabled: true,
+        commitment: state_guard
+            .config
+            .svm_networks
+            .get(network_id)
+            .and_then(|existing| existing.commitment.clone()),
     };
 
+    // Repeating an identical call must not rewrite the config or rebuild clients
+    if state_guard.config.svm_networks.get(network_id) == Some(&network) {
+        log::info!("Network '{network_id}' already enabled with these settings");
+        return Ok(serde_json::json!({
+            "success": true,
+            "changed": false,
+            "message": format!("Network '{}' already enabled, no change", network_id)
+        }));
+    }
+
     let mut new_config = state_guard.config.clone();
-    new_config
+    let before = new_config
         .svm_networks
-        .insert(network_id.to_string(), network);
-
-    // Validate and save configuration
-    new_config
-        .save()
-        .map_err(|e| anyhow::anyhow!("Failed to save configuration: {}", e))?;
+        .insert(network_id.to_string(), network.clone());
 
-    state_guard.update_config(new_config);
+    commit_network_change(&mut state_guard, new_config)?;
+    crate::logging::log_audit_event("enableSvmNetwork", network_id, before.as_ref(), Some(&network));
 
     log::info!("Successfully enabled network '{network_id}'");
     Ok(serde_json::json!({
         "success": true,
+        "changed": true,
         "message": format!("Network '{}' enabled successfully", network_id)
     }))
 }
 
+/// Saves `new_config` and only then applies it to the running server
+///
+/// Callers derive `new_config` from `state_guard.config` and keep the write
+/// lock until this returns, so concurrent network changes cannot interleave
+/// and drop each other's edits. A failed save leaves both config.json and the
+/// running configuration untouched.
+fn commit_network_change(state_guard: &mut ServerState, new_config: Config) -> Result<()> {
+    new_config
+        .save()
+        .context("Failed to save configuration")?;
+    state_guard.update_config(new_config);
+    Ok(())
+}
+
 /// Disables an SVM network
 ///
 /// # Arguments
@@ -2971,27 +6121,27 @@ async fn enable_svm_network(
 /// # Returns
 /// * `Result<Value>` - Success/error response
 async fn disable_svm_network(state: Arc<RwLock<ServerState>>, network_id: &str) -> Result<Value> {
-    validate_network_id(network_id).map_err(|e| anyhow::anyhow!("Invalid network ID: {}", e))?;
+    validate_network_id(network_id)
+        .map_err(|e| McpError::validation(format!("Invalid network ID: {e}")).with_parameter("networkId"))?;
 
     log::info!("Disabling SVM network '{network_id}'");
 
     let mut state_guard = state.write().await;
 
     let mut new_config = state_guard.config.clone();
-    if let Some(network) = new_config.svm_networks.get_mut(network_id) {
+    let (before, after) = if let Some(network) = new_config.svm_networks.get_mut(network_id) {
+        let before = network.clone();
         network.enabled = false;
+        (before, network.clone())
     } else {
         return Ok(serde_json::json!({
             "success": false,
             "error": format!("Network '{}' not found", network_id)
         }));
-    }
-
-    new_config
-        .save()
-        .map_err(|e| anyhow::anyhow!("Failed to save configuration: {}", e))?;
+    };
 
-    state_guard.update_config(new_config);
+    commit_network_change(&mut state_guard, new_config)?;
+    crate::logging::log_audit_event("disableSvmNetwork", network_id, Some(&before), Some(&after));
 
     log::info!("Successfully disabled network '{network_id}'");
     Ok(serde_json::json!({
@@ -3013,15 +6163,19 @@ async fn disable_svm_network(state: Arc<RwLock<ServerState>>, network_id: &str)
 /// # Security
 /// - Validates network ID format
 /// - Enforces HTTPS for RPC URL
+/// - Restricts RPC host to `allowed_rpc_hosts` when configured
 /// - Validates configuration before saving
 async fn set_network_rpc_url(
     state: Arc<RwLock<ServerState>>,
     network_id: &str,
     rpc_url: &str,
 ) -> Result<Value> {
-    validate_network_id(network_id).map_err(|e| anyhow::anyhow!("Invalid network ID: {}", e))?;
+    validate_network_id(network_id)
+        .map_err(|e| McpError::validation(format!("Invalid network ID: {e}")).with_parameter("networkId"))?;
 
-    validate_rpc_url(rpc_url).map_err(|e| anyhow::anyhow!("Invalid RPC URL: {}", e))?;
+    let allowed_hosts = state.read().await.config.allowed_rpc_hosts.clone();
+    validate_rpc_url_with_allowlist(rpc_url, &allowed_hosts)
+        .map_err(|e| McpError::validation(format!("Invalid RPC URL: {e}")).with_parameter("rpcUrl"))?;
 
     log::info!(
         "Updating RPC URL for network '{}': {}",
@@ -3032,20 +6186,19 @@ async fn set_network_rpc_url(
     let mut state_guard = state.write().await;
 
     let mut new_config = state_guard.config.clone();
-    if let Some(network) = new_config.svm_networks.get_mut(network_id) {
+    let (before, after) = if let Some(network) = new_config.svm_networks.get_mut(network_id) {
+        let before = network.clone();
         network.rpc_url = rpc_url.to_string();
+        (before, network.clone())
     } else {
         return Ok(serde_json::json!({
             "success": false,
             "error": format!("Network '{}' not found", network_id)
         }));
-    }
-
-    new_config
-        .save()
-        .map_err(|e| anyhow::anyhow!("Failed to save configuration: {}", e))?;
+    };
 
-    state_guard.update_config(new_config);
+    commit_network_change(&mut state_guard, new_config)?;
+    crate::logging::log_audit_event("setNetworkRpcUrl", network_id, Some(&before), Some(&after));
 
     log::info!("Successfully updated RPC URL for network '{network_id}'");
     Ok(serde_json::json!({
@@ -3054,6 +6207,380 @@ async fn set_network_rpc_url(
     }))
 }
 
+/// Upper bound on a single health probe issued by `getEnabledNetworks`
+pub const NETWORK_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reports every enabled SVM network with a freshly probed health status
+///
+/// # Arguments
+/// * `state` - Server state holding the network configuration and clients
+///
+/// # Returns
+/// * `Result<Value>` - `{networks: [{id, name, rpcUrl, status, latencyMs, error?}]}`
+///
+/// # Security
+/// - RPC URLs are reduced to scheme and host so embedded API keys are not exposed
+/// - Each probe is bounded by `NETWORK_PROBE_TIMEOUT`
+async fn get_enabled_networks(state: Arc<RwLock<ServerState>>) -> Result<Value> {
+    let state_guard = state.read().await;
+
+    let mut network_ids = state_guard.get_enabled_networks();
+    network_ids.sort_unstable();
+
+    let probes = network_ids.into_iter().map(|network_id| {
+        let state_guard = &state_guard;
+        async move {
+            let network = &state_guard.config.svm_networks[network_id];
+            let rpc_url = sanitize_for_logging(&network.rpc_url);
+            let mut entry = serde_json::json!({
+                "id": network_id,
+                "name": network.name,
+                "rpcUrl": rpc_url,
+            });
+
+            let Some(client) = state_guard.svm_clients.get(network_id) else {
+                entry["status"] = "unreachable".into();
+                entry["error"] = "RPC client not initialized".into();
+                return entry;
+            };
+            entry["commitment"] = serde_json::json!(client.commitment().commitment);
+
+            let start = std::time::Instant::now();
+            let outcome = tokio::time::timeout(NETWORK_PROBE_TIMEOUT, client.get_health()).await;
+            entry["latencyMs"] = (start.elapsed().as_millis() as u64).into();
+            match outcome {
+                Ok(Ok(())) => entry["status"] = "reachable".into(),
+                Ok(Err(e)) => {
+                    entry["status"] = "unreachable".into();
+                    // Client errors may echo the full URL, including any API key
+                    entry["error"] = e.to_string().replace(&network.rpc_url, &rpc_url).into();
+                }
+                Err(_) => {
+                    entry["status"] = "unreachable".into();
+                    entry["error"] =
+                        format!("Health probe timed out after {}ms", NETWORK_PROBE_TIMEOUT.as_millis()).into();
+                }
+            }
+            entry
+        }
+    });
+
+    let networks = futures_util::future::join_all(probes).await;
+    Ok(serde_json::json!({ "networks": networks }))
+}
+
+/// Cluster status snapshots keyed on the RPC URL they were fetched from
+static CLUSTER_STATUS_CACHE: once_cell::sync::Lazy<
+    dashmap::DashMap<String, (Value, std::time::Instant)>,
+> = once_cell::sync::Lazy::new(dashmap::DashMap::new);
+
+/// Fetches slot, block height, epoch info and health concurrently
+///
+/// A snapshot in which every field loaded is reused for
+/// `cluster_status_cache_ttl_ms`, so dashboards polling several times a second
+/// cost one round of RPC calls per TTL. Partial results are never cached.
+///
+/// # Returns
+/// * `Result<Value>` - `{slot, blockHeight, epochInfo, health, cached}` where any
+///   field that failed to load is `{error}` instead of its value
+async fn get_cluster_status(state: Arc<RwLock<ServerState>>) -> Result<Value> {
+    let state_guard = state.read().await;
+    let client = state_guard.get_next_rpc_client();
+    let rpc_url = client.url();
+    let ttl = Duration::from_millis(state_guard.config.cluster_status_cache_ttl_ms);
+
+    if let Some(entry) = CLUSTER_STATUS_CACHE.get(&rpc_url) {
+        let (status, fetched_at) = entry.value();
+        if fetched_at.elapsed() < ttl {
+            let mut status = status.clone();
+            status["cached"] = true.into();
+            return Ok(status);
+        }
+    }
+
+    let (slot, block_height, epoch_info, health) = tokio::join!(
+        client.get_slot(),
+        client.get_block_height(),
+        client.get_epoch_info(),
+        client.get_health(),
+    );
+
+    let sanitized_url = sanitize_for_logging(&rpc_url);
+    let mut complete = true;
+    let mut field = |outcome: std::result::Result<Value, solana_client::client_error::ClientError>| {
+        outcome.unwrap_or_else(|e| {
+            complete = false;
+            // Client errors may echo the full URL, including any API key
+            serde_json::json!({ "error": e.to_string().replace(&rpc_url, &sanitized_url) })
+        })
+    };
+    let mut status = serde_json::json!({
+        "slot": field(slot.map(Value::from)),
+        "blockHeight": field(block_height.map(Value::from)),
+        "epochInfo": field(epoch_info.map(|info| serde_json::to_value(info).unwrap_or_default())),
+        "health": field(health.map(|()| Value::from("ok"))),
+    });
+
+    if complete && !ttl.is_zero() {
+        CLUSTER_STATUS_CACHE.insert(rpc_url, (status.clone(), std::time::Instant::now()));
+    }
+    status["cached"] = false.into();
+    Ok(status)
+}
+
+/// Headroom estimateComputeUnits adds to the consumed units by default, in percent
+const DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT: u64 = 10;
+
+/// Largest headroom estimateComputeUnits accepts, in percent
+const MAX_COMPUTE_UNIT_MARGIN_PERCENT: u64 = 1000;
+
+/// Slot lag up to which getHealthDetailed still calls a node healthy
+///
+/// Matches the distance at which Agave's own getHealth starts reporting behind.
+const DEFAULT_MAX_HEALTHY_SLOT_LAG: u64 = 150;
+
+/// Reports node health along with how far the node trails the cluster
+///
+/// The highest known slot is the largest of the node's max shred insert slot
+/// (the newest slot it has seen shreds for from the cluster) and the processed
+/// slot of every other endpoint in the RPC pool. Pool endpoints that fail to
+/// answer are left out.
+///
+/// # Arguments
+/// * `max_slot_lag` - Largest lag still reported as healthy
+///
+/// # Returns
+/// * `Result<Value>` - `{health, slot, highestKnownSlot, slotLag, maxSlotLag,
+///   healthy}` where `health` is `"ok"` or `{error}`
+async fn get_health_detailed(state: Arc<RwLock<ServerState>>, max_slot_lag: u64) -> Result<Value> {
+    let state_guard = state.read().await;
+    let client = state_guard.get_next_rpc_client();
+    let rpc_url = client.url();
+    let processed = CommitmentConfig::processed();
+
+    let peer_slots = state_guard.rpc_clients.iter().filter(|peer| peer.url() != rpc_url).map(|peer| async move {
+        tokio::time::timeout(NETWORK_PROBE_TIMEOUT, peer.get_slot_with_commitment(processed)).await.ok()?.ok()
+    });
+    let (health, slot, max_shred_insert_slot, peer_slots) = tokio::join!(
+        client.get_health(),
+        client.get_slot_with_commitment(processed),
+        client.get_max_shred_insert_slot(),
+        futures_util::future::join_all(peer_slots),
+    );
+    let slot = slot.map_err(McpError::from).context("Get slot failed")?;
+    let max_shred_insert_slot = max_shred_insert_slot.map_err(McpError::from).context("Get max shred insert slot failed")?;
+
+    let highest_known_slot = peer_slots.into_iter().flatten().fold(max_shred_insert_slot.max(slot), u64::max);
+    let slot_lag = highest_known_slot - slot;
+    let health = match health {
+        Ok(()) => Value::from("ok"),
+        // Client errors may echo the full URL, including any API key
+        Err(e) => serde_json::json!({ "error": e.to_string().replace(&rpc_url, &sanitize_for_logging(&rpc_url)) }),
+    };
+
+    Ok(serde_json::json!({
+        "healthy": health == "ok" && slot_lag <= max_slot_lag,
+        "health": health,
+        "slot": slot,
+        "highestKnownSlot": highest_known_slot,
+        "slotLag": slot_lag,
+        "maxSlotLag": max_slot_lag,
+    }))
+}
+
+/// Method support reports keyed on the RPC URL they were probed against
+static NETWORK_METHODS_CACHE: once_cell::sync::Lazy<
+    dashmap::DashMap<String, (Value, std::time::Instant)>,
+> = once_cell::sync::Lazy::new(dashmap::DashMap::new);
+
+/// How many method probes run against one network at a time
+const METHOD_PROBE_CONCURRENCY: usize = 8;
+
+/// Reports which upstream methods a network implements and which tools that enables
+///
+/// Every method behind a listed tool is probed once. Reports in which every
+/// probe got an answer are reused for `network_methods_cache_ttl_seconds`;
+/// methods that timed out are listed as unknown and keep the report uncached.
+///
+/// # Arguments
+/// * `network_id` - Enabled SVM network to probe, or `None` for the primary RPC client
+/// * `refresh` - Probe again even if a cached report is still fresh
+///
+/// # Returns
+/// * `Result<Value>` - `{networkId, rpcUrl, supportedMethods, unsupportedMethods,
+///   unknownMethods, supportedTools, unsupportedTools: [{name, missingMethods}],
+///   unverifiedTools, cached}`
+async fn probe_network_methods(
+    state: Arc<RwLock<ServerState>>,
+    network_id: Option<&str>,
+    refresh: bool,
+) -> Result<Value> {
+    let state_guard = state.read().await;
+    let (label, client) = match network_id {
+        Some(id) => {
+            let client = state_guard.svm_clients.get(id).ok_or_else(|| {
+                McpError::validation(format!("Network '{id}' is not enabled")).with_parameter("networkId")
+            })?;
+            (id, client)
+        }
+        None => (crate::network_health::PRIMARY_NETWORK_LABEL, &*state_guard.rpc_client),
+    };
+    let rpc_url = client.url();
+    let ttl = Duration::from_secs(state_guard.config.network_methods_cache_ttl_seconds);
+
+    if !refresh {
+        if let Some(entry) = NETWORK_METHODS_CACHE.get(&rpc_url) {
+            let (report, probed_at) = entry.value();
+            if probed_at.elapsed() < ttl {
+                let mut report = report.clone();
+                report["networkId"] = label.into();
+                report["cached"] = true.into();
+                return Ok(report);
+            }
+        }
+    }
+
+    // deploySbpfToDevnet talks to its own endpoint rather than the probed one
+    let tools: Vec<(&'static str, Vec<&'static str>)> = TOOL_DEFINITIONS
+        .iter()
+        .filter(|tool| tool.name != "deploySbpfToDevnet")
+        .map(|tool| (tool.name.as_str(), planned_rpc_methods(&tool.name)))
+        .filter(|(_, methods)| !methods.is_empty())
+        .collect();
+    let methods: Vec<&'static str> = tools
+        .iter()
+        .flat_map(|(_, methods)| methods.iter().copied())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let mut support = std::collections::HashMap::new();
+    for batch in methods.chunks(METHOD_PROBE_CONCURRENCY) {
+        let probes = batch.iter().map(|&method| async move {
+            (method, crate::rpc::capabilities::probe_method(client, method).await)
+        });
+        support.extend(futures_util::future::join_all(probes).await);
+    }
+
+    let mut method_lists: [Vec<&str>; 3] = Default::default();
+    for (method, supported) in &support {
+        let list = match supported {
+            Some(true) => 0,
+            Some(false) => 1,
+            None => 2,
+        };
+        method_lists[list].push(method);
+    }
+    for list in &mut method_lists {
+        list.sort_unstable();
+    }
+    let [supported_methods, unsupported_methods, unknown_methods] = method_lists;
+
+    let mut supported_tools = Vec::new();
+    let mut unsupported_tools = Vec::new();
+    let mut unverified_tools = Vec::new();
+    for (name, methods) in &tools {
+        let missing: Vec<&str> = methods.iter().copied().filter(|m| support[m] == Some(false)).collect();
+        if !missing.is_empty() {
+            unsupported_tools.push(serde_json::json!({ "name": name, "missingMethods": missing }));
+        } else if methods.iter().all(|m| support[m] == Some(true)) {
+            supported_tools.push(*name);
+        } else {
+            unverified_tools.push(*name);
+        }
+    }
+
+    let mut report = serde_json::json!({
+        "networkId": label,
+        "rpcUrl": sanitize_for_logging(&rpc_url),
+        "supportedMethods": supported_methods,
+        "unsupportedMethods": unsupported_methods,
+        "unknownMethods": unknown_methods,
+        "supportedTools": supported_tools,
+        "unsupportedTools": unsupported_tools,
+        "unverifiedTools": unverified_tools,
+    });
+    if unknown_methods.is_empty() && !ttl.is_zero() {
+        NETWORK_METHODS_CACHE.insert(rpc_url, (report.clone(), std::time::Instant::now()));
+    }
+    report["cached"] = false.into();
+    Ok(report)
+}
+
+/// .sol resolutions keyed on the RPC URL and normalized domain
+static DOMAIN_RESOLUTION_CACHE: once_cell::sync::Lazy<
+    dashmap::DashMap<String, (Pubkey, Pubkey, std::time::Instant)>,
+> = once_cell::sync::Lazy::new(dashmap::DashMap::new);
+
+/// Resolves a .sol domain, reusing answers for `domain_cache_ttl_seconds`
+///
+/// Only successful resolutions are cached, so a name registered moments ago
+/// resolves on the next call.
+///
+/// # Returns
+/// * `McpResult<(Pubkey, Pubkey, bool)>` - Name record, owner, and whether the
+///   answer came from cache
+async fn resolve_domain_cached(
+    state: &Arc<RwLock<ServerState>>,
+    domain: &str,
+    field: &str,
+) -> McpResult<(Pubkey, Pubkey, bool)> {
+    let state_guard = state.read().await;
+    let client = state_guard.get_next_rpc_client();
+    let ttl = Duration::from_secs(state_guard.config.domain_cache_ttl_seconds);
+    let key = format!("{}|{}", client.url(), domain.trim().to_ascii_lowercase());
+
+    if let Some(entry) = DOMAIN_RESOLUTION_CACHE.get(&key) {
+        let (name_account, owner, resolved_at) = *entry.value();
+        if resolved_at.elapsed() < ttl {
+            return Ok((name_account, owner, true));
+        }
+    }
+
+    let (name_account, owner) = crate::rpc::names::resolve_domain(client, domain, field).await?;
+    if !ttl.is_zero() {
+        DOMAIN_RESOLUTION_CACHE.insert(key, (name_account, owner, std::time::Instant::now()));
+    }
+    Ok((name_account, owner, false))
+}
+
+/// Replaces .sol names in pubkey arguments with the pubkeys that own them
+///
+/// Covers the single-key arguments in `PUBKEY_ARGUMENTS` and each entry of
+/// the arrays in `PUBKEY_ARRAY_ARGUMENTS`; a name that does not resolve is
+/// reported against its argument.
+async fn resolve_domain_arguments(arguments: &mut Value, state: &Arc<RwLock<ServerState>>) -> McpResult<()> {
+    use crate::rpc::names::is_sol_domain;
+
+    for &field in PUBKEY_ARGUMENTS {
+        let Some(domain) = arguments
+            .get(field)
+            .and_then(|v| v.as_str())
+            .filter(|v| is_sol_domain(v))
+            .map(str::to_owned)
+        else {
+            continue;
+        };
+        let (_, owner, _) = resolve_domain_cached(state, &domain, field).await?;
+        arguments[field] = Value::String(owner.to_string());
+    }
+
+    for &field in PUBKEY_ARRAY_ARGUMENTS {
+        let Some(items) = arguments.get_mut(field).and_then(|v| v.as_array_mut()) else {
+            continue;
+        };
+        for item in items {
+            let Some(domain) = item.as_str().filter(|v| is_sol_domain(v)) else {
+                continue;
+            };
+            let (_, owner, _) = resolve_domain_cached(state, domain, field).await?;
+            *item = Value::String(owner.to_string());
+        }
+    }
+
+    Ok(())
+}
+
 /// Main request handler for the MCP server
 ///
 /// Parses incoming JSON-RPC requests and routes them to appropriate handlers.
@@ -3069,10 +6596,37 @@ async fn set_network_rpc_url(
 /// # Security
 /// - Validates all input parameters
 /// - Sanitizes logging output to prevent sensitive data exposure
+/// - Full bodies are only logged when `log_bodies` is enabled, with
+///   `log_redact_fields` redacted
 /// - Enforces HTTPS for all network operations
 pub async fn handle_request(
     request: &str,
     state: Arc<RwLock<ServerState>>,
+) -> Result<JsonRpcMessage> {
+    // Read per request so that a config update toggles body logging at runtime
+    let redact_fields = {
+        let state_guard = state.read().await;
+        state_guard
+            .config
+            .log_bodies
+            .then(|| state_guard.config.log_redact_fields.clone())
+    };
+    if let Some(fields) = &redact_fields {
+        crate::logging::log_body("request", request, fields);
+    }
+
+    let response = dispatch_request(request, state).await;
+
+    if let (Some(fields), Ok(response)) = (&redact_fields, &response) {
+        crate::logging::log_body("response", &serde_json::to_string(response)?, fields);
+    }
+    response
+}
+
+/// Parses a JSON-RPC request and routes it to the matching handler
+async fn dispatch_request(
+    request: &str,
+    state: Arc<RwLock<ServerState>>,
 ) -> Result<JsonRpcMessage> {
     // Sanitize request for logging to avoid exposing sensitive data
     log::debug!("Received request: {}", sanitize_for_logging(request));
@@ -3091,6 +6645,18 @@ pub async fn handle_request(
 
             let protocol_version = Some(protocol_version.as_str());
 
+            // JSON-RPC 2.0 ids are a string, number or null; any other id
+            // cannot be echoed back reliably, so the error carries a null id
+            if !matches!(req.id, Value::String(_) | Value::Number(_) | Value::Null) {
+                log::error!("Invalid JSON-RPC id type: {}", req.id);
+                return Ok(create_error_response(
+                    -32600,
+                    "Invalid Request: id must be a string, number or null".to_string(),
+                    Value::Null,
+                    protocol_version,
+                ));
+            }
+
             if req.jsonrpc != JsonRpcVersion::V2 {
                 log::error!("Invalid JSON-RPC version: {:?}", req.jsonrpc);
                 return Ok(create_error_response(
@@ -3148,7 +6714,7 @@ pub async fn handle_request(
                         .await
                 }
                 "tools/call" => {
-                    handle_tools_call(req.params, Some(req.id.clone()), state.clone())
+                    run_cancellable_tools_call(req.params, req.id.clone(), state.clone())
                         .await
                 }
 
@@ -3167,13 +6733,32 @@ pub async fn handle_request(
                 }
                 "resources/list" => {
                     log::info!("Handling resources/list request");
-                    let resources = vec![Resource {
-                        uri: Url::parse("https://docs.solana.com/developing/clients/jsonrpc-api")
-                            .unwrap(),
+                    let mut resources = vec![Resource {
+                        uri: Url::parse(SOLANA_RPC_DOCS_URL).unwrap(),
                         name: "Documentation".to_string(),
                         description: Some("Solana API documentation".to_string()),
                         mime_type: Some("text/html".to_string()),
                     }];
+                    let mut doc_names: Vec<String> = crate::docs::get_all_docs().into_keys().collect();
+                    doc_names.sort();
+                    resources.extend(doc_names.into_iter().filter_map(|name| {
+                        Some(Resource {
+                            uri: Url::parse(&format!("{DOCS_RESOURCE_SCHEME}{name}")).ok()?,
+                            description: Some(format!("Bundled {name} documentation")),
+                            name,
+                            mime_type: Some("text/markdown".to_string()),
+                        })
+                    }));
+                    let mut method_docs: Vec<_> = crate::docs::rpc::get_rpc_method_docs().into_iter().collect();
+                    method_docs.sort_by(|a, b| a.0.cmp(&b.0));
+                    resources.extend(method_docs.into_iter().filter_map(|(method, doc)| {
+                        Some(Resource {
+                            uri: Url::parse(&format!("{RPC_DOCS_RESOURCE_PREFIX}{method}")).ok()?,
+                            name: method,
+                            description: Some(doc.description),
+                            mime_type: Some("text/markdown".to_string()),
+                        })
+                    }));
 
                     let response = ResourcesListResponse {
                         resources,
@@ -3186,6 +6771,7 @@ pub async fn handle_request(
                         req.id,
                     ))
                 }
+                "resources/read" => handle_resources_read(req.params, req.id).await,
                 _ => {
                     log::error!("Method not found: {}", req.method);
                     Ok(create_error_response(
@@ -3211,6 +6797,15 @@ pub async fn handle_request(
                 log::info!("Received initialized notification");
                 Ok(JsonRpcMessage::Notification(notification))
             }
+            "notifications/cancelled" => {
+                log::info!("Received cancelled notification");
+                if let Some(params) = notification.params.clone() {
+                    let cancel_params: CancelledParams = serde_json::from_value(params)?;
+                    let state_guard = state.read().await;
+                    cancel_in_flight_call(&cancel_params, &state_guard);
+                }
+                Ok(JsonRpcMessage::Notification(notification))
+            }
             _ => {
                 log::error!("Unsupported notification: {}", notification.method);
                 Ok(create_error_response(
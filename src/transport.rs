@@ -1,7 +1,9 @@
+use crate::metrics::PROMETHEUS_METRICS;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::io::{self, BufRead, BufReader, Write};
+use std::str::FromStr;
 use std::sync::Mutex;
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
@@ -76,9 +78,44 @@ pub trait Transport {
     fn close(&self) -> Result<()>;
 }
 
+/// How JSON-RPC messages are delimited on the stdio transport
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StdioFraming {
+    /// Detect from the first bytes the client sends and reply in kind
+    #[default]
+    Auto,
+    /// One JSON message per line
+    Line,
+    /// LSP-style `Content-Length` header, a blank line, then the message
+    ContentLength,
+}
+
+impl FromStr for StdioFraming {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "auto" => Ok(Self::Auto),
+            "line" => Ok(Self::Line),
+            "content-length" => Ok(Self::ContentLength),
+            other => Err(anyhow::anyhow!(
+                "Invalid stdio framing '{other}': must be auto, line or content-length"
+            )),
+        }
+    }
+}
+
+/// Largest Content-Length accepted, so a corrupt header cannot exhaust memory
+const MAX_CONTENT_LENGTH: usize = 64 * 1024 * 1024;
+
 pub struct CustomStdioTransport {
-    reader: Mutex<BufReader<io::Stdin>>,
-    writer: Mutex<io::Stdout>,
+    reader: Mutex<Box<dyn BufRead + Send>>,
+    writer: Mutex<Box<dyn Write + Send>>,
+    /// `Auto` until the first message reveals the client's framing
+    framing: Mutex<StdioFraming>,
+    /// Messages sent before the framing was detected, written once it is
+    pending: Mutex<Vec<String>>,
 }
 
 impl Default for CustomStdioTransport {
@@ -88,69 +125,193 @@ impl Default for CustomStdioTransport {
 }
 
 impl CustomStdioTransport {
+    /// Creates a line-delimited transport over stdin and stdout
     pub fn new() -> Self {
+        Self::with_framing(StdioFraming::Line)
+    }
+
+    /// Creates a transport over stdin and stdout using `framing`
+    pub fn with_framing(framing: StdioFraming) -> Self {
+        Self::from_io(BufReader::new(io::stdin()), io::stdout(), framing)
+    }
+
+    /// Creates a transport over arbitrary streams, e.g. in-memory buffers in tests
+    pub fn from_io(
+        reader: impl BufRead + Send + 'static,
+        writer: impl Write + Send + 'static,
+        framing: StdioFraming,
+    ) -> Self {
         Self {
-            reader: Mutex::new(BufReader::new(io::stdin())),
-            writer: Mutex::new(io::stdout()),
+            reader: Mutex::new(Box::new(reader)),
+            writer: Mutex::new(Box::new(writer)),
+            framing: Mutex::new(framing),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Writes `json` with the detected framing, or holds it until detection
+    fn write_message(&self, json: &str) -> Result<()> {
+        let framing = {
+            let framing = self.lock(&self.framing, "framing")?;
+            if *framing == StdioFraming::Auto {
+                self.lock(&self.pending, "pending")?.push(json.to_string());
+                return Ok(());
+            }
+            *framing
+        };
+        self.write_framed(json, framing)
+    }
+
+    fn write_framed(&self, json: &str, framing: StdioFraming) -> Result<()> {
+        let mut writer = self.lock(&self.writer, "writer")?;
+        match framing {
+            StdioFraming::ContentLength => write!(writer, "Content-Length: {}\r\n\r\n{json}", json.len())?,
+            _ => writeln!(writer, "{json}")?,
+        }
+        writer.flush()?;
+        log::debug!("Sent message ({} bytes)", json.len());
+        PROMETHEUS_METRICS.record_transport_message("sent", json.len());
+        Ok(())
+    }
+
+    /// Settles `Auto` framing from the first non-whitespace byte of input
+    ///
+    /// JSON starts with `{` or `[`; anything else is taken as a header.
+    /// Messages held back until now are written in the detected framing.
+    fn detect_framing(&self, reader: &mut Box<dyn BufRead + Send>) -> Result<StdioFraming> {
+        let framing = *self.lock(&self.framing, "framing")?;
+        if framing != StdioFraming::Auto {
+            return Ok(framing);
+        }
+
+        // Peek without holding the framing lock, so sends are not blocked on input
+        let first = loop {
+            let buf = reader.fill_buf()?;
+            let Some(&byte) = buf.first() else {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Connection closed").into());
+            };
+            if !byte.is_ascii_whitespace() {
+                break byte;
+            }
+            reader.consume(1);
+        };
+        let mut framing = self.lock(&self.framing, "framing")?;
+        *framing = match first {
+            b'{' | b'[' => StdioFraming::Line,
+            _ => StdioFraming::ContentLength,
+        };
+        log::info!("Detected {:?} framing on stdio", *framing);
+
+        for json in self.lock(&self.pending, "pending")?.drain(..) {
+            self.write_framed(&json, *framing)?;
         }
+        Ok(*framing)
+    }
+
+    fn lock<'a, T>(&self, mutex: &'a Mutex<T>, name: &str) -> Result<std::sync::MutexGuard<'a, T>> {
+        mutex.lock().map_err(|_| {
+            let err = io::Error::other(format!("Failed to acquire {name} lock"));
+            log::error!("Transport error: {err}");
+            err.into()
+        })
     }
 }
 
+/// Reads one `Content-Length` framed message body
+///
+/// Headers other than Content-Length (such as Content-Type) are ignored, and
+/// blank lines before the headers are skipped.
+///
+/// # Returns
+/// * `Result<Option<String>>` - `None` at end of input
+fn read_content_length_message(reader: &mut dyn BufRead) -> Result<Option<String>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            match length {
+                Some(_) => break,
+                None => continue,
+            }
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Malformed header: {header}")).into());
+        };
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            let value: usize = value
+                .trim()
+                .parse()
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid Content-Length: {value}")))?;
+            if value > MAX_CONTENT_LENGTH {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Content-Length {value} exceeds {MAX_CONTENT_LENGTH} bytes"),
+                )
+                .into());
+            }
+            length = Some(value);
+        }
+    }
+
+    let mut body = vec![0; length.unwrap_or_default()];
+    reader.read_exact(&mut body)?;
+    Ok(Some(String::from_utf8(body)?))
+}
+
 impl Transport for CustomStdioTransport {
     fn send_raw(&self, json: &str) -> Result<()> {
-        let mut writer = self
-            .writer
-            .lock()
-            .map_err(|_| io::Error::other("Failed to acquire writer lock"))?;
-        let json = json.trim();
-        writeln!(writer, "{json}")?;
-        writer.flush()?;
-        Ok(())
+        self.write_message(json.trim())
     }
 
     fn send(&self, message: &JsonRpcMessage) -> Result<()> {
-        log::debug!("Sending message: {}", serde_json::to_string(message)?);
-        let mut writer = self.writer.lock().map_err(|_| {
-            let err = io::Error::other("Failed to acquire writer lock");
-            log::error!("Transport error: {err}");
-            err
-        })?;
-        let mut buf = Vec::new();
-        let mut ser = serde_json::Serializer::new(&mut buf);
-        message.serialize(&mut ser)?;
-        writer.write_all(&buf)?;
-        writer.write_all(b"\n")?;
-        writer.flush()?;
-        Ok(())
+        let json = serde_json::to_string(message)?;
+        log::trace!("Sending message: {json}");
+        self.write_message(&json)
     }
 
     fn receive(&self) -> Result<JsonRpcMessage> {
-        let mut line = String::new();
-        let mut reader = self.reader.lock().map_err(|_| {
-            let err = io::Error::other("Failed to acquire reader lock");
-            log::error!("Transport error: {err}");
-            err
+        let mut reader = self.lock(&self.reader, "reader")?;
+        let framing = self.detect_framing(&mut reader).map_err(|e| {
+            if !e.to_string().contains("Connection closed") {
+                log::error!("Transport error: {e}");
+            }
+            e
         })?;
 
-        match reader.read_line(&mut line) {
-            Ok(0) => {
+        let read = match framing {
+            StdioFraming::ContentLength => read_content_length_message(&mut *reader),
+            _ => {
+                let mut line = String::new();
+                reader.read_line(&mut line).map(|read| (read > 0).then_some(line)).map_err(Into::into)
+            }
+        };
+        match read {
+            Ok(None) => {
                 let err = io::Error::new(io::ErrorKind::UnexpectedEof, "Connection closed");
                 log::info!("Transport connection closed");
                 Err(err.into())
             }
-            Ok(_) => {
-                if line.trim().is_empty() {
+            Ok(Some(message)) => {
+                if message.trim().is_empty() {
                     let err = io::Error::new(io::ErrorKind::InvalidData, "Empty message received");
                     log::error!("Transport error: {err}");
                     return Err(err.into());
                 }
-                log::debug!("Received raw message: {}", line.trim());
-                let message = serde_json::from_str(&line)?;
+                let message = message.trim();
+                // Sizes only at debug level; payloads may be sensitive
+                log::debug!("Received message ({} bytes)", message.len());
+                log::trace!("Received raw message: {message}");
+                PROMETHEUS_METRICS.record_transport_message("received", message.len());
+                let message = serde_json::from_str(message)?;
                 Ok(message)
             }
             Err(e) => {
                 log::error!("Transport error: {e}");
-                Err(e.into())
+                Err(e)
             }
         }
     }
@@ -1,5 +1,10 @@
 /// Validation module for input sanitization and security checks
+use crate::error::{McpError, McpResult};
 use anyhow::{anyhow, Result};
+use serde_json::Value;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{rpc_filter::RpcFilterType, rpc_request::MAX_GET_PROGRAM_ACCOUNT_FILTERS};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
 use url::Url;
 
 /// Sanitization constants for consistent data handling
@@ -73,6 +78,47 @@ pub fn validate_rpc_url(url_str: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validates an RPC URL and, when an allowlist is configured, its host
+///
+/// # Arguments
+/// * `url_str` - The URL string to validate
+/// * `allowed_hosts` - Permitted hosts; empty permits any host. Entries of the
+///   form `*.example.com` match any subdomain of `example.com`
+///
+/// # Returns
+/// * `Result<()>` - Ok if valid and permitted, Err with description otherwise
+///
+/// # Security
+/// - Applies every check from `validate_rpc_url`
+/// - Prevents clients from redirecting RPC traffic to arbitrary endpoints
+pub fn validate_rpc_url_with_allowlist(url_str: &str, allowed_hosts: &[String]) -> Result<()> {
+    validate_rpc_url(url_str)?;
+
+    if allowed_hosts.is_empty() {
+        return Ok(());
+    }
+
+    let url = Url::parse(url_str).map_err(|e| anyhow!("Invalid URL format: {}", e))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("RPC URL must have a valid host"))?
+        .to_ascii_lowercase();
+
+    let permitted = allowed_hosts.iter().any(|allowed| {
+        let allowed = allowed.trim().to_ascii_lowercase();
+        match allowed.strip_prefix("*.") {
+            Some(domain) => host.ends_with(&format!(".{domain}")),
+            None => host == allowed,
+        }
+    });
+
+    if !permitted {
+        return Err(anyhow!("RPC host '{}' is not in allowed_rpc_hosts", host));
+    }
+
+    Ok(())
+}
+
 /// Checks if an address is internal/localhost
 fn is_internal_address(host: &str) -> bool {
     host == "localhost"
@@ -162,6 +208,223 @@ pub fn validate_commitment(commitment: &str) -> Result<()> {
     }
 }
 
+/// Parses an optional commitment argument into a `CommitmentConfig`
+///
+/// # Arguments
+/// * `value` - The argument as supplied by the caller, if present
+/// * `field` - Name of the argument, reported back on failure
+///
+/// # Returns
+/// * `McpResult<Option<CommitmentConfig>>` - `None` when omitted or null, a
+///   validation error naming `field` for anything other than
+///   processed/confirmed/finalized
+pub fn parse_commitment(value: Option<&Value>, field: &str) -> McpResult<Option<CommitmentConfig>> {
+    let commitment = match value {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(commitment)) => commitment.as_str(),
+        Some(other) => {
+            return Err(McpError::validation(format!(
+                "Invalid {field}: expected a string, got {other}"
+            ))
+            .with_parameter(field))
+        }
+    };
+
+    match commitment {
+        "processed" => Ok(Some(CommitmentConfig::processed())),
+        "confirmed" => Ok(Some(CommitmentConfig::confirmed())),
+        "finalized" => Ok(Some(CommitmentConfig::finalized())),
+        _ => Err(McpError::validation(format!(
+            "Invalid {field} '{commitment}'. Must be 'processed', 'confirmed', or 'finalized'"
+        ))
+        .with_parameter(field)),
+    }
+}
+
+/// Maps an account encoding name to its `UiAccountEncoding`
+///
+/// Accepts the names used by the Solana RPC API: base58, base64, base64+zstd
+/// and jsonParsed.
+pub fn account_encoding_from_name(name: &str) -> Option<UiAccountEncoding> {
+    match name {
+        "base58" => Some(UiAccountEncoding::Base58),
+        "base64" => Some(UiAccountEncoding::Base64),
+        "base64+zstd" => Some(UiAccountEncoding::Base64Zstd),
+        "jsonParsed" => Some(UiAccountEncoding::JsonParsed),
+        _ => None,
+    }
+}
+
+/// Parses an optional account encoding argument into a `UiAccountEncoding`
+///
+/// # Arguments
+/// * `value` - The argument as supplied by the caller, if present
+/// * `field` - Name of the argument, reported back on failure
+///
+/// # Returns
+/// * `McpResult<Option<UiAccountEncoding>>` - `None` when omitted or null, a
+///   validation error naming `field` for unsupported encodings
+pub fn parse_account_encoding(value: Option<&Value>, field: &str) -> McpResult<Option<UiAccountEncoding>> {
+    let name = match value {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(name)) => name.as_str(),
+        Some(other) => {
+            return Err(McpError::validation(format!(
+                "Invalid {field}: expected a string, got {other}"
+            ))
+            .with_parameter(field))
+        }
+    };
+
+    account_encoding_from_name(name).map(Some).ok_or_else(|| {
+        McpError::validation(format!(
+            "Invalid {field} '{name}'. Must be 'base58', 'base64', 'base64+zstd', or 'jsonParsed'"
+        ))
+        .with_parameter(field)
+    })
+}
+
+/// Parses an optional list of `getProgramAccounts` filters
+///
+/// # Arguments
+/// * `value` - The `memcmp`/`dataSize` filter array as supplied by the caller
+/// * `field` - Name of the argument, reported back on failure
+///
+/// # Returns
+/// * `McpResult<Option<Vec<RpcFilterType>>>` - `None` when omitted or null, a
+///   validation error naming `field` for malformed filters, more than the RPC
+///   node accepts, or memcmp bytes that are undecodable or too long
+pub fn parse_program_account_filters(value: Option<&Value>, field: &str) -> McpResult<Option<Vec<RpcFilterType>>> {
+    let value = match value {
+        None | Some(Value::Null) => return Ok(None),
+        Some(value) => value,
+    };
+    let filters: Vec<RpcFilterType> = serde_json::from_value(value.clone())
+        .map_err(|e| McpError::validation(format!("Invalid {field}: {e}")).with_parameter(field))?;
+
+    if filters.len() > MAX_GET_PROGRAM_ACCOUNT_FILTERS {
+        return Err(McpError::validation(format!(
+            "Invalid {field}: at most {MAX_GET_PROGRAM_ACCOUNT_FILTERS} filters are allowed, got {}",
+            filters.len()
+        ))
+        .with_parameter(field));
+    }
+    for filter in &filters {
+        filter
+            .verify()
+            .map_err(|e| McpError::validation(format!("Invalid {field}: {e}")).with_parameter(field))?;
+    }
+    Ok(Some(filters))
+}
+
+/// Decodes a base58 string and checks it has exactly `expected_len` bytes
+fn decode_base58_exact(value: &str, field: &str, expected_len: usize) -> McpResult<Vec<u8>> {
+    let bytes = bs58::decode(value).into_vec().map_err(|e| {
+        McpError::validation(format!("Invalid {field}: not valid base58 ({e})")).with_parameter(field)
+    })?;
+
+    if bytes.len() != expected_len {
+        return Err(McpError::validation(format!(
+            "Invalid {field}: expected {expected_len} bytes, got {}",
+            bytes.len()
+        ))
+        .with_parameter(field));
+    }
+
+    Ok(bytes)
+}
+
+/// Validates and parses a base58-encoded public key
+///
+/// # Arguments
+/// * `value` - The base58 string supplied by the caller
+/// * `field` - Name of the argument, reported back on failure
+///
+/// # Returns
+/// * `McpResult<Pubkey>` - The parsed key, or a validation error naming `field`
+pub fn validate_pubkey(value: &str, field: &str) -> McpResult<Pubkey> {
+    let bytes = decode_base58_exact(value, field, 32)?;
+    let array: [u8; 32] = bytes.try_into().expect("length checked above");
+    Ok(Pubkey::new_from_array(array))
+}
+
+/// Validates and parses a base58-encoded transaction signature
+///
+/// # Arguments
+/// * `value` - The base58 string supplied by the caller
+/// * `field` - Name of the argument, reported back on failure
+///
+/// # Returns
+/// * `McpResult<Signature>` - The parsed signature, or a validation error naming `field`
+pub fn validate_signature(value: &str, field: &str) -> McpResult<Signature> {
+    let bytes = decode_base58_exact(value, field, 64)?;
+    let array: [u8; 64] = bytes.try_into().expect("length checked above");
+    Ok(Signature::from(array))
+}
+
+/// Maximum pubkeys accepted by getMultipleAccounts, matching the Solana RPC limit
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Maximum pubkeys accepted by the getBalances tool, fetched in
+/// `MAX_MULTIPLE_ACCOUNTS`-sized chunks
+pub const MAX_BALANCES_TOTAL: usize = 10 * MAX_MULTIPLE_ACCOUNTS;
+
+/// Maximum signatures per getSignatureStatuses RPC request, matching the Solana RPC limit
+pub const MAX_SIGNATURE_STATUSES: usize = 256;
+
+/// Maximum signatures accepted by the getSignatureStatuses tool, fetched in
+/// `MAX_SIGNATURE_STATUSES`-sized chunks
+pub const MAX_SIGNATURE_STATUSES_TOTAL: usize = 10 * MAX_SIGNATURE_STATUSES;
+
+/// Maximum addresses per getHistoricalFeeStats request, each costing one getSignaturesForAddress call
+pub const MAX_FEE_STATS_ADDRESSES: usize = 128;
+
+/// Largest account data size the runtime allows, matching `MAX_PERMITTED_DATA_LENGTH`
+pub const MAX_ACCOUNT_DATA_SIZE: usize = 10 * 1024 * 1024;
+
+/// Validates that an array argument does not exceed its element cap
+///
+/// # Arguments
+/// * `len` - Number of elements supplied
+/// * `max` - Maximum number of elements allowed
+/// * `field` - Name of the argument, reported back on failure
+///
+/// # Returns
+/// * `McpResult<()>` - Ok if within the cap, a validation error naming `field` otherwise
+pub fn validate_array_len(len: usize, max: usize, field: &str) -> McpResult<()> {
+    if len > max {
+        return Err(McpError::validation(format!(
+            "Too many {field}: {len} provided, maximum is {max}"
+        ))
+        .with_parameter(field));
+    }
+    Ok(())
+}
+
+/// Tools that perform or confirm writes and must not act on `processed` data
+pub const WRITE_COMMITMENT_TOOLS: &[&str] = &["sendTransaction", "confirmTransaction"];
+
+/// Validates a commitment level for tools that perform or confirm writes
+///
+/// # Arguments
+/// * `commitment` - The commitment level to validate
+///
+/// # Returns
+/// * `Result<()>` - Ok for `confirmed` or `finalized`, Err otherwise
+///
+/// # Security
+/// - `processed` data can still be rolled back by a fork, so acting on it
+///   may report writes that never land
+pub fn validate_write_commitment(commitment: &str) -> Result<()> {
+    validate_commitment(commitment)?;
+    if commitment == "processed" {
+        return Err(anyhow!(
+            "Commitment 'processed' is not allowed for write operations because it may be rolled back; use 'confirmed' or 'finalized'"
+        ));
+    }
+    Ok(())
+}
+
 /// Sanitizes a string for safe logging (removes sensitive information)
 ///
 /// # Arguments
@@ -237,6 +500,23 @@ mod tests {
         assert!(validate_rpc_url("https://").is_err());
     }
 
+    #[test]
+    fn test_validate_rpc_url_with_allowlist() {
+        let allowed = vec!["api.opensvm.com".to_string(), "*.solana.com".to_string()];
+
+        assert!(validate_rpc_url_with_allowlist("https://api.opensvm.com", &allowed).is_ok());
+        assert!(validate_rpc_url_with_allowlist("https://API.OPENSVM.COM/rpc", &allowed).is_ok());
+        assert!(validate_rpc_url_with_allowlist("https://api.devnet.solana.com", &allowed).is_ok());
+
+        let err = validate_rpc_url_with_allowlist("https://attacker.example.com", &allowed).unwrap_err();
+        assert!(err.to_string().contains("not in allowed_rpc_hosts"));
+        assert!(validate_rpc_url_with_allowlist("https://solana.com.attacker.io", &allowed).is_err());
+        assert!(validate_rpc_url_with_allowlist("http://api.opensvm.com", &allowed).is_err());
+
+        // An empty allowlist keeps the HTTPS-only behavior
+        assert!(validate_rpc_url_with_allowlist("https://attacker.example.com", &[]).is_ok());
+    }
+
     #[test]
     fn test_validate_network_id() {
         assert!(validate_network_id("solana-mainnet").is_ok());
@@ -254,6 +534,27 @@ mod tests {
         assert!(validate_network_name(&"x".repeat(129)).is_err());
     }
 
+    #[test]
+    fn test_parse_commitment() {
+        let parse = |v: serde_json::Value| parse_commitment(Some(&v), "commitment");
+        assert_eq!(parse(serde_json::json!("processed")).unwrap(), Some(CommitmentConfig::processed()));
+        assert_eq!(parse(serde_json::json!("finalized")).unwrap(), Some(CommitmentConfig::finalized()));
+        assert_eq!(parse(serde_json::Value::Null).unwrap(), None);
+        assert_eq!(parse_commitment(None, "commitment").unwrap(), None);
+        assert!(parse(serde_json::json!("recent")).is_err());
+        assert!(parse(serde_json::json!(1)).is_err());
+    }
+
+    #[test]
+    fn test_parse_account_encoding() {
+        let parse = |v: serde_json::Value| parse_account_encoding(Some(&v), "encoding");
+        assert_eq!(parse(serde_json::json!("jsonParsed")).unwrap(), Some(UiAccountEncoding::JsonParsed));
+        assert_eq!(parse(serde_json::json!("base64+zstd")).unwrap(), Some(UiAccountEncoding::Base64Zstd));
+        assert_eq!(parse(serde_json::Value::Null).unwrap(), None);
+        assert!(parse(serde_json::json!("binary")).is_err());
+        assert!(parse(serde_json::json!(true)).is_err());
+    }
+
     #[test]
     fn test_validate_commitment() {
         assert!(validate_commitment("processed").is_ok());
@@ -262,6 +563,41 @@ mod tests {
         assert!(validate_commitment("invalid").is_err());
     }
 
+    #[test]
+    fn test_validate_pubkey() {
+        let key = Pubkey::new_unique();
+        assert_eq!(validate_pubkey(&key.to_string(), "pubkey").unwrap(), key);
+
+        // Valid base58 but only 3 bytes long
+        let err = validate_pubkey("abc", "owner").unwrap_err();
+        assert_eq!(err.json_rpc_code(), -32602);
+        assert!(err.to_string().contains("owner"));
+        assert!(validate_pubkey("0OIl", "pubkey").is_err());
+    }
+
+    #[test]
+    fn test_validate_signature() {
+        let signature = Signature::from([7u8; 64]);
+        assert_eq!(validate_signature(&signature.to_string(), "signature").unwrap(), signature);
+        assert!(validate_signature(&Pubkey::new_unique().to_string(), "signature").is_err());
+    }
+
+    #[test]
+    fn test_validate_array_len() {
+        assert!(validate_array_len(MAX_MULTIPLE_ACCOUNTS, MAX_MULTIPLE_ACCOUNTS, "pubkeys").is_ok());
+        let err = validate_array_len(MAX_MULTIPLE_ACCOUNTS + 1, MAX_MULTIPLE_ACCOUNTS, "pubkeys")
+            .unwrap_err();
+        assert_eq!(err.json_rpc_code(), -32602);
+    }
+
+    #[test]
+    fn test_validate_write_commitment() {
+        assert!(validate_write_commitment("processed").is_err());
+        assert!(validate_write_commitment("confirmed").is_ok());
+        assert!(validate_write_commitment("finalized").is_ok());
+        assert!(validate_write_commitment("invalid").is_err());
+    }
+
     #[test]
     fn test_sanitize_for_logging() {
         // Test URL sanitization with path and query
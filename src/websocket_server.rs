@@ -1,23 +1,34 @@
 use axum::{
     extract::{State, WebSocketUpgrade},
-    response::IntoResponse,
+    http::{header::ORIGIN, HeaderMap, StatusCode},
+    middleware,
+    response::{IntoResponse, Response},
     routing::get,
     Router,
 };
 use axum::extract::ws::{Message, WebSocket};
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{SinkExt, Stream, StreamExt};
 use tokio::net::TcpListener;
 use tokio::time::{timeout, Duration};
 use tracing::{info, error, debug, warn};
 use std::sync::Arc;
 use serde_json::{json, Value};
 use dashmap::DashMap;
-use tokio::sync::mpsc;
+use std::collections::VecDeque;
+use tokio::sync::Notify;
 
-use crate::config::Config;
+use crate::auth::require_bearer_token;
+use crate::config::{Config, WebSocketOverflowPolicy};
+use crate::server::ServerState;
+use crate::validation::parse_program_account_filters;
 use solana_pubsub_client::nonblocking::pubsub_client::PubsubClient;
 use solana_sdk::pubkey::Pubkey;
-use solana_client::rpc_config::{RpcTransactionLogsFilter, RpcTransactionLogsConfig};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionLogsFilter, RpcTransactionLogsConfig,
+};
 
 /// WebSocket server for Solana RPC subscriptions
 pub struct SolanaWebSocketServer {
@@ -32,14 +43,254 @@ struct Subscription {
     id: u64,
     method: String,
     params: Value,
-    client_tx: mpsc::UnboundedSender<Message>,
+    client_tx: ClientSender,
+    /// Upstream task feeding this subscription, aborted when it is removed
+    task: Option<tokio::task::AbortHandle>,
+}
+
+/// Subscriptions owned by a single WebSocket connection
+///
+/// Ids are allocated per connection starting at 1, as a client connected
+/// directly to a Solana node would see, and unsubscribe can only reach
+/// subscriptions created on the same connection.
+#[derive(Debug, Default)]
+struct ConnectionSubscriptions {
+    active: DashMap<u64, Subscription>,
+    /// One-shot streams still running, aborted when the connection closes
+    streams: DashMap<u64, tokio::task::AbortHandle>,
+    last_id: std::sync::atomic::AtomicU64,
+}
+
+impl ConnectionSubscriptions {
+    /// Allocates the next subscription id for this connection
+    fn next_id(&self) -> u64 {
+        self.last_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1
+    }
+
+    fn insert(&self, subscription: Subscription) {
+        self.active.insert(subscription.id, subscription);
+    }
+
+    /// Attaches the upstream task spawned for a subscription
+    ///
+    /// The task is aborted at once if the subscription was already removed.
+    fn attach_task(&self, id: u64, task: tokio::task::AbortHandle) {
+        match self.active.get_mut(&id) {
+            Some(mut subscription) => subscription.task = Some(task),
+            None => task.abort(),
+        }
+    }
+
+    /// Removes a subscription and stops its upstream task, returning false if
+    /// this connection does not own it
+    fn remove(&self, id: u64) -> bool {
+        match self.active.remove(&id) {
+            Some((_, subscription)) => {
+                if let Some(task) = subscription.task {
+                    task.abort();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn contains(&self, id: u64) -> bool {
+        self.active.contains_key(&id)
+    }
+
+    fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    fn clear(&self) {
+        for subscription in self.active.iter() {
+            if let Some(task) = &subscription.task {
+                task.abort();
+            }
+        }
+        self.active.clear();
+        for stream in self.streams.iter() {
+            stream.abort();
+        }
+        self.streams.clear();
+    }
 }
 
 /// Manages active subscriptions for a WebSocket connection
-type SubscriptionManager = Arc<DashMap<u64, Subscription>>;
+type SubscriptionManager = Arc<ConnectionSubscriptions>;
+
+/// Round-robin pool of HTTP RPC clients shared by every connection
+///
+/// Built from `rpc_urls` (falling back to `rpc_url`) with the configured
+/// commitment and `rpc_timeout_seconds`, like the MCP server's own pool.
+struct RpcPool {
+    clients: Vec<Arc<RpcClient>>,
+    next: std::sync::atomic::AtomicUsize,
+}
+
+impl RpcPool {
+    fn new(config: &Config) -> Self {
+        let commitment = ServerState::parse_commitment(&config.commitment);
+        let timeout = Duration::from_secs(config.rpc_timeout_seconds);
+        let clients = ServerState::build_rpc_pool(config, commitment, timeout)
+            .into_iter()
+            .map(Arc::new)
+            .collect();
+        Self { clients, next: std::sync::atomic::AtomicUsize::new(0) }
+    }
+
+    /// Returns the next client in rotation
+    fn next_client(&self) -> Arc<RpcClient> {
+        let index = self.next.fetch_add(1, std::sync::atomic::Ordering::Relaxed) % self.clients.len();
+        Arc::clone(&self.clients[index])
+    }
+}
+
+/// State shared by every connection of the WebSocket server
+#[derive(Clone)]
+struct WebSocketState {
+    config: Arc<Config>,
+    rpc_pool: Arc<RpcPool>,
+}
+
+/// Number of currently open WebSocket connections
+static ACTIVE_CONNECTIONS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Error returned once a connection's outbound queue stops accepting messages
+#[derive(Debug)]
+struct ClientGone;
+
+impl std::fmt::Display for ClientGone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("client connection closed")
+    }
+}
+
+impl std::error::Error for ClientGone {}
+
+/// Bounded queue of messages waiting to be written to one client
+///
+/// Subscriptions push notifications faster than a slow client may read them,
+/// so the queue holds at most `capacity` messages and applies `policy` when
+/// a push would exceed that.
+#[derive(Debug)]
+struct OutboundQueue {
+    state: std::sync::Mutex<OutboundState>,
+    ready: Notify,
+    capacity: usize,
+    policy: WebSocketOverflowPolicy,
+}
+
+#[derive(Debug, Default)]
+struct OutboundState {
+    messages: VecDeque<Message>,
+    closed: bool,
+    dropped: u64,
+}
+
+impl OutboundQueue {
+    fn state(&self) -> std::sync::MutexGuard<'_, OutboundState> {
+        // The state stays consistent even if a holder panicked
+        self.state.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Sending half of a client's outbound queue, shared by all its subscriptions
+#[derive(Debug, Clone)]
+struct ClientSender(Arc<OutboundQueue>);
+
+/// Receiving half of a client's outbound queue, owned by the forward task
+///
+/// Dropping it closes the queue so subscription tasks stop forwarding.
+struct ClientReceiver(Arc<OutboundQueue>);
+
+/// Creates a client outbound queue holding at most `capacity` messages
+fn client_queue(capacity: usize, policy: WebSocketOverflowPolicy) -> (ClientSender, ClientReceiver) {
+    let queue = Arc::new(OutboundQueue {
+        state: std::sync::Mutex::new(OutboundState::default()),
+        ready: Notify::new(),
+        capacity,
+        policy,
+    });
+    (ClientSender(Arc::clone(&queue)), ClientReceiver(queue))
+}
+
+impl ClientSender {
+    /// Queues a message for the client, applying the overflow policy when full
+    ///
+    /// # Errors
+    /// * `ClientGone` once the connection is closed, including when the
+    ///   disconnect policy has just closed it as too slow
+    fn send(&self, message: Message) -> Result<(), ClientGone> {
+        let mut state = self.0.state();
+        if state.closed {
+            return Err(ClientGone);
+        }
+
+        if state.messages.len() >= self.0.capacity {
+            match self.0.policy {
+                WebSocketOverflowPolicy::DropOldest => {
+                    state.messages.pop_front();
+                    state.dropped += 1;
+                    if state.dropped == 1 {
+                        warn!("WebSocket client is not keeping up - dropping oldest queued messages");
+                    }
+                    crate::metrics::PROMETHEUS_METRICS.record_websocket_message_dropped();
+                }
+                WebSocketOverflowPolicy::Disconnect => {
+                    warn!("WebSocket client outbound queue full - closing connection as too slow");
+                    // Discard the backlog so the close frame is written next
+                    state.messages.clear();
+                    state.messages.push_back(Message::Close(Some(axum::extract::ws::CloseFrame {
+                        code: axum::extract::ws::close_code::POLICY,
+                        reason: "client too slow".into(),
+                    })));
+                    state.closed = true;
+                    drop(state);
+                    self.0.ready.notify_one();
+                    return Err(ClientGone);
+                }
+            }
+        }
+
+        state.messages.push_back(message);
+        drop(state);
+        self.0.ready.notify_one();
+        Ok(())
+    }
+
+    /// Returns true once the connection no longer accepts messages
+    fn is_closed(&self) -> bool {
+        self.0.state().closed
+    }
+}
+
+impl ClientReceiver {
+    /// Waits for the next queued message, or `None` once closed and drained
+    async fn recv(&mut self) -> Option<Message> {
+        loop {
+            {
+                let mut state = self.0.state();
+                if let Some(message) = state.messages.pop_front() {
+                    return Some(message);
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+            self.0.ready.notified().await;
+        }
+    }
+}
 
-/// Global subscription counter
-static SUBSCRIPTION_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+impl Drop for ClientReceiver {
+    fn drop(&mut self) {
+        let mut state = self.0.state();
+        state.closed = true;
+        state.messages.clear();
+    }
+}
 
 /// WebSocket message timeout
 fn ws_message_timeout(config: &crate::config::Config) -> Duration {
@@ -56,6 +307,41 @@ fn max_idle_timeout(config: &crate::config::Config) -> Duration {
     Duration::from_secs(config.timeouts.max_idle_seconds)
 }
 
+/// Interval between server-initiated pings
+fn ping_interval(config: &crate::config::Config) -> Duration {
+    Duration::from_secs(config.timeouts.websocket_ping_interval_seconds)
+}
+
+/// Time allowed for a pong before the connection is considered half-open
+fn pong_timeout(config: &crate::config::Config) -> Duration {
+    Duration::from_secs(config.timeouts.websocket_pong_timeout_seconds)
+}
+
+/// Tracks outstanding server pings to detect half-open connections
+#[derive(Debug, Default)]
+struct KeepaliveTracker {
+    /// When the oldest unanswered ping was sent
+    awaiting_pong_since: Option<tokio::time::Instant>,
+}
+
+impl KeepaliveTracker {
+    /// Record that a ping was sent, keeping the oldest outstanding timestamp
+    fn ping_sent(&mut self, now: tokio::time::Instant) {
+        self.awaiting_pong_since.get_or_insert(now);
+    }
+
+    /// Record that the peer answered
+    fn pong_received(&mut self) {
+        self.awaiting_pong_since = None;
+    }
+
+    /// Whether a ping has gone unanswered for longer than `pong_timeout`
+    fn is_half_open(&self, now: tokio::time::Instant, pong_timeout: Duration) -> bool {
+        self.awaiting_pong_since
+            .is_some_and(|sent| now.duration_since(sent) > pong_timeout)
+    }
+}
+
 impl SolanaWebSocketServer {
     pub fn new(port: u16, config: Arc<Config>) -> Self {
         Self { port, config }
@@ -63,9 +349,12 @@ impl SolanaWebSocketServer {
 
     /// Start the WebSocket server
     pub async fn start(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let app = Router::new()
-            .route("/", get(websocket_handler))
-            .with_state(self.config.clone());
+        let auth = Arc::new(self.config.auth.clone());
+        if auth.is_enabled() {
+            info!("Bearer-token authentication enabled for WebSocket upgrades");
+        }
+
+        let app = router(self.config.clone(), auth);
 
         let addr = format!("0.0.0.0:{}", self.port);
         info!("Starting WebSocket server on {}", addr);
@@ -76,27 +365,65 @@ impl SolanaWebSocketServer {
     }
 }
 
+/// Builds the WebSocket upgrade and health routes
+fn router(config: Arc<Config>, auth: Arc<crate::config::AuthConfig>) -> Router {
+    let rpc_pool = Arc::new(RpcPool::new(&config));
+    Router::new()
+        .route(
+            "/",
+            get(websocket_handler)
+                .route_layer(middleware::from_fn_with_state(auth, require_bearer_token)),
+        )
+        .route("/health", get(health_handler))
+        .with_state(WebSocketState { config, rpc_pool })
+}
+
 /// WebSocket upgrade handler
+///
+/// When `allowed_ws_origins` is set, a browser upgrade from any other origin
+/// is refused with 403 so a malicious page cannot ride on the user's network
+/// access. Requests without an Origin header come from non-browser clients
+/// and are accepted.
 async fn websocket_handler(
     ws: WebSocketUpgrade,
-    State(config): State<Arc<Config>>,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_websocket(socket, config))
+    State(WebSocketState { config, rpc_pool }): State<WebSocketState>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(origin) = headers.get(ORIGIN) {
+        let allowed = config.allowed_ws_origins.is_empty()
+            || origin.to_str().is_ok_and(|origin| config.allowed_ws_origins.iter().any(|allowed| allowed == origin));
+        if !allowed {
+            warn!("Rejected WebSocket upgrade from origin {:?}", origin);
+            return (StatusCode::FORBIDDEN, "Origin not allowed").into_response();
+        }
+    }
+    ws.on_upgrade(move |socket| handle_websocket(socket, config, rpc_pool))
+}
+
+/// Liveness probe for load balancers that cannot open a WebSocket
+async fn health_handler() -> impl IntoResponse {
+    axum::Json(json!({
+        "status": "ok",
+        "service": "solana-mcp-websocket",
+        "activeConnections": ACTIVE_CONNECTIONS.load(std::sync::atomic::Ordering::Relaxed)
+    }))
 }
 
 /// Handle WebSocket connection
-async fn handle_websocket(socket: WebSocket, config: Arc<Config>) {
+async fn handle_websocket(socket: WebSocket, config: Arc<Config>, rpc_pool: Arc<RpcPool>) {
     let (mut sender, mut receiver) = socket.split();
-    let subscriptions: SubscriptionManager = Arc::new(DashMap::new());
-    let (tx, mut rx) = mpsc::unbounded_channel();
+    let subscriptions = SubscriptionManager::default();
+    let (tx, mut rx) = client_queue(config.websocket_send_queue_capacity, config.websocket_overflow_policy);
 
     info!("New WebSocket connection established");
 
     let ws_msg_timeout = ws_message_timeout(&config);
     let max_idle = max_idle_timeout(&config);
+    let pong_wait = pong_timeout(&config);
+    ACTIVE_CONNECTIONS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
     // Spawn task to forward messages from subscriptions to WebSocket with timeout
-    let forward_task = tokio::spawn(async move {
+    let mut forward_task = tokio::spawn(async move {
         while let Some(message) = rx.recv().await {
             match timeout(ws_msg_timeout, sender.send(message)).await {
                 Ok(Ok(_)) => continue,
@@ -112,6 +439,13 @@ async fn handle_websocket(socket: WebSocket, config: Arc<Config>) {
         }
     });
 
+    // Server-initiated pings run independently of client activity so that
+    // connections silently dropped by proxies are detected
+    let mut ping_ticker = tokio::time::interval(ping_interval(&config));
+    ping_ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    ping_ticker.tick().await;
+    let mut keepalive = KeepaliveTracker::default();
+
     // Process incoming WebSocket messages with overall connection timeout
     let mut last_activity = tokio::time::Instant::now();
     
@@ -122,13 +456,20 @@ async fn handle_websocket(socket: WebSocket, config: Arc<Config>) {
             break;
         }
 
-        // Wait for next message with timeout
-        match timeout(ws_msg_timeout, receiver.next()).await {
-            Ok(Some(msg)) => {
+        tokio::select! {
+            _ = &mut forward_task => {
+                info!("WebSocket forward task ended - closing connection");
+                break;
+            }
+            msg = receiver.next() => {
+                let Some(msg) = msg else {
+                    info!("WebSocket stream ended");
+                    break;
+                };
                 last_activity = tokio::time::Instant::now();
                 match msg {
                     Ok(Message::Text(text)) => {
-                        if let Err(e) = handle_message(&text, &subscriptions, &tx, &config).await {
+                        if let Err(e) = handle_message(&text, &subscriptions, &tx, &config, &rpc_pool).await {
                             error!("Error handling WebSocket message: {}", e);
                             let error_response = json!({
                                 "jsonrpc": "2.0",
@@ -152,8 +493,7 @@ async fn handle_websocket(socket: WebSocket, config: Arc<Config>) {
                         let _ = tx.send(Message::Pong(data));
                     }
                     Ok(Message::Pong(_)) => {
-                        // Update activity timestamp on pong
-                        last_activity = tokio::time::Instant::now();
+                        keepalive.pong_received();
                     }
                     Err(e) => {
                         error!("WebSocket error: {}", e);
@@ -162,16 +502,17 @@ async fn handle_websocket(socket: WebSocket, config: Arc<Config>) {
                     _ => {}
                 }
             }
-            Ok(None) => {
-                info!("WebSocket stream ended");
-                break;
-            }
-            Err(_) => {
-                // Message timeout - check if connection is still alive with ping
+            _ = ping_ticker.tick() => {
+                let now = tokio::time::Instant::now();
+                if keepalive.is_half_open(now, pong_wait) {
+                    warn!("WebSocket peer did not answer ping within {:?} - closing half-open connection", pong_wait);
+                    break;
+                }
                 if tx.send(Message::Ping(vec![].into())).is_err() {
                     error!("Failed to send ping - connection lost");
                     break;
                 }
+                keepalive.ping_sent(now);
             }
         }
     }
@@ -180,14 +521,322 @@ async fn handle_websocket(socket: WebSocket, config: Arc<Config>) {
     info!("Cleaning up WebSocket connection and {} subscriptions", subscriptions.len());
     cleanup_subscriptions(&subscriptions).await;
     forward_task.abort();
+    ACTIVE_CONNECTIONS.fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Delay before the first attempt to reconnect a dropped upstream subscription
+const UPSTREAM_RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// How one upstream subscription session ended
+#[derive(Debug, PartialEq)]
+enum UpstreamEnd {
+    /// Nothing more to forward: the client went away or the subscription completed
+    Finished,
+    /// The upstream connection or its stream failed and may be re-established
+    Dropped {
+        reason: String,
+        /// Whether the session received any notification before dropping
+        received: bool,
+    },
+}
+
+impl UpstreamEnd {
+    /// A session that failed before receiving anything
+    fn dropped(reason: impl std::fmt::Display) -> Self {
+        Self::Dropped { reason: reason.to_string(), received: false }
+    }
+
+    /// Treats a stream that ended after delivering a notification as complete
+    fn completed_after_delivery(self) -> Self {
+        match self {
+            Self::Dropped { received: true, .. } => Self::Finished,
+            other => other,
+        }
+    }
+}
+
+/// Delay before reconnect `attempt` (zero-based), doubling up to `cap`
+fn reconnect_backoff(attempt: u32, cap: Duration) -> Duration {
+    UPSTREAM_RECONNECT_INITIAL_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(cap)
+}
+
+/// Forwards upstream notifications to the client until either side goes away
+///
+/// Notifications rejected by `keep` are not sent, but still count as received
+/// since they show the upstream stream is healthy.
+async fn forward_notifications<T: serde::Serialize>(
+    stream: &mut (impl Stream<Item = T> + Unpin),
+    method: &str,
+    subscription_id: u64,
+    tx: &ClientSender,
+    keep: impl Fn(&T) -> bool,
+) -> UpstreamEnd {
+    let mut received = false;
+    while let Some(result) = stream.next().await {
+        received = true;
+        if !keep(&result) {
+            continue;
+        }
+
+        if !send_notification(tx, method, subscription_id, &result) {
+            return UpstreamEnd::Finished;
+        }
+    }
+
+    UpstreamEnd::Dropped { reason: "upstream stream ended".to_string(), received }
+}
+
+/// Forwards at most one notification per `min_interval`, always the latest
+///
+/// The first notification goes out immediately; later ones arriving within
+/// the interval replace each other and the survivor is sent when it elapses.
+/// A notification still held back when the upstream ends is flushed first.
+async fn forward_throttled_notifications<T: serde::Serialize>(
+    stream: &mut (impl Stream<Item = T> + Unpin),
+    method: &str,
+    subscription_id: u64,
+    tx: &ClientSender,
+    min_interval: Duration,
+) -> UpstreamEnd {
+    let mut received = false;
+    let mut pending: Option<T> = None;
+    let mut next_send = tokio::time::Instant::now();
+
+    loop {
+        tokio::select! {
+            item = stream.next() => match item {
+                Some(result) => {
+                    received = true;
+                    if pending.is_none() && tokio::time::Instant::now() >= next_send {
+                        if !send_notification(tx, method, subscription_id, &result) {
+                            return UpstreamEnd::Finished;
+                        }
+                        next_send = tokio::time::Instant::now() + min_interval;
+                    } else {
+                        pending = Some(result);
+                    }
+                }
+                None => {
+                    if let Some(result) = pending.take() {
+                        if !send_notification(tx, method, subscription_id, &result) {
+                            return UpstreamEnd::Finished;
+                        }
+                    }
+                    return UpstreamEnd::Dropped { reason: "upstream stream ended".to_string(), received };
+                }
+            },
+            _ = tokio::time::sleep_until(next_send), if pending.is_some() => {
+                if let Some(result) = pending.take() {
+                    if !send_notification(tx, method, subscription_id, &result) {
+                        return UpstreamEnd::Finished;
+                    }
+                }
+                next_send = tokio::time::Instant::now() + min_interval;
+            }
+        }
+    }
+}
+
+/// Sends one subscription notification, returning false once the client is gone
+fn send_notification<T: serde::Serialize>(tx: &ClientSender, method: &str, subscription_id: u64, result: &T) -> bool {
+    let notification = json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": {
+            "result": result,
+            "subscription": subscription_id
+        }
+    });
+
+    if let Ok(msg) = serde_json::to_string(&notification) {
+        if tx.send(Message::Text(msg.into())).is_err() {
+            debug!("Client disconnected, stopping {} subscription {}", method, subscription_id);
+            return false;
+        }
+    }
+    true
+}
+
+/// Runs an upstream subscription, reconnecting with exponential backoff
+///
+/// `session` subscribes on the given client and forwards until the stream
+/// ends. When it drops, a fresh PubsubClient is connected and the session is
+/// restarted; the backoff resets once a session has received anything. When
+/// the configured attempts are exhausted the client gets a JSON-RPC error
+/// carrying the subscription id, so it knows to resubscribe.
+#[allow(clippy::too_many_arguments)]
+async fn run_upstream_subscription<F, Fut>(
+    subscription_id: u64,
+    method: &'static str,
+    ws_url: String,
+    mut pubsub_client: PubsubClient,
+    subscriptions: SubscriptionManager,
+    tx: ClientSender,
+    config: Arc<Config>,
+    mut session: F,
+) where
+    F: FnMut(PubsubClient) -> Fut,
+    Fut: std::future::Future<Output = UpstreamEnd>,
+{
+    let max_attempts = config.timeouts.websocket_reconnect_attempts;
+    let backoff_cap = Duration::from_secs(config.timeouts.websocket_reconnect_max_backoff_seconds);
+    let mut attempt = 0;
+
+    loop {
+        let (mut reason, received) = match session(pubsub_client).await {
+            UpstreamEnd::Finished => return,
+            UpstreamEnd::Dropped { reason, received } => (reason, received),
+        };
+        if received {
+            attempt = 0;
+        }
+
+        pubsub_client = loop {
+            // Nothing to resume once the client unsubscribed or disconnected
+            if tx.is_closed() || !subscriptions.contains(subscription_id) {
+                return;
+            }
+            if attempt >= max_attempts {
+                error!(
+                    "Giving up on {} subscription {} after {} reconnect attempts: {}",
+                    method, subscription_id, attempt, reason
+                );
+                // Aborts this task too, which is harmless as it returns without awaiting again
+                subscriptions.remove(subscription_id);
+                let error_response = json!({
+                    "jsonrpc": "2.0",
+                    "error": {
+                        "code": -32603,
+                        "message": format!("Upstream {} subscription lost: {}", method, reason),
+                        "data": { "subscription": subscription_id }
+                    },
+                    "id": null
+                });
+                if let Ok(error_msg) = serde_json::to_string(&error_response) {
+                    let _ = tx.send(Message::Text(error_msg.into()));
+                }
+                return;
+            }
+
+            let delay = reconnect_backoff(attempt, backoff_cap);
+            attempt += 1;
+            warn!(
+                "Upstream {} subscription {} dropped ({}), reconnecting in {:?} (attempt {}/{})",
+                method, subscription_id, reason, delay, attempt, max_attempts
+            );
+            tokio::time::sleep(delay).await;
+
+            match timeout(subscription_timeout(&config), PubsubClient::new(&ws_url)).await {
+                Ok(Ok(pubsub_client)) => break pubsub_client,
+                Ok(Err(e)) => reason = e.to_string(),
+                Err(_) => reason = "pubsub client creation timeout".to_string(),
+            }
+        };
+    }
+}
+
+/// One upstream connection handed to a subscription session
+struct UpstreamSession {
+    client: PubsubClient,
+    subscription_id: u64,
+    tx: ClientSender,
+    method: &'static str,
+    subscribe_timeout: Duration,
+}
+
+impl UpstreamSession {
+    /// Awaits the upstream subscribe call on `client`, bounded by the subscription timeout
+    async fn subscribe<T, E: std::fmt::Display>(
+        &self,
+        subscribe: impl std::future::Future<Output = Result<T, E>>,
+    ) -> Result<T, UpstreamEnd> {
+        match timeout(self.subscribe_timeout, subscribe).await {
+            Ok(Ok(subscription)) => Ok(subscription),
+            Ok(Err(e)) => Err(UpstreamEnd::dropped(e)),
+            Err(_) => Err(UpstreamEnd::dropped(format!("{} creation timeout", self.method))),
+        }
+    }
+}
+
+/// Registers a subscription on this connection and starts its upstream session
+///
+/// Connects a PubsubClient to the configured node, stores the subscription
+/// before spawning so reconnects can see it, and runs `session` under
+/// `run_upstream_subscription` until the client unsubscribes or disconnects.
+/// The client gets the new subscription id once the task is running.
+#[allow(clippy::too_many_arguments)]
+async fn spawn_upstream_subscription<F, Fut>(
+    method: &'static str,
+    params: Value,
+    id: Value,
+    subscriptions: &SubscriptionManager,
+    tx: &ClientSender,
+    config: &Arc<Config>,
+    mut session: F,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    F: FnMut(UpstreamSession) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = UpstreamEnd> + Send + 'static,
+{
+    let subscription_id = subscriptions.next_id();
+    let ws_url = config.rpc_url.replace("https://", "wss://").replace("http://", "ws://");
+    let subscribe_timeout = subscription_timeout(config);
+
+    let pubsub_client = match timeout(subscribe_timeout, PubsubClient::new(&ws_url)).await {
+        Ok(Ok(client)) => client,
+        Ok(Err(e)) => return Err(format!("Failed to create pubsub client: {}", e).into()),
+        Err(_) => return Err("Pubsub client creation timeout".into()),
+    };
+
+    subscriptions.insert(Subscription {
+        id: subscription_id,
+        method: method.to_string(),
+        params,
+        client_tx: tx.clone(),
+        task: None,
+    });
+
+    let session_tx = tx.clone();
+    let task = tokio::spawn(run_upstream_subscription(
+        subscription_id,
+        method,
+        ws_url,
+        pubsub_client,
+        Arc::clone(subscriptions),
+        tx.clone(),
+        Arc::clone(config),
+        move |client| {
+            session(UpstreamSession {
+                client,
+                subscription_id,
+                tx: session_tx.clone(),
+                method,
+                subscribe_timeout,
+            })
+        },
+    ));
+    subscriptions.attach_task(subscription_id, task.abort_handle());
+
+    let response = json!({
+        "jsonrpc": "2.0",
+        "result": subscription_id,
+        "id": id
+    });
+    let response_msg = serde_json::to_string(&response)?;
+    tx.send(Message::Text(response_msg.into()))?;
+
+    Ok(())
 }
 
 /// Handle incoming JSON-RPC message
 async fn handle_message(
     text: &str,
     subscriptions: &SubscriptionManager,
-    tx: &mpsc::UnboundedSender<Message>,
-    _config: &Arc<Config>,
+    tx: &ClientSender,
+    config: &Arc<Config>,
+    rpc_pool: &RpcPool,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let request: Value = serde_json::from_str(text)?;
     
@@ -202,15 +851,18 @@ async fn handle_message(
 
     match method {
         // Subscription methods
-        "accountSubscribe" => handle_account_subscribe(params, id, subscriptions, tx, _config).await?,
-        "blockSubscribe" => handle_block_subscribe(params, id, subscriptions, tx, _config).await?,
-        "logsSubscribe" => handle_logs_subscribe(params, id, subscriptions, tx, _config).await?,
-        "programSubscribe" => handle_program_subscribe(params, id, subscriptions, tx, _config).await?,
-        "rootSubscribe" => handle_root_subscribe(params, id, subscriptions, tx, _config).await?,
-        "signatureSubscribe" => handle_signature_subscribe(params, id, subscriptions, tx, _config).await?,
-        "slotSubscribe" => handle_slot_subscribe(params, id, subscriptions, tx, _config).await?,
-        "slotsUpdatesSubscribe" => handle_slots_updates_subscribe(params, id, subscriptions, tx, _config).await?,
-        "voteSubscribe" => handle_vote_subscribe(params, id, subscriptions, tx, _config).await?,
+        "accountSubscribe" => handle_account_subscribe(params, id, subscriptions, tx, config).await?,
+        "blockSubscribe" => handle_block_subscribe(params, id, subscriptions, tx, config).await?,
+        "logsSubscribe" => handle_logs_subscribe(params, id, subscriptions, tx, config).await?,
+        "programSubscribe" => handle_program_subscribe(params, id, subscriptions, tx, config).await?,
+        "rootSubscribe" => handle_root_subscribe(params, id, subscriptions, tx, config).await?,
+        "signatureSubscribe" => handle_signature_subscribe(params, id, subscriptions, tx, config).await?,
+        "slotSubscribe" => handle_slot_subscribe(params, id, subscriptions, tx, config).await?,
+        "slotsUpdatesSubscribe" => handle_slots_updates_subscribe(params, id, subscriptions, tx, config).await?,
+        "voteSubscribe" => handle_vote_subscribe(params, id, subscriptions, tx, config).await?,
+
+        // Streamed one-shot queries
+        "getProgramAccountsStream" => handle_program_accounts_stream(params, id, subscriptions, tx, rpc_pool).await?,
 
         // Unsubscribe methods
         "accountUnsubscribe" => handle_unsubscribe(params, id, subscriptions, tx).await?,
@@ -240,13 +892,69 @@ async fn handle_message(
     Ok(())
 }
 
+/// Replies to a subscribe request with `-32602` naming the offending parameter
+fn send_invalid_params(
+    tx: &ClientSender,
+    id: Value,
+    message: String,
+    parameter: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let error_response = json!({
+        "jsonrpc": "2.0",
+        "error": {
+            "code": -32602,
+            "message": message,
+            "data": { "parameter": parameter }
+        },
+        "id": id
+    });
+    let error_msg = serde_json::to_string(&error_response)?;
+    tx.send(Message::Text(error_msg.into()))?;
+    Ok(())
+}
+
+/// Parses the optional `{encoding, commitment, dataSlice, minContextSlot}`
+/// config object from `params[1]` of an account or program subscription
+fn account_info_config(params: &Value) -> Result<Option<RpcAccountInfoConfig>, String> {
+    let config = match params.get(1) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(config) => config,
+    };
+    let account_config: RpcAccountInfoConfig = serde_json::from_value(config.clone())
+        .map_err(|e| format!("Invalid subscription config: {e}"))?;
+    // The flattened commitment silently becomes None when it fails to parse
+    if let Some(commitment) = config.get("commitment") {
+        serde_json::from_value::<solana_sdk::commitment_config::CommitmentLevel>(commitment.clone())
+            .map_err(|_| format!("Invalid subscription commitment: {commitment}"))?;
+    }
+    Ok(Some(account_config))
+}
+
+/// Parses the optional programSubscribe config from `params[1]`
+///
+/// Filters go through the same checks as the getProgramAccounts tools.
+/// `Err` carries the message and the parameter to report it against.
+fn program_accounts_config(params: &Value) -> Result<Option<RpcProgramAccountsConfig>, (String, &'static str)> {
+    let Some(account_config) = account_info_config(params).map_err(|message| (message, "config"))? else {
+        return Ok(None);
+    };
+    let filters = parse_program_account_filters(params[1].get("filters"), "filters")
+        .map_err(|e| (e.safe_message(), "filters"))?;
+    Ok(Some(RpcProgramAccountsConfig {
+        filters,
+        account_config,
+        with_context: params[1].get("withContext").and_then(Value::as_bool),
+        sort_results: None,
+    }))
+}
+
 /// Handle account subscription
 async fn handle_account_subscribe(
     params: Value,
     id: Value,
     subscriptions: &SubscriptionManager,
-    tx: &mpsc::UnboundedSender<Message>,
-    _config: &Arc<Config>,
+    tx: &ClientSender,
+    config: &Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let params_array = params.as_array().ok_or("Invalid params")?;
     if params_array.is_empty() {
@@ -255,71 +963,25 @@ async fn handle_account_subscribe(
 
     let pubkey_str = params_array[0].as_str().ok_or("Invalid pubkey")?;
     let pubkey: Pubkey = pubkey_str.parse()?;
-
-    let subscription_id = SUBSCRIPTION_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-    
-    // Create PubsubClient for this subscription with timeout
-    let ws_url = _config.rpc_url.replace("https://", "wss://").replace("http://", "ws://");
-    let sub_timeout = subscription_timeout(_config);
-    
-    let pubsub_client = match timeout(sub_timeout, PubsubClient::new(&ws_url)).await {
-        Ok(Ok(client)) => client,
-        Ok(Err(e)) => return Err(format!("Failed to create pubsub client: {}", e).into()),
-        Err(_) => return Err("Pubsub client creation timeout".into()),
+    let account_config = match account_info_config(&params) {
+        Ok(account_config) => account_config,
+        Err(message) => return send_invalid_params(tx, id, message, "config"),
     };
 
-    // Start the subscription with timeout
-    let tx_clone = tx.clone();
-    let subscription_id_clone = subscription_id;
-    tokio::spawn(async move {
-        match timeout(sub_timeout, pubsub_client.account_subscribe(&pubkey, None)).await {
-            Ok(Ok((mut stream, _unsubscriber))) => {
-                info!("Account subscription {} started for pubkey {}", subscription_id_clone, pubkey);
-                while let Some(account_info) = stream.next().await {
-                    let notification = json!({
-                        "jsonrpc": "2.0",
-                        "method": "accountNotification",
-                        "params": {
-                            "result": account_info,
-                            "subscription": subscription_id_clone
-                        }
-                    });
-                    
-                    if let Ok(msg) = serde_json::to_string(&notification) {
-                        if tx_clone.send(Message::Text(msg.into())).is_err() {
-                            debug!("Client disconnected, stopping account subscription {}", subscription_id_clone);
-                            break;
-                        }
-                    }
-                }
-            }
-            Ok(Err(e)) => {
-                error!("Failed to create account subscription {}: {}", subscription_id_clone, e);
-            }
-            Err(_) => {
-                error!("Account subscription {} creation timeout", subscription_id_clone);
-            }
+    spawn_upstream_subscription("accountSubscribe", params, id, subscriptions, tx, config, move |session| {
+        let account_config = account_config.clone();
+        async move {
+            let (mut stream, _unsubscriber) =
+                match session.subscribe(session.client.account_subscribe(&pubkey, account_config)).await {
+                    Ok(subscription) => subscription,
+                    Err(end) => return end,
+                };
+            info!("Account subscription {} started for pubkey {}", session.subscription_id, pubkey);
+            forward_notifications(&mut stream, "accountNotification", session.subscription_id, &session.tx, |_| true)
+                .await
         }
-    });
-
-    // Store subscription info
-    subscriptions.insert(subscription_id, Subscription {
-        id: subscription_id,
-        method: "accountSubscribe".to_string(),
-        params,
-        client_tx: tx.clone(),
-    });
-
-    // Send success response
-    let response = json!({
-        "jsonrpc": "2.0",
-        "result": subscription_id,
-        "id": id
-    });
-    let response_msg = serde_json::to_string(&response)?;
-    tx.send(Message::Text(response_msg.into()))?;
-
-    Ok(())
+    })
+    .await
 }
 
 /// Handle block subscription
@@ -327,13 +989,13 @@ async fn handle_block_subscribe(
     params: Value,
     id: Value,
     subscriptions: &SubscriptionManager,
-    tx: &mpsc::UnboundedSender<Message>,
-    _config: &Arc<Config>,
+    tx: &ClientSender,
+    config: &Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let subscription_id = SUBSCRIPTION_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    let subscription_id = subscriptions.next_id();
     
     // Create PubsubClient for this subscription
-    let ws_url = _config.rpc_url.replace("https://", "wss://").replace("http://", "ws://");
+    let ws_url = config.rpc_url.replace("https://", "wss://").replace("http://", "ws://");
     let _pubsub_client = PubsubClient::new(&ws_url).await?;
 
     // Parse block subscription filter
@@ -351,11 +1013,12 @@ async fn handle_block_subscribe(
     });
 
     // Store subscription info
-    subscriptions.insert(subscription_id, Subscription {
+    subscriptions.insert(Subscription {
         id: subscription_id,
         method: "blockSubscribe".to_string(),
         params,
         client_tx: tx.clone(),
+        task: None,
     });
 
     // Send success response
@@ -370,19 +1033,68 @@ async fn handle_block_subscribe(
     Ok(())
 }
 
+/// Server-side post-filter for logsNotification messages
+///
+/// The upstream subscription is opened unchanged; notifications whose log
+/// lines do not match are dropped before they reach the client.
+#[derive(Debug)]
+enum LogLineFilter {
+    /// Forward when any log line contains the substring
+    Contains(String),
+    /// Forward when any log line matches the regular expression
+    Pattern(regex::Regex),
+}
+
+impl LogLineFilter {
+    /// Parses the optional `logFilter` object from the subscription config
+    ///
+    /// Accepts `{"contains": "..."}` or `{"pattern": "..."}` in `params[1]`.
+    fn from_params(params: &Value) -> Result<Option<Self>, String> {
+        let Some(log_filter) = params.get(1).and_then(|config| config.get("logFilter")) else {
+            return Ok(None);
+        };
+        let object = log_filter
+            .as_object()
+            .ok_or("logFilter must be an object with 'contains' or 'pattern'")?;
+
+        match (object.get("contains"), object.get("pattern")) {
+            (Some(_), Some(_)) => Err("logFilter accepts only one of 'contains' or 'pattern'".to_string()),
+            (Some(contains), None) => match contains.as_str() {
+                Some(substring) if !substring.is_empty() => Ok(Some(Self::Contains(substring.to_string()))),
+                _ => Err("logFilter.contains must be a non-empty string".to_string()),
+            },
+            (None, Some(pattern)) => {
+                let pattern = pattern.as_str().ok_or("logFilter.pattern must be a string")?;
+                regex::Regex::new(pattern)
+                    .map(|regex| Some(Self::Pattern(regex)))
+                    .map_err(|e| format!("Invalid logFilter.pattern: {e}"))
+            }
+            (None, None) => Err("logFilter must set 'contains' or 'pattern'".to_string()),
+        }
+    }
+
+    /// Returns true if any log line satisfies the filter
+    fn matches(&self, logs: &[String]) -> bool {
+        match self {
+            Self::Contains(substring) => logs.iter().any(|line| line.contains(substring.as_str())),
+            Self::Pattern(regex) => logs.iter().any(|line| regex.is_match(line)),
+        }
+    }
+}
+
 /// Handle logs subscription
 async fn handle_logs_subscribe(
     params: Value,
     id: Value,
     subscriptions: &SubscriptionManager,
-    tx: &mpsc::UnboundedSender<Message>,
-    _config: &Arc<Config>,
+    tx: &ClientSender,
+    config: &Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let subscription_id = SUBSCRIPTION_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-    
-    // Create PubsubClient for this subscription
-    let ws_url = _config.rpc_url.replace("https://", "wss://").replace("http://", "ws://");
-    let pubsub_client = PubsubClient::new(&ws_url).await?;
+    // Reject a malformed post-filter before opening an upstream connection
+    let log_filter = match LogLineFilter::from_params(&params) {
+        Ok(log_filter) => log_filter,
+        Err(message) => return send_invalid_params(tx, id, message, "logFilter"),
+    };
 
     // Parse logs subscription filter
     let filter = if let Some(params_array) = params.as_array() {
@@ -421,57 +1133,28 @@ async fn handle_logs_subscribe(
         RpcTransactionLogsFilter::All
     };
 
-    let config = RpcTransactionLogsConfig {
+    let logs_config = RpcTransactionLogsConfig {
         commitment: None,
     };
-
-    // Start the subscription
-    let tx_clone = tx.clone();
-    let subscription_id_clone = subscription_id;
-    tokio::spawn(async move {
-        match pubsub_client.logs_subscribe(filter, config).await {
-            Ok((mut stream, _unsubscriber)) => {
-                while let Some(log_info) = stream.next().await {
-                    let notification = json!({
-                        "jsonrpc": "2.0",
-                        "method": "logsNotification",
-                        "params": {
-                            "result": log_info,
-                            "subscription": subscription_id_clone
-                        }
-                    });
-                    
-                    if let Ok(msg) = serde_json::to_string(&notification) {
-                        if tx_clone.send(Message::Text(msg.into())).is_err() {
-                            break;
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Failed to create logs subscription: {}", e);
-            }
+    let log_filter = Arc::new(log_filter);
+
+    spawn_upstream_subscription("logsSubscribe", params, id, subscriptions, tx, config, move |session| {
+        let filter = filter.clone();
+        let logs_config = logs_config.clone();
+        let log_filter = Arc::clone(&log_filter);
+        async move {
+            let (mut stream, _unsubscriber) =
+                match session.subscribe(session.client.logs_subscribe(filter, logs_config)).await {
+                    Ok(subscription) => subscription,
+                    Err(end) => return end,
+                };
+            forward_notifications(&mut stream, "logsNotification", session.subscription_id, &session.tx, |log_info| {
+                log_filter.as_ref().as_ref().map_or(true, |log_filter| log_filter.matches(&log_info.value.logs))
+            })
+            .await
         }
-    });
-
-    // Store subscription info
-    subscriptions.insert(subscription_id, Subscription {
-        id: subscription_id,
-        method: "logsSubscribe".to_string(),
-        params,
-        client_tx: tx.clone(),
-    });
-
-    // Send success response
-    let response = json!({
-        "jsonrpc": "2.0",
-        "result": subscription_id,
-        "id": id
-    });
-    let response_msg = serde_json::to_string(&response)?;
-    tx.send(Message::Text(response_msg.into()))?;
-
-    Ok(())
+    })
+    .await
 }
 
 /// Handle program subscription
@@ -479,8 +1162,8 @@ async fn handle_program_subscribe(
     params: Value,
     id: Value,
     subscriptions: &SubscriptionManager,
-    tx: &mpsc::UnboundedSender<Message>,
-    _config: &Arc<Config>,
+    tx: &ClientSender,
+    config: &Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let params_array = params.as_array().ok_or("Invalid params")?;
     if params_array.is_empty() {
@@ -489,59 +1172,175 @@ async fn handle_program_subscribe(
 
     let pubkey_str = params_array[0].as_str().ok_or("Invalid pubkey")?;
     let pubkey: Pubkey = pubkey_str.parse()?;
+    // Reject malformed filters before opening an upstream connection
+    let program_config = match program_accounts_config(&params) {
+        Ok(program_config) => program_config,
+        Err((message, parameter)) => return send_invalid_params(tx, id, message, parameter),
+    };
 
-    let subscription_id = SUBSCRIPTION_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-    
-    // Create PubsubClient for this subscription
-    let ws_url = _config.rpc_url.replace("https://", "wss://").replace("http://", "ws://");
-    let pubsub_client = PubsubClient::new(&ws_url).await?;
-
-    // Start the subscription
-    let tx_clone = tx.clone();
-    let subscription_id_clone = subscription_id;
-    tokio::spawn(async move {
-        match pubsub_client.program_subscribe(&pubkey, None).await {
-            Ok((mut stream, _unsubscriber)) => {
-                while let Some(program_info) = stream.next().await {
-                    let notification = json!({
-                        "jsonrpc": "2.0",
-                        "method": "programNotification",
-                        "params": {
-                            "result": program_info,
-                            "subscription": subscription_id_clone
-                        }
-                    });
-                    
-                    if let Ok(msg) = serde_json::to_string(&notification) {
-                        if tx_clone.send(Message::Text(msg.into())).is_err() {
-                            break;
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Failed to create program subscription: {}", e);
-            }
+    spawn_upstream_subscription("programSubscribe", params, id, subscriptions, tx, config, move |session| {
+        let program_config = program_config.clone();
+        async move {
+            let (mut stream, _unsubscriber) =
+                match session.subscribe(session.client.program_subscribe(&pubkey, program_config)).await {
+                    Ok(subscription) => subscription,
+                    Err(end) => return end,
+                };
+            forward_notifications(&mut stream, "programNotification", session.subscription_id, &session.tx, |_| true)
+                .await
         }
-    });
+    })
+    .await
+}
 
-    // Store subscription info
-    subscriptions.insert(subscription_id, Subscription {
-        id: subscription_id,
-        method: "programSubscribe".to_string(),
-        params,
-        client_tx: tx.clone(),
-    });
+/// Accounts per getProgramAccountsStream chunk when `chunkSize` is omitted
+const DEFAULT_STREAM_CHUNK_SIZE: usize = 100;
+
+/// Largest `chunkSize` a getProgramAccountsStream request may ask for
+const MAX_STREAM_CHUNK_SIZE: usize = 1000;
+
+/// Parses the optional `chunkSize` from the getProgramAccountsStream config
+fn stream_chunk_size(params: &Value) -> Result<usize, String> {
+    match params.get(1).and_then(|config| config.get("chunkSize")) {
+        None | Some(Value::Null) => Ok(DEFAULT_STREAM_CHUNK_SIZE),
+        Some(value) => value
+            .as_u64()
+            .filter(|size| (1..=MAX_STREAM_CHUNK_SIZE as u64).contains(size))
+            .map(|size| size as usize)
+            .ok_or_else(|| format!("chunkSize must be an integer between 1 and {MAX_STREAM_CHUNK_SIZE}, got {value}")),
+    }
+}
 
-    // Send success response
+/// Largest number of accounts a single getMultipleAccounts call may request
+const MULTIPLE_ACCOUNTS_BATCH: usize = 100;
+
+/// Handle getProgramAccountsStream
+///
+/// Replies with a stream id, then pages through the program's accounts over
+/// HTTP RPC: the matching pubkeys are listed first without account data, and
+/// each chunk of `chunkSize` accounts is fetched with getMultipleAccounts just
+/// before it is sent as a `programAccountsStreamNotification` numbered by
+/// `sequence`. A final notification carries `complete: true`. A failed fetch
+/// is reported as a JSON-RPC error carrying the stream id. The stream stops
+/// when the connection closes.
+async fn handle_program_accounts_stream(
+    params: Value,
+    id: Value,
+    subscriptions: &SubscriptionManager,
+    tx: &ClientSender,
+    rpc_pool: &RpcPool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let pubkey = match params.get(0).and_then(Value::as_str).map(str::parse::<Pubkey>) {
+        Some(Ok(pubkey)) => pubkey,
+        _ => {
+            let message = format!("programId must be a base58 pubkey, got {}", params.get(0).unwrap_or(&Value::Null));
+            return send_invalid_params(tx, id, message, "programId");
+        }
+    };
+    let mut program_config = match program_accounts_config(&params) {
+        Ok(program_config) => program_config.unwrap_or_default(),
+        Err((message, parameter)) => return send_invalid_params(tx, id, message, parameter),
+    };
+    let chunk_size = match stream_chunk_size(&params) {
+        Ok(chunk_size) => chunk_size,
+        Err(message) => return send_invalid_params(tx, id, message, "chunkSize"),
+    };
+    // base58 is the node default but cannot encode accounts over 128 bytes
+    program_config.account_config.encoding.get_or_insert(UiAccountEncoding::Base64);
+    // The accounts are streamed bare, so the context wrapper is never requested
+    program_config.with_context = None;
+
+    let stream_id = subscriptions.next_id();
     let response = json!({
         "jsonrpc": "2.0",
-        "result": subscription_id,
+        "result": stream_id,
         "id": id
     });
     let response_msg = serde_json::to_string(&response)?;
     tx.send(Message::Text(response_msg.into()))?;
 
+    let client = rpc_pool.next_client();
+    let tx = tx.clone();
+    let registry = Arc::clone(subscriptions);
+    let task = tokio::spawn(async move {
+        if let Err(e) = stream_program_accounts(&client, &pubkey, program_config, chunk_size, &tx, stream_id).await {
+            error!("getProgramAccountsStream {} failed: {}", stream_id, e);
+            let error_response = json!({
+                "jsonrpc": "2.0",
+                "error": {
+                    "code": -32603,
+                    "message": format!("getProgramAccountsStream failed: {}", e),
+                    "data": { "subscription": stream_id }
+                },
+                "id": null
+            });
+            if let Ok(error_msg) = serde_json::to_string(&error_response) {
+                let _ = tx.send(Message::Text(error_msg.into()));
+            }
+        }
+        registry.streams.remove(&stream_id);
+    });
+    // A stream that already finished leaves a stale handle, which is harmless to abort
+    subscriptions.streams.insert(stream_id, task.abort_handle());
+
+    Ok(())
+}
+
+/// Sends one program's accounts to the client a chunk at a time
+///
+/// Only the pubkey list is held for the whole stream; account data is
+/// fetched per chunk. Accounts closed after they were listed are skipped.
+async fn stream_program_accounts(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    program_config: RpcProgramAccountsConfig,
+    chunk_size: usize,
+    tx: &ClientSender,
+    stream_id: u64,
+) -> Result<(), solana_client::client_error::ClientError> {
+    let method = "programAccountsStreamNotification";
+    let account_config = program_config.account_config.clone();
+    let listing_config = RpcProgramAccountsConfig {
+        account_config: RpcAccountInfoConfig {
+            data_slice: Some(solana_account_decoder::UiDataSliceConfig { offset: 0, length: 0 }),
+            ..program_config.account_config.clone()
+        },
+        ..program_config
+    };
+    let listed = client
+        .send::<Vec<Value>>(RpcRequest::GetProgramAccounts, json!([program_id.to_string(), listing_config]))
+        .await?;
+    let pubkeys: Vec<String> = listed
+        .into_iter()
+        .filter_map(|keyed| keyed.get("pubkey").and_then(Value::as_str).map(str::to_string))
+        .collect();
+
+    let mut sequence = 0;
+    let mut total = 0;
+    for chunk in pubkeys.chunks(chunk_size) {
+        let mut accounts = Vec::with_capacity(chunk.len());
+        for batch in chunk.chunks(MULTIPLE_ACCOUNTS_BATCH) {
+            let response = client
+                .send::<Value>(RpcRequest::GetMultipleAccounts, json!([batch, account_config]))
+                .await?;
+            let values = response["value"].as_array().cloned().unwrap_or_default();
+            accounts.extend(
+                batch
+                    .iter()
+                    .zip(values)
+                    .filter(|(_, account)| !account.is_null())
+                    .map(|(pubkey, account)| json!({ "pubkey": pubkey, "account": account })),
+            );
+        }
+        total += accounts.len();
+        let result = json!({ "sequence": sequence, "accounts": accounts });
+        if !send_notification(tx, method, stream_id, &result) {
+            return Ok(());
+        }
+        sequence += 1;
+    }
+    let result = json!({ "sequence": sequence, "complete": true, "totalAccounts": total });
+    send_notification(tx, method, stream_id, &result);
     Ok(())
 }
 
@@ -550,62 +1349,17 @@ async fn handle_root_subscribe(
     params: Value,
     id: Value,
     subscriptions: &SubscriptionManager,
-    tx: &mpsc::UnboundedSender<Message>,
-    _config: &Arc<Config>,
+    tx: &ClientSender,
+    config: &Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let subscription_id = SUBSCRIPTION_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-    
-    // Create PubsubClient for this subscription
-    let ws_url = _config.rpc_url.replace("https://", "wss://").replace("http://", "ws://");
-    let pubsub_client = PubsubClient::new(&ws_url).await?;
-
-    // Start the subscription
-    let tx_clone = tx.clone();
-    let subscription_id_clone = subscription_id;
-    tokio::spawn(async move {
-        match pubsub_client.root_subscribe().await {
-            Ok((mut stream, _unsubscriber)) => {
-                while let Some(root_info) = stream.next().await {
-                    let notification = json!({
-                        "jsonrpc": "2.0",
-                        "method": "rootNotification",
-                        "params": {
-                            "result": root_info,
-                            "subscription": subscription_id_clone
-                        }
-                    });
-                    
-                    if let Ok(msg) = serde_json::to_string(&notification) {
-                        if tx_clone.send(Message::Text(msg.into())).is_err() {
-                            break;
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Failed to create root subscription: {}", e);
-            }
-        }
-    });
-
-    // Store subscription info
-    subscriptions.insert(subscription_id, Subscription {
-        id: subscription_id,
-        method: "rootSubscribe".to_string(),
-        params,
-        client_tx: tx.clone(),
-    });
-
-    // Send success response
-    let response = json!({
-        "jsonrpc": "2.0",
-        "result": subscription_id,
-        "id": id
-    });
-    let response_msg = serde_json::to_string(&response)?;
-    tx.send(Message::Text(response_msg.into()))?;
-
-    Ok(())
+    spawn_upstream_subscription("rootSubscribe", params, id, subscriptions, tx, config, |session| async move {
+        let (mut stream, _unsubscriber) = match session.subscribe(session.client.root_subscribe()).await {
+            Ok(subscription) => subscription,
+            Err(end) => return end,
+        };
+        forward_notifications(&mut stream, "rootNotification", session.subscription_id, &session.tx, |_| true).await
+    })
+    .await
 }
 
 // Implement remaining subscription handlers...
@@ -613,8 +1367,8 @@ async fn handle_signature_subscribe(
     params: Value,
     id: Value,
     subscriptions: &SubscriptionManager,
-    tx: &mpsc::UnboundedSender<Message>,
-    _config: &Arc<Config>,
+    tx: &ClientSender,
+    config: &Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let params_array = params.as_array().ok_or("Invalid params")?;
     if params_array.is_empty() {
@@ -624,138 +1378,83 @@ async fn handle_signature_subscribe(
     let signature_str = params_array[0].as_str().ok_or("Invalid signature")?;
     let signature = signature_str.parse().map_err(|e| format!("Invalid signature: {}", e))?;
 
-    let subscription_id = SUBSCRIPTION_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-    
-    // Create PubsubClient for this subscription
-    let ws_url = _config.rpc_url.replace("https://", "wss://").replace("http://", "ws://");
-    let pubsub_client = PubsubClient::new(&ws_url).await?;
-
-    // Start the subscription
-    let tx_clone = tx.clone();
-    let subscription_id_clone = subscription_id;
-    tokio::spawn(async move {
-        match pubsub_client.signature_subscribe(&signature, None).await {
-            Ok((mut stream, _unsubscriber)) => {
-                while let Some(signature_info) = stream.next().await {
-                    let notification = json!({
-                        "jsonrpc": "2.0",
-                        "method": "signatureNotification",
-                        "params": {
-                            "result": signature_info,
-                            "subscription": subscription_id_clone
-                        }
-                    });
-                    
-                    if let Ok(msg) = serde_json::to_string(&notification) {
-                        if tx_clone.send(Message::Text(msg.into())).is_err() {
-                            break;
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Failed to create signature subscription: {}", e);
-            }
+    spawn_upstream_subscription("signatureSubscribe", params, id, subscriptions, tx, config, move |session| {
+        async move {
+            let (stream, _unsubscriber) =
+                match session.subscribe(session.client.signature_subscribe(&signature, None)).await {
+                    Ok(subscription) => subscription,
+                    Err(end) => return end,
+                };
+            // The node cancels a signature subscription after its one notification
+            let (subscription_id, tx) = (session.subscription_id, &session.tx);
+            forward_notifications(&mut stream.take(1), "signatureNotification", subscription_id, tx, |_| true)
+                .await
+                .completed_after_delivery()
         }
-    });
-
-    // Store subscription info
-    subscriptions.insert(subscription_id, Subscription {
-        id: subscription_id,
-        method: "signatureSubscribe".to_string(),
-        params,
-        client_tx: tx.clone(),
-    });
-
-    // Send success response
-    let response = json!({
-        "jsonrpc": "2.0",
-        "result": subscription_id,
-        "id": id
-    });
-    let response_msg = serde_json::to_string(&response)?;
-    tx.send(Message::Text(response_msg.into()))?;
+    })
+    .await
+}
 
-    Ok(())
+/// Parses the optional `minIntervalMs` from `params[0]` of a slotSubscribe
+///
+/// `None` (omitted, null or 0) forwards every slot notification.
+fn slot_min_interval(params: &Value) -> Result<Option<Duration>, String> {
+    match params.get(0).and_then(|options| options.get("minIntervalMs")) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .map(|ms| (ms > 0).then(|| Duration::from_millis(ms)))
+            .ok_or_else(|| format!("Invalid minIntervalMs: expected a non-negative integer, got {value}")),
+    }
 }
 
 async fn handle_slot_subscribe(
     params: Value,
     id: Value,
     subscriptions: &SubscriptionManager,
-    tx: &mpsc::UnboundedSender<Message>,
-    _config: &Arc<Config>,
+    tx: &ClientSender,
+    config: &Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let subscription_id = SUBSCRIPTION_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-    
-    // Create PubsubClient for this subscription
-    let ws_url = _config.rpc_url.replace("https://", "wss://").replace("http://", "ws://");
-    let pubsub_client = PubsubClient::new(&ws_url).await?;
+    let min_interval = match slot_min_interval(&params) {
+        Ok(min_interval) => min_interval,
+        Err(message) => return send_invalid_params(tx, id, message, "minIntervalMs"),
+    };
 
-    // Start the subscription
-    let tx_clone = tx.clone();
-    let subscription_id_clone = subscription_id;
-    tokio::spawn(async move {
-        match pubsub_client.slot_subscribe().await {
-            Ok((mut stream, _unsubscriber)) => {
-                while let Some(slot_info) = stream.next().await {
-                    let notification = json!({
-                        "jsonrpc": "2.0",
-                        "method": "slotNotification",
-                        "params": {
-                            "result": slot_info,
-                            "subscription": subscription_id_clone
-                        }
-                    });
-                    
-                    if let Ok(msg) = serde_json::to_string(&notification) {
-                        if tx_clone.send(Message::Text(msg.into())).is_err() {
-                            break;
-                        }
-                    }
+    spawn_upstream_subscription("slotSubscribe", params, id, subscriptions, tx, config, move |session| {
+        async move {
+            let (mut stream, _unsubscriber) = match session.subscribe(session.client.slot_subscribe()).await {
+                Ok(subscription) => subscription,
+                Err(end) => return end,
+            };
+            let (subscription_id, tx) = (session.subscription_id, &session.tx);
+            match min_interval {
+                Some(min_interval) => {
+                    forward_throttled_notifications(&mut stream, "slotNotification", subscription_id, tx, min_interval)
+                        .await
                 }
-            }
-            Err(e) => {
-                error!("Failed to create slot subscription: {}", e);
+                None => forward_notifications(&mut stream, "slotNotification", subscription_id, tx, |_| true).await,
             }
         }
-    });
-
-    // Store subscription info
-    subscriptions.insert(subscription_id, Subscription {
-        id: subscription_id,
-        method: "slotSubscribe".to_string(),
-        params,
-        client_tx: tx.clone(),
-    });
-
-    // Send success response
-    let response = json!({
-        "jsonrpc": "2.0",
-        "result": subscription_id,
-        "id": id
-    });
-    let response_msg = serde_json::to_string(&response)?;
-    tx.send(Message::Text(response_msg.into()))?;
-
-    Ok(())
+    })
+    .await
 }
 
 async fn handle_slots_updates_subscribe(
     params: Value,
     id: Value,
     subscriptions: &SubscriptionManager,
-    tx: &mpsc::UnboundedSender<Message>,
+    tx: &ClientSender,
     _config: &Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let subscription_id = SUBSCRIPTION_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    let subscription_id = subscriptions.next_id();
     
     // Store subscription info (slots updates subscription is unstable)
-    subscriptions.insert(subscription_id, Subscription {
+    subscriptions.insert(Subscription {
         id: subscription_id,
         method: "slotsUpdatesSubscribe".to_string(),
         params,
         client_tx: tx.clone(),
+        task: None,
     });
 
     warn!("Slots updates subscription is unstable and may not be supported on all RPC endpoints");
@@ -776,17 +1475,18 @@ async fn handle_vote_subscribe(
     params: Value,
     id: Value,
     subscriptions: &SubscriptionManager,
-    tx: &mpsc::UnboundedSender<Message>,
+    tx: &ClientSender,
     _config: &Arc<Config>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let subscription_id = SUBSCRIPTION_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    let subscription_id = subscriptions.next_id();
     
     // Store subscription info (vote subscription is unstable)
-    subscriptions.insert(subscription_id, Subscription {
+    subscriptions.insert(Subscription {
         id: subscription_id,
         method: "voteSubscribe".to_string(),
         params,
         client_tx: tx.clone(),
+        task: None,
     });
 
     warn!("Vote subscription is unstable and may not be supported on all RPC endpoints");
@@ -808,7 +1508,7 @@ async fn handle_unsubscribe(
     params: Value,
     id: Value,
     subscriptions: &SubscriptionManager,
-    tx: &mpsc::UnboundedSender<Message>,
+    tx: &ClientSender,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let params_array = params.as_array().ok_or("Invalid params")?;
     if params_array.is_empty() {
@@ -817,7 +1517,7 @@ async fn handle_unsubscribe(
 
     let subscription_id = params_array[0].as_u64().ok_or("Invalid subscription ID")?;
     
-    let success = subscriptions.remove(&subscription_id).is_some();
+    let success = subscriptions.remove(subscription_id);
 
     // Send response
     let response = json!({
@@ -846,4 +1546,497 @@ pub fn start_websocket_server_task(port: u16, config: Arc<Config>) -> tokio::tas
             error!("WebSocket server failed: {}", e);
         }
     })
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keepalive_tracker_detects_half_open() {
+        let start = tokio::time::Instant::now();
+        let mut keepalive = KeepaliveTracker::default();
+        assert!(!keepalive.is_half_open(start, Duration::from_secs(10)));
+
+        keepalive.ping_sent(start);
+        // A second ping must not reset the outstanding timestamp
+        keepalive.ping_sent(start + Duration::from_secs(5));
+        assert!(!keepalive.is_half_open(start + Duration::from_secs(10), Duration::from_secs(10)));
+        assert!(keepalive.is_half_open(start + Duration::from_secs(11), Duration::from_secs(10)));
+
+        keepalive.pong_received();
+        assert!(!keepalive.is_half_open(start + Duration::from_secs(60), Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_log_line_filter() {
+        let logs = vec![
+            "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+            "Program log: Error: custom program error: 0x1771".to_string(),
+        ];
+
+        assert!(LogLineFilter::from_params(&json!(["all"])).unwrap().is_none());
+        assert!(LogLineFilter::from_params(&json!(["all", {"commitment": "confirmed"}])).unwrap().is_none());
+
+        let contains = LogLineFilter::from_params(&json!(["all", {"logFilter": {"contains": "0x1771"}}]))
+            .unwrap()
+            .unwrap();
+        assert!(contains.matches(&logs));
+        assert!(!contains.matches(&logs[..1]));
+
+        let pattern = LogLineFilter::from_params(&json!(["all", {"logFilter": {"pattern": "error: 0x17[0-9a-f]{2}$"}}]))
+            .unwrap()
+            .unwrap();
+        assert!(pattern.matches(&logs));
+        assert!(!pattern.matches(&[]));
+
+        for invalid in [
+            json!("0x1771"),
+            json!({}),
+            json!({"contains": ""}),
+            json!({"pattern": "("}),
+            json!({"contains": "a", "pattern": "b"}),
+        ] {
+            assert!(
+                LogLineFilter::from_params(&json!(["all", {"logFilter": invalid}])).is_err(),
+                "{invalid} accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn test_account_info_config() {
+        let pubkey = Pubkey::new_unique().to_string();
+        assert!(account_info_config(&json!([pubkey])).unwrap().is_none());
+
+        let config = account_info_config(&json!([pubkey, {"encoding": "jsonParsed", "commitment": "finalized"}]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.encoding, Some(solana_account_decoder::UiAccountEncoding::JsonParsed));
+        assert_eq!(
+            config.commitment,
+            Some(solana_sdk::commitment_config::CommitmentConfig::finalized())
+        );
+
+        for invalid in [json!("jsonParsed"), json!({"encoding": "hex"}), json!({"commitment": "latest"})] {
+            assert!(account_info_config(&json!([pubkey, invalid])).is_err(), "{invalid} accepted");
+        }
+    }
+
+    #[test]
+    fn test_program_accounts_config() {
+        let program = Pubkey::new_unique().to_string();
+        assert!(program_accounts_config(&json!([program])).unwrap().is_none());
+
+        let config = program_accounts_config(&json!([
+            program,
+            {
+                "encoding": "base64",
+                "commitment": "confirmed",
+                "filters": [{"dataSize": 165}, {"memcmp": {"offset": 32, "bytes": program}}]
+            }
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(config.filters.map(|filters| filters.len()), Some(2));
+        assert_eq!(config.account_config.encoding, Some(solana_account_decoder::UiAccountEncoding::Base64));
+
+        let too_many = vec![json!({"dataSize": 165}); 5];
+        for (invalid, parameter) in [
+            (json!({"filters": [{"bogus": 1}]}), "filters"),
+            (json!({"filters": too_many}), "filters"),
+            (json!({"filters": [{"memcmp": {"offset": 0, "bytes": "0OIl"}}]}), "filters"),
+            (json!({"encoding": "hex"}), "config"),
+        ] {
+            let (_, reported) = program_accounts_config(&json!([program, invalid])).unwrap_err();
+            assert_eq!(reported, parameter, "{invalid}");
+        }
+    }
+
+    fn text(message: Option<Message>) -> String {
+        match message {
+            Some(Message::Text(text)) => text.to_string(),
+            other => panic!("expected a text message, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_queue_drops_oldest_when_full() {
+        let (tx, mut rx) = client_queue(2, WebSocketOverflowPolicy::DropOldest);
+        for n in 0..3 {
+            tx.send(Message::Text(n.to_string().into())).unwrap();
+        }
+
+        assert_eq!(text(rx.recv().await), "1");
+        assert_eq!(text(rx.recv().await), "2");
+
+        drop(rx);
+        assert!(tx.is_closed());
+        assert!(tx.send(Message::Text("late".into())).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_client_queue_disconnects_slow_client() {
+        let (tx, mut rx) = client_queue(2, WebSocketOverflowPolicy::Disconnect);
+        tx.send(Message::Text("0".into())).unwrap();
+        tx.send(Message::Text("1".into())).unwrap();
+        assert!(tx.send(Message::Text("2".into())).is_err());
+        assert!(tx.is_closed());
+
+        // The backlog is discarded in favour of an immediate close frame
+        match rx.recv().await {
+            Some(Message::Close(Some(frame))) => assert_eq!(frame.reason, "client too slow"),
+            other => panic!("expected a close frame, got {other:?}"),
+        }
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[test]
+    fn test_reconnect_backoff_doubles_up_to_cap() {
+        let cap = Duration::from_secs(30);
+        assert_eq!(reconnect_backoff(0, cap), Duration::from_secs(1));
+        assert_eq!(reconnect_backoff(3, cap), Duration::from_secs(8));
+        assert_eq!(reconnect_backoff(5, cap), cap);
+        assert_eq!(reconnect_backoff(u32::MAX, cap), cap);
+
+        assert_eq!(
+            UpstreamEnd::Dropped { reason: "closed".to_string(), received: true }.completed_after_delivery(),
+            UpstreamEnd::Finished
+        );
+        assert_eq!(UpstreamEnd::dropped("closed").completed_after_delivery(), UpstreamEnd::dropped("closed"));
+    }
+
+    #[test]
+    fn test_slot_min_interval() {
+        assert_eq!(slot_min_interval(&json!([])).unwrap(), None);
+        assert_eq!(slot_min_interval(&Value::Null).unwrap(), None);
+        assert_eq!(slot_min_interval(&json!([{"minIntervalMs": 0}])).unwrap(), None);
+        assert_eq!(
+            slot_min_interval(&json!([{"minIntervalMs": 1000}])).unwrap(),
+            Some(Duration::from_secs(1))
+        );
+        assert!(slot_min_interval(&json!([{"minIntervalMs": -5}])).is_err());
+        assert!(slot_min_interval(&json!([{"minIntervalMs": "1000"}])).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_throttled_notifications_forward_latest_per_interval() {
+        let (upstream, upstream_rx) = tokio::sync::mpsc::unbounded_channel::<u64>();
+        let mut stream = Box::pin(futures_util::stream::unfold(upstream_rx, |mut rx| async move {
+            rx.recv().await.map(|slot| (slot, rx))
+        }));
+        let (tx, mut rx) = client_queue(16, WebSocketOverflowPolicy::DropOldest);
+
+        let forwarder = tokio::spawn(async move {
+            forward_throttled_notifications(&mut stream, "slotNotification", 1, &tx, Duration::from_millis(100)).await
+        });
+        for slot in 1..=3 {
+            upstream.send(slot).unwrap();
+        }
+        let slots: Vec<Value> = [rx.recv().await, rx.recv().await]
+            .into_iter()
+            .map(|message| serde_json::from_str::<Value>(&text(message)).unwrap()["params"]["result"].clone())
+            .collect();
+        assert_eq!(slots, vec![json!(1), json!(3)]);
+
+        upstream.send(4).unwrap();
+        drop(upstream);
+        let flushed: Value = serde_json::from_str(&text(rx.recv().await)).unwrap();
+        assert_eq!(flushed["params"]["result"], 4);
+        assert!(matches!(forwarder.await.unwrap(), UpstreamEnd::Dropped { received: true, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_upstream_subscription_reconnects_then_reports_error() {
+        // Upstream that accepts every websocket connection and then idles
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    if let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await {
+                        while ws.next().await.is_some() {}
+                    }
+                });
+            }
+        });
+
+        let mut config = Config::load().expect("Failed to load config");
+        config.timeouts.websocket_reconnect_attempts = 2;
+        config.timeouts.websocket_reconnect_max_backoff_seconds = 0;
+        let subscriptions = SubscriptionManager::default();
+        let (tx, mut rx) = client_queue(4, WebSocketOverflowPolicy::DropOldest);
+        subscriptions.insert(Subscription {
+            id: 7,
+            method: "slotSubscribe".to_string(),
+            params: json!([]),
+            client_tx: tx.clone(),
+            task: None,
+        });
+
+        let sessions = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let session_count = Arc::clone(&sessions);
+        run_upstream_subscription(
+            7,
+            "slotSubscribe",
+            ws_url.clone(),
+            PubsubClient::new(&ws_url).await.unwrap(),
+            Arc::clone(&subscriptions),
+            tx,
+            Arc::new(config),
+            move |_pubsub_client| {
+                session_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                async { UpstreamEnd::dropped("upstream stream ended") }
+            },
+        )
+        .await;
+
+        // The initial session plus one per reconnect attempt
+        assert_eq!(sessions.load(std::sync::atomic::Ordering::SeqCst), 3);
+        assert!(!subscriptions.contains(7));
+
+        let error: Value = serde_json::from_str(&text(rx.recv().await)).unwrap();
+        assert_eq!(error["error"]["code"], -32603);
+        assert_eq!(error["error"]["data"]["subscription"], 7);
+    }
+
+    type ClientStream = tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >;
+
+    /// Sends a JSON-RPC request and waits for the next text frame
+    async fn ws_request(ws: &mut ClientStream, request: Value) -> Value {
+        use tokio_tungstenite::tungstenite::Message as ClientMessage;
+
+        ws.send(ClientMessage::Text(request.to_string().into())).await.unwrap();
+        ws_next(ws).await
+    }
+
+    /// Waits for the next text frame
+    async fn ws_next(ws: &mut ClientStream) -> Value {
+        use tokio_tungstenite::tungstenite::Message as ClientMessage;
+
+        loop {
+            if let ClientMessage::Text(text) = ws.next().await.unwrap().unwrap() {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    /// Starts an upstream pubsub node that answers slotSubscribe and then
+    /// sends a slotNotification every 20ms until the connection closes
+    async fn mock_slot_upstream() -> String {
+        use tokio_tungstenite::tungstenite::Message as UpstreamMessage;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await else { return };
+                    let request = loop {
+                        match ws.next().await {
+                            Some(Ok(UpstreamMessage::Text(text))) => break serde_json::from_str::<Value>(&text).unwrap(),
+                            Some(Ok(_)) => continue,
+                            _ => return,
+                        }
+                    };
+                    let reply = json!({"jsonrpc": "2.0", "result": 42, "id": request["id"]});
+                    if ws.send(UpstreamMessage::Text(reply.to_string().into())).await.is_err() {
+                        return;
+                    }
+                    for slot in 1u64.. {
+                        let notification = json!({
+                            "jsonrpc": "2.0",
+                            "method": "slotNotification",
+                            "params": { "result": { "parent": slot - 1, "root": 0, "slot": slot }, "subscription": 42 }
+                        });
+                        if ws.send(UpstreamMessage::Text(notification.to_string().into())).await.is_err() {
+                            return;
+                        }
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                    }
+                });
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_subscription_ids_are_scoped_per_connection() {
+        use tokio_tungstenite::tungstenite::Message as ClientMessage;
+
+        let mut config = Config::load().expect("Failed to load config");
+        config.rpc_url = mock_slot_upstream().await;
+        let app = router(Arc::new(config), Arc::new(crate::config::AuthConfig::default()));
+        let url = format!("ws://{}/", crate::http_server::spawn_test_server(app).await);
+
+        // voteSubscribe needs no upstream, so ids come straight from the connection
+        let subscribe_twice = |url: String| async move {
+            let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+            let mut ids = Vec::new();
+            for id in 1..=2 {
+                let response = ws_request(&mut ws, json!({"jsonrpc": "2.0", "id": id, "method": "voteSubscribe"})).await;
+                ids.push(response["result"].as_u64().unwrap());
+            }
+            (ws, ids)
+        };
+        let ((mut first, first_ids), (mut second, second_ids)) =
+            tokio::join!(subscribe_twice(url.clone()), subscribe_twice(url));
+        assert_eq!(first_ids, vec![1, 2]);
+        assert_eq!(second_ids, vec![1, 2]);
+
+        let unsubscribe = json!({"jsonrpc": "2.0", "id": 3, "method": "voteUnsubscribe", "params": [2]});
+        assert_eq!(ws_request(&mut first, unsubscribe.clone()).await["result"], true);
+        assert_eq!(ws_request(&mut first, unsubscribe.clone()).await["result"], false);
+        // The other connection's subscription 2 is untouched
+        assert_eq!(ws_request(&mut second, unsubscribe).await["result"], true);
+
+        // Unsubscribing stops the upstream task, so notifications stop too
+        let subscribe = json!({"jsonrpc": "2.0", "id": 4, "method": "slotSubscribe"});
+        let slot_subscription = ws_request(&mut second, subscribe).await["result"].as_u64().unwrap();
+        let notification = ws_next(&mut second).await;
+        assert_eq!(notification["method"], "slotNotification");
+        assert_eq!(notification["params"]["subscription"], slot_subscription);
+
+        let unsubscribe = json!({"jsonrpc": "2.0", "id": 5, "method": "slotUnsubscribe", "params": [slot_subscription]});
+        second.send(ClientMessage::Text(unsubscribe.to_string().into())).await.unwrap();
+        // Notifications sent before the unsubscribe was handled may still precede its reply
+        loop {
+            let message = ws_next(&mut second).await;
+            if message["id"] == 5 {
+                assert_eq!(message["result"], true);
+                break;
+            }
+            assert_eq!(message["method"], "slotNotification");
+        }
+        assert!(
+            timeout(Duration::from_millis(300), ws_next(&mut second)).await.is_err(),
+            "notification arrived after unsubscribe"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_program_accounts_stream_sends_sequenced_chunks() {
+        // Mock HTTP RPC listing five accounts, one of which closes before it is fetched
+        let rpc = Router::new().route(
+            "/",
+            axum::routing::post(|axum::Json(request): axum::Json<Value>| async move {
+                let result = match request["method"].as_str().unwrap() {
+                    "getProgramAccounts" => {
+                        // Only pubkeys are listed up front
+                        assert_eq!(request["params"][1]["dataSlice"], json!({ "offset": 0, "length": 0 }));
+                        json!((0..5).map(|i| json!({ "pubkey": format!("account{i}") })).collect::<Vec<_>>())
+                    }
+                    "getMultipleAccounts" => {
+                        assert_eq!(request["params"][1]["encoding"], "base64");
+                        let pubkeys = request["params"][0].as_array().unwrap();
+                        assert!(pubkeys.len() <= 2, "fetched more than one chunk: {pubkeys:?}");
+                        let accounts: Vec<Value> = pubkeys
+                            .iter()
+                            .map(|pubkey| if pubkey == "account3" { Value::Null } else { json!({ "lamports": 1 }) })
+                            .collect();
+                        json!({ "context": { "slot": 1 }, "value": accounts })
+                    }
+                    other => panic!("unexpected method {other}"),
+                };
+                axum::Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+            }),
+        );
+        let rpc_url = format!("http://{}", crate::http_server::spawn_test_server(rpc).await);
+
+        let mut config = Config::load().expect("Failed to load config");
+        config.rpc_url = rpc_url;
+        config.rpc_urls.clear();
+        let app = router(Arc::new(config), Arc::new(crate::config::AuthConfig::default()));
+        let url = format!("ws://{}/", crate::http_server::spawn_test_server(app).await);
+        let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+        let bad_program = json!({"jsonrpc": "2.0", "id": 9, "method": "getProgramAccountsStream", "params": ["not-a-key"]});
+        let response = ws_request(&mut ws, bad_program).await;
+        assert_eq!(response["id"], 9);
+        assert_eq!(response["error"]["code"], -32602);
+        assert_eq!(response["error"]["data"]["parameter"], "programId");
+
+        let program = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+        let invalid = json!({"jsonrpc": "2.0", "id": 1, "method": "getProgramAccountsStream", "params": [program, {"chunkSize": 0}]});
+        let response = ws_request(&mut ws, invalid).await;
+        assert_eq!(response["error"]["code"], -32602);
+        assert_eq!(response["error"]["data"]["parameter"], "chunkSize");
+
+        let request = json!({"jsonrpc": "2.0", "id": 2, "method": "getProgramAccountsStream", "params": [program, {"chunkSize": 2}]});
+        let stream_id = ws_request(&mut ws, request).await["result"].as_u64().unwrap();
+
+        let mut streamed = Vec::new();
+        for sequence in 0..3 {
+            let notification = ws_next(&mut ws).await;
+            assert_eq!(notification["method"], "programAccountsStreamNotification");
+            assert_eq!(notification["params"]["subscription"], stream_id);
+            assert_eq!(notification["params"]["result"]["sequence"], sequence);
+            streamed.extend(notification["params"]["result"]["accounts"].as_array().unwrap().clone());
+        }
+        assert_eq!(streamed.len(), 4);
+        assert_eq!(streamed[3]["pubkey"], "account4");
+        assert_eq!(streamed[3]["account"]["lamports"], 1);
+
+        let complete = ws_next(&mut ws).await;
+        assert_eq!(complete["params"]["result"]["sequence"], 3);
+        assert_eq!(complete["params"]["result"]["complete"], true);
+        assert_eq!(complete["params"]["result"]["totalAccounts"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_aborts_running_streams() {
+        let subscriptions = SubscriptionManager::default();
+        let stream = tokio::spawn(std::future::pending::<()>());
+        subscriptions.streams.insert(subscriptions.next_id(), stream.abort_handle());
+
+        cleanup_subscriptions(&subscriptions).await;
+        assert!(stream.await.unwrap_err().is_cancelled());
+        assert!(subscriptions.streams.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_upgrade_rejects_disallowed_origins() {
+        use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Error as WsError};
+
+        let mut config = Config::load().expect("Failed to load config");
+        config.allowed_ws_origins = vec!["https://app.example.com".to_string()];
+        let app = router(Arc::new(config), Arc::new(crate::config::AuthConfig::default()));
+        let url = format!("ws://{}/", crate::http_server::spawn_test_server(app).await);
+
+        let connect = |origin: Option<&'static str>| {
+            let mut request = url.clone().into_client_request().unwrap();
+            if let Some(origin) = origin {
+                request.headers_mut().insert(ORIGIN, origin.parse().unwrap());
+            }
+            tokio_tungstenite::connect_async(request)
+        };
+
+        assert!(connect(Some("https://app.example.com")).await.is_ok());
+        match connect(Some("https://evil.example.com")).await {
+            Err(WsError::Http(response)) => assert_eq!(response.status(), StatusCode::FORBIDDEN),
+            other => panic!("expected 403, got {:?}", other.map(|_| ())),
+        }
+        // Non-browser clients send no Origin and are still accepted
+        assert!(connect(None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_health_route() {
+        use tower::ServiceExt;
+
+        let app: Router = Router::new().route("/health", get(health_handler));
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/health")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+    }
+}
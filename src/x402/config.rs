@@ -5,7 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use crate::error::{McpError, McpResult};
-use super::validation::validate_caip2_network;
+use super::types::PaymentRequirements;
+use super::validation::{validate_caip2_network, validate_payment_amount, validate_timeout};
 
 /// Supported asset configuration for a network
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -51,6 +52,11 @@ pub struct X402Config {
     pub max_retries: u32,
     /// Supported networks and assets
     pub networks: HashMap<String, NetworkConfig>,
+    /// Tools that require payment, keyed by tool name
+    ///
+    /// Tools not listed here are served without payment.
+    #[serde(default)]
+    pub tool_payments: HashMap<String, PaymentRequirements>,
 }
 
 fn default_request_timeout() -> u64 {
@@ -69,6 +75,7 @@ impl Default for X402Config {
             request_timeout_seconds: default_request_timeout(),
             max_retries: default_max_retries(),
             networks: HashMap::new(),
+            tool_payments: HashMap::new(),
         }
     }
 }
@@ -185,9 +192,34 @@ impl X402Config {
             }
         }
 
+        // Validate per-tool payment requirements
+        for (tool_name, requirements) in &self.tool_payments {
+            let invalid = |e: McpError| {
+                McpError::validation(format!("Invalid payment for tool '{}': {}", tool_name, e))
+            };
+            validate_caip2_network(&requirements.network).map_err(invalid)?;
+            validate_payment_amount(&requirements.amount).map_err(invalid)?;
+            validate_timeout(requirements.max_timeout_seconds).map_err(invalid)?;
+
+            if requirements.asset.is_empty() || requirements.pay_to.is_empty() {
+                return Err(McpError::validation(format!(
+                    "Tool '{}' payment must have asset and pay_to configured",
+                    tool_name
+                )));
+            }
+        }
+
         Ok(())
     }
 
+    /// Returns the payment requirements for a tool, if it is gated
+    pub fn tool_payment(&self, tool_name: &str) -> Option<&PaymentRequirements> {
+        if !self.enabled {
+            return None;
+        }
+        self.tool_payments.get(tool_name)
+    }
+
     /// Returns a network configuration by network ID
     pub fn get_network(&self, network_id: &str) -> Option<&NetworkConfig> {
         self.networks.get(network_id)
@@ -253,6 +285,7 @@ mod tests {
             request_timeout_seconds: 30,
             max_retries: 3,
             networks,
+            tool_payments: HashMap::new(),
         };
 
         assert!(config.validate().is_ok());
@@ -282,11 +315,55 @@ mod tests {
             request_timeout_seconds: 30,
             max_retries: 3,
             networks,
+            tool_payments: HashMap::new(),
         };
 
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_tool_payment_requires_valid_network() {
+        let mut config = X402Config {
+            enabled: true,
+            facilitator_base_url: "https://facilitator.example.com".to_string(),
+            ..X402Config::default()
+        };
+        config.networks.insert(
+            "solana-mainnet".to_string(),
+            NetworkConfig {
+                network: "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp".to_string(),
+                assets: vec![AssetConfig {
+                    address: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+                    name: "USDC".to_string(),
+                    decimals: 6,
+                }],
+                pay_to: "FeeRecipient123456789".to_string(),
+                min_compute_unit_price: None,
+                max_compute_unit_price: None,
+            },
+        );
+        let mut requirements = PaymentRequirements {
+            scheme: "exact".to_string(),
+            network: "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp".to_string(),
+            amount: "1000".to_string(),
+            asset: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+            pay_to: "FeeRecipient123456789".to_string(),
+            max_timeout_seconds: 60,
+            extra: None,
+        };
+        config.tool_payments.insert("getBlock".to_string(), requirements.clone());
+        assert!(config.validate().is_ok());
+        assert!(config.tool_payment("getBlock").is_some());
+        assert!(config.tool_payment("getBalance").is_none());
+
+        requirements.network = "solana-mainnet".to_string();
+        config.tool_payments.insert("getBlock".to_string(), requirements);
+        assert!(config.validate().is_err());
+
+        config.enabled = false;
+        assert!(config.tool_payment("getBlock").is_none());
+    }
+
     #[test]
     fn test_get_network() {
         let mut networks = HashMap::new();
@@ -307,6 +384,7 @@ mod tests {
             request_timeout_seconds: 30,
             max_retries: 3,
             networks,
+            tool_payments: HashMap::new(),
         };
 
         assert!(config.get_network("solana-mainnet").is_some());
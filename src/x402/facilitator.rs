@@ -8,11 +8,37 @@
 use super::config::X402Config;
 use super::types::{PaymentPayload, PaymentRequirements, SettlementResponse, VerifyResponse};
 use crate::error::{McpError, McpResult};
-use reqwest::Client;
+use reqwest::{header::RETRY_AFTER, Client, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use uuid::Uuid;
 
+/// Longest Retry-After the client waits out; a longer one fails the call at once
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// Why a single facilitator request failed
+enum AttemptError {
+    /// The facilitator answered 429, with the Retry-After it sent if any
+    RateLimited { error: McpError, retry_after: Option<Duration> },
+    Failed(McpError),
+}
+
+impl From<McpError> for AttemptError {
+    fn from(error: McpError) -> Self {
+        Self::Failed(error)
+    }
+}
+
+/// Parses a Retry-After value, either delay-seconds or an HTTP date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(at.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
 /// Request body for /verify and /settle endpoints
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -100,7 +126,7 @@ impl FacilitatorClient {
 
         let url = format!("{}/verify", self.base_url);
         
-        self.execute_with_retry(&url, &request, &trace_id, "verify").await
+        self.execute_with_retry(&url, &request, &trace_id, "verify", None).await
     }
 
     /// Settles a payment by broadcasting to blockchain
@@ -108,6 +134,7 @@ impl FacilitatorClient {
     /// # Arguments
     /// * `payment_payload` - Payment payload from client
     /// * `payment_requirements` - Original payment requirements
+    /// * `idempotency_key` - Sent as `Idempotency-Key`, the same on every retry
     ///
     /// # Returns
     /// * `McpResult<SettlementResponse>` - Settlement result
@@ -115,6 +142,7 @@ impl FacilitatorClient {
         &self,
         payment_payload: &PaymentPayload,
         payment_requirements: &PaymentRequirements,
+        idempotency_key: &str,
     ) -> McpResult<SettlementResponse> {
         let trace_id = Uuid::new_v4();
         
@@ -132,7 +160,7 @@ impl FacilitatorClient {
 
         let url = format!("{}/settle", self.base_url);
         
-        self.execute_with_retry(&url, &request, &trace_id, "settle").await
+        self.execute_with_retry(&url, &request, &trace_id, "settle", Some(idempotency_key)).await
     }
 
     /// Queries supported networks and schemes
@@ -200,12 +228,18 @@ impl FacilitatorClient {
     }
 
     /// Executes a request with exponential backoff retry
+    ///
+    /// A 429 from the facilitator is retried after the Retry-After it sends,
+    /// falling back to the backoff when the header is absent. A Retry-After
+    /// longer than `MAX_RETRY_AFTER`, or a 429 on the last attempt, fails with
+    /// an error naming the rate limit so the paid call is not served.
     async fn execute_with_retry<T, R>(
         &self,
         url: &str,
         request: &R,
         trace_id: &Uuid,
         operation: &str,
+        idempotency_key: Option<&str>,
     ) -> McpResult<T>
     where
         T: serde::de::DeserializeOwned,
@@ -213,13 +247,16 @@ impl FacilitatorClient {
     {
         let mut attempt = 0;
         let mut last_error = None;
+        let mut retry_after = None;
 
         while attempt <= self.max_retries {
             if attempt > 0 {
-                // Exponential backoff with jitter
-                let base_delay = 100 * (2_u64.pow(attempt - 1));
-                let jitter = rand::random::<u64>() % 100;
-                let delay = Duration::from_millis(base_delay + jitter);
+                // The facilitator's Retry-After, else exponential backoff with jitter
+                let delay = retry_after.take().unwrap_or_else(|| {
+                    let base_delay = 100 * (2_u64.pow(attempt - 1));
+                    let jitter = rand::random::<u64>() % 100;
+                    Duration::from_millis(base_delay + jitter)
+                });
                 
                 tracing::debug!(
                     trace_id = %trace_id,
@@ -239,7 +276,7 @@ impl FacilitatorClient {
                 operation
             );
 
-            match self.execute_once(url, request, trace_id).await {
+            match self.execute_once(url, request, trace_id, idempotency_key).await {
                 Ok(result) => {
                     tracing::info!(
                         trace_id = %trace_id,
@@ -248,14 +285,40 @@ impl FacilitatorClient {
                     );
                     return Ok(result);
                 }
-                Err(e) => {
+                Err(AttemptError::RateLimited { error, retry_after: wait }) => {
+                    tracing::warn!(
+                        trace_id = %trace_id,
+                        attempt = attempt + 1,
+                        retry_after_ms = wait.map(|wait| wait.as_millis()),
+                        "Facilitator rate limited request"
+                    );
+                    if let Some(wait) = wait.filter(|wait| *wait > MAX_RETRY_AFTER) {
+                        return Err(McpError::server(format!(
+                            "Facilitator rate limited {operation}; it asked to retry after {}s",
+                            wait.as_secs()
+                        ))
+                        .with_endpoint(url));
+                    }
+                    if attempt == self.max_retries {
+                        return Err(McpError::server(format!(
+                            "Facilitator rate limited {operation} after {} attempts: {}",
+                            attempt + 1,
+                            error
+                        ))
+                        .with_endpoint(url));
+                    }
+                    retry_after = wait;
+                    last_error = Some(error);
+                    attempt += 1;
+                }
+                Err(AttemptError::Failed(error)) => {
                     tracing::warn!(
                         trace_id = %trace_id,
                         attempt = attempt + 1,
-                        error = %e,
+                        error = %error,
                         "Request failed"
                     );
-                    last_error = Some(e);
+                    last_error = Some(error);
                     attempt += 1;
                 }
             }
@@ -272,15 +335,20 @@ impl FacilitatorClient {
         url: &str,
         request: &R,
         trace_id: &Uuid,
-    ) -> McpResult<T>
+        idempotency_key: Option<&str>,
+    ) -> Result<T, AttemptError>
     where
         T: serde::de::DeserializeOwned,
         R: Serialize,
     {
-        let response = self.client
+        let mut builder = self.client
             .post(url)
             .header("X-Trace-ID", trace_id.to_string())
-            .header("Content-Type", "application/json")
+            .header("Content-Type", "application/json");
+        if let Some(key) = idempotency_key {
+            builder = builder.header("Idempotency-Key", key);
+        }
+        let response = builder
             .json(request)
             .send()
             .await
@@ -292,12 +360,18 @@ impl FacilitatorClient {
         let status = response.status();
         
         if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after);
             let body = response.text().await.unwrap_or_default();
             
-            return Err(McpError::server(format!(
-                "Facilitator error: {} - {}",
-                status, body
-            )));
+            let error = McpError::server(format!("Facilitator error: {} - {}", status, body));
+            return Err(match status {
+                StatusCode::TOO_MANY_REQUESTS => AttemptError::RateLimited { error, retry_after },
+                _ => AttemptError::Failed(error),
+            });
         }
 
         let result: T = response.json().await.map_err(|e| {
@@ -333,9 +407,20 @@ mod tests {
             request_timeout_seconds: 30,
             max_retries: 3,
             networks,
+            tool_payments: HashMap::new(),
         };
 
         let client = FacilitatorClient::new(&config);
         assert!(client.is_ok());
     }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("3"), Some(Duration::from_secs(3)));
+        assert_eq!(parse_retry_after(" 0 "), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(Duration::ZERO));
+        let later = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(120));
+        assert!(parse_retry_after(&later).unwrap() > Duration::from_secs(100));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
 }
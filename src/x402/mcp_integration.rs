@@ -6,10 +6,119 @@
 use super::config::X402Config;
 use super::facilitator::FacilitatorClient;
 use super::types::{PaymentPayload, PaymentRequired, PaymentRequirements, ResourceInfo, SettlementResponse};
-use super::validation::{validate_x402_version, validate_payment_amount, validate_timeout};
+use super::validation::{
+    validate_caip2_network, validate_x402_version, validate_payment_amount, validate_timeout,
+};
 use crate::error::{McpError, McpResult};
 use crate::transport::{JsonRpcError, JsonRpcMessage, JsonRpcResponse, JsonRpcVersion};
+use dashmap::DashMap;
 use serde_json::Value;
+use std::time::{Duration, Instant};
+
+/// A settled payment and the call it paid for
+struct SettledPayment {
+    /// Resource of the tool the payment was settled for
+    resource: String,
+    requirements: PaymentRequirements,
+    /// Arguments of the paid call; only an identical retry is replayed
+    arguments: Value,
+    /// Result delivered for the payment, settlement proof included
+    result: Value,
+    expires_at: Instant,
+}
+
+/// Payments this server has settled, keyed on idempotency key, until their payment window closes
+///
+/// Each settlement is recorded together with the result it paid for, so a
+/// client that lost the response can retry the identical call and receive
+/// the same result without being charged again.
+#[derive(Default)]
+pub struct SettledPayments {
+    entries: DashMap<String, SettledPayment>,
+}
+
+impl SettledPayments {
+    /// Creates an empty settlement record
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Looks up the result already delivered for this payment within its window
+    ///
+    /// # Returns
+    /// * `McpResult<Option<Value>>` - The recorded result when the payment was
+    ///   settled for this tool, price and arguments, `None` when it was never
+    ///   settled, or an error when it paid for a different call
+    pub fn replay(
+        &self,
+        payment_payload: &PaymentPayload,
+        tool_name: &str,
+        payment_requirements: &PaymentRequirements,
+        arguments: &Value,
+    ) -> McpResult<Option<Value>> {
+        let Some(entry) = self.entries.get(&payment_idempotency_key(payment_payload)) else {
+            return Ok(None);
+        };
+        if Instant::now() >= entry.expires_at {
+            return Ok(None);
+        }
+        entry.check_call(tool_name, payment_requirements, arguments)?;
+        Ok(Some(entry.result.clone()))
+    }
+
+    /// Records a settled payment with the result it paid for
+    ///
+    /// Settlement and result are stored in one entry, so a retry can never
+    /// observe a settlement without its result. When identical calls race, the
+    /// first recorded result wins and is returned to every caller.
+    ///
+    /// # Returns
+    /// * `McpResult<Value>` - The result to deliver for this payment, or an
+    ///   error when a racing call already recorded it for a different call
+    pub fn record(
+        &self,
+        payment_payload: &PaymentPayload,
+        tool_name: &str,
+        payment_requirements: &PaymentRequirements,
+        arguments: &Value,
+        result: Value,
+    ) -> McpResult<Value> {
+        let now = Instant::now();
+        self.entries.retain(|_, settled| now < settled.expires_at);
+        match self.entries.entry(payment_idempotency_key(payment_payload)) {
+            dashmap::mapref::entry::Entry::Occupied(entry) => {
+                entry.get().check_call(tool_name, payment_requirements, arguments)?;
+                Ok(entry.get().result.clone())
+            }
+            dashmap::mapref::entry::Entry::Vacant(entry) => {
+                entry.insert(SettledPayment {
+                    resource: tool_resource_url(tool_name),
+                    requirements: payment_requirements.clone(),
+                    arguments: arguments.clone(),
+                    result: result.clone(),
+                    expires_at: now + Duration::from_secs(payment_requirements.max_timeout_seconds),
+                });
+                Ok(result)
+            }
+        }
+    }
+}
+
+impl SettledPayment {
+    /// Fails unless this payment was settled for the same tool, price and arguments
+    fn check_call(&self, tool_name: &str, payment_requirements: &PaymentRequirements, arguments: &Value) -> McpResult<()> {
+        if self.resource != tool_resource_url(tool_name) || self.requirements != *payment_requirements {
+            return Err(McpError::validation(format!(
+                "Payment was settled for {}, not for this call",
+                self.resource
+            )));
+        }
+        if self.arguments != *arguments {
+            return Err(McpError::validation("Payment has already been used for a different call"));
+        }
+        Ok(())
+    }
+}
 
 /// x402-specific JSON-RPC error codes
 pub const PAYMENT_REQUIRED_CODE: i32 = -40200;
@@ -113,9 +222,25 @@ pub fn extract_payment_payload(meta: Option<&Value>) -> McpResult<Option<Payment
     Ok(Some(payment_payload))
 }
 
-/// Processes a payment for a tool call
+/// Derives the idempotency key of a payment from its signed payload
+///
+/// The scheme payload (for SVM exact, the signed transfer transaction) is the
+/// payment's nonce: a retried call carries the same bytes, a new payment does not.
+pub fn payment_idempotency_key(payment_payload: &PaymentPayload) -> String {
+    let payload = serde_json::to_vec(&payment_payload.payload).unwrap_or_default();
+    solana_sdk::hash::hashv(&[payment_payload.accepted.network.as_bytes(), &payload]).to_string()
+}
+
+/// Resource URL identifying a gated tool in payment requirements
+fn tool_resource_url(tool_name: &str) -> String {
+    format!("mcp://tool/{}", tool_name)
+}
+
+/// Verifies a payment for a tool call without settling it
 ///
-/// Verifies and settles the payment using the facilitator.
+/// Runs before the tool, so nothing is charged yet. Callers check
+/// [`SettledPayments::replay`] first: a payment that was already settled is
+/// answered from the record rather than sent to the facilitator again.
 ///
 /// # Arguments
 /// * `payment_payload` - The payment payload from the client
@@ -123,15 +248,13 @@ pub fn extract_payment_payload(meta: Option<&Value>) -> McpResult<Option<Payment
 /// * `config` - x402 configuration
 ///
 /// # Returns
-/// * `McpResult<SettlementResponse>` - Settlement result from facilitator
-pub async fn process_payment(
+/// * `McpResult<()>` - Ok if the facilitator accepts the payment
+pub async fn verify_payment(
     payment_payload: &PaymentPayload,
     payment_requirements: &PaymentRequirements,
     config: &X402Config,
-) -> McpResult<SettlementResponse> {
+) -> McpResult<()> {
     let facilitator = FacilitatorClient::new(config)?;
-
-    // First verify the payment
     tracing::info!("Verifying payment authorization");
     let verify_response = facilitator.verify(payment_payload, payment_requirements).await?;
 
@@ -150,11 +273,34 @@ pub async fn process_payment(
 
     tracing::info!(
         payer = ?verify_response.payer,
-        "Payment verified successfully, proceeding to settlement"
+        "Payment verified successfully"
     );
+    Ok(())
+}
 
-    // Settle the payment
-    let settlement_response = facilitator.settle(payment_payload, payment_requirements).await?;
+/// Settles a verified payment once the tool has produced its result
+///
+/// The facilitator receives the payment's idempotency key as
+/// `Idempotency-Key`, so settlements racing each other are charged once.
+/// Callers record the settled result with [`SettledPayments::record`].
+///
+/// # Arguments
+/// * `payment_payload` - The payment payload from the client
+/// * `payment_requirements` - The original payment requirements
+/// * `config` - x402 configuration
+///
+/// # Returns
+/// * `McpResult<SettlementResponse>` - Settlement result from facilitator
+pub async fn settle_payment(
+    payment_payload: &PaymentPayload,
+    payment_requirements: &PaymentRequirements,
+    config: &X402Config,
+) -> McpResult<SettlementResponse> {
+    let idempotency_key = payment_idempotency_key(payment_payload);
+    let facilitator = FacilitatorClient::new(config)?;
+    let settlement_response = facilitator
+        .settle(payment_payload, payment_requirements, &idempotency_key)
+        .await?;
 
     if !settlement_response.success {
         let reason = settlement_response.error_reason
@@ -180,6 +326,75 @@ pub async fn process_payment(
     Ok(settlement_response)
 }
 
+/// Verifies and settles a payment in one step
+///
+/// Kept for callers of the earlier API. It settles before any tool has run,
+/// so a failed call is still charged; `tools/call` instead verifies first and
+/// settles only a successful result.
+///
+/// # Arguments
+/// * `payment_payload` - The payment payload from the client
+/// * `payment_requirements` - The original payment requirements
+/// * `config` - x402 configuration
+///
+/// # Returns
+/// * `McpResult<SettlementResponse>` - Settlement result from facilitator
+#[deprecated(note = "call verify_payment before the tool runs and settle_payment after it succeeds")]
+pub async fn process_payment(
+    payment_payload: &PaymentPayload,
+    payment_requirements: &PaymentRequirements,
+    config: &X402Config,
+) -> McpResult<SettlementResponse> {
+    verify_payment(payment_payload, payment_requirements, config).await?;
+    settle_payment(payment_payload, payment_requirements, config).await
+}
+
+/// Attaches settlement proof to a successful tool response
+///
+/// The settlement is placed under `result._meta.settlement` so the client
+/// receives the transaction reference alongside the paid result. Results that
+/// are not JSON objects are wrapped as `{"value": ...}` to make room for it.
+/// Error responses are returned unchanged.
+///
+/// # Arguments
+/// * `response` - The tool call response
+/// * `settlement` - Settlement returned by the facilitator
+///
+/// # Returns
+/// * `JsonRpcMessage` - The response carrying the settlement in `_meta`
+pub fn attach_settlement_meta(
+    response: JsonRpcMessage,
+    settlement: &SettlementResponse,
+) -> JsonRpcMessage {
+    let JsonRpcMessage::Response(mut response) = response else {
+        return response;
+    };
+    let Some(result) = response.result.take() else {
+        return JsonRpcMessage::Response(response);
+    };
+
+    let mut result = match result {
+        Value::Object(map) => map,
+        other => {
+            let mut map = serde_json::Map::new();
+            map.insert("value".to_string(), other);
+            map
+        }
+    };
+    let settlement = serde_json::to_value(settlement).unwrap_or(Value::Null);
+    match result.get_mut("_meta") {
+        Some(Value::Object(meta)) => {
+            meta.insert("settlement".to_string(), settlement);
+        }
+        _ => {
+            result.insert("_meta".to_string(), serde_json::json!({ "settlement": settlement }));
+        }
+    }
+
+    response.result = Some(Value::Object(result));
+    JsonRpcMessage::Response(response)
+}
+
 /// Builds payment requirements for a tool call
 ///
 /// # Arguments
@@ -197,6 +412,14 @@ pub fn build_payment_requirements(
     let network_config = config.get_network(network_id)
         .ok_or_else(|| McpError::validation(format!("Network '{}' not configured", network_id)))?;
 
+    // A malformed identifier would produce requirements no client can honor
+    validate_caip2_network(&network_config.network).map_err(|e| {
+        McpError::validation(format!(
+            "Network '{}' is misconfigured: {}",
+            network_id, e
+        ))
+    })?;
+
     // Build payment requirements for each supported asset
     let mut accepts = Vec::new();
     for asset in &network_config.assets {
@@ -218,17 +441,36 @@ pub fn build_payment_requirements(
         )));
     }
 
-    Ok(PaymentRequired {
+    Ok(payment_required_for(tool_name, accepts))
+}
+
+/// Builds the Payment Required body for a tool gated by `tool_payments`
+///
+/// # Arguments
+/// * `tool_name` - Name of the tool being called
+/// * `requirements` - The tool's configured payment requirements
+///
+/// # Returns
+/// * `PaymentRequired` - Payment requirements offering the single configured method
+pub fn build_tool_payment_required(
+    tool_name: &str,
+    requirements: &PaymentRequirements,
+) -> PaymentRequired {
+    payment_required_for(tool_name, vec![requirements.clone()])
+}
+
+fn payment_required_for(tool_name: &str, accepts: Vec<PaymentRequirements>) -> PaymentRequired {
+    PaymentRequired {
         x402_version: 2,
         error: Some(format!("Payment required to call tool '{}'", tool_name)),
         resource: ResourceInfo {
-            url: format!("mcp://tool/{}", tool_name),
+            url: tool_resource_url(tool_name),
             description: Some(format!("MCP tool call: {}", tool_name)),
             mime_type: Some("application/json".to_string()),
         },
         accepts,
         extensions: None,
-    })
+    }
 }
 
 #[cfg(test)]
@@ -275,6 +517,7 @@ mod tests {
             request_timeout_seconds: 30,
             max_retries: 3,
             networks,
+            tool_payments: HashMap::new(),
         };
 
         let result = build_payment_requirements("getBalance", &config, "solana-mainnet").unwrap();
@@ -284,6 +527,53 @@ mod tests {
         assert_eq!(result.accepts[0].network, "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp");
     }
 
+    fn config_with_network(network_id: &str, network: &str) -> X402Config {
+        let mut config = X402Config {
+            enabled: true,
+            facilitator_base_url: "https://facilitator.example.com".to_string(),
+            ..X402Config::default()
+        };
+        config.networks.insert(
+            network_id.to_string(),
+            super::super::config::NetworkConfig {
+                network: network.to_string(),
+                assets: vec![super::super::config::AssetConfig {
+                    address: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+                    name: "USDC".to_string(),
+                    decimals: 6,
+                }],
+                pay_to: "FeeRecipient123".to_string(),
+                min_compute_unit_price: None,
+                max_compute_unit_price: None,
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn test_build_payment_requirements_accepts_solana_caip2_ids() {
+        for (network_id, network) in [
+            ("solana-mainnet", "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp"),
+            ("solana-devnet", "solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1"),
+        ] {
+            let config = config_with_network(network_id, network);
+            let result = build_payment_requirements("getBalance", &config, network_id).unwrap();
+            assert_eq!(result.accepts[0].network, network);
+        }
+    }
+
+    #[test]
+    fn test_build_payment_requirements_rejects_invalid_caip2_network() {
+        for network in ["solana-devnet", "solana:", "Solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp"] {
+            let config = config_with_network("solana-devnet", network);
+            let err = build_payment_requirements("getBalance", &config, "solana-devnet")
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("Network 'solana-devnet' is misconfigured"), "{err}");
+            assert!(err.contains("CAIP-2"), "{err}");
+        }
+    }
+
     #[test]
     fn test_create_payment_required_response() {
         let payment_required = PaymentRequired {
@@ -309,6 +599,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_attach_settlement_meta() {
+        let settlement = SettlementResponse {
+            success: true,
+            error_reason: None,
+            payer: Some("payer_address".to_string()),
+            transaction: "tx_hash_123".to_string(),
+            network: "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp".to_string(),
+        };
+        let response = |result: Value| {
+            JsonRpcMessage::Response(JsonRpcResponse {
+                jsonrpc: JsonRpcVersion::V2,
+                id: Value::from(1),
+                result: Some(result),
+                error: None,
+            })
+        };
+
+        match attach_settlement_meta(response(serde_json::json!({ "slot": 7 })), &settlement) {
+            JsonRpcMessage::Response(r) => {
+                let result = r.result.unwrap();
+                assert_eq!(result["slot"], 7);
+                assert_eq!(result["_meta"]["settlement"]["transaction"], "tx_hash_123");
+            }
+            _ => panic!("Expected Response message"),
+        }
+
+        match attach_settlement_meta(response(Value::from(42)), &settlement) {
+            JsonRpcMessage::Response(r) => {
+                let result = r.result.unwrap();
+                assert_eq!(result["value"], 42);
+                assert_eq!(result["_meta"]["settlement"]["network"], settlement.network);
+            }
+            _ => panic!("Expected Response message"),
+        }
+    }
+
     #[test]
     fn test_create_invalid_payment_response() {
         let response = create_invalid_payment_response(
@@ -23,6 +23,8 @@ pub use facilitator::FacilitatorClient;
 pub use validation::{validate_caip2_network, validate_x402_version};
 pub use mcp_integration::{
     create_payment_required_response, create_invalid_payment_response,
-    extract_payment_payload, process_payment, build_payment_requirements,
-    PAYMENT_REQUIRED_CODE, INVALID_PAYMENT_CODE,
+    extract_payment_payload, payment_idempotency_key, build_payment_requirements,
+    verify_payment, settle_payment, process_payment,
+    build_tool_payment_required, attach_settlement_meta,
+    SettledPayments, PAYMENT_REQUIRED_CODE, INVALID_PAYMENT_CODE,
 };
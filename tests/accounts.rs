@@ -0,0 +1,136 @@
+/// Integration tests for account RPC helpers
+use base64::Engine;
+use solana_mcp_server::rpc::accounts::{paginate_program_accounts, render_account_encodings};
+use solana_sdk::{account::Account, program_pack::Pack, pubkey::Pubkey};
+use spl_token::state::Mint;
+
+#[test]
+fn test_paginate_program_accounts_is_deterministic() {
+    let mut keys: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    let accounts: Vec<(Pubkey, u8)> = keys.iter().rev().map(|k| (*k, 0)).collect();
+    keys.sort();
+
+    let (first, cursor) = paginate_program_accounts(accounts.clone(), 2, None);
+    assert_eq!(first.iter().map(|(k, _)| *k).collect::<Vec<_>>(), keys[0..2]);
+    assert_eq!(cursor, Some(keys[1]));
+
+    let (second, cursor) = paginate_program_accounts(accounts.clone(), 2, cursor.as_ref());
+    assert_eq!(second.iter().map(|(k, _)| *k).collect::<Vec<_>>(), keys[2..4]);
+    assert_eq!(cursor, Some(keys[3]));
+
+    let (last, cursor) = paginate_program_accounts(accounts, 2, cursor.as_ref());
+    assert_eq!(last.iter().map(|(k, _)| *k).collect::<Vec<_>>(), keys[4..5]);
+    assert_eq!(cursor, None);
+}
+
+#[test]
+fn test_paginate_program_accounts_exact_page() {
+    let accounts: Vec<(Pubkey, u8)> = (0..3).map(|_| (Pubkey::new_unique(), 0)).collect();
+
+    let (page, cursor) = paginate_program_accounts(accounts, 3, None);
+    assert_eq!(page.len(), 3);
+    assert_eq!(cursor, None);
+}
+
+#[test]
+fn test_render_account_encodings_base64_and_json_parsed() {
+    let mint = Mint {
+        mint_authority: solana_sdk::program_option::COption::None,
+        supply: 1_000_000,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: solana_sdk::program_option::COption::None,
+    };
+    let mut data = vec![0u8; Mint::LEN];
+    Mint::pack(mint, &mut data).unwrap();
+    let account = Account {
+        lamports: 1_461_600,
+        data: data.clone(),
+        owner: spl_token::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    let encodings = vec!["base64".to_string(), "jsonParsed".to_string(), "hex".to_string()];
+    let rendered = render_account_encodings(&Pubkey::new_unique(), &account, &encodings);
+
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(rendered["base64"].as_str().unwrap())
+        .unwrap();
+    assert_eq!(decoded, data);
+    assert_eq!(rendered["jsonParsed"]["parsed"]["type"], "mint");
+    assert_eq!(rendered["jsonParsed"]["parsed"]["info"]["decimals"], 6);
+    assert!(rendered["hex"]["error"].is_string());
+}
+
+#[test]
+fn test_render_account_encodings_marks_unparsable_json() {
+    let account = Account {
+        lamports: 1,
+        data: vec![1, 2, 3],
+        owner: Pubkey::new_unique(),
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    let rendered = render_account_encodings(&Pubkey::new_unique(), &account, &["jsonParsed".to_string()]);
+    assert!(rendered["jsonParsed"]["error"].is_string());
+}
+
+#[test]
+fn test_describe_program_account_upgradeable_program() {
+    use solana_mcp_server::rpc::accounts::describe_program_account;
+    use solana_loader_v3_interface::state::UpgradeableLoaderState;
+    use solana_sdk_ids::bpf_loader_upgradeable;
+
+    let programdata_address = Pubkey::new_unique();
+    let account = Account {
+        lamports: 1_141_440,
+        data: bincode::serialize(&UpgradeableLoaderState::Program { programdata_address }).unwrap(),
+        owner: bpf_loader_upgradeable::id(),
+        executable: true,
+        rent_epoch: 0,
+    };
+
+    let result = describe_program_account(Some(&account));
+    assert_eq!(result["exists"], true);
+    assert_eq!(result["executable"], true);
+    assert_eq!(result["owner"], bpf_loader_upgradeable::id().to_string());
+    assert_eq!(result["programDataAddress"], programdata_address.to_string());
+}
+
+#[test]
+fn test_describe_program_account_plain_and_missing() {
+    use solana_mcp_server::rpc::accounts::describe_program_account;
+
+    let account = Account {
+        lamports: 1_000_000,
+        data: vec![],
+        owner: solana_sdk::system_program::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    let result = describe_program_account(Some(&account));
+    assert_eq!(result["exists"], true);
+    assert_eq!(result["executable"], false);
+    assert!(result.get("programDataAddress").is_none());
+
+    let missing = describe_program_account(None);
+    assert_eq!(missing["exists"], false);
+}
+
+#[test]
+fn test_program_accounts_with_context_includes_slot() {
+    use solana_client::rpc_response::{Response, RpcKeyedAccount, RpcResponseContext};
+    use solana_mcp_server::rpc::accounts::format_program_accounts_with_context;
+
+    let response: Response<Vec<RpcKeyedAccount>> = Response {
+        context: RpcResponseContext { slot: 321_000, api_version: None },
+        value: vec![],
+    };
+
+    let result = format_program_accounts_with_context(response);
+    assert_eq!(result["context"]["slot"], 321_000);
+    assert!(result["accounts"].as_array().unwrap().is_empty());
+}
@@ -0,0 +1,55 @@
+/// Integration tests for program error decoding
+use serde_json::json;
+use solana_mcp_server::program_errors::{decode_program_error, parse_error_code};
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+fn test_decode_spl_token_error() {
+    // TokenError::InsufficientFunds
+    let decoded = decode_program_error(&spl_token::id(), 1);
+    assert_eq!(decoded["program"], "spl-token");
+    assert_eq!(decoded["name"], "InsufficientFunds");
+    assert_eq!(decoded["message"], "Insufficient funds");
+    assert_eq!(decoded["hex"], "0x1");
+}
+
+#[test]
+fn test_decode_unknown_code_of_known_program_skips_anchor() {
+    let decoded = decode_program_error(&spl_token::id(), 6000);
+    assert_eq!(decoded["program"], "spl-token");
+    assert_eq!(decoded["code"], 6000);
+    assert!(decoded.get("name").is_none());
+    assert!(decoded.get("customErrorIndex").is_none());
+    assert_eq!(decoded["note"], "Unknown error code for spl-token; returned raw");
+
+    let decoded = decode_program_error(&solana_sdk::system_program::id(), 2006);
+    assert_eq!(decoded["program"], "system");
+    assert!(decoded.get("name").is_none());
+}
+
+#[test]
+fn test_decode_anchor_custom_error() {
+    let decoded = decode_program_error(&Pubkey::new_unique(), 6003);
+    assert_eq!(decoded["program"], "anchor");
+    assert_eq!(decoded["customErrorIndex"], 3);
+    assert!(decoded["note"].as_str().unwrap().contains("#3"));
+
+    let framework = decode_program_error(&Pubkey::new_unique(), 2006);
+    assert_eq!(framework["name"], "ConstraintSeeds");
+}
+
+#[test]
+fn test_decode_unknown_error_returns_raw_code() {
+    let decoded = decode_program_error(&Pubkey::new_unique(), 42);
+    assert_eq!(decoded["code"], 42);
+    assert!(decoded.get("name").is_none());
+    assert!(decoded["note"].is_string());
+}
+
+#[test]
+fn test_parse_error_code_accepts_hex_and_decimal() {
+    assert_eq!(parse_error_code(&json!("0x1771")), Some(6001));
+    assert_eq!(parse_error_code(&json!(17)), Some(17));
+    assert_eq!(parse_error_code(&json!("17")), Some(17));
+    assert_eq!(parse_error_code(&json!("zz")), None);
+}
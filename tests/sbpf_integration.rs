@@ -129,6 +129,41 @@ fn test_base64_encoding_decoding() {
     assert_eq!(test_data, decoded.as_slice());
 }
 
+#[test]
+fn test_instruction_spec_deserialization() {
+    use solana_mcp_server::sbpf::InstructionSpec;
+
+    let spec: InstructionSpec = serde_json::from_value(serde_json::json!({
+        "data": BASE64.encode([1u8, 2, 3]),
+        "accounts": ["11111111111111111111111111111111"]
+    }))
+    .unwrap();
+    assert_eq!(spec.accounts.unwrap().len(), 1);
+
+    let bare: InstructionSpec = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert!(bare.data.is_none());
+    assert!(bare.accounts.is_none());
+}
+
+#[tokio::test]
+async fn test_execute_test_with_limits_rejects_invalid_binary() {
+    use solana_mcp_server::sbpf::{SbpfError, TEST_WALL_CLOCK_LIMIT};
+
+    let params = TestParams {
+        binary: vec![0u8; 1024],
+        accounts: vec![],
+        instruction_data: None,
+        signers: vec![],
+        instructions: vec![],
+        compute_unit_limit: Some(u32::MAX),
+    };
+
+    let result = TestExecutor::new()
+        .execute_test_with_limits(params, TEST_WALL_CLOCK_LIMIT)
+        .await;
+    assert!(matches!(result, Err(SbpfError::NotElfFile)));
+}
+
 #[cfg(test)]
 mod integration_tests {
     use super::*;
@@ -0,0 +1,43 @@
+/// Integration tests for system RPC helpers
+use solana_mcp_server::rpc::system::{compute_fee_stats, interleave_signatures};
+use solana_sdk::signature::Signature;
+
+#[test]
+fn test_compute_fee_stats_from_fixed_fees() {
+    let fees = [0, 1_000, 5_000, 10_000, 2_000, 0, 50_000, 7_500, 3_000, 1_500];
+
+    let stats = compute_fee_stats(&fees);
+    assert_eq!(stats["count"], 10);
+    assert_eq!(stats["min"], 0);
+    assert_eq!(stats["max"], 50_000);
+    assert_eq!(stats["mean"], 8_000);
+    assert_eq!(stats["median"], 2_000);
+    assert_eq!(stats["percentiles"]["p25"], 1_000);
+    assert_eq!(stats["percentiles"]["p75"], 7_500);
+    assert_eq!(stats["percentiles"]["p90"], 10_000);
+    assert_eq!(stats["percentiles"]["p95"], 50_000);
+}
+
+#[test]
+fn test_compute_fee_stats_empty_sample() {
+    let stats = compute_fee_stats(&[]);
+    assert_eq!(stats["count"], 0);
+    assert!(stats.get("median").is_none());
+}
+
+#[test]
+fn test_interleave_signatures_draws_from_every_address() {
+    let signature = |byte: u8| Signature::from([byte; 64]);
+    let per_address = vec![
+        vec![signature(1), signature(2), signature(3)],
+        vec![signature(4), signature(1)],
+        vec![signature(5)],
+    ];
+
+    let sample = interleave_signatures(per_address.clone(), 4);
+    assert_eq!(sample, vec![signature(1), signature(4), signature(5), signature(2)]);
+
+    // Duplicates across addresses are counted once
+    let sample = interleave_signatures(per_address, 10);
+    assert_eq!(sample.len(), 5);
+}
@@ -0,0 +1,127 @@
+/// Integration tests for token RPC helpers
+use solana_mcp_server::rpc::tokens::{decode_token_account, token_ui_balance};
+use solana_sdk::{program_option::COption, program_pack::Pack, pubkey::Pubkey};
+use spl_token::state::{Account as TokenAccount, AccountState};
+
+fn packed_token_account(mint: Pubkey, amount: u64) -> Vec<u8> {
+    let account = TokenAccount {
+        mint,
+        owner: Pubkey::new_unique(),
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    };
+    let mut data = vec![0u8; TokenAccount::LEN];
+    TokenAccount::pack(account, &mut data).unwrap();
+    data
+}
+
+#[test]
+fn test_decode_token_account() {
+    let mint = Pubkey::new_unique();
+    let data = packed_token_account(mint, 42);
+
+    let (decoded_mint, amount) =
+        decode_token_account(&Pubkey::new_unique(), &spl_token::id(), &data).unwrap();
+    assert_eq!(decoded_mint, mint);
+    assert_eq!(amount, 42);
+}
+
+#[test]
+fn test_decode_token_account_rejects_non_token_account() {
+    let data = packed_token_account(Pubkey::new_unique(), 42);
+    let system_owner = solana_sdk::system_program::id();
+
+    let err = decode_token_account(&Pubkey::new_unique(), &system_owner, &data).unwrap_err();
+    assert!(err.to_string().contains("is not a token account"));
+    assert!(decode_token_account(&Pubkey::new_unique(), &spl_token::id(), &[0u8; 10]).is_err());
+}
+
+#[test]
+fn test_token_ui_balance() {
+    let balance = token_ui_balance(1_500_000, 6);
+    assert_eq!(balance["amount"], "1500000");
+    assert_eq!(balance["decimals"], 6);
+    assert_eq!(balance["uiAmount"], 1.5);
+    assert_eq!(balance["uiAmountString"], "1.5");
+}
+
+#[test]
+fn test_get_associated_token_address_legacy_and_token_2022() {
+    use solana_mcp_server::rpc::tokens::{get_associated_token_address, TOKEN_2022_PROGRAM_ID};
+
+    let owner = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+
+    let legacy = get_associated_token_address(&owner, &mint, None).unwrap();
+    let expected = spl_associated_token_account::get_associated_token_address(&owner, &mint);
+    assert_eq!(legacy["address"], expected.to_string());
+    assert_eq!(legacy["tokenProgramId"], spl_token::id().to_string());
+
+    let token_2022 = get_associated_token_address(&owner, &mint, Some(&TOKEN_2022_PROGRAM_ID)).unwrap();
+    assert_ne!(token_2022["address"], legacy["address"]);
+
+    assert!(get_associated_token_address(&owner, &mint, Some(&Pubkey::new_unique())).is_err());
+}
+
+#[test]
+fn test_decode_token_2022_extensions() {
+    use solana_mcp_server::rpc::tokens::{decode_token_2022_extensions, TOKEN_2022_PROGRAM_ID};
+    use solana_sdk::account::Account;
+    use spl_token_2022::extension::{
+        interest_bearing_mint::InterestBearingConfig, transfer_fee::TransferFeeConfig,
+        BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+    };
+    use spl_token_2022::state::Mint;
+
+    let space = ExtensionType::try_calculate_account_len::<Mint>(&[
+        ExtensionType::TransferFeeConfig,
+        ExtensionType::InterestBearingConfig,
+    ])
+    .unwrap();
+    let mut data = vec![0u8; space];
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+    state.init_extension::<TransferFeeConfig>(true).unwrap();
+    state.init_extension::<InterestBearingConfig>(true).unwrap();
+    state.base = Mint {
+        decimals: 6,
+        is_initialized: true,
+        ..Default::default()
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+
+    let mint = Pubkey::new_unique();
+    let account = Account {
+        lamports: 1_000_000,
+        data,
+        owner: TOKEN_2022_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    let extensions = decode_token_2022_extensions(&mint, &account).unwrap();
+    let names: Vec<&str> = extensions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["extension"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"transferFeeConfig"));
+    assert!(names.contains(&"interestBearingConfig"));
+}
+
+#[test]
+fn test_resolve_token_program_id_defaults_to_classic() {
+    use solana_mcp_server::rpc::tokens::{resolve_token_program_id, TOKEN_2022_PROGRAM_ID};
+
+    assert_eq!(resolve_token_program_id(None).unwrap(), spl_token::id());
+    assert_eq!(
+        resolve_token_program_id(Some(&TOKEN_2022_PROGRAM_ID)).unwrap(),
+        TOKEN_2022_PROGRAM_ID
+    );
+    assert!(resolve_token_program_id(Some(&Pubkey::new_unique())).is_err());
+}
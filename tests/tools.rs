@@ -0,0 +1,3044 @@
+/// Integration tests for MCP tool dispatch
+use serde_json::{json, Value};
+use solana_mcp_server::tools::handle_tools_call;
+use solana_mcp_server::{Config, ServerState};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Build server state from the default configuration with optional overrides
+fn test_state(configure: impl FnOnce(&mut Config)) -> Arc<RwLock<ServerState>> {
+    let mut config = Config::load().expect("Failed to load config");
+    configure(&mut config);
+    Arc::new(RwLock::new(ServerState::new(config)))
+}
+
+/// Call a tool and return the serialized JSON-RPC response
+async fn call_tool(state: Arc<RwLock<ServerState>>, name: &str, arguments: Value) -> Value {
+    let response = handle_tools_call(
+        Some(json!({ "name": name, "arguments": arguments })),
+        Some(json!(1)),
+        state,
+    )
+    .await
+    .expect("tools/call should produce a response");
+    serde_json::to_value(response).expect("Failed to serialize response")
+}
+
+/// Serve `handler` as a JSON-RPC endpoint on a local port and return its URL
+async fn spawn_mock_rpc<H, T>(handler: H) -> String
+where
+    H: axum::handler::Handler<T, ()>,
+    T: 'static,
+{
+    let app = axum::Router::new().route("/", axum::routing::post(handler));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn test_tools_call_returns_busy_when_saturated() {
+    let state = test_state(|config| {
+        config.max_concurrent_rpc = 1;
+        config.timeouts.rpc_permit_wait_ms = 10;
+    });
+
+    // Hold the only permit so the tool call cannot proceed
+    let semaphore = state.read().await.rpc_semaphore.clone();
+    let _held = semaphore.acquire_owned().await.unwrap();
+
+    let response = call_tool(state, "getHealth", json!({})).await;
+    assert_eq!(response["error"]["code"], -32005);
+}
+
+#[tokio::test]
+async fn test_missing_or_invalid_arguments_are_invalid_params() {
+    let state = test_state(|_| {});
+    for (tool, arguments, parameter) in [
+        ("getSignatureStatuses", json!({}), "signatures"),
+        ("getSignatureStatuses", json!({ "signatures": [1] }), "signatures"),
+        ("getBalance", json!({}), "pubkey"),
+        ("getMultipleAccounts", json!({ "pubkeys": [true] }), "pubkeys"),
+        ("testSbpfProgram", json!({ "programBinary": "not base64!" }), "programBinary"),
+    ] {
+        let response = call_tool(state.clone(), tool, arguments).await;
+        assert_eq!(response["error"]["code"], -32602, "{tool}: {response}");
+        assert_eq!(response["error"]["data"]["parameter"], parameter, "{tool}");
+    }
+}
+
+#[tokio::test]
+async fn test_tools_call_blocks_denylisted_method() {
+    use solana_mcp_server::tools::handle_tools_list;
+
+    let state = test_state(|config| {
+        config.enabled_tools = vec!["requestAirdrop".to_string(), "getSlot".to_string(), "getHealth".to_string()];
+        config.disabled_tools = vec!["requestAirdrop".to_string()];
+    });
+
+    let response = call_tool(state.clone(), "requestAirdrop", json!({})).await;
+    assert_eq!(response["error"]["code"], -32601);
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("not enabled"));
+
+    // The deny list wins over the allow list, and tools/list applies the same policy
+    let listed = handle_tools_list(Some(json!(1)), &*state.read().await).await.unwrap();
+    let listed = serde_json::to_value(listed).unwrap();
+    let mut names: Vec<&str> = listed["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["getHealth", "getSlot"]);
+}
+
+#[tokio::test]
+async fn test_svm_networks_timeout_with_cache_available() {
+    use solana_mcp_server::tools::{fetch_svm_networks_with_fallback, SvmNetworkListCache};
+    use std::time::Duration;
+
+    let cache = SvmNetworkListCache::new();
+    cache.store(json!({ "solana": { "name": "Solana" } }));
+
+    let hanging = std::future::pending::<anyhow::Result<Value>>();
+    let result = fetch_svm_networks_with_fallback(hanging, Duration::from_millis(10), &cache, true)
+        .await
+        .expect("cached list should be served");
+    assert_eq!(result["cached"], true);
+    assert_eq!(result["networks"]["solana"]["name"], "Solana");
+    assert_eq!(result["error"]["reason"], "timeout");
+
+    let hanging = std::future::pending::<anyhow::Result<Value>>();
+    let error = fetch_svm_networks_with_fallback(hanging, Duration::from_millis(10), &cache, false)
+        .await
+        .unwrap_err();
+    assert!(error.cached_available());
+}
+
+#[tokio::test]
+async fn test_svm_networks_timeout_without_cache() {
+    use solana_mcp_server::tools::{
+        fetch_svm_networks_with_fallback, SvmNetworkListCache, SvmNetworksFetchError,
+    };
+    use std::time::Duration;
+
+    let cache = SvmNetworkListCache::new();
+    let hanging = std::future::pending::<anyhow::Result<Value>>();
+    let error = fetch_svm_networks_with_fallback(hanging, Duration::from_millis(10), &cache, true)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        SvmNetworksFetchError::Timeout { timeout_ms: 10, cached_available: false }
+    );
+    assert_eq!(error.to_json()["cachedAvailable"], false);
+}
+
+#[tokio::test]
+async fn test_svm_networks_success_populates_cache() {
+    use solana_mcp_server::tools::{fetch_svm_networks_with_fallback, SvmNetworkListCache};
+    use std::time::Duration;
+
+    let cache = SvmNetworkListCache::new();
+    let fetch = async { Ok(json!(["solana"])) };
+    let result = fetch_svm_networks_with_fallback(fetch, Duration::from_secs(1), &cache, true)
+        .await
+        .unwrap();
+
+    assert_eq!(result, json!(["solana"]));
+    assert_eq!(cache.get(), Some(json!(["solana"])));
+}
+
+#[tokio::test]
+async fn test_svm_networks_served_from_cache_within_ttl() {
+    use solana_mcp_server::tools::{fetch_svm_networks_cached, SvmNetworkListCache};
+    use std::time::Duration;
+
+    let cache = SvmNetworkListCache::new();
+    cache.store(json!(["solana"]));
+
+    // A fresh entry is returned without polling the fetch
+    let fetch = async { panic!("fresh cache entry should not be refetched") };
+    let result =
+        fetch_svm_networks_cached(fetch, Duration::from_secs(300), Duration::from_secs(1), &cache, true)
+            .await
+            .unwrap();
+    assert_eq!(result, json!(["solana"]));
+
+    // A zero TTL always refetches
+    let fetch = async { Ok(json!(["eclipse"])) };
+    let result = fetch_svm_networks_cached(fetch, Duration::ZERO, Duration::from_secs(1), &cache, true)
+        .await
+        .unwrap();
+    assert_eq!(result, json!(["eclipse"]));
+}
+
+#[tokio::test]
+async fn test_svm_networks_backoff_serves_stale_without_refetching() {
+    use solana_mcp_server::tools::{fetch_svm_networks_cached, SvmNetworkListCache};
+    use std::time::Duration;
+
+    let cache = SvmNetworkListCache::new();
+    cache.store(json!(["solana"]));
+
+    let failing = async { Err(anyhow::anyhow!("HTTP 503")) };
+    let result = fetch_svm_networks_cached(failing, Duration::ZERO, Duration::from_secs(1), &cache, true)
+        .await
+        .unwrap();
+    assert_eq!(result["cached"], true);
+    assert_eq!(result["error"]["reason"], "fetchFailed");
+    assert!(cache.backoff_error().is_some());
+
+    // While backing off, the stale list is served and the fetch is never polled
+    let fetch = async { panic!("refresh should be suppressed during backoff") };
+    let result = fetch_svm_networks_cached(fetch, Duration::ZERO, Duration::from_secs(1), &cache, true)
+        .await
+        .unwrap();
+    assert_eq!(result["networks"], json!(["solana"]));
+    assert_eq!(result["error"]["detail"], "HTTP 503");
+}
+
+#[tokio::test]
+async fn test_svm_networks_backoff_without_cache_returns_error_without_refetching() {
+    use solana_mcp_server::tools::{fetch_svm_networks_cached, SvmNetworkListCache};
+    use std::time::Duration;
+
+    let cache = SvmNetworkListCache::new();
+    let failing = async { Err(anyhow::anyhow!("HTTP 503")) };
+    let first = fetch_svm_networks_cached(failing, Duration::ZERO, Duration::from_secs(1), &cache, true)
+        .await
+        .unwrap_err();
+
+    // With nothing cached, the backoff answers with the same error instead of fetching
+    let fetch = async { panic!("refresh should be suppressed during backoff") };
+    let error = fetch_svm_networks_cached(fetch, Duration::ZERO, Duration::from_secs(1), &cache, true)
+        .await
+        .unwrap_err();
+    assert_eq!(error, first);
+    assert_eq!(error.to_json()["reason"], "fetchFailed");
+    assert_eq!(error.to_json()["cachedAvailable"], false);
+}
+
+#[tokio::test]
+async fn test_confirm_transaction_rejects_processed_commitment() {
+    let state = test_state(|_| {});
+    let signature = solana_sdk::signature::Signature::default().to_string();
+
+    let response = call_tool(
+        state,
+        "confirmTransaction",
+        json!({ "signature": signature, "commitment": "processed" }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("'confirmed' or 'finalized'"));
+}
+
+#[tokio::test]
+async fn test_send_transaction_rejects_processed_commitment() {
+    use base64::Engine;
+
+    let state = test_state(|config| {
+        // Unroutable endpoint so a call that slips past the guard fails differently
+        config.rpc_url = "https://127.0.0.1:1".to_string();
+        config.rpc_urls = vec![];
+    });
+    let transaction = base64::engine::general_purpose::STANDARD.encode([0u8; 64]);
+
+    let response = call_tool(
+        state,
+        "sendTransaction",
+        json!({ "transaction": transaction, "encoding": "base64", "commitment": "processed" }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("'confirmed' or 'finalized'"));
+}
+
+#[tokio::test]
+async fn test_schema_restricted_commitment_enforced_for_every_tool() {
+    let state = test_state(|_| {});
+
+    // Each of these advertises only confirmed and finalized in its schema
+    for tool in ["getRecentActivity", "blockSubscribe"] {
+        let response = call_tool(state.clone(), tool, json!({ "commitment": "processed" })).await;
+        assert_eq!(response["error"]["code"], -32602, "{tool} accepted processed commitment");
+        assert!(response["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("confirmed, finalized"));
+    }
+}
+
+#[tokio::test]
+async fn test_confirm_transaction_accepts_confirmed_commitment() {
+    let state = test_state(|config| {
+        // Unroutable endpoint so the call fails fast after passing the guard
+        config.rpc_url = "https://127.0.0.1:1".to_string();
+        config.rpc_urls = vec![];
+    });
+    let signature = solana_sdk::signature::Signature::default().to_string();
+
+    let response = call_tool(
+        state,
+        "confirmTransaction",
+        json!({ "signature": signature, "commitment": "confirmed" }),
+    )
+    .await;
+    assert_ne!(response["error"]["code"], -32602);
+}
+
+#[tokio::test]
+async fn test_unknown_commitment_rejected_for_read_tools() {
+    let pubkey = solana_sdk::pubkey::Pubkey::default().to_string();
+
+    for (tool, arguments) in [
+        ("getBalance", json!({ "pubkey": pubkey, "commitment": "recent" })),
+        ("getSlot", json!({ "commitment": "max" })),
+        ("getLatestBlockhash", json!({ "commitment": "Finalized" })),
+    ] {
+        let response = call_tool(test_state(|_| {}), tool, arguments).await;
+        assert_eq!(response["error"]["code"], -32602, "{tool} accepted a bad commitment");
+        assert_eq!(response["error"]["data"]["parameter"], "commitment");
+    }
+
+    // A valid level reaches the upstream request instead of being dropped
+    let (rpc_url, calls) = mock_recording_rpc().await;
+    let state = test_state(|config| {
+        config.rpc_url = rpc_url;
+        config.rpc_urls.clear();
+    });
+    let response = call_tool(
+        state,
+        "getAccountInfoAndContext",
+        json!({ "pubkey": pubkey, "commitment": "finalized" }),
+    )
+    .await;
+    assert!(response.get("error").is_none(), "{response}");
+    let calls = calls.lock().unwrap().clone();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].0, "getAccountInfo");
+    assert_eq!(calls[0].1[1]["commitment"], "finalized");
+}
+
+#[tokio::test]
+async fn test_cancelled_notification_aborts_in_flight_tool_call() {
+    use solana_mcp_server::tools::handle_request;
+    use std::time::Duration;
+
+    let state = test_state(|config| {
+        config.max_concurrent_rpc = 1;
+        config.timeouts.rpc_permit_wait_ms = 60_000;
+    });
+    state.write().await.initialized = true;
+
+    // Hold the only permit so the tool call stays in flight until cancelled
+    let semaphore = state.read().await.rpc_semaphore.clone();
+    let _held = semaphore.acquire_owned().await.unwrap();
+
+    let call = json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": { "name": "getHealth", "arguments": {} }
+    });
+    let call_state = state.clone();
+    let call_task =
+        tokio::spawn(async move { handle_request(&call.to_string(), call_state).await });
+
+    let in_flight = state.read().await.in_flight_calls.clone();
+    let key = ServerState::request_key(solana_mcp_server::server::LOCAL_SESSION, &json!(7));
+    while !in_flight.contains_key(&key) {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    let cancel = json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/cancelled",
+        "params": { "requestId": 7, "reason": "user abandoned query" }
+    });
+    handle_request(&cancel.to_string(), state.clone()).await.unwrap();
+
+    let response = tokio::time::timeout(Duration::from_secs(5), call_task)
+        .await
+        .expect("cancelled call should finish promptly")
+        .unwrap()
+        .unwrap();
+    let response = serde_json::to_value(response).unwrap();
+    assert_eq!(response["error"]["code"], -32800);
+    assert!(in_flight.is_empty());
+}
+
+#[tokio::test]
+async fn test_cancellation_is_scoped_to_the_calling_session() {
+    use solana_mcp_server::server::with_call_session;
+    use solana_mcp_server::tools::handle_request;
+    use std::time::Duration;
+
+    let state = test_state(|config| {
+        config.max_concurrent_rpc = 1;
+        config.timeouts.rpc_permit_wait_ms = 60_000;
+    });
+    state.write().await.initialized = true;
+    let semaphore = state.read().await.rpc_semaphore.clone();
+    let _held = semaphore.acquire_owned().await.unwrap();
+
+    let call = json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": { "name": "getHealth", "arguments": {} }
+    });
+    let call_state = state.clone();
+    let call_task = tokio::spawn(with_call_session("alice".to_string(), async move {
+        handle_request(&call.to_string(), call_state).await
+    }));
+    let in_flight = state.read().await.in_flight_calls.clone();
+    let key = ServerState::request_key("alice", &json!(7));
+    while !in_flight.contains_key(&key) {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    // Another session reusing the id neither cancels nor replaces the call
+    let cancel = json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/cancelled",
+        "params": { "requestId": 7 }
+    });
+    with_call_session("mallory".to_string(), handle_request(&cancel.to_string(), state.clone()))
+        .await
+        .unwrap();
+    assert!(in_flight.contains_key(&key));
+
+    // A second call with the same id in the same session is refused
+    let duplicate = with_call_session("alice".to_string(), async {
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": { "name": "getHealth", "arguments": {} }
+        });
+        handle_request(&call.to_string(), state.clone()).await.unwrap()
+    })
+    .await;
+    assert_eq!(serde_json::to_value(duplicate).unwrap()["error"]["code"], -32600);
+    assert!(in_flight.contains_key(&key));
+
+    // Dropping the caller aborts the call and releases its entry
+    call_task.abort();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while in_flight.contains_key(&key) {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("abandoned call should be removed");
+}
+
+#[tokio::test]
+async fn test_cancelled_without_matching_call_succeeds() {
+    use solana_mcp_server::tools::handle_request;
+
+    let state = test_state(|_| {});
+    state.write().await.initialized = true;
+
+    let cancel = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "cancelled",
+        "params": { "requestId": 99 }
+    });
+    let response = handle_request(&cancel.to_string(), state).await.unwrap();
+    let response = serde_json::to_value(response).unwrap();
+    assert!(response.get("error").is_none() || response["error"].is_null());
+}
+
+#[tokio::test]
+async fn test_request_ids_must_be_string_number_or_null() {
+    use solana_mcp_server::tools::handle_request;
+
+    let state = test_state(|_| {});
+    state.write().await.initialized = true;
+
+    for id in [json!({ "nested": 1 }), json!([1]), json!(true)] {
+        let request = json!({ "jsonrpc": "2.0", "id": id, "method": "tools/list" });
+        let response = handle_request(&request.to_string(), state.clone()).await.unwrap();
+        let response = serde_json::to_value(response).unwrap();
+        assert_eq!(response["error"]["code"], -32600, "id {id}");
+        assert_eq!(response["id"], Value::Null);
+    }
+
+    for id in [json!("abc"), json!(42)] {
+        let request = json!({ "jsonrpc": "2.0", "id": id, "method": "tools/list" });
+        let response = handle_request(&request.to_string(), state.clone()).await.unwrap();
+        let response = serde_json::to_value(response).unwrap();
+        assert!(response.get("error").is_none(), "id {id}: {response}");
+        assert_eq!(response["id"], id);
+    }
+}
+
+#[tokio::test]
+async fn test_global_in_flight_limit_rejects_then_recovers() {
+    use std::time::Duration;
+
+    let state = test_state(|config| {
+        config.max_in_flight_requests = 1;
+        config.max_concurrent_rpc = 1;
+        config.timeouts.rpc_permit_wait_ms = 60_000;
+        config.rpc_url = "https://127.0.0.1:1".to_string();
+        config.rpc_urls = vec![];
+    });
+
+    // Park one call on the RPC semaphore so it occupies the only in-flight slot
+    let semaphore = state.read().await.rpc_semaphore.clone();
+    let held = semaphore.acquire_owned().await.unwrap();
+    let first_state = state.clone();
+    let first = tokio::spawn(async move { call_tool(first_state, "getHealth", json!({})).await });
+
+    let counter = state.read().await.in_flight_requests.clone();
+    while counter.load(std::sync::atomic::Ordering::Acquire) == 0 {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    let busy = call_tool(state.clone(), "getHealth", json!({})).await;
+    assert_eq!(busy["error"]["code"], -32005);
+    assert!(busy["error"]["data"]["retryAfterMs"].as_u64().unwrap() > 0);
+
+    // Once the parked call completes the slot is released
+    drop(held);
+    first.await.unwrap();
+    assert_eq!(counter.load(std::sync::atomic::Ordering::Acquire), 0);
+
+    let recovered = call_tool(state, "getHealth", json!({})).await;
+    assert_ne!(recovered["error"]["code"], -32005);
+}
+
+#[tokio::test]
+async fn test_malformed_pubkey_rejected_as_invalid_params() {
+    let state = test_state(|_| {});
+
+    let response = call_tool(state.clone(), "getBalance", json!({ "pubkey": "abc" })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "pubkey");
+
+    let response = call_tool(
+        state,
+        "getTokenAccountsByOwner",
+        json!({ "owner": solana_sdk::pubkey::Pubkey::new_unique().to_string(), "programId": "not-base58!" }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "programId");
+}
+
+#[tokio::test]
+async fn test_malformed_signature_rejected_as_invalid_params() {
+    let state = test_state(|_| {});
+    let too_short = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+
+    let response = call_tool(state, "getTransaction", json!({ "signature": too_short })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "signature");
+    assert!(response["error"]["message"].as_str().unwrap().contains("64 bytes"));
+}
+
+#[tokio::test]
+async fn test_multiple_accounts_array_cap() {
+    let state = test_state(|_| {});
+    let pubkeys: Vec<String> = (0..101)
+        .map(|_| solana_sdk::pubkey::Pubkey::new_unique().to_string())
+        .collect();
+
+    let response = call_tool(state, "getMultipleAccounts", json!({ "pubkeys": pubkeys })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "pubkeys");
+}
+
+#[tokio::test]
+async fn test_historical_fee_stats_addresses_are_validated() {
+    let state = test_state(|_| {});
+    let addresses: Vec<String> = (0..129)
+        .map(|_| solana_sdk::pubkey::Pubkey::new_unique().to_string())
+        .collect();
+
+    for arguments in [json!({ "addresses": addresses }), json!({}), json!({ "addresses": [1] })] {
+        let response = call_tool(state.clone(), "getHistoricalFeeStats", arguments).await;
+        assert_eq!(response["error"]["code"], -32602);
+        assert_eq!(response["error"]["data"]["parameter"], "addresses");
+    }
+
+    let address = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    for sample_size in [json!(0), json!(101), json!("25"), json!(-1)] {
+        let response = call_tool(
+            state.clone(),
+            "getHistoricalFeeStats",
+            json!({ "addresses": [address], "sampleSize": sample_size }),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], -32602, "sampleSize {sample_size} was accepted");
+        assert_eq!(response["error"]["data"]["parameter"], "sampleSize");
+    }
+}
+
+#[tokio::test]
+async fn test_network_mutation_tools_disabled_by_config() {
+    use solana_mcp_server::tools::{handle_tools_list, NETWORK_MUTATION_TOOLS};
+
+    let state = test_state(|config| config.allow_network_mutation = false);
+
+    let response = call_tool(
+        state.clone(),
+        "setNetworkRpcUrl",
+        json!({ "networkId": "solana", "rpcUrl": "https://example.com" }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32601);
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Method disabled"));
+
+    let listed = handle_tools_list(Some(json!(1)), &*state.read().await).await.unwrap();
+    let listed = serde_json::to_value(listed).unwrap();
+    let names: Vec<&str> = listed["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert!(names.contains(&"getBalance"));
+    assert!(NETWORK_MUTATION_TOOLS.iter().all(|tool| !names.contains(tool)));
+}
+
+#[tokio::test]
+async fn test_enabled_and_disabled_tools_constrain_list_and_call() {
+    use solana_mcp_server::tools::handle_tools_list;
+
+    let state = test_state(|config| {
+        config.enabled_tools = vec!["getServerConfig".to_string(), "decodeProgramError".to_string(), "getBalance".to_string()];
+        config.disabled_tools = vec!["getBalance".to_string()];
+    });
+    assert!(state.read().await.config.validate().is_ok());
+
+    let listed = handle_tools_list(Some(json!(1)), &*state.read().await).await.unwrap();
+    let listed = serde_json::to_value(listed).unwrap();
+    let mut names: Vec<&str> = listed["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["decodeProgramError", "getServerConfig"]);
+
+    // The denylist wins over the allowlist, and unlisted tools are rejected too
+    for tool in ["getBalance", "getSlot"] {
+        let response = call_tool(state.clone(), tool, json!({ "pubkey": "11111111111111111111111111111111" })).await;
+        assert_eq!(response["error"]["code"], -32601, "{tool}");
+        assert!(response["error"]["message"].as_str().unwrap().contains("not enabled"));
+    }
+    let response = call_tool(state.clone(), "getServerConfig", json!({})).await;
+    assert!(response["result"].is_object());
+
+    // Misspelled names are caught when the config is validated
+    state.write().await.config.disabled_tools = vec!["getBalence".to_string()];
+    let error = state.read().await.config.validate().unwrap_err();
+    assert!(error.to_string().contains("getBalence"));
+}
+
+#[tokio::test]
+async fn test_get_enabled_networks_reports_status_with_sanitized_url() {
+    use solana_mcp_server::SvmNetwork;
+
+    let state = test_state(|config| {
+        config.svm_networks.insert(
+            "local".to_string(),
+            SvmNetwork {
+                name: "Local".to_string(),
+                rpc_url: "https://127.0.0.1:1/rpc?api-key=secret".to_string(),
+                enabled: true,
+                commitment: None,
+            },
+        );
+        config.svm_networks.insert(
+            "off".to_string(),
+            SvmNetwork {
+                name: "Disabled".to_string(),
+                rpc_url: "https://127.0.0.1:2".to_string(),
+                enabled: false,
+                commitment: None,
+            },
+        );
+    });
+
+    let response = call_tool(state, "getEnabledNetworks", json!({})).await;
+    let networks = response["result"]["networks"].as_array().unwrap();
+
+    assert_eq!(networks.len(), 1);
+    assert_eq!(networks[0]["id"], "local");
+    assert_eq!(networks[0]["name"], "Local");
+    assert_eq!(networks[0]["status"], "unreachable");
+    assert!(networks[0]["latencyMs"].is_u64());
+    assert!(!response.to_string().contains("secret"));
+}
+
+#[tokio::test]
+async fn test_svm_network_commitment_overrides_global_default() {
+    use solana_mcp_server::SvmNetwork;
+    use solana_sdk::commitment_config::CommitmentLevel;
+
+    let network = |commitment: Option<&str>| SvmNetwork {
+        name: "Test".to_string(),
+        rpc_url: "https://127.0.0.1:1".to_string(),
+        enabled: true,
+        commitment: commitment.map(str::to_string),
+    };
+    let state = test_state(|config| {
+        config.commitment = "confirmed".to_string();
+        config.svm_networks.insert("fast".to_string(), network(Some("processed")));
+        config.svm_networks.insert("plain".to_string(), network(None));
+    });
+
+    let commitment = |state: &ServerState, id: &str| state.svm_clients[id].commitment().commitment;
+    {
+        let state_guard = state.read().await;
+        assert_eq!(commitment(&state_guard, "fast"), CommitmentLevel::Processed);
+        assert_eq!(commitment(&state_guard, "plain"), CommitmentLevel::Confirmed);
+    }
+
+    let mut state_guard = state.write().await;
+    let mut new_config = state_guard.config.clone();
+    new_config.commitment = "finalized".to_string();
+    state_guard.update_config(new_config.clone());
+    assert_eq!(commitment(&state_guard, "fast"), CommitmentLevel::Processed);
+    assert_eq!(commitment(&state_guard, "plain"), CommitmentLevel::Finalized);
+
+    new_config.svm_networks.insert("bad".to_string(), network(Some("recent")));
+    assert!(new_config.validate().is_err());
+}
+
+#[tokio::test]
+async fn test_scan_sbpf_binary_rejects_non_elf_as_invalid_params() {
+    use base64::Engine;
+
+    let state = test_state(|_| {});
+    let not_elf = base64::engine::general_purpose::STANDARD.encode([0u8; 128]);
+
+    let response = call_tool(state.clone(), "scanSbpfBinary", json!({ "programBinary": not_elf })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "programBinary");
+    assert!(response["error"]["message"].as_str().unwrap().contains("Not a valid ELF file"));
+
+    // File paths are only honoured for the local stdio transport
+    let response = call_tool(state, "scanSbpfBinary", json!({ "programPath": "/etc/hostname" })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "programPath");
+}
+
+#[tokio::test]
+async fn test_validate_sbpf_binary_reports_structure() {
+    use base64::Engine;
+
+    let state = test_state(|_| {});
+    let not_elf = base64::engine::general_purpose::STANDARD.encode([0u8; 128]);
+
+    let response = call_tool(state.clone(), "validateSbpfBinary", json!({ "programBinary": not_elf })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "programBinary");
+    assert!(response["error"]["message"].as_str().unwrap().contains("Not a valid ELF file"));
+
+    // A bare ELF header parses but is missing everything the loader needs
+    let mut header_only = vec![0u8; 1024];
+    header_only[0..4].copy_from_slice(&[0x7F, 0x45, 0x4C, 0x46]);
+    header_only[4] = 2;
+    header_only[5] = 1;
+    header_only[6] = 1;
+    header_only[16] = 0x02;
+    header_only[18] = 0xF7;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(&header_only);
+
+    let response = call_tool(state, "validateSbpfBinary", json!({ "programBinary": encoded })).await;
+    let result = &response["result"];
+    assert_eq!(result["loadable"], false);
+    assert_eq!(result["entrypoint"], "0x0");
+    assert!(result["section_layout"].as_array().unwrap().is_empty());
+    assert!(!result["errors"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_scan_sbpf_binary_rejects_unknown_min_severity() {
+    use base64::Engine;
+
+    let state = test_state(|_| {});
+    let mut header_only = vec![0u8; 1024];
+    header_only[0..4].copy_from_slice(&[0x7F, 0x45, 0x4C, 0x46]);
+    header_only[4] = 2;
+    header_only[5] = 1;
+    header_only[6] = 1;
+    header_only[18] = 0xF7;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(&header_only);
+
+    let response = call_tool(
+        state.clone(),
+        "scanSbpfBinary",
+        json!({ "programBinary": encoded, "minSeverity": "severe" }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "minSeverity");
+
+    let response = call_tool(
+        state.clone(),
+        "scanSbpfBinary",
+        json!({ "programBinary": encoded, "minSeverity": "critical" }),
+    )
+    .await;
+    let result = &response["result"];
+    let listed = result["vulnerabilities"].as_array().unwrap();
+    assert!(listed.iter().all(|v| v["severity"] == "critical"));
+    assert_eq!(listed.len() as u64, result["critical_count"].as_u64().unwrap());
+    assert!(result["info_count"].as_u64().unwrap() > 0);
+
+    let response = call_tool(state, "scanSbpfBinary", json!({ "programBinary": encoded, "summaryOnly": true })).await;
+    let result = &response["result"];
+    assert!(result.get("vulnerabilities").is_none());
+    assert!(result["risk_score"].is_u64());
+    assert_eq!(result["passed"], false);
+}
+
+#[tokio::test]
+async fn test_scan_sbpf_binary_enforces_size_limit() {
+    use solana_mcp_server::tools::MAX_SBPF_TOOL_BINARY_BYTES;
+
+    let state = test_state(|_| {});
+    let oversized = "A".repeat(MAX_SBPF_TOOL_BINARY_BYTES / 3 * 4 + 8);
+
+    let response = call_tool(state, "scanSbpfBinary", json!({ "programBinary": oversized })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert!(response["error"]["message"].as_str().unwrap().contains("too large"));
+}
+
+#[tokio::test]
+async fn test_run_sbpf_test_rejects_invalid_input_as_invalid_params() {
+    use base64::Engine;
+
+    let state = test_state(|_| {});
+    let not_elf = base64::engine::general_purpose::STANDARD.encode([0u8; 128]);
+
+    let response = call_tool(state.clone(), "runSbpfTest", json!({ "programBinary": not_elf })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "programBinary");
+
+    let response = call_tool(
+        state,
+        "runSbpfTest",
+        json!({ "programBinary": not_elf, "instructions": [{ "accounts": "not-a-list" }] }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "instructions");
+}
+
+#[tokio::test]
+async fn test_deploy_sbpf_to_devnet_requires_flag_and_payer() {
+    use base64::Engine;
+
+    let binary = base64::engine::general_purpose::STANDARD.encode([0u8; 128]);
+
+    // Disabled by default because deployment spends SOL
+    let state = test_state(|_| {});
+    let response = call_tool(state, "deploySbpfToDevnet", json!({ "programBinary": binary })).await;
+    assert_eq!(response["error"]["code"], -32601);
+
+    let state = test_state(|config| config.sbpf_deploy.enabled = true);
+    let response = call_tool(state.clone(), "deploySbpfToDevnet", json!({ "programBinary": binary })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "payerKeypair");
+
+    let response = call_tool(
+        state,
+        "deploySbpfToDevnet",
+        json!({ "programBinary": binary, "payerKeypair": "AAAA" }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "payerKeypair");
+}
+
+/// Start a JSON-RPC server answering the cluster status methods; getHealth fails unless `healthy`
+async fn mock_cluster_rpc(healthy: bool) -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+    use axum::Json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let counter = counter.clone();
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let result = match request["method"].as_str() {
+                Some("getSlot") => json!(1234),
+                Some("getBlockHeight") => json!(1200),
+                Some("getEpochInfo") => json!({
+                    "absoluteSlot": 1234,
+                    "blockHeight": 1200,
+                    "epoch": 3,
+                    "slotIndex": 34,
+                    "slotsInEpoch": 432000,
+                    "transactionCount": 99
+                }),
+                Some("getHealth") if healthy => json!("ok"),
+                _ => {
+                    return Json(json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "error": { "code": -32005, "message": "Node is behind by 42 slots" }
+                    }))
+                }
+            };
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }
+    })
+    .await;
+    (rpc_url, calls)
+}
+
+#[tokio::test]
+async fn test_cluster_status_reports_partial_failures() {
+    let (rpc_url, _) = mock_cluster_rpc(false).await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state, "getClusterStatus", json!({})).await;
+    let result = &response["result"];
+    assert_eq!(result["slot"], 1234);
+    assert_eq!(result["blockHeight"], 1200);
+    assert_eq!(result["epochInfo"]["epoch"], 3);
+    assert!(result["health"]["error"].as_str().unwrap().contains("behind"));
+    assert_eq!(result["cached"], false);
+}
+
+#[tokio::test]
+async fn test_cluster_status_is_cached_within_ttl() {
+    use std::sync::atomic::Ordering;
+
+    let (rpc_url, calls) = mock_cluster_rpc(true).await;
+    let state = test_state(|config| {
+        config.rpc_urls = vec![rpc_url];
+        config.cluster_status_cache_ttl_ms = 60_000;
+    });
+
+    let first = call_tool(state.clone(), "getClusterStatus", json!({})).await;
+    assert_eq!(first["result"]["health"], "ok");
+    assert_eq!(first["result"]["cached"], false);
+    let fetched = calls.load(Ordering::SeqCst);
+    assert_eq!(fetched, 4);
+
+    let second = call_tool(state, "getClusterStatus", json!({})).await;
+    assert_eq!(second["result"]["cached"], true);
+    assert_eq!(second["result"]["slot"], 1234);
+    assert_eq!(calls.load(Ordering::SeqCst), fetched);
+}
+
+/// Start a JSON-RPC server reporting healthy at `slot` while having seen shreds up to `max_shred_insert_slot`
+async fn mock_lagging_rpc(slot: u64, max_shred_insert_slot: u64) -> String {
+    use axum::Json;
+
+    spawn_mock_rpc(move |Json(request): Json<Value>| async move {
+        let result = match request["method"].as_str() {
+            Some("getHealth") => json!("ok"),
+            Some("getSlot") => json!(slot),
+            Some("getMaxShredInsertSlot") => json!(max_shred_insert_slot),
+            _ => Value::Null,
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_health_detailed_flags_nodes_that_lag_the_cluster() {
+    let rpc_url = mock_lagging_rpc(1_000, 6_000).await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getHealthDetailed", json!({})).await;
+    let result = &response["result"];
+    assert_eq!(result["health"], "ok");
+    assert_eq!(result["slot"], 1_000);
+    assert_eq!(result["highestKnownSlot"], 6_000);
+    assert_eq!(result["slotLag"], 5_000);
+    assert_eq!(result["maxSlotLag"], 150);
+    assert_eq!(result["healthy"], false);
+
+    let response = call_tool(state, "getHealthDetailed", json!({ "maxSlotLag": 10_000 })).await;
+    assert_eq!(response["result"]["healthy"], true);
+
+    // Other pool endpoints count towards the highest known slot
+    let lagging = mock_lagging_rpc(1_000, 1_000).await;
+    let ahead = mock_lagging_rpc(9_000, 9_000).await;
+    let state = test_state(|config| config.rpc_urls = vec![lagging, ahead]);
+    let response = call_tool(state, "getHealthDetailed", json!({})).await;
+    assert_eq!(response["result"]["slot"], 1_000);
+    assert_eq!(response["result"]["slotLag"], 8_000);
+}
+
+/// Start a JSON-RPC server holding one 3-byte system account; any other pubkey is missing
+async fn mock_account_rpc(pubkey: String) -> String {
+    mock_account_rpc_with_data(pubkey, vec![1, 2, 3]).await
+}
+
+/// Start a JSON-RPC server holding one system account with `data`; any other pubkey is missing
+async fn mock_account_rpc_with_data(pubkey: String, data: Vec<u8>) -> String {
+    use axum::Json;
+    use base64::Engine;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
+    spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let pubkey = pubkey.clone();
+        let (encoded, space) = (encoded.clone(), data.len());
+        async move {
+            let account = |key: &Value| {
+                (key.as_str() == Some(pubkey.as_str())).then(|| {
+                    json!({
+                        "lamports": 1_000_000,
+                        "data": [encoded, "base64"],
+                        "owner": "11111111111111111111111111111111",
+                        "executable": false,
+                        "rentEpoch": 0,
+                        "space": space
+                    })
+                })
+            };
+            let params = &request["params"];
+            let value = match request["method"].as_str() {
+                Some("getAccountInfo") => json!(account(&params[0])),
+                Some("getMultipleAccounts") => json!(params[0]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(account)
+                    .collect::<Vec<_>>()),
+                _ => Value::Null,
+            };
+            Json(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": { "context": { "slot": 1 }, "value": value }
+            }))
+        }
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_account_tools_fall_back_to_default_encoding() {
+    let pubkey = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let missing = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let rpc_url = mock_account_rpc(pubkey.clone()).await;
+    let state = test_state(|config| {
+        config.rpc_urls = vec![rpc_url];
+        config.default_account_encoding = Some("base58".to_string());
+    });
+
+    let response = call_tool(state.clone(), "getAccountInfo", json!({ "pubkey": pubkey })).await;
+    assert_eq!(response["result"]["account"]["data"], json!(["Ldp", "base58"]));
+
+    // A per-call encoding overrides the configured default
+    let response = call_tool(
+        state.clone(),
+        "getAccountInfo",
+        json!({ "pubkey": pubkey, "encoding": "base64" }),
+    )
+    .await;
+    assert_eq!(response["result"]["account"]["data"], json!(["AQID", "base64"]));
+
+    let response = call_tool(
+        state.clone(),
+        "getMultipleAccounts",
+        json!({ "pubkeys": [pubkey, missing] }),
+    )
+    .await;
+    let accounts = &response["result"]["accounts"];
+    assert_eq!(accounts[0]["data"], json!(["Ldp", "base58"]));
+    assert_eq!(accounts[1], Value::Null);
+
+    let response = call_tool(state, "getAccountInfo", json!({ "pubkey": pubkey, "encoding": "hex" })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "encoding");
+}
+
+/// Answers every call with an empty context response and records the
+/// params of each method other than getVersion
+async fn mock_recording_rpc() -> (String, Arc<std::sync::Mutex<Vec<(String, Value)>>>) {
+    use axum::Json;
+
+    let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorded = calls.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let recorded = recorded.clone();
+        async move {
+            let method = request["method"].as_str().unwrap_or_default().to_string();
+            let result = match method.as_str() {
+                "getVersion" => json!({ "solana-core": "2.1.11", "feature-set": 4215500110u32 }),
+                "getProgramAccounts" => json!({ "context": { "slot": 1 }, "value": [] }),
+                _ => json!({ "context": { "slot": 1 }, "value": null }),
+            };
+            if method != "getVersion" {
+                recorded.lock().unwrap().push((method, request["params"].clone()));
+            }
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }
+    })
+    .await;
+    (rpc_url, calls)
+}
+
+#[tokio::test]
+async fn test_program_accounts_and_context_uses_requested_commitment_and_encoding() {
+    let (rpc_url, calls) = mock_recording_rpc().await;
+    let state = test_state(|config| {
+        config.rpc_url = rpc_url;
+        config.rpc_urls.clear();
+        config.default_account_encoding = Some("base58".to_string());
+    });
+    let program_id = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+
+    let response = call_tool(
+        state.clone(),
+        "getProgramAccountsAndContext",
+        json!({ "program_id": program_id, "commitment": "finalized" }),
+    )
+    .await;
+    assert!(response.get("error").is_none(), "{response}");
+    let response = call_tool(
+        state.clone(),
+        "getProgramAccountsAndContext",
+        json!({ "program_id": program_id, "encoding": "jsonParsed", "commitment": "processed" }),
+    )
+    .await;
+    assert!(response.get("error").is_none(), "{response}");
+
+    let calls = calls.lock().unwrap().clone();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].1[1]["commitment"], "finalized");
+    assert_eq!(calls[0].1[1]["encoding"], "base58");
+    assert_eq!(calls[1].1[1]["commitment"], "processed");
+    assert_eq!(calls[1].1[1]["encoding"], "jsonParsed");
+
+    let response = call_tool(
+        state,
+        "getProgramAccountsAndContext",
+        json!({ "program_id": program_id, "encoding": "hex" }),
+    )
+    .await;
+    assert_eq!(response["error"]["data"]["parameter"], "encoding");
+}
+
+#[tokio::test]
+async fn test_program_accounts_page_honors_encoding_filters_and_limit() {
+    let (rpc_url, calls) = mock_recording_rpc().await;
+    let state = test_state(|config| {
+        config.rpc_url = rpc_url;
+        config.rpc_urls.clear();
+    });
+    let program_id = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+
+    let response = call_tool(
+        state.clone(),
+        "getProgramAccounts",
+        json!({
+            "programId": program_id,
+            "config": { "encoding": "jsonParsed" },
+            "filters": [{ "dataSize": 165 }]
+        }),
+    )
+    .await;
+    assert!(response.get("error").is_none(), "{response}");
+    assert_eq!(response["result"]["totalAccounts"], 0);
+
+    let calls = calls.lock().unwrap().clone();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].1[1]["encoding"], "jsonParsed");
+    assert_eq!(calls[0].1[1]["filters"], json!([{ "dataSize": 165 }]));
+
+    for limit in [json!(0), json!(10_001), json!("10")] {
+        let response = call_tool(
+            state.clone(),
+            "getProgramAccounts",
+            json!({ "programId": program_id, "limit": limit }),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], -32602, "limit {limit} was accepted");
+        assert_eq!(response["error"]["data"]["parameter"], "limit");
+    }
+}
+
+#[tokio::test]
+async fn test_read_tools_forward_requested_commitment() {
+    let (rpc_url, calls) = mock_recording_rpc().await;
+    let state = test_state(|config| {
+        config.rpc_url = rpc_url;
+        config.rpc_urls.clear();
+    });
+    let pubkey = || solana_sdk::pubkey::Pubkey::new_unique().to_string();
+
+    // The mock's empty responses may fail to decode; only the outgoing params matter here
+    call_tool(state.clone(), "getEpochInfo", json!({ "commitment": "processed" })).await;
+    call_tool(state.clone(), "getTokenSupply", json!({ "mint": pubkey(), "commitment": "processed" })).await;
+    call_tool(state.clone(), "getTokenAccountBalance", json!({ "account": pubkey(), "commitment": "processed" })).await;
+    call_tool(state.clone(), "getTokenAccountsByOwner", json!({ "owner": pubkey(), "commitment": "processed" })).await;
+    call_tool(state, "getTokenAccountsByMint", json!({ "mint": pubkey(), "commitment": "processed" })).await;
+
+    let calls = calls.lock().unwrap().clone();
+    let config_of = |method: &str| {
+        let (_, params) = calls
+            .iter()
+            .find(|(name, _)| name == method)
+            .unwrap_or_else(|| panic!("{method} was never called"));
+        params.as_array().unwrap().last().unwrap().clone()
+    };
+    for method in ["getEpochInfo", "getTokenSupply", "getTokenAccountBalance", "getTokenAccountsByOwner", "getProgramAccounts"] {
+        assert_eq!(config_of(method)["commitment"], "processed", "{method} dropped the commitment");
+    }
+}
+
+#[tokio::test]
+async fn test_get_balances_chunks_and_preserves_order() {
+    use axum::Json;
+    use solana_sdk::pubkey::Pubkey;
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // Each account holds 10 lamports per unit of its first key byte; multiples of 7 do not exist
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let counter = counter.clone();
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(request["method"], "getMultipleAccounts");
+            assert_eq!(request["params"][1]["dataSlice"]["length"], 0);
+            let accounts: Vec<Value> = request["params"][0]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|key| {
+                    let byte = Pubkey::from_str(key.as_str().unwrap()).unwrap().to_bytes()[0];
+                    if byte % 7 == 0 {
+                        return Value::Null;
+                    }
+                    json!({
+                        "lamports": u64::from(byte) * 10,
+                        "data": ["", "base64"],
+                        "owner": "11111111111111111111111111111111",
+                        "executable": false,
+                        "rentEpoch": 0,
+                        "space": 0
+                    })
+                })
+                .collect();
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": { "context": { "slot": 9 }, "value": accounts } }))
+        }
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    // Descending so order is not an accident of sorting
+    let pubkeys: Vec<String> = (1..=150u8).rev().map(|byte| Pubkey::new_from_array([byte; 32]).to_string()).collect();
+    let response = call_tool(state.clone(), "getBalances", json!({ "pubkeys": pubkeys })).await;
+    let balances = response["result"]["balances"].as_array().unwrap();
+    assert_eq!(balances.len(), 150);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(balances[0], json!({ "pubkey": pubkeys[0], "lamports": 1_500 }));
+    assert_eq!(balances[149]["lamports"], 10);
+    assert_eq!(balances[150 - 140]["lamports"], 0);
+    assert_eq!(response["result"]["context"]["slot"], 9);
+
+    let arguments = json!({ "pubkeys": [pubkeys[10]], "nullIfMissing": true });
+    let response = call_tool(state.clone(), "getBalances", arguments).await;
+    assert_eq!(response["result"]["balances"][0]["lamports"], Value::Null);
+
+    let too_many: Vec<String> = (0..1001).map(|_| Pubkey::new_unique().to_string()).collect();
+    let response = call_tool(state, "getBalances", json!({ "pubkeys": too_many })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "pubkeys");
+}
+
+#[tokio::test]
+async fn test_base58_requests_for_large_accounts_fall_back_to_base64() {
+    use base64::Engine;
+
+    let pubkey = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let data = vec![7u8; 200];
+    let rpc_url = mock_account_rpc_with_data(pubkey.clone(), data.clone()).await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getAccountInfo", json!({ "pubkey": pubkey, "encoding": "base58" })).await;
+    let account = &response["result"]["account"];
+    assert_eq!(account["data"], json!([base64::engine::general_purpose::STANDARD.encode(&data), "base64"]));
+    assert!(account["warning"].as_str().unwrap().contains("200 bytes, over the 128-byte base58 limit"));
+
+    let response = call_tool(state, "getMultipleAccounts", json!({ "pubkeys": [pubkey], "encoding": "base58" })).await;
+    assert_eq!(response["result"]["accounts"][0]["data"][1], "base64");
+}
+
+#[tokio::test]
+async fn test_missing_account_is_null_not_an_error() {
+    let existing = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let missing = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let rpc_url = mock_account_rpc(existing).await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getAccountInfo", json!({ "pubkey": missing })).await;
+    assert!(response.get("error").is_none(), "unexpected error: {response}");
+    assert_eq!(response["result"]["account"], Value::Null);
+
+    let response = call_tool(
+        state,
+        "getAccountInfo",
+        json!({ "pubkey": missing, "encodings": ["base64", "jsonParsed"] }),
+    )
+    .await;
+    assert!(response.get("error").is_none(), "unexpected error: {response}");
+    assert_eq!(response["result"]["account"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_account_info_encodings_are_validated_and_use_requested_commitment() {
+    let (rpc_url, calls) = mock_recording_rpc().await;
+    let state = test_state(|config| {
+        config.rpc_url = rpc_url;
+        config.rpc_urls.clear();
+    });
+    let pubkey = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+
+    for encodings in [json!("base64"), json!([1, 2]), json!({ "base64": true })] {
+        let response = call_tool(
+            state.clone(),
+            "getAccountInfo",
+            json!({ "pubkey": pubkey, "encodings": encodings }),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], -32602, "{encodings} was accepted");
+        assert_eq!(response["error"]["data"]["parameter"], "encodings");
+    }
+    assert!(calls.lock().unwrap().is_empty());
+
+    let response = call_tool(
+        state,
+        "getAccountInfo",
+        json!({ "pubkey": pubkey, "encodings": ["base64"], "commitment": "processed" }),
+    )
+    .await;
+    assert!(response.get("error").is_none(), "{response}");
+    let calls = calls.lock().unwrap().clone();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].1[1]["commitment"], "processed");
+}
+
+#[tokio::test]
+async fn test_account_lookup_transport_failure_is_still_an_error() {
+    let state = test_state(|config| config.rpc_urls = vec!["http://127.0.0.1:1".to_string()]);
+    let pubkey = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+
+    let response = call_tool(state, "getAccountInfo", json!({ "pubkey": pubkey })).await;
+    assert_eq!(response["error"]["code"], -32603);
+}
+
+#[tokio::test]
+async fn test_dry_run_describes_call_without_sending_it() {
+    let pubkey = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let state = test_state(|config| {
+        // Nothing listens here, so a real call could not succeed
+        config.rpc_urls = vec!["http://127.0.0.1:1/?api-key=secret".to_string()];
+    });
+
+    let response = call_tool(
+        state.clone(),
+        "getBalance",
+        json!({ "pubkey": pubkey, "commitment": "finalized", "dryRun": true }),
+    )
+    .await;
+    let plan = &response["result"];
+    assert_eq!(plan["dryRun"], true);
+    assert_eq!(plan["rpcMethods"], json!(["getBalance"]));
+    assert_eq!(plan["commitment"], "finalized");
+    assert_eq!(plan["arguments"], json!({ "pubkey": pubkey, "commitment": "finalized" }));
+    assert!(!plan["endpoint"].as_str().unwrap().contains("secret"));
+
+    // .sol names are listed, not looked up, since the endpoint is unreachable
+    let response = call_tool(
+        state.clone(),
+        "getBalance",
+        json!({ "pubkey": "bonfida.sol", "dryRun": true }),
+    )
+    .await;
+    assert!(response.get("error").is_none(), "{response}");
+    assert_eq!(
+        response["result"]["unresolvedDomains"],
+        json!([{ "argument": "pubkey", "domain": "bonfida.sol" }])
+    );
+
+    let response = call_tool(state.clone(), "getSbpfFaq", json!({ "dryRun": true })).await;
+    assert_eq!(response["result"]["rpcMethods"], json!([]));
+    assert_eq!(response["result"]["endpoint"], Value::Null);
+
+    let response = call_tool(state, "noSuchTool", json!({ "dryRun": true })).await;
+    assert_eq!(response["error"]["code"], -32601);
+}
+
+#[tokio::test]
+async fn test_dry_run_still_validates_arguments() {
+    let state = test_state(|_| {});
+
+    for (tool, arguments, parameter) in [
+        ("getBalance", json!({ "dryRun": true }), "pubkey"),
+        ("getBalance", json!({ "pubkey": "not-a-key", "dryRun": true }), "pubkey"),
+        ("getConfirmedBlocks", json!({ "startSlot": "ten", "dryRun": true }), "startSlot"),
+        ("getSlot", json!({ "commitment": "recent", "dryRun": true }), "commitment"),
+        (
+            "getAccountInfo",
+            json!({ "pubkey": solana_sdk::pubkey::Pubkey::new_unique().to_string(), "encoding": "hex", "dryRun": true }),
+            "encoding",
+        ),
+    ] {
+        let response = call_tool(state.clone(), tool, arguments).await;
+        assert_eq!(response["error"]["code"], -32602, "{tool} dry run skipped validation");
+        assert_eq!(response["error"]["data"]["parameter"], parameter);
+    }
+}
+
+/// Start a JSON-RPC server holding a name record for `bonfida.sol` owned by `owner`
+async fn mock_name_service_rpc(owner: solana_sdk::pubkey::Pubkey) -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+    use axum::Json;
+    use base64::Engine;
+    use solana_mcp_server::rpc::names::{domain_name_account, NAME_SERVICE_PROGRAM_ID, SOL_TLD_ACCOUNT};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let record = domain_name_account("bonfida.sol", "domain").unwrap().to_string();
+    let mut data = SOL_TLD_ACCOUNT.to_bytes().to_vec();
+    data.extend_from_slice(&owner.to_bytes());
+    data.extend_from_slice(&[0u8; 32]);
+    let data = base64::engine::general_purpose::STANDARD.encode(data);
+
+    let lookups = Arc::new(AtomicUsize::new(0));
+    let counter = lookups.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let (record, data, counter) = (record.clone(), data.clone(), counter.clone());
+        async move {
+            let value = match request["method"].as_str() {
+                Some("getAccountInfo") => {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    (request["params"][0] == record.as_str()).then(|| {
+                        json!({
+                            "lamports": 1_000_000,
+                            "data": [data, "base64"],
+                            "owner": NAME_SERVICE_PROGRAM_ID.to_string(),
+                            "executable": false,
+                            "rentEpoch": 0,
+                            "space": 96
+                        })
+                    })
+                    .into()
+                }
+                Some("getBalance") if request["params"][0] == owner.to_string().as_str() => json!(42),
+                _ => json!(0),
+            };
+            Json(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": { "context": { "slot": 1 }, "value": value }
+            }))
+        }
+    })
+    .await;
+    (rpc_url, lookups)
+}
+
+#[tokio::test]
+async fn test_resolve_domain_returns_owner_and_caches() {
+    use std::sync::atomic::Ordering;
+
+    let owner = solana_sdk::pubkey::Pubkey::new_unique();
+    let (rpc_url, lookups) = mock_name_service_rpc(owner).await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "resolveDomain", json!({ "domain": "Bonfida.sol" })).await;
+    assert_eq!(response["result"]["owner"], owner.to_string());
+    assert_eq!(response["result"]["domain"], "bonfida.sol");
+    assert_eq!(response["result"]["cached"], false);
+
+    let response = call_tool(state.clone(), "resolveDomain", json!({ "domain": "bonfida.sol" })).await;
+    assert_eq!(response["result"]["cached"], true);
+    assert_eq!(lookups.load(Ordering::SeqCst), 1);
+
+    let response = call_tool(state, "resolveDomain", json!({ "domain": "unregistered.sol" })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert!(response["error"]["message"].as_str().unwrap().contains("not registered"));
+}
+
+#[tokio::test]
+async fn test_pubkey_arguments_accept_sol_domains() {
+    let owner = solana_sdk::pubkey::Pubkey::new_unique();
+    let (rpc_url, _) = mock_name_service_rpc(owner).await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getBalance", json!({ "pubkey": "bonfida.sol" })).await;
+    assert_eq!(response["result"]["balance"], 42);
+
+    let response = call_tool(state.clone(), "getBalance", json!({ "pubkey": "unregistered.sol" })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "pubkey");
+
+    // Each entry of an addresses array is resolved too
+    let addresses = json!({ "addresses": ["bonfida.sol", "unregistered.sol"] });
+    let response = call_tool(state.clone(), "getRecentPrioritizationFees", addresses).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "addresses");
+    assert!(response["error"]["message"].as_str().unwrap().contains("unregistered.sol"));
+
+    let dry_run = json!({ "addresses": ["bonfida.sol"], "dryRun": true });
+    let response = call_tool(state, "getHistoricalFeeStats", dry_run).await;
+    assert_eq!(
+        response["result"]["unresolvedDomains"],
+        json!([{ "argument": "addresses", "domain": "bonfida.sol" }])
+    );
+}
+
+#[tokio::test]
+async fn test_block_production_reports_counts_per_identity() {
+    use axum::Json;
+
+    let identity = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let leader = identity.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let leader = leader.clone();
+        async move {
+            let config = &request["params"][0];
+            Json(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": {
+                    "context": { "slot": 200 },
+                    "value": {
+                        "byIdentity": { leader: [4, 3] },
+                        "range": {
+                            "firstSlot": config["range"]["firstSlot"],
+                            "lastSlot": config["range"]["lastSlot"]
+                        }
+                    }
+                }
+            }))
+        }
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(
+        state,
+        "getBlockProduction",
+        json!({ "identity": identity, "firstSlot": 100, "lastSlot": 150 }),
+    )
+    .await;
+    let result = &response["result"];
+    assert_eq!(result["range"], json!({ "firstSlot": 100, "lastSlot": 150 }));
+    assert_eq!(result["byIdentity"][&identity], json!({ "leaderSlots": 4, "blocksProduced": 3 }));
+}
+
+#[tokio::test]
+async fn test_block_production_rejects_invalid_range_and_identity() {
+    let state = test_state(|_| {});
+
+    for (arguments, parameter) in [
+        (json!({ "firstSlot": 150, "lastSlot": 100 }), "lastSlot"),
+        (json!({ "lastSlot": 100 }), "firstSlot"),
+        (json!({ "firstSlot": -1 }), "firstSlot"),
+        (json!({ "identity": "not-a-pubkey" }), "identity"),
+    ] {
+        let response = call_tool(state.clone(), "getBlockProduction", arguments).await;
+        assert_eq!(response["error"]["code"], -32602);
+        assert_eq!(response["error"]["data"]["parameter"], parameter);
+    }
+}
+
+#[tokio::test]
+async fn test_vote_accounts_min_stake_filters_both_lists() {
+    use axum::Json;
+
+    let vote_account = |stake: u64| {
+        json!({
+            "votePubkey": solana_sdk::pubkey::Pubkey::new_unique().to_string(),
+            "nodePubkey": solana_sdk::pubkey::Pubkey::new_unique().to_string(),
+            "activatedStake": stake,
+            "epochVoteAccount": true,
+            "commission": 5,
+            "lastVote": 100,
+            "epochCredits": [],
+            "rootSlot": 90
+        })
+    };
+    let status = json!({
+        "current": [vote_account(5_000), vote_account(50)],
+        "delinquent": [vote_account(0), vote_account(9_000)]
+    });
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let status = status.clone();
+        async move { Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": status })) }
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getVoteAccounts", json!({})).await;
+    assert_eq!(response["result"]["current"].as_array().unwrap().len(), 2);
+    assert_eq!(response["result"]["delinquent"].as_array().unwrap().len(), 2);
+
+    let response = call_tool(state.clone(), "getVoteAccounts", json!({ "minStake": 1_000 })).await;
+    let result = &response["result"];
+    assert_eq!(result["current"].as_array().unwrap().len(), 1);
+    assert_eq!(result["current"][0]["activatedStake"], 5_000);
+    assert_eq!(result["delinquent"].as_array().unwrap().len(), 1);
+    assert_eq!(result["delinquent"][0]["activatedStake"], 9_000);
+
+    let response = call_tool(state, "getVoteAccounts", json!({ "votePubkey": "bad" })).await;
+    assert_eq!(response["error"]["data"]["parameter"], "votePubkey");
+}
+
+/// Start a JSON-RPC server echoing getSlotLeaders limits and serving a two-leader schedule
+async fn mock_leader_rpc(leaders: [String; 2]) -> String {
+    use axum::Json;
+
+    spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let [first, second] = leaders.clone();
+        async move {
+            let result = match request["method"].as_str() {
+                Some("getSlotLeaders") => {
+                    let limit = request["params"][1].as_u64().unwrap() as usize;
+                    json!(vec![first; limit])
+                }
+                Some("getLeaderSchedule") => json!({ first: [0, 1], second: [2, 3] }),
+                _ => Value::Null,
+            };
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_slot_leaders_clamps_limit_and_rejects_zero() {
+    let leader = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let other = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let rpc_url = mock_leader_rpc([leader.clone(), other]).await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getSlotLeaders", json!({ "startSlot": 10, "limit": 3 })).await;
+    assert_eq!(response["result"]["leaders"], json!([leader, leader, leader]));
+
+    let response = call_tool(state.clone(), "getSlotLeaders", json!({ "startSlot": 10, "limit": 9000 })).await;
+    assert_eq!(response["result"]["leaders"].as_array().unwrap().len(), 5000);
+
+    for (arguments, parameter) in [
+        (json!({ "startSlot": 0, "limit": 3 }), "startSlot"),
+        (json!({ "startSlot": 10, "limit": 0 }), "limit"),
+        (json!({ "startSlot": 10 }), "limit"),
+    ] {
+        let response = call_tool(state.clone(), "getSlotLeaders", arguments).await;
+        assert_eq!(response["error"]["code"], -32602);
+        assert_eq!(response["error"]["data"]["parameter"], parameter);
+    }
+}
+
+#[tokio::test]
+async fn test_leader_schedule_filters_to_identity() {
+    let leader = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let other = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let rpc_url = mock_leader_rpc([leader.clone(), other.clone()]).await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getLeaderSchedule", json!({})).await;
+    assert_eq!(response["result"]["schedule"].as_object().unwrap().len(), 2);
+
+    let response = call_tool(state.clone(), "getLeaderSchedule", json!({ "identity": other })).await;
+    assert_eq!(response["result"]["schedule"], json!({ other: [2, 3] }));
+
+    let response = call_tool(state, "getLeaderSchedule", json!({ "identity": "nope" })).await;
+    assert_eq!(response["error"]["data"]["parameter"], "identity");
+}
+
+#[tokio::test]
+async fn test_inflation_reward_keeps_positions_and_forwards_commitment() {
+    use axum::Json;
+
+    let rewarded = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let unrewarded = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let seen_config = Arc::new(std::sync::Mutex::new(Value::Null));
+    let (target, recorder) = (rewarded.clone(), seen_config.clone());
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let (target, recorder) = (target.clone(), recorder.clone());
+        async move {
+            *recorder.lock().unwrap() = request["params"][1].clone();
+            let rewards: Vec<Value> = request["params"][0]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|address| {
+                    if address == target.as_str() {
+                        json!({ "epoch": 7, "effectiveSlot": 3000, "amount": 25, "postBalance": 1025, "commission": 5 })
+                    } else {
+                        Value::Null
+                    }
+                })
+                .collect();
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": rewards }))
+        }
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(
+        state.clone(),
+        "getInflationReward",
+        json!({ "addresses": [unrewarded, rewarded], "epoch": 7, "commitment": "finalized" }),
+    )
+    .await;
+    let rewards = &response["result"]["rewards"];
+    assert_eq!(rewards[0], Value::Null);
+    assert_eq!(rewards[1]["amount"], 25);
+    let config = seen_config.lock().unwrap().clone();
+    assert_eq!(config["epoch"], 7);
+    assert_eq!(config["commitment"], "finalized");
+
+    let response = call_tool(state, "getInflationReward", json!({ "addresses": [] })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "addresses");
+}
+
+#[tokio::test]
+async fn test_fee_for_message_decodes_both_encodings_and_reports_expired_blockhash() {
+    use axum::Json;
+    use base64::Engine;
+    use solana_sdk::{
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        pubkey::Pubkey,
+    };
+
+    let live_blockhash = Hash::new_unique();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| async move {
+        let wire = base64::engine::general_purpose::STANDARD
+            .decode(request["params"][0].as_str().unwrap())
+            .unwrap();
+        let message: Message = bincode::deserialize(&wire).unwrap();
+        let fee = (message.recent_blockhash == live_blockhash).then_some(5000);
+        let result = json!({ "context": { "slot": 1 }, "value": fee });
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let payer = Pubkey::new_unique();
+    let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![AccountMeta::new(payer, true)]);
+    let message_bytes = |blockhash| {
+        bincode::serialize(&Message::new_with_blockhash(
+            std::slice::from_ref(&instruction),
+            Some(&payer),
+            &blockhash,
+        ))
+        .unwrap()
+    };
+
+    let live = message_bytes(live_blockhash);
+    let response = call_tool(
+        state.clone(),
+        "getFeeForMessage",
+        json!({ "message": base64::engine::general_purpose::STANDARD.encode(&live) }),
+    )
+    .await;
+    assert_eq!(response["result"]["fee"], 5000);
+
+    let response = call_tool(
+        state.clone(),
+        "getFeeForMessage",
+        json!({ "message": bs58::encode(&live).into_string(), "encoding": "base58" }),
+    )
+    .await;
+    assert_eq!(response["result"]["fee"], 5000);
+
+    let expired = message_bytes(Hash::new_unique());
+    let response = call_tool(
+        state.clone(),
+        "getFeeForMessage",
+        json!({ "message": base64::engine::general_purpose::STANDARD.encode(&expired) }),
+    )
+    .await;
+    assert_eq!(response["result"]["fee"], Value::Null);
+
+    for (arguments, parameter) in [
+        (json!({ "message": "not base64!" }), "message"),
+        (json!({ "message": "AQID" }), "message"),
+        (json!({ "message": "AQID", "encoding": "base32" }), "encoding"),
+    ] {
+        let response = call_tool(state.clone(), "getFeeForMessage", arguments).await;
+        assert_eq!(response["error"]["code"], -32602);
+        assert_eq!(response["error"]["data"]["parameter"], parameter);
+    }
+}
+
+#[tokio::test]
+async fn test_minimum_balance_for_rent_exemption_bounds_data_size() {
+    use axum::Json;
+
+    let rpc_url = spawn_mock_rpc(|Json(request): Json<Value>| async move {
+        // Mirrors the runtime's rent: (128 + size) * 3480 * 2
+        let size = request["params"][0].as_u64().unwrap();
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": (128 + size) * 6960 }))
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getMinimumBalanceForRentExemption", json!({ "dataSize": 0 })).await;
+    assert_eq!(response["result"]["lamports"], 890880);
+
+    for data_size in [json!(-1), json!(10 * 1024 * 1024 + 1), json!("165")] {
+        let response = call_tool(
+            state.clone(),
+            "getMinimumBalanceForRentExemption",
+            json!({ "dataSize": data_size }),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], -32602, "{data_size} accepted");
+        assert_eq!(response["error"]["data"]["parameter"], "dataSize");
+    }
+}
+
+#[tokio::test]
+async fn test_supply_and_largest_accounts_forward_options() {
+    use axum::Json;
+
+    let seen_params = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorder = seen_params.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let recorder = recorder.clone();
+        async move {
+            recorder.lock().unwrap().push(request["params"].clone());
+            let value = match request["method"].as_str() {
+                Some("getSupply") => json!({
+                    "total": 1016000,
+                    "circulating": 16000,
+                    "nonCirculating": 1000000,
+                    "nonCirculatingAccounts": []
+                }),
+                Some("getLargestAccounts") => json!([{ "address": "11111111111111111111111111111111", "lamports": 999974 }]),
+                _ => Value::Null,
+            };
+            let result = json!({ "context": { "slot": 1 }, "value": value });
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getSupply", json!({ "commitment": "finalized" })).await;
+    assert_eq!(response["result"]["supply"]["value"]["circulating"], 16000);
+    assert_eq!(seen_params.lock().unwrap().pop().unwrap()[0]["commitment"], "finalized");
+
+    let response = call_tool(state.clone(), "getLargestAccounts", json!({ "filter": "nonCirculating" })).await;
+    assert_eq!(response["result"]["accounts"]["value"][0]["lamports"], 999974);
+    assert_eq!(seen_params.lock().unwrap().pop().unwrap()[0]["filter"], "nonCirculating");
+
+    let response = call_tool(state, "getLargestAccounts", json!({ "filter": "staked" })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "filter");
+}
+
+#[tokio::test]
+async fn test_program_accounts_parsed_flags_unparsed_accounts() {
+    use axum::Json;
+
+    let seen_params = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorder = seen_params.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let recorder = recorder.clone();
+        async move {
+            recorder.lock().unwrap().push(request["params"].clone());
+            let account = |data: Value| {
+                json!({
+                    "lamports": 2039280,
+                    "data": data,
+                    "owner": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                    "executable": false,
+                    "rentEpoch": 0,
+                    "space": 165
+                })
+            };
+            let result = json!([
+                {
+                    "pubkey": "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T",
+                    "account": account(json!({
+                        "program": "spl-token",
+                        "parsed": { "type": "account", "info": { "tokenAmount": { "amount": "5" } } },
+                        "space": 165
+                    }))
+                },
+                {
+                    "pubkey": "11111111111111111111111111111111",
+                    "account": account(json!(["AAAA", "base64"]))
+                }
+            ]);
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(
+        state.clone(),
+        "getProgramAccountsParsed",
+        json!({
+            "programId": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "filters": [{ "dataSize": 165 }]
+        }),
+    )
+    .await;
+    let result = &response["result"];
+    assert_eq!(result["unparsedAccounts"], 1);
+    assert_eq!(result["accounts"][0]["account"]["data"]["program"], "spl-token");
+    assert!(result["accounts"][0].get("warning").is_none());
+    assert_eq!(result["accounts"][1]["account"]["data"][1], "base64");
+    assert!(result["accounts"][1]["warning"].is_string());
+
+    let params = seen_params.lock().unwrap().pop().unwrap();
+    assert_eq!(params[1]["encoding"], "jsonParsed");
+    assert_eq!(params[1]["filters"][0]["dataSize"], 165);
+
+    let response = call_tool(
+        state,
+        "getProgramAccountsParsed",
+        json!({ "programId": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "filters": [{ "bogus": 1 }] }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "filters");
+}
+
+#[tokio::test]
+async fn test_block_time_and_commitment_validate_slot() {
+    use axum::Json;
+
+    let rpc_url = spawn_mock_rpc(|Json(request): Json<Value>| async move {
+        let slot = request["params"][0].as_u64().unwrap();
+        let result = match request["method"].as_str() {
+            Some("getBlockTime") if slot == 100 => json!(1627852800),
+            Some("getBlockCommitment") => json!({ "commitment": null, "totalStake": 42 }),
+            _ => Value::Null,
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getBlockTime", json!({ "slot": 100 })).await;
+    assert_eq!(response["result"]["timestamp"], 1627852800);
+
+    let response = call_tool(state.clone(), "getBlockTime", json!({ "slot": 101 })).await;
+    assert_eq!(response["result"]["timestamp"], Value::Null);
+
+    let response = call_tool(state.clone(), "getBlockCommitment", json!({ "slot": 100 })).await;
+    assert_eq!(response["result"]["totalStake"], 42);
+
+    for tool in ["getBlockTime", "getBlockCommitment"] {
+        for arguments in [json!({ "slot": -1 }), json!({})] {
+            let response = call_tool(state.clone(), tool, arguments).await;
+            assert_eq!(response["error"]["code"], -32602);
+            assert_eq!(response["error"]["data"]["parameter"], "slot");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_rpc_timeout_bounds_slow_upstream() {
+    use axum::Json;
+
+    let rpc_url = spawn_mock_rpc(|Json(request): Json<Value>| async move {
+        tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": 1 }))
+    })
+    .await;
+    let state = test_state(|config| {
+        config.rpc_urls = vec![rpc_url];
+        config.rpc_timeout_seconds = 1;
+    });
+
+    let started = std::time::Instant::now();
+    let response = call_tool(state, "getSlot", json!({})).await;
+    assert_eq!(response["error"]["code"], -32603);
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_epoch_schedule_and_stake_minimum_delegation() {
+    use axum::Json;
+
+    let seen_params = Arc::new(std::sync::Mutex::new(Value::Null));
+    let recorder = seen_params.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let recorder = recorder.clone();
+        async move {
+            *recorder.lock().unwrap() = request["params"].clone();
+            let result = match request["method"].as_str() {
+                Some("getEpochSchedule") => json!({
+                    "slotsPerEpoch": 432000,
+                    "leaderScheduleSlotOffset": 432000,
+                    "warmup": false,
+                    "firstNormalEpoch": 0,
+                    "firstNormalSlot": 0
+                }),
+                Some("getStakeMinimumDelegation") => json!({ "context": { "slot": 1 }, "value": 1000000000u64 }),
+                _ => Value::Null,
+            };
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getEpochSchedule", json!({})).await;
+    assert_eq!(response["result"]["schedule"]["slotsPerEpoch"], 432000);
+
+    let response = call_tool(state, "getStakeMinimumDelegation", json!({ "commitment": "processed" })).await;
+    assert_eq!(response["result"]["minimum"], 1000000000u64);
+    assert_eq!(seen_params.lock().unwrap()[0]["commitment"], "processed");
+}
+
+#[tokio::test]
+async fn test_identity_and_inflation_tools() {
+    use axum::Json;
+
+    let identity = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let seen_params = Arc::new(std::sync::Mutex::new(Value::Null));
+    let (node, recorder) = (identity.clone(), seen_params.clone());
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let (node, recorder) = (node.clone(), recorder.clone());
+        async move {
+            *recorder.lock().unwrap() = request["params"].clone();
+            let result = match request["method"].as_str() {
+                Some("getIdentity") => json!({ "identity": node }),
+                Some("getInflationGovernor") => json!({
+                    "initial": 0.08,
+                    "terminal": 0.015,
+                    "taper": 0.15,
+                    "foundation": 0.05,
+                    "foundationTerm": 7.0
+                }),
+                Some("getInflationRate") => json!({ "total": 0.149, "validator": 0.148, "foundation": 0.001, "epoch": 100 }),
+                _ => Value::Null,
+            };
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getIdentity", json!({})).await;
+    assert_eq!(response["result"]["identity"], identity);
+
+    let response = call_tool(state.clone(), "getInflationGovernor", json!({ "commitment": "finalized" })).await;
+    assert_eq!(response["result"]["governor"]["terminal"], 0.015);
+    assert_eq!(seen_params.lock().unwrap()[0]["commitment"], "finalized");
+
+    let response = call_tool(state, "getInflationRate", json!({})).await;
+    assert_eq!(response["result"]["inflation_rate"]["epoch"], 100);
+}
+
+/// Builds the smallest arguments that satisfy a tool schema's required properties
+fn minimal_arguments(schema: &Value) -> Value {
+    let mut arguments = serde_json::Map::new();
+    for field in schema["required"].as_array().into_iter().flatten() {
+        let field = field.as_str().unwrap();
+        let property = &schema["properties"][field];
+        let value = match (property["enum"].get(0), property["type"].as_str()) {
+            (Some(first), _) => first.clone(),
+            (None, Some("integer" | "number")) => json!(1),
+            (None, Some("boolean")) => json!(false),
+            (None, Some("array")) => json!([]),
+            (None, Some("object")) => json!({}),
+            _ => json!("11111111111111111111111111111111"),
+        };
+        arguments.insert(field.to_string(), value);
+    }
+    Value::Object(arguments)
+}
+
+#[tokio::test]
+async fn test_every_listed_tool_is_dispatched() {
+    use axum::Json;
+    use solana_mcp_server::tools::handle_tools_list;
+
+    // Every upstream call fails fast; only the dispatch outcome matters here
+    let rpc_url = spawn_mock_rpc(|Json(request): Json<Value>| async move {
+        Json(json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "error": { "code": -32000, "message": "mock upstream" }
+        }))
+    })
+    .await;
+    // Network management tools fetch the public network list, so leave them unlisted
+    let state = test_state(|config| {
+        config.rpc_urls = vec![rpc_url];
+        config.allow_network_mutation = false;
+    });
+
+    let listed = handle_tools_list(Some(json!(1)), &*state.read().await).await.unwrap();
+    let listed = serde_json::to_value(listed).unwrap();
+    let tools = listed["result"]["tools"].as_array().unwrap();
+    assert!(!tools.is_empty());
+
+    let mut undispatched = Vec::new();
+    for tool in tools {
+        let name = tool["name"].as_str().unwrap();
+        let arguments = minimal_arguments(&tool["inputSchema"]);
+        // Upstream failures may surface as Err, which still means the tool was dispatched
+        let Ok(response) = handle_tools_call(
+            Some(json!({ "name": name, "arguments": arguments })),
+            Some(json!(1)),
+            state.clone(),
+        )
+        .await
+        else {
+            continue;
+        };
+        let response = serde_json::to_value(response).unwrap();
+        let message = response["error"]["message"].as_str().unwrap_or_default();
+        if message.starts_with("Tool not found") {
+            undispatched.push(name.to_string());
+        }
+    }
+    assert!(undispatched.is_empty(), "advertised but not dispatched: {undispatched:?}");
+}
+
+#[tokio::test]
+async fn test_send_transaction_and_genesis_hash_render_base58() {
+    use axum::Json;
+    use base64::Engine;
+    use solana_sdk::{
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        pubkey::Pubkey,
+        signature::Signature,
+        transaction::Transaction,
+    };
+
+    let genesis = Hash::new_unique();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| async move {
+        let result = match request["method"].as_str() {
+            // Unsigned transactions carry the default signature
+            Some("sendTransaction") => json!(Signature::default().to_string()),
+            Some("getGenesisHash") => json!(genesis.to_string()),
+            _ => Value::Null,
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let payer = Pubkey::new_unique();
+    let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![AccountMeta::new(payer, true)]);
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+    let encoded = base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap());
+
+    let response = call_tool(state.clone(), "sendTransaction", json!({ "transaction": encoded })).await;
+    assert_eq!(response["result"]["signature"], Signature::default().to_string());
+
+    let response = call_tool(state.clone(), "sendTransaction", json!({ "transaction": "AQID" })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "transaction");
+
+    let response = call_tool(state, "getGenesisHash", json!({})).await;
+    assert_eq!(response["result"]["hash"], genesis.to_string());
+}
+
+#[tokio::test]
+async fn test_estimate_compute_units_adds_margin_or_returns_logs() {
+    use axum::Json;
+    use base64::Engine;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        pubkey::Pubkey,
+        transaction::Transaction,
+    };
+
+    // Transactions whose program has no accounts fail in simulation, the rest consume 12,345 units
+    let rpc_url = spawn_mock_rpc(|Json(request): Json<Value>| async move {
+        let params = &request["params"];
+        assert_eq!(params[1]["sigVerify"], false);
+        assert_eq!(params[1]["replaceRecentBlockhash"], true);
+        let wire = base64::engine::general_purpose::STANDARD.decode(params[0].as_str().unwrap()).unwrap();
+        let transaction: Transaction = bincode::deserialize(&wire).unwrap();
+        let value = match transaction.message.instructions[0].accounts.is_empty() {
+            false => json!({ "err": null, "logs": ["Program log: ok"], "unitsConsumed": 12_345 }),
+            true => json!({
+                "err": { "InstructionError": [0, { "Custom": 1 }] },
+                "logs": ["Program log: insufficient funds"],
+                "unitsConsumed": 800
+            }),
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": { "context": { "slot": 1 }, "value": value } }))
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let payer = Pubkey::new_unique();
+    let encode = |accounts: Vec<AccountMeta>| {
+        let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], accounts);
+        let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+        base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap())
+    };
+    let succeeding = encode(vec![AccountMeta::new(payer, true)]);
+
+    let response = call_tool(state.clone(), "estimateComputeUnits", json!({ "transaction": succeeding })).await;
+    let result = &response["result"];
+    assert_eq!(result["unitsConsumed"], 12_345);
+    assert_eq!(result["recommendedComputeUnitLimit"], 13_580);
+    assert_eq!(result["marginPercent"], 10);
+
+    let arguments = json!({ "transaction": succeeding, "marginPercent": 50 });
+    let response = call_tool(state.clone(), "estimateComputeUnits", arguments).await;
+    assert_eq!(response["result"]["recommendedComputeUnitLimit"], 18_518);
+
+    let arguments = json!({ "transaction": succeeding, "marginPercent": 5_000 });
+    let response = call_tool(state.clone(), "estimateComputeUnits", arguments).await;
+    assert_eq!(response["error"]["data"]["parameter"], "marginPercent");
+
+    let response = call_tool(state, "estimateComputeUnits", json!({ "transaction": encode(Vec::new()) })).await;
+    let result = &response["result"];
+    assert_eq!(result["err"]["InstructionError"][1]["Custom"], 1);
+    assert_eq!(result["logs"], json!(["Program log: insufficient funds"]));
+    assert_eq!(result["unitsConsumed"], 800);
+    assert!(result.get("recommendedComputeUnitLimit").is_none());
+}
+
+#[tokio::test]
+async fn test_recent_activity_summarizes_oldest_first() {
+    use axum::Json;
+    use base64::Engine;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        pubkey::Pubkey,
+        signature::Signature,
+        transaction::Transaction,
+    };
+
+    let payer = Pubkey::new_unique();
+    let program = Pubkey::new_unique();
+    let inner_program = Pubkey::new_unique();
+    let instruction = Instruction::new_with_bytes(
+        program,
+        &[1],
+        vec![AccountMeta::new(payer, true), AccountMeta::new_readonly(inner_program, false)],
+    );
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+    let inner_index = transaction.message.account_keys.iter().position(|key| *key == inner_program).unwrap();
+    let encoded = base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap());
+
+    let landed = Signature::from([1u8; 64]).to_string();
+    let failed = Signature::from([2u8; 64]).to_string();
+    let (landed_sig, failed_sig) = (landed.clone(), failed.clone());
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let (landed, failed, encoded) = (landed_sig.clone(), failed_sig.clone(), encoded.clone());
+        async move {
+            let result = match request["method"].as_str() {
+                // Newest first, as the node lists them
+                Some("getSignaturesForAddress") => json!([
+                    { "signature": landed, "slot": 20, "err": null, "memo": null, "blockTime": 1700000020 },
+                    { "signature": failed, "slot": 10, "err": { "InstructionError": [0, "InvalidArgument"] }, "memo": null, "blockTime": 1700000010 }
+                ]),
+                Some("getTransaction") if request["params"][0] == landed.as_str() => json!({
+                    "slot": 20,
+                    "blockTime": 1700000020,
+                    "transaction": [encoded, "base64"],
+                    "meta": {
+                        "err": null,
+                        "status": { "Ok": null },
+                        "fee": 5000,
+                        "preBalances": [],
+                        "postBalances": [],
+                        "innerInstructions": [{
+                            "index": 0,
+                            "instructions": [{ "programIdIndex": inner_index, "accounts": [], "data": "", "stackHeight": 2 }]
+                        }]
+                    }
+                }),
+                _ => Value::Null,
+            };
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(
+        state.clone(),
+        "getRecentActivity",
+        json!({ "address": payer.to_string(), "limit": 1000 }),
+    )
+    .await;
+    let activity = response["result"]["transactions"].as_array().unwrap();
+    assert_eq!(activity.len(), 2);
+
+    // A transaction that cannot be fetched keeps what the signature listing knows
+    assert_eq!(activity[0]["signature"], failed);
+    assert_eq!(activity[0]["success"], false);
+    assert!(activity[0]["fee"].is_null());
+    assert_eq!(activity[0]["programs"], json!([]));
+
+    assert_eq!(activity[1]["signature"], landed);
+    assert_eq!(activity[1]["slot"], 20);
+    assert_eq!(activity[1]["success"], true);
+    assert_eq!(activity[1]["fee"], 5000);
+    assert_eq!(activity[1]["programs"], json!([program.to_string(), inner_program.to_string()]));
+
+    let response = call_tool(state, "getRecentActivity", json!({ "address": payer.to_string(), "commitment": "processed" })).await;
+    assert_eq!(response["error"]["code"], -32602);
+}
+
+#[tokio::test]
+async fn test_signature_statuses_chunk_in_order_and_search_history_for_missing_only() {
+    use axum::Json;
+    use std::collections::HashMap;
+
+    let signatures: Vec<String> = (0..300u16)
+        .map(|index| {
+            let mut bytes = [1u8; 64];
+            bytes[..2].copy_from_slice(&index.to_le_bytes());
+            solana_sdk::signature::Signature::from(bytes).to_string()
+        })
+        .collect();
+    let slots: HashMap<String, u64> = signatures.iter().cloned().zip(0..).collect();
+    let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorder = calls.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let (slots, recorder) = (slots.clone(), recorder.clone());
+        async move {
+            let requested = request["params"][0].as_array().unwrap().clone();
+            let history = request["params"][1]["searchTransactionHistory"] == true;
+            recorder.lock().unwrap().push((requested.len(), history));
+            // Odd slots have aged out of the recent status cache
+            let value: Vec<Value> = requested
+                .iter()
+                .map(|signature| {
+                    let slot = slots[signature.as_str().unwrap()];
+                    if history || slot % 2 == 0 {
+                        json!({ "slot": slot, "confirmations": null, "err": null, "status": { "Ok": null }, "confirmationStatus": "finalized" })
+                    } else {
+                        Value::Null
+                    }
+                })
+                .collect();
+            let result = json!({ "context": { "slot": if history { 90 } else { 100 } }, "value": value });
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getSignatureStatuses", json!({ "signatures": signatures })).await;
+    let statuses = response["result"]["value"].as_array().unwrap();
+    assert_eq!(statuses.len(), 300);
+    assert_eq!(statuses[256]["slot"], 256);
+    assert_eq!(statuses[257], Value::Null);
+    assert_eq!(response["result"]["context"]["slot"], 100);
+    let mut made: Vec<_> = calls.lock().unwrap().drain(..).collect();
+    made.sort();
+    assert_eq!(made, vec![(44, false), (256, false)]);
+
+    let response = call_tool(
+        state.clone(),
+        "getSignatureStatuses",
+        json!({ "signatures": signatures, "searchTransactionHistory": true }),
+    )
+    .await;
+    let statuses = response["result"]["value"].as_array().unwrap();
+    assert!(statuses.iter().enumerate().all(|(index, status)| status["slot"] == index as u64));
+    assert_eq!(response["result"]["context"]["slot"], 90);
+    let mut made: Vec<_> = calls.lock().unwrap().drain(..).collect();
+    made.sort();
+    assert_eq!(made, vec![(22, true), (44, false), (128, true), (256, false)]);
+}
+
+#[tokio::test]
+async fn test_include_timing_attaches_meta_to_results() {
+    use axum::Json;
+
+    let url = spawn_mock_rpc(|Json(request): Json<Value>| async move {
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": 890880 }))
+    })
+    .await;
+
+    let state = test_state(|config| config.rpc_urls = vec![url.clone()]);
+    let response = call_tool(state, "getMinimumBalanceForRentExemption", json!({ "dataSize": 0 })).await;
+    assert!(response["result"].get("_meta").is_none());
+
+    let state = test_state(|config| {
+        config.rpc_urls = vec![url.clone()];
+        config.include_timing = true;
+    });
+    let response = call_tool(state, "getMinimumBalanceForRentExemption", json!({ "dataSize": 0 })).await;
+    assert_eq!(response["result"]["lamports"], 890880);
+    assert!(response["result"]["_meta"]["durationMs"].is_u64());
+    assert_eq!(response["result"]["_meta"]["rpcUrl"], url);
+}
+
+#[tokio::test]
+async fn test_get_transaction_defaults_max_version_and_explains_newer_versions() {
+    use axum::Json;
+
+    let seen_config = Arc::new(std::sync::Mutex::new(Value::Null));
+    let recorder = seen_config.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let recorder = recorder.clone();
+        async move {
+            *recorder.lock().unwrap() = request["params"][1].clone();
+            Json(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": {
+                    "code": -32015,
+                    "message": "Transaction version (1) is not supported by the requesting client. Please try the request again with the following configuration parameter: \"maxSupportedTransactionVersion\": 1"
+                }
+            }))
+        }
+    })
+    .await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+    let signature = solana_sdk::signature::Signature::default().to_string();
+
+    for tool in ["getTransaction", "getTransactionWithConfig"] {
+        let response = call_tool(state.clone(), tool, json!({ "signature": signature })).await;
+        assert_eq!(seen_config.lock().unwrap()["maxSupportedTransactionVersion"], 0, "{tool}");
+        assert_eq!(response["error"]["code"], -32602, "{tool}");
+        assert_eq!(response["error"]["data"]["parameter"], "maxSupportedTransactionVersion");
+        let message = response["error"]["message"].as_str().unwrap();
+        assert!(message.contains("version 1"), "{message}");
+
+        call_tool(state.clone(), tool, json!({ "signature": signature, "maxSupportedTransactionVersion": 1 })).await;
+        assert_eq!(seen_config.lock().unwrap()["maxSupportedTransactionVersion"], 1, "{tool}");
+    }
+}
+
+#[tokio::test]
+async fn test_server_config_is_sanitized() {
+    let state = test_state(|config| {
+        config.rpc_urls = vec!["https://rpc.example.com/v1/secret-api-key".to_string()];
+        config.commitment = "finalized".to_string();
+        config.auth.bearer_token = Some("super-secret".to_string());
+        config.sbpf_deploy.payer_keypair_path = Some("/home/deployer/id.json".to_string());
+    });
+
+    let response = call_tool(state, "getServerConfig", json!({})).await;
+    let config = &response["result"];
+    assert_eq!(config["commitment"], "finalized");
+    assert_eq!(config["rpc_urls"][0], "https://rpc.example.com/[PATH_REDACTED]");
+    assert_eq!(config["auth"]["bearer_token"], "[REDACTED]");
+    assert_eq!(config["sbpf_deploy"]["payer_keypair_path"], "[REDACTED]");
+    assert!(config["timeouts"].is_object());
+    assert!(!response.to_string().contains("secret-api-key"));
+    assert!(!response.to_string().contains("super-secret"));
+}
+
+#[tokio::test]
+async fn test_rpc_failure_carries_structured_error_data() {
+    let state = test_state(|config| config.rpc_urls = vec!["http://127.0.0.1:1".to_string()]);
+    let pubkey = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+
+    let response = call_tool(state, "getBalance", json!({ "pubkey": pubkey })).await;
+    assert_eq!(response["error"]["code"], -32603);
+    let data = &response["error"]["data"];
+    assert_eq!(data["errorType"], "network");
+    assert_eq!(data["method"], "getBalance");
+    assert!(data["requestId"].as_str().is_some_and(|id| uuid::Uuid::parse_str(id).is_ok()));
+}
+
+/// Start a JSON-RPC server where `owner` holds Token accounts for two 6-decimal mints
+///
+/// `mints[0]` is held across two accounts (5 and 7); `mints[1]` only by an empty account.
+async fn mock_token_holdings_rpc(owner: String, mints: [solana_sdk::pubkey::Pubkey; 2]) -> String {
+    use axum::Json;
+    use base64::Engine;
+    use spl_token::solana_program::program_pack::Pack;
+
+    let mut mint_data = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint { decimals: 6, is_initialized: true, supply: 12, ..Default::default() }
+        .pack_into_slice(&mut mint_data);
+    let mint_data = base64::engine::general_purpose::STANDARD.encode(mint_data);
+
+    spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let owner = owner.clone();
+        let mint_data = mint_data.clone();
+        async move {
+            let token_account = |mint: &solana_sdk::pubkey::Pubkey, amount: u64| {
+                json!({
+                    "pubkey": solana_sdk::pubkey::Pubkey::new_unique().to_string(),
+                    "account": {
+                        "lamports": 2_039_280,
+                        "data": {
+                            "program": "spl-token",
+                            "parsed": {
+                                "type": "account",
+                                "info": {
+                                    "mint": mint.to_string(),
+                                    "owner": owner,
+                                    "tokenAmount": { "amount": amount.to_string(), "decimals": 6 }
+                                }
+                            },
+                            "space": 165
+                        },
+                        "owner": spl_token::id().to_string(),
+                        "executable": false,
+                        "rentEpoch": 0,
+                        "space": 165
+                    }
+                })
+            };
+            let params = &request["params"];
+            let value = match request["method"].as_str() {
+                Some("getTokenAccountsByOwner") if params[1]["programId"] == spl_token::id().to_string() => json!([
+                    token_account(&mints[0], 5),
+                    token_account(&mints[1], 0),
+                    token_account(&mints[0], 7),
+                ]),
+                Some("getTokenAccountsByOwner") => json!([]),
+                Some("getMultipleAccounts") => json!(params[0]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|_| json!({
+                        "lamports": 1_461_600,
+                        "data": [mint_data, "base64"],
+                        "owner": spl_token::id().to_string(),
+                        "executable": false,
+                        "rentEpoch": 0,
+                        "space": 82
+                    }))
+                    .collect::<Vec<_>>()),
+                _ => Value::Null,
+            };
+            Json(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": { "context": { "slot": 1 }, "value": value }
+            }))
+        }
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_wallet_token_holdings_sum_per_mint() {
+    let owner = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+    let mut mints = [solana_sdk::pubkey::Pubkey::new_unique(), solana_sdk::pubkey::Pubkey::new_unique()];
+    mints.sort();
+    let rpc_url = mock_token_holdings_rpc(owner.clone(), mints).await;
+    let state = test_state(|config| config.rpc_urls = vec![rpc_url]);
+
+    let response = call_tool(state.clone(), "getWalletTokenHoldings", json!({ "owner": owner })).await;
+    assert!(response.get("error").is_none(), "unexpected error: {response}");
+    let holdings = &response["result"]["holdings"];
+    assert_eq!(
+        holdings,
+        &json!([{ "mint": mints[0].to_string(), "uiAmount": 0.000012, "rawAmount": "12", "decimals": 6 }])
+    );
+
+    let response = call_tool(state, "getWalletTokenHoldings", json!({ "owner": owner, "includeZero": true })).await;
+    let holdings = response["result"]["holdings"].as_array().unwrap();
+    assert_eq!(holdings.len(), 2);
+    assert_eq!(holdings[1]["mint"], mints[1].to_string());
+    assert_eq!(holdings[1]["rawAmount"], "0");
+}
+
+#[tokio::test]
+async fn test_resources_read_serves_bundled_docs() {
+    use solana_mcp_server::tools::handle_request;
+
+    let state = test_state(|_| {});
+    state.write().await.initialized = true;
+    let request = |method: &str, params: Value| {
+        json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string()
+    };
+
+    let listed = handle_request(&request("resources/list", json!({})), state.clone()).await.unwrap();
+    let listed = serde_json::to_value(listed).unwrap();
+    let uris: Vec<&str> = listed["result"]["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|resource| resource["uri"].as_str().unwrap())
+        .collect();
+    assert!(uris.contains(&"docs://transactions"), "{uris:?}");
+
+    let read = handle_request(&request("resources/read", json!({ "uri": "docs://transactions" })), state.clone())
+        .await
+        .unwrap();
+    let read = serde_json::to_value(read).unwrap();
+    let contents = &read["result"]["contents"][0];
+    assert_eq!(contents["uri"], "docs://transactions");
+    assert_eq!(contents["mimeType"], "text/markdown");
+    assert!(!contents["text"].as_str().unwrap().is_empty());
+
+    let missing = handle_request(&request("resources/read", json!({ "uri": "docs://nope" })), state.clone())
+        .await
+        .unwrap();
+    let missing = serde_json::to_value(missing).unwrap();
+    assert_eq!(missing["error"]["code"], -32002);
+    assert_eq!(missing["error"]["data"]["uri"], "docs://nope");
+
+    let invalid = handle_request(&request("resources/read", json!({})), state).await.unwrap();
+    assert_eq!(serde_json::to_value(invalid).unwrap()["error"]["code"], -32602);
+}
+
+#[tokio::test]
+async fn test_resources_read_renders_rpc_method_docs() {
+    use solana_mcp_server::tools::handle_request;
+
+    let state = test_state(|_| {});
+    state.write().await.initialized = true;
+    let request = |method: &str, params: Value| {
+        json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string()
+    };
+
+    let listed = handle_request(&request("resources/list", json!({})), state.clone()).await.unwrap();
+    let listed = serde_json::to_value(listed).unwrap();
+    let resources = listed["result"]["resources"].as_array().unwrap();
+    let account_info = resources
+        .iter()
+        .find(|resource| resource["uri"] == "mcp://docs/rpc/getAccountInfo")
+        .expect("getAccountInfo docs should be listed");
+    assert_eq!(account_info["mimeType"], "text/markdown");
+
+    let read = handle_request(
+        &request("resources/read", json!({ "uri": "mcp://docs/rpc/getAccountInfo" })),
+        state.clone(),
+    )
+    .await
+    .unwrap();
+    let read = serde_json::to_value(read).unwrap();
+    let text = read["result"]["contents"][0]["text"].as_str().unwrap();
+    assert!(text.starts_with("# getAccountInfo"), "{text}");
+    assert!(text.contains("- `pubkey` (string): "), "{text}");
+    assert!(text.contains("- `encoding` (string, optional): "), "{text}");
+    assert!(text.contains("\"method\": \"getAccountInfo\""), "examples should be rendered: {text}");
+
+    let missing = handle_request(&request("resources/read", json!({ "uri": "mcp://docs/rpc/getNothing" })), state)
+        .await
+        .unwrap();
+    assert_eq!(serde_json::to_value(missing).unwrap()["error"]["code"], -32002);
+}
+
+#[tokio::test]
+async fn test_tools_list_descriptions_enriched_from_method_docs() {
+    use solana_mcp_server::tools::handle_tools_list;
+
+    let state = test_state(|_| {});
+    let listed = handle_tools_list(Some(json!(1)), &*state.read().await).await.unwrap();
+    let listed = serde_json::to_value(listed).unwrap();
+    let description = |name: &str| {
+        listed["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|tool| tool["name"] == name)
+            .and_then(|tool| tool["description"].as_str())
+            .unwrap()
+            .to_string()
+    };
+
+    let balance = description("getBalance");
+    assert!(balance.starts_with("Returns the balance of the account of provided Pubkey"), "{balance}");
+    assert!(balance.contains("\n- `pubkey` (string): "), "{balance}");
+    assert!(balance.contains("Underlying Solana RPC request"), "{balance}");
+    assert!(balance.contains("\"method\": \"getBalance\""), "{balance}");
+
+    // Tools without method docs keep their one-liner
+    assert_eq!(
+        description("getTokenUiBalance"),
+        "Returns a token account balance with mint decimals and human-readable uiAmount"
+    );
+}
+
+/// Serves getVersion as `version` and records every other method called
+async fn mock_versioned_rpc(version: &'static str) -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+    use axum::Json;
+
+    let methods = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorded = methods.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let recorded = recorded.clone();
+        async move {
+            let method = request["method"].as_str().unwrap_or_default().to_string();
+            let result = match method.as_str() {
+                "getVersion" => json!({ "solana-core": version, "feature-set": 4215500110u32 }),
+                _ => json!({ "context": { "slot": 1 }, "value": true }),
+            };
+            recorded.lock().unwrap().push(method);
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }
+    })
+    .await;
+    (rpc_url, methods)
+}
+
+#[tokio::test]
+async fn test_tools_call_rejects_methods_unsupported_by_node_version() {
+    let (old_url, old_methods) = mock_versioned_rpc("1.8.16").await;
+    let state = test_state(|config| {
+        config.rpc_url = old_url;
+        config.rpc_urls.clear();
+    });
+    let blockhash = json!({ "blockhash": "4sGjMW1sUnHzSxGspuhpqLDx6wiyjNtZAMdL4VZHirAn" });
+
+    let response = call_tool(state.clone(), "isBlockhashValid", blockhash.clone()).await;
+    assert_eq!(response["error"]["code"], -32601);
+    let message = response["error"]["message"].as_str().unwrap();
+    assert!(message.starts_with("Method not supported by configured node"), "{message}");
+    assert_eq!(response["error"]["data"]["method"], "isBlockhashValid");
+    assert_eq!(response["error"]["data"]["nodeVersion"], "1.8.16");
+
+    // The probe is cached and the rejected method never went upstream
+    call_tool(state.clone(), "isBlockhashValid", blockhash.clone()).await;
+    assert_eq!(*old_methods.lock().unwrap(), vec!["getVersion".to_string()]);
+
+    // Pointing at a newer node re-runs the probe
+    let (new_url, new_methods) = mock_versioned_rpc("2.1.11").await;
+    {
+        let mut state_guard = state.write().await;
+        let mut config = state_guard.config.clone();
+        config.rpc_url = new_url;
+        state_guard.update_config(config);
+    }
+    let response = call_tool(state.clone(), "isBlockhashValid", blockhash).await;
+    assert!(response.get("error").is_none(), "{response}");
+    assert_eq!(new_methods.lock().unwrap()[0], "getVersion");
+
+    let response = call_tool(state, "getRecentBlockhash", json!({})).await;
+    assert!(response["error"]["message"].as_str().unwrap().contains("removed in solana-core 2.0.0"));
+}
+
+#[tokio::test]
+async fn test_concurrent_gated_calls_share_one_version_probe() {
+    let (url, methods) = mock_versioned_rpc("1.8.16").await;
+    let state = test_state(|config| {
+        config.rpc_url = url;
+        config.rpc_urls.clear();
+    });
+    let blockhash = json!({ "blockhash": "4sGjMW1sUnHzSxGspuhpqLDx6wiyjNtZAMdL4VZHirAn" });
+
+    let calls = (0..8).map(|_| call_tool(state.clone(), "isBlockhashValid", blockhash.clone()));
+    for response in futures_util::future::join_all(calls).await {
+        assert_eq!(response["error"]["code"], -32601);
+    }
+    assert_eq!(*methods.lock().unwrap(), vec!["getVersion".to_string()]);
+}
+
+#[tokio::test]
+async fn test_enable_svm_network_is_idempotent() {
+    use solana_mcp_server::SvmNetwork;
+
+    let existing = SvmNetwork {
+        name: "Local".to_string(),
+        rpc_url: "https://rpc.local.example.com".to_string(),
+        enabled: true,
+        commitment: Some("processed".to_string()),
+    };
+    let state = test_state(|config| {
+        config.svm_networks.insert("local".to_string(), existing.clone());
+    });
+
+    // Identical settings short-circuit before the config is saved
+    let response = call_tool(
+        state.clone(),
+        "enableSvmNetwork",
+        json!({ "networkId": "local", "name": "Local", "rpcUrl": "https://rpc.local.example.com" }),
+    )
+    .await;
+    assert_eq!(response["result"]["success"], true);
+    assert_eq!(response["result"]["changed"], false);
+    assert!(response["result"]["message"].as_str().unwrap().contains("already enabled, no change"));
+    assert_eq!(state.read().await.config.svm_networks["local"], existing);
+}
+
+#[tokio::test]
+async fn test_disable_svm_network_save_failure_leaves_config_unchanged() {
+    use solana_mcp_server::SvmNetwork;
+
+    let network = |name: &str| SvmNetwork {
+        name: name.to_string(),
+        rpc_url: "https://rpc.local.example.com".to_string(),
+        enabled: true,
+        commitment: None,
+    };
+    // An entry that fails validation makes the save step reject the new config
+    let state = test_state(|config| {
+        config.svm_networks.insert("target".to_string(), network("Target"));
+        config.svm_networks.insert("other".to_string(), network("Other"));
+        config.svm_networks.insert("broken".to_string(), network(""));
+    });
+    let networks_before = state.read().await.config.svm_networks.clone();
+    let disk_before = std::fs::read("config.json").ok();
+
+    let response = call_tool(state.clone(), "disableSvmNetwork", json!({ "networkId": "target" })).await;
+    assert!(response["error"]["message"].as_str().unwrap().contains("Failed to save configuration"));
+
+    let state_guard = state.read().await;
+    assert_eq!(state_guard.config.svm_networks, networks_before);
+    assert!(state_guard.svm_clients.contains_key("target"));
+    assert_eq!(std::fs::read("config.json").ok(), disk_before);
+    assert!(!std::path::Path::new("config.json.tmp").exists());
+}
+
+#[tokio::test]
+async fn test_probe_network_methods_reports_missing_methods_and_caches() {
+    use axum::Json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // Rejects probe params like a real node, except for the methods it lacks
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let counter = counter.clone();
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let error = match request["method"].as_str() {
+                Some("getInflationReward" | "getInflationRate") => json!({ "code": -32601, "message": "Method not found" }),
+                _ => json!({ "code": -32602, "message": "Invalid params" }),
+            };
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "error": error }))
+        }
+    })
+    .await;
+    let state = test_state(|config| {
+        config.svm_networks.insert(
+            "no-inflation".to_string(),
+            solana_mcp_server::SvmNetwork {
+                name: "No Inflation".to_string(),
+                rpc_url,
+                enabled: true,
+                commitment: None,
+            },
+        );
+    });
+
+    let arguments = json!({ "networkId": "no-inflation" });
+    let response = call_tool(state.clone(), "probeNetworkMethods", arguments.clone()).await;
+    let report = &response["result"];
+    assert_eq!(report["cached"], false);
+    assert_eq!(report["unsupportedMethods"], json!(["getInflationRate", "getInflationReward"]));
+    assert_eq!(report["unknownMethods"], json!([]));
+    let unsupported = report["unsupportedTools"].as_array().unwrap();
+    assert!(unsupported.contains(&json!({ "name": "getInflationReward", "missingMethods": ["getInflationReward"] })));
+    let supported = report["supportedTools"].as_array().unwrap();
+    assert!(supported.contains(&json!("getBalance")));
+    assert!(!supported.contains(&json!("getInflationReward")));
+
+    // The second report comes from cache without touching the network
+    let probes = calls.load(Ordering::SeqCst);
+    let response = call_tool(state.clone(), "probeNetworkMethods", arguments).await;
+    assert_eq!(response["result"]["cached"], true);
+    assert_eq!(calls.load(Ordering::SeqCst), probes);
+
+    let response = call_tool(state, "probeNetworkMethods", json!({ "networkId": "missing" })).await;
+    assert_eq!(response["error"]["code"], -32602);
+}
+
+#[tokio::test]
+async fn test_tools_call_rejects_oversized_results() {
+    use solana_mcp_server::tools::RESPONSE_TOO_LARGE_CODE;
+
+    // getServerConfig is answered locally, so its size depends only on the config
+    let state = test_state(|config| config.max_response_bytes = 64);
+    let response = call_tool(state, "getServerConfig", json!({})).await;
+    assert_eq!(response["error"]["code"], RESPONSE_TOO_LARGE_CODE);
+    assert!(response["error"]["message"].as_str().unwrap().starts_with("Response too large"));
+    assert_eq!(response["error"]["data"]["tool"], "getServerConfig");
+    assert_eq!(response["error"]["data"]["maxBytes"], 64);
+    assert!(response["error"]["data"]["sizeBytes"].as_u64().unwrap() > 64);
+    assert!(response["error"]["data"]["hint"].is_string());
+
+    let state = test_state(|config| config.max_response_bytes = 0);
+    let response = call_tool(state, "getServerConfig", json!({})).await;
+    assert!(response.get("error").is_none());
+}
+
+#[tokio::test]
+async fn test_get_blocks_rejects_wide_ranges_unless_scanning() {
+    use axum::Json;
+
+    // Every even slot in the requested range holds a block
+    let ranges = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorded = ranges.clone();
+    let rpc_url = spawn_mock_rpc(move |Json(request): Json<Value>| {
+        let recorded = recorded.clone();
+        async move {
+            let (start, end) = (request["params"][0].as_u64().unwrap(), request["params"][1].as_u64().unwrap());
+            recorded.lock().unwrap().push((start, end));
+            let blocks: Vec<u64> = (start..=end).filter(|slot| slot % 2 == 0).collect();
+            Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": blocks }))
+        }
+    })
+    .await;
+
+    let state = test_state(|config| {
+        config.rpc_url = rpc_url;
+        config.rpc_urls.clear();
+        config.max_block_range_slots = 10;
+    });
+
+    let response = call_tool(state.clone(), "getBlocks", json!({ "startSlot": 0, "endSlot": 24 })).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert!(response["error"]["message"].as_str().unwrap().contains("getBlocksWithLimit"));
+    assert!(ranges.lock().unwrap().is_empty());
+
+    let response = call_tool(state, "getBlocks", json!({ "startSlot": 0, "endSlot": 24, "scan": true })).await;
+    let result = &response["result"];
+    let expected: Vec<u64> = (0..=24).step_by(2).collect();
+    assert_eq!(result["blocks"], json!(expected));
+    assert_eq!(result["requests"], 3);
+    assert_eq!(*ranges.lock().unwrap(), vec![(0, 9), (10, 19), (20, 24)]);
+}
+
+#[tokio::test]
+async fn test_decode_account_data_for_known_and_unknown_owners() {
+    use base64::Engine;
+    use solana_sdk::program_pack::Pack;
+
+    let encode = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+    let state = test_state(|_| {});
+    let token_program = spl_token::id().to_string();
+
+    let mut mint = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint { decimals: 6, is_initialized: true, supply: 1_000_000, ..Default::default() }.pack_into_slice(&mut mint);
+    let response = call_tool(state.clone(), "decodeAccountData", json!({ "data": encode(&mint), "ownerProgram": token_program })).await;
+    assert_eq!(response["result"]["program"], "spl-token");
+    assert_eq!(response["result"]["parsed"]["type"], "mint");
+    assert_eq!(response["result"]["parsed"]["info"]["decimals"], 6);
+    assert_eq!(response["result"]["parsed"]["info"]["supply"], "1000000");
+
+    let mut account = vec![0u8; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        amount: 2_500_000,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut account);
+    let arguments = json!({ "data": encode(&account), "ownerProgram": token_program });
+    let response = call_tool(state.clone(), "decodeAccountData", arguments.clone()).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["parameter"], "decimals");
+
+    let mut arguments = arguments;
+    arguments["decimals"] = json!(6);
+    let response = call_tool(state.clone(), "decodeAccountData", arguments).await;
+    assert_eq!(response["result"]["parsed"]["type"], "account");
+    assert_eq!(response["result"]["parsed"]["info"]["tokenAmount"]["uiAmountString"], "2.5");
+
+    // Unknown owners get the bytes back untouched
+    let owner = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+    let response = call_tool(state, "decodeAccountData", json!({ "data": encode(&[1, 2, 3]), "ownerProgram": owner })).await;
+    assert_eq!(response["result"]["program"], Value::Null);
+    assert_eq!(response["result"]["data"], encode(&[1, 2, 3]));
+    assert!(response["result"]["note"].as_str().unwrap().starts_with("No decoder"));
+}
+
+#[tokio::test]
+async fn test_compress_flag_encodes_results_above_threshold() {
+    use base64::Engine;
+
+    let engine = base64::engine::general_purpose::STANDARD;
+    let state = test_state(|config| config.compression_threshold_bytes = 1024);
+    let owner = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+    let large = json!({ "data": engine.encode([7u8; 4096]), "ownerProgram": owner, "compress": true });
+
+    let plain = call_tool(state.clone(), "decodeAccountData", json!({ "data": large["data"], "ownerProgram": owner })).await;
+    let response = call_tool(state.clone(), "decodeAccountData", large).await;
+    let result = &response["result"];
+    assert_eq!(result["encoding"], "base64+zstd");
+    let compressed = engine.decode(result["data"].as_str().unwrap()).unwrap();
+    assert!(compressed.len() < 1024);
+    let decompressed: Value = serde_json::from_slice(&zstd::decode_all(compressed.as_slice()).unwrap()).unwrap();
+    assert_eq!(decompressed, plain["result"]);
+
+    // Results under the threshold stay plain JSON
+    let small = json!({ "data": engine.encode([1, 2, 3]), "ownerProgram": owner, "compress": true });
+    let response = call_tool(state, "decodeAccountData", small).await;
+    assert_eq!(response["result"]["space"], 3);
+    assert!(response["result"].get("encoding").is_none());
+}
+
+#[tokio::test]
+async fn test_get_recent_activity_reports_progress_when_requested() {
+    use axum::Json;
+    use solana_mcp_server::transport::JsonRpcMessage;
+
+    // Two signatures whose transactions the node no longer has
+    let rpc_url = spawn_mock_rpc(|Json(request): Json<Value>| async move {
+        let result = match request["method"].as_str() {
+            Some("getSignaturesForAddress") => json!((1..=2u8)
+                .map(|n| json!({
+                    "signature": solana_sdk::signature::Signature::from([n; 64]).to_string(),
+                    "slot": n,
+                    "err": null,
+                    "memo": null,
+                    "blockTime": null,
+                    "confirmationStatus": "finalized"
+                }))
+                .collect::<Vec<_>>()),
+            _ => Value::Null,
+        };
+        Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+    })
+    .await;
+
+    let state = test_state(|config| {
+        config.rpc_url = rpc_url;
+        config.rpc_urls.clear();
+    });
+    let (sink, mut notifications) = tokio::sync::mpsc::unbounded_channel();
+    state.write().await.progress_sink = Some(sink);
+
+    let params = json!({
+        "name": "getRecentActivity",
+        "arguments": { "address": "Gh9ZwEmdLJ8DscKNTkTqPbNwLNNBjuSzaG9Vp2KGtKJr" },
+        "_meta": { "progressToken": 17 }
+    });
+    let response = handle_tools_call(Some(params), Some(json!(1)), state.clone()).await.unwrap();
+    let response = serde_json::to_value(response).unwrap();
+    assert_eq!(response["result"]["transactions"].as_array().unwrap().len(), 2);
+
+    let mut progress = Vec::new();
+    while let Ok(JsonRpcMessage::Notification(notification)) = notifications.try_recv() {
+        assert_eq!(notification.method, "notifications/progress");
+        let params = notification.params.unwrap();
+        assert_eq!(params["progressToken"], 17);
+        assert_eq!(params["total"], 2);
+        progress.push(params["progress"].as_u64().unwrap());
+    }
+    assert_eq!(progress, vec![0, 1, 2]);
+
+    // Without a token nothing is reported
+    call_tool(state, "getRecentActivity", json!({ "address": "Gh9ZwEmdLJ8DscKNTkTqPbNwLNNBjuSzaG9Vp2KGtKJr" })).await;
+    assert!(notifications.try_recv().is_err());
+}
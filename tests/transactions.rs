@@ -0,0 +1,98 @@
+/// Integration tests for transaction RPC helpers
+use solana_mcp_server::rpc::transactions::parse_compiled_instructions;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use std::str::FromStr;
+
+#[test]
+fn test_parse_compiled_instructions_decodes_known_programs() {
+    let from = Pubkey::new_unique();
+    let to = Pubkey::new_unique();
+    let memo_program = Pubkey::from_str("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr").unwrap();
+    let keys = vec![from, to, solana_sdk::system_program::id(), memo_program];
+
+    // SystemInstruction::Transfer is variant 2 followed by the lamports
+    let mut transfer_data = 2u32.to_le_bytes().to_vec();
+    transfer_data.extend_from_slice(&5_000u64.to_le_bytes());
+
+    let instructions = vec![
+        CompiledInstruction::new_from_raw_parts(2, transfer_data, vec![0, 1]),
+        CompiledInstruction::new_from_raw_parts(3, b"hello".to_vec(), vec![0]),
+    ];
+
+    let parsed = parse_compiled_instructions(&keys, &instructions);
+    assert_eq!(parsed[0]["program"], "system");
+    assert_eq!(parsed[0]["type"], "transfer");
+    assert_eq!(parsed[0]["info"]["lamports"], 5_000);
+    assert_eq!(parsed[0]["info"]["source"], from.to_string());
+
+    assert_eq!(parsed[1]["program"], "spl-memo");
+    assert_eq!(parsed[1]["info"], "hello");
+}
+
+#[test]
+fn test_parse_compiled_instructions_passes_through_unknown_programs() {
+    let payer = Pubkey::new_unique();
+    let program = Pubkey::new_unique();
+    let keys = vec![payer, solana_sdk::system_program::id(), program];
+
+    let instructions = vec![
+        CompiledInstruction::new_from_raw_parts(2, vec![1, 2, 3], vec![0]),
+        // Truncated system instruction data fails to decode but must not abort
+        CompiledInstruction::new_from_raw_parts(1, vec![2], vec![0]),
+    ];
+
+    let parsed = parse_compiled_instructions(&keys, &instructions);
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0]["program"], "unknown");
+    assert_eq!(parsed[0]["programId"], program.to_string());
+    assert_eq!(parsed[0]["data"], bs58::encode([1, 2, 3]).into_string());
+    assert_eq!(parsed[0]["accounts"][0], payer.to_string());
+    assert!(parsed[0].get("parseError").is_none());
+
+    assert_eq!(parsed[1]["program"], "unknown");
+    assert!(parsed[1]["parseError"].is_string());
+}
+
+#[test]
+fn test_parse_transaction_instructions_reshapes_json_parsed() {
+    use solana_mcp_server::rpc::transactions::parse_transaction_instructions;
+    use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+
+    let payer = Pubkey::new_unique().to_string();
+    let program = Pubkey::new_unique().to_string();
+    let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_value(serde_json::json!({
+        "slot": 1,
+        "transaction": {
+            "signatures": ["1111111111111111111111111111111111111111111111111111111111111111"],
+            "message": {
+                "accountKeys": [
+                    { "pubkey": payer, "writable": true, "signer": true, "source": "transaction" },
+                    { "pubkey": program, "writable": false, "signer": false, "source": "transaction" }
+                ],
+                "recentBlockhash": "4sGjMW1sUnHzSxGspuhpqLDx6wiyjNtZAMdL4VZHirAn",
+                "instructions": [
+                    {
+                        "program": "system",
+                        "programId": "11111111111111111111111111111111",
+                        "parsed": { "type": "transfer", "info": { "lamports": 5000 } },
+                        "stackHeight": null
+                    },
+                    { "programId": program, "accounts": [payer], "data": "3Bxs", "stackHeight": null }
+                ]
+            }
+        },
+        "meta": null,
+        "blockTime": null
+    }))
+    .unwrap();
+
+    let parsed = parse_transaction_instructions(&tx).expect("jsonParsed transactions are decoded");
+    assert_eq!(parsed[0]["program"], "system");
+    assert_eq!(parsed[0]["type"], "transfer");
+    assert_eq!(parsed[0]["info"]["lamports"], 5000);
+
+    assert_eq!(parsed[1]["program"], "unknown");
+    assert_eq!(parsed[1]["programId"], program);
+    assert_eq!(parsed[1]["accounts"][0], payer);
+    assert_eq!(parsed[1]["data"], "3Bxs");
+}
@@ -1,3 +1,112 @@
 pub use solana_mcp_server::transport::{
     JsonRpcError, JsonRpcMessage, JsonRpcRequest, JsonRpcResponse, JsonRpcVersion, Transport,
 };
+
+use solana_mcp_server::transport::{CustomStdioTransport, StdioFraming};
+use std::io::{Cursor, Write};
+use std::sync::{Arc, Mutex};
+
+/// Collects everything the transport writes
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl Output {
+    fn text(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn stdio(input: &str, framing: StdioFraming) -> (CustomStdioTransport, Output) {
+    let output = Output::default();
+    let transport = CustomStdioTransport::from_io(Cursor::new(input.as_bytes().to_vec()), output.clone(), framing);
+    (transport, output)
+}
+
+fn content_length(json: &str) -> String {
+    format!("Content-Length: {}\r\n\r\n{json}", json.len())
+}
+
+const PING: &str = r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#;
+const PONG: &str = r#"{"jsonrpc":"2.0","id":1,"result":{}}"#;
+
+#[test]
+fn test_content_length_framing_round_trip() {
+    let input = format!("Content-Type: application/json\r\n{}{}", content_length(PING), content_length(PING));
+    let (transport, output) = stdio(&input, StdioFraming::ContentLength);
+
+    for _ in 0..2 {
+        let JsonRpcMessage::Request(request) = transport.receive().unwrap() else {
+            panic!("expected a request");
+        };
+        assert_eq!(request.method, "ping");
+    }
+    assert!(transport.receive().unwrap_err().to_string().contains("Connection closed"));
+
+    transport.send_raw(PONG).unwrap();
+    assert_eq!(output.text(), content_length(PONG));
+}
+
+#[test]
+fn test_auto_framing_detects_the_client_and_flushes_earlier_messages() {
+    let notification = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+
+    let (transport, output) = stdio(&content_length(PING), StdioFraming::Auto);
+    transport.send_raw(notification).unwrap();
+    assert_eq!(output.text(), "", "nothing is written before the framing is known");
+    assert!(matches!(transport.receive().unwrap(), JsonRpcMessage::Request(_)));
+    transport.send_raw(PONG).unwrap();
+    assert_eq!(output.text(), content_length(notification) + &content_length(PONG));
+
+    let (transport, output) = stdio(&format!("\n{PING}\n"), StdioFraming::Auto);
+    transport.send_raw(notification).unwrap();
+    assert!(matches!(transport.receive().unwrap(), JsonRpcMessage::Request(_)));
+    transport.send_raw(PONG).unwrap();
+    assert_eq!(output.text(), format!("{notification}\n{PONG}\n"));
+}
+
+#[test]
+fn test_line_framing_and_framing_names() {
+    let (transport, output) = stdio(&format!("{PING}\n"), StdioFraming::Line);
+    assert!(matches!(transport.receive().unwrap(), JsonRpcMessage::Request(_)));
+    transport.send_raw(PONG).unwrap();
+    assert_eq!(output.text(), format!("{PONG}\n"));
+
+    assert_eq!("content-length".parse::<StdioFraming>().unwrap(), StdioFraming::ContentLength);
+    assert_eq!(serde_json::from_str::<StdioFraming>("\"auto\"").unwrap(), StdioFraming::Auto);
+    assert!("lsp".parse::<StdioFraming>().is_err());
+}
+
+#[test]
+fn test_messages_are_counted_with_their_size() {
+    use solana_mcp_server::metrics::PROMETHEUS_METRICS;
+    let count = |direction: &str| PROMETHEUS_METRICS.transport_messages_total.with_label_values(&[direction]).get();
+    let bytes = |direction: &str| PROMETHEUS_METRICS.transport_bytes_total.with_label_values(&[direction]).get();
+    let before = [count("received"), bytes("received"), count("sent"), bytes("sent")];
+
+    let (transport, _output) = stdio(&content_length(PING), StdioFraming::ContentLength);
+    transport.receive().unwrap();
+    transport.send_raw(PONG).unwrap();
+
+    // Other tests share the global counters, so only a lower bound holds
+    assert!(count("received") >= before[0] + 1.0);
+    assert!(bytes("received") >= before[1] + PING.len() as f64);
+    assert!(count("sent") >= before[2] + 1.0);
+    assert!(bytes("sent") >= before[3] + PONG.len() as f64);
+}
+
+#[tokio::test]
+async fn test_self_test_passes_without_a_network() {
+    solana_mcp_server::run_self_test(solana_mcp_server::server::SELF_TEST_TIMEOUT)
+        .await
+        .expect("self-test should pass");
+}
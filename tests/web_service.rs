@@ -92,4 +92,34 @@ async fn test_http_server_instantiation() {
             // Types compile correctly even if config fails
         }
     }
-}
\ No newline at end of file
+}
+
+/// Test that stdio mode prints the readiness marker on stderr, never stdout
+#[tokio::test]
+async fn test_stdio_mode_prints_ready_marker_on_stderr() {
+    use solana_mcp_server::server::READY_MARKER;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_solana-mcp-server"))
+        .arg("stdio")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start server");
+    // Closing stdin after one request makes the server exit
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"tools/list\"}\n")
+        .unwrap();
+    let output = child.wait_with_output().expect("Failed to wait for server");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stderr.lines().any(|line| line == READY_MARKER), "stderr: {stderr}");
+    assert!(!stdout.contains(READY_MARKER));
+    assert!(stdout.contains("\"id\":1"), "the server should still answer after the marker");
+}
@@ -11,7 +11,12 @@ mod x402_tests {
         PaymentPayload, PaymentRequirements, ResourceInfo, X402Config,
         PAYMENT_REQUIRED_CODE, INVALID_PAYMENT_CODE,
     };
+    use serde_json::{json, Value};
+    use solana_mcp_server::tools::handle_tools_call;
+    use solana_mcp_server::{Config, ServerState};
     use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
 
     fn create_test_config() -> X402Config {
         let mut networks = HashMap::new();
@@ -36,6 +41,7 @@ mod x402_tests {
             request_timeout_seconds: 30,
             max_retries: 3,
             networks,
+            tool_payments: HashMap::new(),
         }
     }
 
@@ -206,6 +212,419 @@ mod x402_tests {
         // Step 6: Server would verify and settle with facilitator
         // (This would require actual facilitator integration)
     }
+
+    /// Server state with x402 enabled and `getSbpfReadme` priced per call
+    fn gated_state(facilitator_base_url: &str) -> Arc<RwLock<ServerState>> {
+        let mut config = Config::load().expect("Failed to load config");
+        config.x402 = create_test_config();
+        config.x402.facilitator_base_url = facilitator_base_url.to_string();
+        config.x402.max_retries = 0;
+        config.x402.tool_payments.insert(
+            "getSbpfReadme".to_string(),
+            create_test_config()
+                .get_network("solana-mainnet")
+                .map(|network| PaymentRequirements {
+                    scheme: "exact".to_string(),
+                    network: network.network.clone(),
+                    amount: "2500".to_string(),
+                    asset: network.assets[0].address.clone(),
+                    pay_to: network.pay_to.clone(),
+                    max_timeout_seconds: 60,
+                    extra: None,
+                })
+                .unwrap(),
+        );
+        Arc::new(RwLock::new(ServerState::new(config)))
+    }
+
+    async fn call_tool(state: Arc<RwLock<ServerState>>, params: Value) -> Value {
+        let response = handle_tools_call(Some(params), Some(json!(1)), state)
+            .await
+            .expect("tools/call should produce a response");
+        serde_json::to_value(response).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_gated_tool_without_payment_returns_payment_required() {
+        let state = gated_state("https://facilitator.example.com");
+
+        let response = call_tool(state, json!({ "name": "getSbpfReadme", "arguments": {} })).await;
+        assert_eq!(response["error"]["code"], PAYMENT_REQUIRED_CODE);
+        let accepts = &response["error"]["data"]["accepts"];
+        assert_eq!(accepts[0]["amount"], "2500");
+        assert_eq!(response["error"]["data"]["resource"]["url"], "mcp://tool/getSbpfReadme");
+    }
+
+    #[tokio::test]
+    async fn test_ungated_tool_is_served_without_payment() {
+        let state = gated_state("https://facilitator.example.com");
+
+        let response = call_tool(state, json!({ "name": "getSbpfTutorial", "arguments": {} })).await;
+        assert!(response.get("error").is_none());
+        assert!(response["result"]["title"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_gated_tool_rejects_unverifiable_payment() {
+        // Nothing listens on port 1, so verification cannot succeed
+        let state = gated_state("http://127.0.0.1:1");
+        let requirements = state.read().await.config.x402.tool_payments["getSbpfReadme"].clone();
+        let payment = PaymentPayload {
+            x402_version: 2,
+            resource: None,
+            accepted: requirements,
+            payload: json!({ "transaction": "base64_encoded_tx" }),
+            extensions: None,
+        };
+
+        let response = call_tool(
+            state.clone(),
+            json!({ "name": "getSbpfReadme", "arguments": {}, "_meta": { "payment": payment } }),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], INVALID_PAYMENT_CODE);
+
+        let response = call_tool(
+            state,
+            json!({ "name": "getSbpfReadme", "arguments": {}, "_meta": { "payment": { "x402Version": 2 } } }),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], INVALID_PAYMENT_CODE);
+    }
+
+    #[tokio::test]
+    async fn test_refused_and_dry_run_calls_are_not_charged() {
+        // Nothing listens on port 1, so any attempt to collect payment would fail
+        let state = gated_state("http://127.0.0.1:1");
+        let requirements = state.read().await.config.x402.tool_payments["getSbpfReadme"].clone();
+        let payment = PaymentPayload {
+            x402_version: 2,
+            resource: None,
+            accepted: requirements,
+            payload: json!({ "transaction": "never_collected_tx" }),
+            extensions: None,
+        };
+
+        let response = call_tool(
+            state.clone(),
+            json!({ "name": "getSbpfReadme", "arguments": { "dryRun": true }, "_meta": { "payment": payment } }),
+        )
+        .await;
+        assert!(response.get("error").is_none(), "{response}");
+        assert_eq!(response["result"]["tool"], "getSbpfReadme");
+
+        state.write().await.config.disabled_tools = vec!["getSbpfReadme".to_string()];
+        let response = call_tool(
+            state,
+            json!({ "name": "getSbpfReadme", "arguments": {}, "_meta": { "payment": payment } }),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], -32601);
+    }
+
+    /// Start a facilitator that accepts every payment and answers /settle with `settle`
+    async fn spawn_facilitator<H, T>(settle: H) -> String
+    where
+        H: axum::handler::Handler<T, ()>,
+        T: 'static,
+    {
+        use axum::{routing::post, Json, Router};
+
+        let app = Router::new()
+            .route(
+                "/verify",
+                post(|| async { Json(json!({ "isValid": true, "payer": "ClientWalletAddress" })) }),
+            )
+            .route("/settle", post(settle));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}")
+    }
+
+    /// Start a facilitator that accepts every payment and answers /settle with `settlement`
+    async fn mock_facilitator(settlement: Value) -> String {
+        spawn_facilitator(move || async move { axum::Json(settlement) }).await
+    }
+
+    /// Calls the gated tool paying with `transaction`; settled payments are remembered by `state`
+    async fn paid_call(state: Arc<RwLock<ServerState>>, transaction: &str) -> Value {
+        let requirements = state.read().await.config.x402.tool_payments["getSbpfReadme"].clone();
+        let payment = PaymentPayload {
+            x402_version: 2,
+            resource: None,
+            accepted: requirements,
+            payload: json!({ "transaction": transaction }),
+            extensions: None,
+        };
+        call_tool(
+            state,
+            json!({ "name": "getSbpfReadme", "arguments": {}, "_meta": { "payment": payment } }),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_verified_payment_that_fails_settlement_is_not_served() {
+        let facilitator = mock_facilitator(json!({
+            "success": false,
+            "errorReason": "insufficient_funds",
+            "transaction": "",
+            "network": "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp"
+        }))
+        .await;
+
+        let response = paid_call(gated_state(&facilitator), "unfunded_tx").await;
+        assert_eq!(response["error"]["code"], INVALID_PAYMENT_CODE);
+        assert!(response["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("insufficient_funds"));
+        assert!(response.get("result").is_none());
+    }
+
+    /// Start a facilitator whose /settle answers 429 with `retry_after` for the first `throttled` calls
+    async fn throttling_facilitator(throttled: usize, retry_after: &'static str) -> String {
+        use axum::{http::StatusCode, Json};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        spawn_facilitator(move || async move {
+            if calls.fetch_add(1, Ordering::SeqCst) < throttled {
+                return Err((StatusCode::TOO_MANY_REQUESTS, [("retry-after", retry_after)], "slow down"));
+            }
+            Ok(Json(json!({
+                "success": true,
+                "transaction": "5vRsettledTx",
+                "network": "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp"
+            })))
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_settlement_is_retried_then_fails_cleanly() {
+        let state = gated_state(&throttling_facilitator(1, "0").await);
+        state.write().await.config.x402.max_retries = 1;
+        let response = paid_call(state, "throttled_once_tx").await;
+        assert_eq!(response["result"]["_meta"]["settlement"]["transaction"], "5vRsettledTx");
+
+        let state = gated_state(&throttling_facilitator(usize::MAX, "0").await);
+        state.write().await.config.x402.max_retries = 1;
+        let response = paid_call(state, "throttled_tx").await;
+        assert_eq!(response["error"]["code"], INVALID_PAYMENT_CODE);
+        assert!(response["error"]["message"].as_str().unwrap().contains("rate limited settle after 2 attempts"));
+        assert!(response.get("result").is_none());
+
+        // A Retry-After beyond what a tool call can wait out fails at once
+        let state = gated_state(&throttling_facilitator(usize::MAX, "3600").await);
+        state.write().await.config.x402.max_retries = 3;
+        let started = std::time::Instant::now();
+        let response = paid_call(state, "throttled_for_an_hour_tx").await;
+        assert!(response["error"]["message"].as_str().unwrap().contains("retry after 3600s"));
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    /// Start a facilitator that accepts every payment and counts its /settle calls
+    async fn counting_facilitator() -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        use axum::Json;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let settled = Arc::new(AtomicUsize::new(0));
+        let counter = settled.clone();
+        let url = spawn_facilitator(move || async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Json(json!({
+                "success": true,
+                "transaction": "5vRsettledTx",
+                "network": "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp"
+            }))
+        })
+        .await;
+        (url, settled)
+    }
+
+    #[tokio::test]
+    async fn test_failed_gated_calls_are_not_settled() {
+        use std::sync::atomic::Ordering;
+
+        let (facilitator, settled) = counting_facilitator().await;
+        let state = gated_state(&facilitator);
+        let requirements = state.read().await.config.x402.tool_payments["getSbpfReadme"].clone();
+        let payment = PaymentPayload {
+            x402_version: 2,
+            resource: None,
+            accepted: requirements,
+            payload: json!({ "transaction": "invalid_params_tx" }),
+            extensions: None,
+        };
+
+        let response = call_tool(
+            state.clone(),
+            json!({ "name": "getSbpfReadme", "arguments": { "commitment": "bogus" }, "_meta": { "payment": payment } }),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], -32602);
+        assert_eq!(settled.load(Ordering::SeqCst), 0);
+
+        // A result dropped by the size limit is not charged either
+        state.write().await.config.max_response_bytes = 64;
+        let response = paid_call(state.clone(), "oversized_tx").await;
+        assert_eq!(response["error"]["code"], -32011);
+        assert_eq!(settled.load(Ordering::SeqCst), 0);
+
+        state.write().await.config.max_response_bytes = 0;
+        let response = paid_call(state, "delivered_tx").await;
+        assert_eq!(response["result"]["_meta"]["settlement"]["transaction"], "5vRsettledTx");
+        assert_eq!(settled.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_settled_payment_returns_transaction_in_meta() {
+        let facilitator = mock_facilitator(json!({
+            "success": true,
+            "payer": "ClientWalletAddress",
+            "transaction": "5vRsettledTx",
+            "network": "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp"
+        }))
+        .await;
+
+        let response = paid_call(gated_state(&facilitator), "settled_tx").await;
+        assert!(response.get("error").is_none());
+        assert!(response["result"]["title"].is_string());
+        assert_eq!(response["result"]["_meta"]["settlement"]["transaction"], "5vRsettledTx");
+        assert_eq!(response["result"]["_meta"]["settlement"]["payer"], "ClientWalletAddress");
+    }
+
+    #[tokio::test]
+    async fn test_payment_is_settled_once_and_retries_get_the_same_result() {
+        use axum::{http::HeaderMap, Json};
+        use std::sync::Mutex;
+
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let seen = keys.clone();
+        let facilitator = spawn_facilitator(move |headers: HeaderMap| async move {
+            let key = headers["idempotency-key"].to_str().unwrap().to_string();
+            seen.lock().unwrap().push(key);
+            Json(json!({
+                "success": true,
+                "transaction": format!("5vRsettledTx{}", seen.lock().unwrap().len()),
+                "network": "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp"
+            }))
+        })
+        .await;
+        let state = gated_state(&facilitator);
+
+        // A client that lost the response retries and gets the recorded result, uncharged
+        let first = paid_call(state.clone(), "replayed_tx").await;
+        let retry = paid_call(state.clone(), "replayed_tx").await;
+        assert_eq!(first["result"]["_meta"]["settlement"]["transaction"], "5vRsettledTx1");
+        assert_eq!(retry["result"], first["result"]);
+        assert_eq!(keys.lock().unwrap().len(), 1);
+
+        // The same payment cannot pay for a call with different arguments
+        let requirements = state.read().await.config.x402.tool_payments["getSbpfReadme"].clone();
+        let payment = PaymentPayload {
+            x402_version: 2,
+            resource: None,
+            accepted: requirements,
+            payload: json!({ "transaction": "replayed_tx" }),
+            extensions: None,
+        };
+        let reuse = call_tool(
+            state.clone(),
+            json!({ "name": "getSbpfReadme", "arguments": { "compress": true }, "_meta": { "payment": payment } }),
+        )
+        .await;
+        assert_eq!(reuse["error"]["code"], INVALID_PAYMENT_CODE);
+        assert!(reuse["error"]["message"].as_str().unwrap().contains("already been used"));
+        assert!(reuse.get("result").is_none());
+        assert_eq!(keys.lock().unwrap().len(), 1);
+
+        // Settlements are scoped to the server state that made them
+        let fresh = paid_call(gated_state(&facilitator), "replayed_tx").await;
+        assert_eq!(fresh["result"]["_meta"]["settlement"]["transaction"], "5vRsettledTx2");
+
+        // A new payment is settled on its own, under a different key
+        let other = paid_call(state, "another_tx").await;
+        assert_eq!(other["result"]["_meta"]["settlement"]["transaction"], "5vRsettledTx3");
+        let keys = keys.lock().unwrap();
+        assert_eq!(keys.len(), 3);
+        assert_eq!(keys[0], keys[1]);
+        assert_ne!(keys[0], keys[2]);
+    }
+
+    #[tokio::test]
+    async fn test_racing_calls_with_one_payment_deliver_only_the_paid_result() {
+        let facilitator = mock_facilitator(json!({
+            "success": true,
+            "transaction": "5vRsettledTx",
+            "network": "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp"
+        }))
+        .await;
+        let state = gated_state(&facilitator);
+        let requirements = state.read().await.config.x402.tool_payments["getSbpfReadme"].clone();
+        let payment = PaymentPayload {
+            x402_version: 2,
+            resource: None,
+            accepted: requirements,
+            payload: json!({ "transaction": "raced_tx" }),
+            extensions: None,
+        };
+        let call = |arguments: Value| {
+            call_tool(
+                state.clone(),
+                json!({ "name": "getSbpfReadme", "arguments": arguments, "_meta": { "payment": payment.clone() } }),
+            )
+        };
+
+        let (plain, compressed) = tokio::join!(call(json!({})), call(json!({ "compress": true })));
+        let (paid, refused) = if plain.get("error").is_none() { (plain, compressed) } else { (compressed, plain) };
+        assert_eq!(paid["result"]["_meta"]["settlement"]["transaction"], "5vRsettledTx");
+        assert_eq!(refused["error"]["code"], INVALID_PAYMENT_CODE, "{refused}");
+        assert!(refused["error"]["message"].as_str().unwrap().contains("already been used"));
+        assert!(refused.get("result").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_payment_settled_for_one_tool_is_rejected_for_another() {
+        use std::sync::atomic::Ordering;
+
+        let (facilitator, settled) = counting_facilitator().await;
+        let state = gated_state(&facilitator);
+        let requirements = state.read().await.config.x402.tool_payments["getSbpfReadme"].clone();
+        // Same price and recipient, so only the tool tells the two calls apart
+        state
+            .write()
+            .await
+            .config
+            .x402
+            .tool_payments
+            .insert("getSbpfTutorial".to_string(), requirements);
+
+        let response = paid_call(state.clone(), "tool_a_tx").await;
+        assert!(response.get("error").is_none(), "{response}");
+
+        let requirements = state.read().await.config.x402.tool_payments["getSbpfTutorial"].clone();
+        let payment = PaymentPayload {
+            x402_version: 2,
+            resource: None,
+            accepted: requirements,
+            payload: json!({ "transaction": "tool_a_tx" }),
+            extensions: None,
+        };
+        let response = call_tool(
+            state,
+            json!({ "name": "getSbpfTutorial", "arguments": {}, "_meta": { "payment": payment } }),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], INVALID_PAYMENT_CODE);
+        assert!(response["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("settled for mcp://tool/getSbpfReadme"));
+        assert_eq!(settled.load(Ordering::SeqCst), 1);
+    }
 }
 
 #[cfg(not(feature = "x402"))]